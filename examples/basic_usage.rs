@@ -1,13 +1,8 @@
 //! Basic usage example for the Finnhub client.
 
 use chrono::{Duration, Utc};
-use finnhub::{
-    models::{
-        news::NewsCategory,
-        stock::{CandleResolution, StatementFrequency, StatementType},
-    },
-    FinnhubClient, Result,
-};
+use finnhub::models::etf::ETFComparison;
+use finnhub::prelude::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -195,6 +190,19 @@ async fn main() -> Result<()> {
         Err(e) => println!("Market status not available: {}", e),
     }
 
+    // Check several exchanges at once
+    let statuses = client.stock().market_status_many(["US", "L", "T"]).await;
+    for (exchange, status) in &statuses.values {
+        println!(
+            "  {}: {}",
+            exchange,
+            if status.is_open { "open" } else { "closed" }
+        );
+    }
+    for (exchange, e) in &statuses.errors {
+        println!("  {}: not available: {}", exchange, e);
+    }
+
     // Get crypto exchanges
     println!("\nFetching crypto exchanges...");
     let exchanges = client.crypto().exchanges().await?;
@@ -315,7 +323,7 @@ async fn main() -> Result<()> {
             );
             for i in 0..5.min(ticks.price.len()) {
                 println!(
-                    "  Time: {} | Price: ${:.2} | Volume: {:.0}",
+                    "  Time: {} | Price: ${:.2} | Volume: {}",
                     chrono::DateTime::from_timestamp_millis(ticks.timestamp[i])
                         .map(|dt| dt.format("%H:%M:%S").to_string())
                         .unwrap_or_else(|| "Unknown".to_string()),
@@ -375,7 +383,10 @@ async fn main() -> Result<()> {
                         "  📊 {} - {} ({})",
                         symbol,
                         earning.date.as_deref().unwrap_or("Unknown"),
-                        earning.hour.as_deref().unwrap_or("Unknown")
+                        earning
+                            .hour
+                            .as_ref()
+                            .map_or("Unknown", |hour| hour.as_str())
                     );
                     if let (Some(est), Some(act)) = (earning.eps_estimate, earning.eps_actual) {
                         println!("     EPS: ${:.2} actual vs ${:.2} estimate", act, est);
@@ -430,6 +441,17 @@ async fn main() -> Result<()> {
         Err(e) => println!("ETF profile not available: {}", e),
     }
 
+    // Compare S&P 500 ETFs
+    println!("\nComparing S&P 500 ETFs...");
+    let profiles = client.etf().compare(&["SPY", "VOO", "IVV"]).await;
+    print!(
+        "{}",
+        ETFComparison::from_profiles(profiles.ok_values().cloned())
+    );
+    for (symbol, e) in &profiles.errors {
+        println!("ETF profile for {} not available: {}", symbol, e);
+    }
+
     // Get ETF holdings
     println!("\nFetching SPY ETF holdings...");
     match client.etf().holdings(Some("SPY"), None, None, None).await {
@@ -1,6 +1,6 @@
 //! Error handling example demonstrating proper error management and recovery strategies.
 
-use finnhub::{ClientConfig, Error, FinnhubClient, Result};
+use finnhub::{ClientConfig, Error, FinnhubClient, RateLimitStrategy, Result};
 use std::time::Duration;
 
 #[tokio::main]
@@ -36,7 +36,12 @@ async fn demonstrate_auth_errors() {
     // Create client with invalid API key
     let invalid_client = FinnhubClient::new("invalid_api_key");
 
-    match invalid_client.stock().quote("AAPL").await {
+    match invalid_client
+        .stock()
+        .quote("AAPL")
+        .await
+        .map_err(Error::into_root)
+    {
         Ok(_) => println!("Unexpected success with invalid key"),
         Err(Error::Unauthorized) => {
             println!("✅ Caught unauthorized error - invalid API key");
@@ -58,7 +63,7 @@ async fn demonstrate_rate_limiting() {
 
     // Create client with very low rate limit for demonstration
     let config = ClientConfig {
-        rate_limit: Some(1), // Very restrictive: 1 request per second
+        rate_limit_strategy: RateLimitStrategy::from_legacy(1), // Very restrictive: 1 request per second
         ..ClientConfig::default()
     };
 
@@ -70,7 +75,12 @@ async fn demonstrate_rate_limiting() {
 
         // Make multiple rapid requests
         for i in 1..=3 {
-            match rate_limited_client.stock().quote("AAPL").await {
+            match rate_limited_client
+                .stock()
+                .quote("AAPL")
+                .await
+                .map_err(Error::into_root)
+            {
                 Ok(quote) => {
                     println!(
                         "✅ Request {}: Success - Price: ${:.2}",
@@ -88,7 +98,12 @@ async fn demonstrate_rate_limiting() {
                     tokio::time::sleep(Duration::from_secs(retry_after)).await;
 
                     // Retry the request
-                    match rate_limited_client.stock().quote("AAPL").await {
+                    match rate_limited_client
+                        .stock()
+                        .quote("AAPL")
+                        .await
+                        .map_err(Error::into_root)
+                    {
                         Ok(quote) => {
                             println!("✅ Retry successful - Price: ${:.2}", quote.current_price)
                         }
@@ -123,7 +138,12 @@ async fn demonstrate_network_errors() {
 
     let network_client = FinnhubClient::with_config("test_key", config);
 
-    match network_client.stock().quote("AAPL").await {
+    match network_client
+        .stock()
+        .quote("AAPL")
+        .await
+        .map_err(Error::into_root)
+    {
         Ok(_) => println!("Unexpected success with invalid URL"),
         Err(Error::Http(http_err)) => {
             println!("✅ Caught network error: {}", http_err);
@@ -158,7 +178,12 @@ async fn demonstrate_data_errors() {
         let client = FinnhubClient::new(api_key);
 
         // Try to get data for an invalid symbol
-        match client.stock().quote("INVALID_SYMBOL_XYZ").await {
+        match client
+            .stock()
+            .quote("INVALID_SYMBOL_XYZ")
+            .await
+            .map_err(Error::into_root)
+        {
             Ok(quote) => {
                 // Even if the request succeeds, validate the data
                 if quote.current_price <= 0.0 {
@@ -259,7 +284,7 @@ async fn demonstrate_retry_strategies() {
         let base_delay = Duration::from_millis(100);
 
         loop {
-            match client.stock().quote("AAPL").await {
+            match client.stock().quote("AAPL").await.map_err(Error::into_root) {
                 Ok(quote) => {
                     println!("✅ Request successful after {} retries", retry_count);
                     println!("   Price: ${:.2}", quote.current_price);
@@ -322,22 +347,52 @@ fn handle_finnhub_error(error: Error) -> String {
                 format!("Network error: {}", http_err)
             }
         }
+        Error::UnexpectedContentType {
+            status, snippet, ..
+        } => {
+            format!("Non-JSON response (HTTP {}): {}", status, snippet)
+        }
+        Error::ResponseTooLarge { limit } => {
+            format!("Response larger than the {} byte limit", limit)
+        }
         Error::Deserialization(serde_err) => {
             format!("Data parsing error: {}", serde_err)
         }
+        Error::DataInvariantViolation(msg) => {
+            format!("Suspicious data from upstream: {}", msg)
+        }
         Error::InvalidParameter(msg) => {
             format!("Invalid parameter: {}", msg)
         }
         Error::InvalidRequest(msg) => {
             format!("Invalid request: {}", msg)
         }
+        Error::InvalidConfig { field, message } => {
+            format!("Invalid client configuration ({}): {}", field, message)
+        }
         Error::UrlParse(url_err) => {
             format!("URL parsing error: {}", url_err)
         }
+        Error::Storage(msg) => {
+            format!("Storage error: {}", msg)
+        }
         Error::Internal(msg) => {
             format!("Internal error: {}", msg)
         }
         Error::Timeout => "Request timed out".to_string(),
+        Error::Endpoint {
+            name,
+            symbol,
+            source,
+        } => {
+            let symbol = symbol.map(|s| format!(" for {}", s)).unwrap_or_default();
+            format!(
+                "{}{} failed: {}",
+                name,
+                symbol,
+                handle_finnhub_error(*source)
+            )
+        }
         #[cfg(feature = "websocket")]
         Error::WebSocket(ws_err) => {
             format!("WebSocket error: {}", ws_err)
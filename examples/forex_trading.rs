@@ -1,7 +1,7 @@
 //! Forex trading example demonstrating currency market data and analysis.
 
 use chrono::{Duration, Utc};
-use finnhub::{models::stock::CandleResolution, FinnhubClient, Result};
+use finnhub::{models::stock::CandleResolution, toolkit, FinnhubClient, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -179,17 +179,8 @@ async fn technical_analysis(client: &FinnhubClient, symbol: &str) -> Result<()>
                 let lows = &candles.low;
 
                 // Calculate moving averages
-                let ma_5 = if prices.len() >= 5 {
-                    prices.iter().rev().take(5).sum::<f64>() / 5.0
-                } else {
-                    prices.iter().sum::<f64>() / prices.len() as f64
-                };
-
-                let ma_20 = if prices.len() >= 20 {
-                    prices.iter().rev().take(20).sum::<f64>() / 20.0
-                } else {
-                    prices.iter().sum::<f64>() / prices.len() as f64
-                };
+                let ma_5 = toolkit::trailing_mean(prices, 5).unwrap_or_default();
+                let ma_20 = toolkit::trailing_mean(prices, 20).unwrap_or_default();
 
                 let current = prices[prices.len() - 1];
 
@@ -212,26 +203,21 @@ async fn technical_analysis(client: &FinnhubClient, symbol: &str) -> Result<()>
                 println!("30-day Low: {:.5}", period_low);
 
                 // Calculate ATR (Average True Range) for volatility
-                let mut true_ranges = Vec::new();
-                for i in 1..prices.len() {
-                    let tr1 = highs[i] - lows[i];
-                    let tr2 = (highs[i] - prices[i - 1]).abs();
-                    let tr3 = (lows[i] - prices[i - 1]).abs();
-                    true_ranges.push(tr1.max(tr2).max(tr3));
-                }
-
-                if !true_ranges.is_empty() {
-                    let atr = true_ranges.iter().sum::<f64>() / true_ranges.len() as f64;
+                let true_ranges = toolkit::true_ranges(highs, lows, prices);
+                if let Some(atr) = toolkit::atr(highs, lows, prices, true_ranges.len()) {
                     println!("Average True Range: {:.5}", atr);
                     println!("Volatility: {:.3}%", (atr / current) * 100.0);
                 }
 
                 // Price position analysis
-                let position_in_range = (current - period_low) / (period_high - period_low);
-                println!(
-                    "Position in 30-day range: {:.1}%",
-                    position_in_range * 100.0
-                );
+                if let Some(position_in_range) =
+                    toolkit::range_position(current, period_low, period_high)
+                {
+                    println!(
+                        "Position in 30-day range: {:.1}%",
+                        position_in_range * 100.0
+                    );
+                }
             }
         }
         Err(e) => println!("Technical analysis data not available: {}", e),
@@ -249,27 +235,11 @@ async fn cross_currency_analysis(client: &FinnhubClient) -> Result<()> {
         Ok(usd_rates) => {
             println!("📊 Implied Cross Rates:");
 
-            // EUR-based crosses
-            if let (Some(eur_usd), Some(gbp_usd)) =
-                (usd_rates.quote.get("EUR"), usd_rates.quote.get("GBP"))
-            {
-                let eur_gbp = eur_usd / gbp_usd;
-                println!("  EUR/GBP: {:.5}", eur_gbp);
-            }
-
-            if let (Some(eur_usd), Some(jpy_usd)) =
-                (usd_rates.quote.get("EUR"), usd_rates.quote.get("JPY"))
-            {
-                let eur_jpy = eur_usd * jpy_usd;
-                println!("  EUR/JPY: {:.3}", eur_jpy);
-            }
-
-            // GBP-based crosses
-            if let (Some(gbp_usd), Some(jpy_usd)) =
-                (usd_rates.quote.get("GBP"), usd_rates.quote.get("JPY"))
+            for (base, quote, decimals) in [("EUR", "GBP", 5), ("EUR", "JPY", 3), ("GBP", "JPY", 3)]
             {
-                let gbp_jpy = gbp_usd * jpy_usd;
-                println!("  GBP/JPY: {:.3}", gbp_jpy);
+                if let Some(rate) = toolkit::cross_rate(&usd_rates.quote, base, quote) {
+                    println!("  {}/{}: {:.*}", base, quote, decimals, rate);
+                }
             }
 
             // Commodity currency analysis
@@ -286,33 +256,9 @@ async fn cross_currency_analysis(client: &FinnhubClient) -> Result<()> {
                 }
             }
 
-            // Calculate DXY-style index (simplified)
-            let weights = [
-                ("EUR", 0.576),
-                ("JPY", 0.136),
-                ("GBP", 0.119),
-                ("CAD", 0.091),
-                ("CHF", 0.036),
-                ("SEK", 0.042),
-            ];
-
-            let mut index_value = 0.0;
-            let mut total_weight = 0.0;
-
-            for (currency, weight) in &weights {
-                if let Some(rate) = usd_rates.quote.get(*currency) {
-                    // For USD index, we want 1/rate for non-USD currencies
-                    index_value += weight * (1.0 / rate);
-                    total_weight += weight;
-                }
-            }
-
-            if total_weight > 0.0 {
-                let normalized_index = (index_value / total_weight) * 100.0;
-                println!(
-                    "\n📈 USD Strength Index (simplified): {:.2}",
-                    normalized_index
-                );
+            // US Dollar Index from the six DXY currencies
+            if let Some(index) = toolkit::dollar_index(&usd_rates.quote) {
+                println!("\n📈 US Dollar Index: {:.2}", index);
             }
         }
         Err(e) => println!("Cross-currency analysis not available: {}", e),
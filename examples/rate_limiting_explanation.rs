@@ -2,7 +2,7 @@
 //!
 //! This example explains the rate limiting behavior without making API calls.
 
-use finnhub::{ClientConfig, FinnhubClient};
+use finnhub::{ClientConfig, FinnhubClient, RateLimitStrategy};
 
 fn main() {
     println!("=== Finnhub Rate Limiting Explanation ===\n");
@@ -31,11 +31,13 @@ fn main() {
     println!();
     println!("   b) Custom rate limit:");
     println!("      let mut config = ClientConfig::default();");
-    println!("      config.rate_limit = Some(60);  // 60 req/s");
+    println!("      config.rate_limit_strategy = RateLimitStrategy::from_legacy(60);  // 60 req/s");
     println!("      let client = FinnhubClient::with_config(api_key, config);");
     println!();
     println!("   c) Effectively disable (set very high):");
-    println!("      config.rate_limit = Some(10000);  // 10k req/s\n");
+    println!(
+        "      config.rate_limit_strategy = RateLimitStrategy::from_legacy(10000);  // 10k req/s\n"
+    );
 
     println!("5. Why use the internal rate limiter?");
     println!("   - Prevents 429 errors from Finnhub's server");
@@ -58,13 +60,13 @@ fn main() {
 
     // High rate client for testing
     let mut test_config = ClientConfig::default();
-    test_config.rate_limit = Some(1000);
+    test_config.rate_limit_strategy = RateLimitStrategy::from_legacy(1000);
     let _test_client = FinnhubClient::with_config(api_key, test_config);
     println!("Created test client with 1000 req/s limit");
 
     // Custom rate client
     let mut custom_config = ClientConfig::default();
-    custom_config.rate_limit = Some(10);
+    custom_config.rate_limit_strategy = RateLimitStrategy::from_legacy(10);
     let _custom_client = FinnhubClient::with_config(api_key, custom_config);
     println!("Created custom client with 10 req/s limit");
 }
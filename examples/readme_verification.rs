@@ -241,7 +241,12 @@ async fn search_discovery_examples(api_key: &str) -> Result<()> {
 async fn error_handling_example(api_key: &str) {
     let client = FinnhubClient::new(api_key);
 
-    match client.stock().quote("INVALID_SYMBOL_XYZ").await {
+    match client
+        .stock()
+        .quote("INVALID_SYMBOL_XYZ")
+        .await
+        .map_err(Error::into_root)
+    {
         Ok(quote) => println!("  Price: ${}", quote.current_price),
         Err(Error::RateLimitExceeded { retry_after }) => {
             println!("  Rate limit hit, retry after {} seconds", retry_after);
@@ -333,7 +338,7 @@ impl QuoteCache {
 
 // Production best practices - error handling
 async fn production_error_handling(client: &FinnhubClient) {
-    match client.stock().quote("AAPL").await {
+    match client.stock().quote("AAPL").await.map_err(Error::into_root) {
         Ok(quote) => process_quote(quote),
         Err(Error::RateLimitExceeded { retry_after }) => {
             // Back off and retry later
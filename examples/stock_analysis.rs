@@ -1,7 +1,7 @@
 //! Stock analysis example demonstrating fundamental and technical analysis features.
 
 use chrono::{Duration, Utc};
-use finnhub::{models::stock::CandleResolution, FinnhubClient, Result};
+use finnhub::{models::stock::CandleResolution, toolkit, FinnhubClient, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -56,8 +56,8 @@ async fn analyze_basic_info(client: &FinnhubClient, symbol: &str) -> Result<()>
             if let Some(market_cap) = profile.market_capitalization {
                 println!("Market Cap: ${:.2}B", market_cap / 1_000_000_000.0);
             }
-            if let Some(shares) = profile.share_outstanding {
-                println!("Shares Outstanding: {:.2}M", shares / 1_000_000.0);
+            if let Some(shares) = profile.shares_outstanding {
+                println!("Shares Outstanding: {:.2}M", shares.as_f64() / 1_000_000.0);
             }
             println!(
                 "Industry: {}",
@@ -93,12 +93,8 @@ async fn analyze_technical_data(client: &FinnhubClient, symbol: &str) -> Result<
             if candles.status == "ok" && !candles.close.is_empty() {
                 let prices = &candles.close;
                 let current = prices[prices.len() - 1];
-                let ma_5 = prices.iter().rev().take(5).sum::<f64>() / 5.0;
-                let ma_20 = if prices.len() >= 20 {
-                    prices.iter().rev().take(20).sum::<f64>() / 20.0
-                } else {
-                    prices.iter().sum::<f64>() / prices.len() as f64
-                };
+                let ma_5 = toolkit::trailing_mean(prices, 5).unwrap_or_default();
+                let ma_20 = toolkit::trailing_mean(prices, 20).unwrap_or_default();
 
                 println!("Current Price: ${:.2}", current);
                 println!("5-day MA: ${:.2}", ma_5);
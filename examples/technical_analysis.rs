@@ -112,10 +112,7 @@ async fn pattern_recognition(client: &FinnhubClient, symbol: &str) -> Result<()>
                     }
 
                     // Calculate risk/reward ratio
-                    let risk = (pattern.entry - pattern.stoploss).abs();
-                    let reward = (pattern.profit1 - pattern.entry).abs();
-                    if risk > 0.0 {
-                        let rr_ratio = reward / risk;
+                    if let Some(rr_ratio) = pattern.risk_reward() {
                         println!("     Risk/Reward: 1:{:.2}", rr_ratio);
                     }
                     println!();
@@ -173,19 +170,7 @@ async fn support_resistance_levels(client: &FinnhubClient, symbol: &str) -> Resu
                 if let Some(current) = current_price {
                     println!("\nCurrent Price: ${:.2}", current);
 
-                    // Find nearest support and resistance
-                    let resistance_levels: Vec<f64> = sorted_levels
-                        .iter()
-                        .filter(|&&level| level > current)
-                        .cloned()
-                        .collect();
-                    let support_levels: Vec<f64> = sorted_levels
-                        .iter()
-                        .filter(|&&level| level < current)
-                        .cloned()
-                        .collect();
-
-                    if let Some(&nearest_resistance) = resistance_levels.last() {
+                    if let Some(nearest_resistance) = levels.nearest_above(current) {
                         let resistance_dist = ((nearest_resistance - current) / current) * 100.0;
                         println!(
                             "Nearest Resistance: ${:.2} (+{:.1}%)",
@@ -193,7 +178,7 @@ async fn support_resistance_levels(client: &FinnhubClient, symbol: &str) -> Resu
                         );
                     }
 
-                    if let Some(&nearest_support) = support_levels.first() {
+                    if let Some(nearest_support) = levels.nearest_below(current) {
                         let support_dist = ((current - nearest_support) / current) * 100.0;
                         println!(
                             "Nearest Support: ${:.2} (-{:.1}%)",
@@ -0,0 +1,54 @@
+//! Terminal ticker tape.
+//!
+//! Streams throttled price updates for a few symbols. With the `websocket`
+//! feature trades come from the WebSocket feed; otherwise quotes are polled.
+//!
+//! Run with: cargo run --example ticker_tape --features websocket
+
+use finnhub::{watch::TapeUpdate, FinnhubClient};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    let api_key = std::env::var("FINNHUB_API_KEY").expect("FINNHUB_API_KEY must be set");
+    let client = FinnhubClient::new(api_key);
+
+    let symbols = ["AAPL", "MSFT", "NVDA", "AMZN"];
+    let tape = client
+        .ticker_tape(symbols)
+        .with_throttle(Duration::from_millis(500))
+        .into_stream()
+        .take_until(tokio::time::sleep(Duration::from_secs(60)));
+    futures::pin_mut!(tape);
+
+    // Previous close per symbol, from the quote snapshots, to show the change
+    // alongside trades.
+    let mut previous_close: HashMap<String, f64> = HashMap::new();
+    while let Some(update) = tape.next().await {
+        let (symbol, update) = match update {
+            Ok(update) => update,
+            Err(e) => {
+                eprintln!("Quote error: {}", e);
+                continue;
+            }
+        };
+        if let TapeUpdate::Quote(quote) = &update {
+            previous_close.insert(symbol.clone(), quote.previous_close);
+        }
+        let price = update.price();
+        match previous_close.get(&symbol) {
+            Some(&close) if close > 0.0 => println!(
+                "{:<6} {:>10.2} {:>+7.2}%",
+                symbol,
+                price,
+                (price - close) / close * 100.0
+            ),
+            _ => println!("{:<6} {:>10.2}", symbol, price),
+        }
+    }
+
+    Ok(())
+}
@@ -65,6 +65,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         );
                     }
                 }
+                WebSocketMessage::News { data } => {
+                    for article in data {
+                        println!("News: [{}] {}", article.related, article.headline);
+                    }
+                }
                 WebSocketMessage::Ping => {
                     println!(
                         "[{}] Received ping",
@@ -89,6 +94,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Report feed health
+    let metrics = stream.metrics().snapshot();
+    println!(
+        "\nReceived {} messages ({:.1}/s over the last 10s)",
+        metrics.messages, metrics.messages_per_sec
+    );
+    for (symbol, m) in &metrics.symbols {
+        println!(
+            "  {}: {} trades, {:.1}/s, lag last {} ms / mean {:.0} ms / max {} ms",
+            symbol, m.trades, m.trades_per_sec, m.last_lag_ms, m.mean_lag_ms, m.max_lag_ms
+        );
+    }
+
     // Unsubscribe before closing
     for symbol in &symbols {
         println!("Unsubscribing from {}...", symbol);
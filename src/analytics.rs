@@ -0,0 +1,317 @@
+//! Cross-asset analytics built on the candle endpoints.
+//!
+//! [`correlation`] pulls candles for a mix of stocks, forex pairs and crypto
+//! pairs, and [`CorrelationMatrix::from_partial`] lines the bars up and
+//! correlates their returns:
+//!
+//! ```rust,no_run
+//! use finnhub::{
+//!     analytics::{self, Asset, CorrelationMatrix},
+//!     models::stock::CandleResolution,
+//!     FinnhubClient,
+//! };
+//!
+//! # async fn run() {
+//! let client = FinnhubClient::new("your-api-key");
+//! let assets = [
+//!     Asset::Stock("SPY".to_string()),
+//!     Asset::Forex("OANDA:EUR_USD".to_string()),
+//!     Asset::Crypto("BINANCE:BTCUSDT".to_string()),
+//! ];
+//! let closes =
+//!     analytics::correlation(&client, &assets, CandleResolution::Daily, 1_704_067_200..=1_719_792_000)
+//!         .await;
+//! for (asset, err) in &closes.errors {
+//!     eprintln!("skipping {asset}: {err}");
+//! }
+//! let matrix = CorrelationMatrix::from_partial(&closes, CandleResolution::Daily);
+//! println!("SPY/BTC: {:?}", matrix.get("SPY", "BINANCE:BTCUSDT"));
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    models::stock::CandleResolution,
+    partial::{self, PartialResult},
+};
+
+/// An instrument to fetch candles for, by asset class.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Asset {
+    /// A stock symbol, e.g. `AAPL` or `VOD.L`.
+    #[cfg(feature = "stock")]
+    Stock(String),
+    /// A forex symbol, e.g. `OANDA:EUR_USD`. Symbols without a venue use
+    /// `ClientConfig::venues`.
+    #[cfg(feature = "forex")]
+    Forex(String),
+    /// A crypto symbol, e.g. `BINANCE:BTCUSDT`. Symbols without a venue use
+    /// `ClientConfig::venues`.
+    #[cfg(feature = "crypto")]
+    Crypto(String),
+}
+
+impl Asset {
+    /// The symbol, as passed to the candle endpoint.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        match self {
+            #[cfg(feature = "stock")]
+            Asset::Stock(symbol) => symbol,
+            #[cfg(feature = "forex")]
+            Asset::Forex(symbol) => symbol,
+            #[cfg(feature = "crypto")]
+            Asset::Crypto(symbol) => symbol,
+        }
+    }
+
+    /// Fetch `(timestamp, close)` pairs.
+    async fn closes(
+        &self,
+        client: &FinnhubClient,
+        resolution: CandleResolution,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<(i64, f64)>> {
+        let (timestamp, close) = match self {
+            #[cfg(feature = "stock")]
+            Asset::Stock(symbol) => {
+                let candles = client.stock().candles(symbol, resolution, from, to).await?;
+                (candles.timestamp, candles.close)
+            }
+            #[cfg(feature = "forex")]
+            Asset::Forex(symbol) => {
+                let candles = client.forex().candles(symbol, resolution, from, to).await?;
+                (candles.timestamp, candles.close)
+            }
+            #[cfg(feature = "crypto")]
+            Asset::Crypto(symbol) => {
+                let candles = client
+                    .crypto()
+                    .candles(symbol, resolution, from, to)
+                    .await?;
+                (candles.timestamp, candles.close)
+            }
+        };
+        Ok(timestamp.into_iter().zip(close).collect())
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Pairwise correlations of bar-to-bar returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    symbols: Vec<String>,
+    values: Vec<Vec<Option<f64>>>,
+    observations: usize,
+}
+
+impl CorrelationMatrix {
+    /// Correlate the returns of several `(timestamp, close)` series.
+    ///
+    /// Only bars present in every series are used, so a stock's weekends and
+    /// holidays are dropped from a crypto pair's series too. For daily and
+    /// longer resolutions bars are matched by UTC date, since venues stamp
+    /// their daily bars at different times of day.
+    #[must_use]
+    pub fn from_closes(series: &[(String, Vec<(i64, f64)>)], resolution: CandleResolution) -> Self {
+        let bucket = |timestamp: i64| match resolution {
+            CandleResolution::Daily | CandleResolution::Weekly | CandleResolution::Monthly => {
+                timestamp.div_euclid(86_400)
+            }
+            _ => timestamp,
+        };
+        let maps: Vec<BTreeMap<i64, f64>> = series
+            .iter()
+            .map(|(_, closes)| closes.iter().map(|&(t, c)| (bucket(t), c)).collect())
+            .collect();
+        let common: Vec<i64> = maps.first().map_or_else(Vec::new, |first| {
+            first
+                .keys()
+                .filter(|key| maps.iter().all(|map| map.contains_key(key)))
+                .copied()
+                .collect()
+        });
+        let returns: Vec<Vec<f64>> = maps
+            .iter()
+            .map(|map| {
+                common
+                    .windows(2)
+                    .map(|pair| map[&pair[1]] / map[&pair[0]] - 1.0)
+                    .collect()
+            })
+            .collect();
+
+        let values = returns
+            .iter()
+            .map(|a| returns.iter().map(|b| pearson(a, b)).collect())
+            .collect();
+        Self {
+            symbols: series.iter().map(|(symbol, _)| symbol.clone()).collect(),
+            values,
+            observations: common.len().saturating_sub(1),
+        }
+    }
+
+    /// Correlate the series of the assets that [`correlation`] fetched,
+    /// skipping the ones that failed. Rows are ordered by symbol.
+    #[must_use]
+    pub fn from_partial(
+        closes: &PartialResult<Vec<(i64, f64)>, Asset>,
+        resolution: CandleResolution,
+    ) -> Self {
+        let mut series: Vec<(String, Vec<(i64, f64)>)> = closes
+            .values
+            .iter()
+            .map(|(asset, closes)| (asset.symbol().to_string(), closes.clone()))
+            .collect();
+        series.sort_by(|a, b| a.0.cmp(&b.0));
+        Self::from_closes(&series, resolution)
+    }
+
+    /// The symbols, in row and column order.
+    #[must_use]
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Number of aligned returns each correlation is computed from.
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// Correlation between two symbols' returns, from -1 to 1. `None` if
+    /// either symbol is unknown or the correlation is undefined, e.g. fewer
+    /// than two returns or a constant price.
+    #[must_use]
+    pub fn get(&self, a: &str, b: &str) -> Option<f64> {
+        let row = self.symbols.iter().position(|s| s == a)?;
+        let column = self.symbols.iter().position(|s| s == b)?;
+        self.values[row][column]
+    }
+
+    /// Rows of the matrix, in [`symbols`](Self::symbols) order.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<Option<f64>>] {
+        &self.values
+    }
+}
+
+/// Fetch `(timestamp, close)` series for `assets` over `range` (UNIX seconds)
+/// to correlate with [`CorrelationMatrix::from_partial`].
+///
+/// Candles are requested concurrently, subject to the client's rate limiter.
+/// An asset whose request fails is reported in the result's errors and
+/// doesn't stop the others.
+pub async fn correlation(
+    client: &FinnhubClient,
+    assets: &[Asset],
+    resolution: CandleResolution,
+    range: RangeInclusive<i64>,
+) -> PartialResult<Vec<(i64, f64)>, Asset> {
+    let (from, to) = range.into_inner();
+    partial::fetch_all(
+        assets.iter().cloned(),
+        partial::DEFAULT_CONCURRENCY,
+        |asset| async move { asset.closes(client, resolution, from, to).await },
+    )
+    .await
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() < 2 {
+        return None;
+    }
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        let (dx, dy) = (x - mean_a, y - mean_b);
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+    let denominator = (var_a * var_b).sqrt();
+    (denominator > 0.0).then(|| (cov / denominator).clamp(-1.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_aligns_bars() {
+        const DAY: i64 = 86_400;
+        // Crypto trades every day and stamps bars at 00:00, the stock skips
+        // day 3 and stamps bars at 05:00.
+        let crypto = vec![
+            (0, 100.0),
+            (DAY, 110.0),
+            (2 * DAY, 99.0),
+            (3 * DAY, 500.0),
+            (4 * DAY, 108.9),
+        ];
+        let stock = vec![
+            (5 * 3600, 10.0),
+            (DAY + 5 * 3600, 12.0),
+            (2 * DAY + 5 * 3600, 9.0),
+            (4 * DAY + 5 * 3600, 11.0),
+        ];
+        let flat = vec![(0, 1.0), (DAY, 1.0), (2 * DAY, 1.0), (4 * DAY, 1.0)];
+        let matrix = CorrelationMatrix::from_closes(
+            &[
+                ("BTC".to_string(), crypto),
+                ("SPY".to_string(), stock),
+                ("USD".to_string(), flat),
+            ],
+            CandleResolution::Daily,
+        );
+
+        assert_eq!(matrix.observations(), 3);
+        assert_eq!(matrix.get("BTC", "BTC"), Some(1.0));
+        let btc_spy = matrix.get("BTC", "SPY").unwrap();
+        assert!(btc_spy > 0.9, "{btc_spy}");
+        assert_eq!(matrix.get("SPY", "BTC"), Some(btc_spy));
+        assert_eq!(matrix.get("BTC", "USD"), None);
+        assert_eq!(matrix.get("BTC", "ETH"), None);
+    }
+
+    #[cfg(all(feature = "stock", feature = "crypto"))]
+    #[test]
+    fn test_from_partial_skips_failed_assets() {
+        let closes: PartialResult<_, Asset> = [
+            (
+                Asset::Stock("SPY".to_string()),
+                Ok(vec![(0, 10.0), (86_400, 11.0), (172_800, 12.0)]),
+            ),
+            (
+                Asset::Crypto("BINANCE:BTCUSDT".to_string()),
+                Ok(vec![(0, 100.0), (86_400, 120.0), (172_800, 125.0)]),
+            ),
+            (
+                Asset::Stock("GONE".to_string()),
+                Err(crate::Error::InvalidParameter("delisted".to_string())),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let matrix = CorrelationMatrix::from_partial(&closes, CandleResolution::Daily);
+
+        assert_eq!(matrix.symbols(), ["BINANCE:BTCUSDT", "SPY"]);
+        assert_eq!(matrix.observations(), 2);
+        assert!(matrix.get("SPY", "BINANCE:BTCUSDT").is_some());
+    }
+}
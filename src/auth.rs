@@ -1,9 +1,92 @@
 //! Authentication handling for the Finnhub API.
+//!
+//! The API key never appears in `Debug` output, logs or error messages: it
+//! is held in an [`ApiKey`], the `token` query parameter is replaced with
+//! [`REDACTED`] and the `X-Finnhub-Token` header is marked sensitive. With
+//! the `zeroize` feature, the memory holding a key is also overwritten when
+//! it is dropped.
 
 use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use url::Url;
+
+/// Placeholder substituted for the API key wherever it would be displayed.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// An API key that doesn't print itself.
+///
+/// `Debug` shows [`REDACTED`] and there is no `Display`, so the key can't end
+/// up in logs or tracing backends through formatting;
+/// [`expose`](Self::expose) returns it where it must be sent. The client
+/// constructors accept anything that converts into one, including `String`
+/// and `&str`. With the `zeroize` feature the key's memory is overwritten
+/// when the value is dropped.
+///
+/// ```
+/// use finnhub::auth::ApiKey;
+///
+/// let key = ApiKey::from(std::env::var("FINNHUB_API_KEY").unwrap_or_default());
+/// println!("{key:?}"); // ApiKey("[REDACTED]")
+/// ```
+#[derive(Clone, Default)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Wrap `api_key`.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self(api_key.into())
+    }
+
+    /// The key itself.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the key is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(api_key: String) -> Self {
+        Self(api_key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(api_key: &str) -> Self {
+        Self(api_key.to_string())
+    }
+}
+
+impl From<&String> for ApiKey {
+    fn from(api_key: &String) -> Self {
+        Self(api_key.clone())
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ApiKey").field(&REDACTED).finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ApiKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
 
 /// Authentication method for API requests.
-#[derive(Debug, Clone, Copy)]
+///
+/// Serialized in snake case: `"url_parameter"` or `"header"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AuthMethod {
     /// Use API key as URL parameter.
     UrlParameter,
@@ -18,15 +101,15 @@ impl Default for AuthMethod {
 }
 
 /// Authentication configuration.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Auth {
-    api_key: String,
+    api_key: ApiKey,
     method: AuthMethod,
 }
 
 impl Auth {
     /// Create a new authentication configuration.
-    pub fn new(api_key: impl Into<String>) -> Self {
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
         Self {
             api_key: api_key.into(),
             method: AuthMethod::default(),
@@ -34,7 +117,7 @@ impl Auth {
     }
 
     /// Create authentication with specific method.
-    pub fn with_method(api_key: impl Into<String>, method: AuthMethod) -> Self {
+    pub fn with_method(api_key: impl Into<ApiKey>, method: AuthMethod) -> Self {
         Self {
             api_key: api_key.into(),
             method,
@@ -43,7 +126,7 @@ impl Auth {
 
     /// Get the API key.
     pub fn api_key(&self) -> &str {
-        &self.api_key
+        self.api_key.expose()
     }
 
     /// Get the authentication method.
@@ -54,7 +137,8 @@ impl Auth {
     /// Apply authentication to a URL.
     pub fn apply_to_url(&self, url: &mut url::Url) {
         if matches!(self.method, AuthMethod::UrlParameter) {
-            url.query_pairs_mut().append_pair("token", &self.api_key);
+            url.query_pairs_mut()
+                .append_pair("token", self.api_key.expose());
         }
     }
 
@@ -62,10 +146,88 @@ impl Auth {
     pub fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
         if matches!(self.method, AuthMethod::Header) {
-            if let Ok(value) = HeaderValue::from_str(&self.api_key) {
+            if let Ok(mut value) = HeaderValue::from_str(self.api_key.expose()) {
+                value.set_sensitive(true);
                 headers.insert("X-Finnhub-Token", value);
             }
         }
         headers
     }
+
+    /// Replace every occurrence of the API key in `text` with [`REDACTED`].
+    #[must_use]
+    pub fn redact(&self, text: &str) -> String {
+        if self.api_key.is_empty() {
+            text.to_string()
+        } else {
+            text.replace(self.api_key.expose(), REDACTED)
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Auth")
+            .field("api_key", &REDACTED)
+            .field("method", &self.method)
+            .finish()
+    }
+}
+
+/// Replace the value of the `token` query parameter in `url` with [`REDACTED`].
+pub fn redact_url(url: &mut Url) {
+    if !url.query_pairs().any(|(key, _)| key == "token") {
+        return;
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if key == "token" {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let auth = Auth::new("secret-key");
+        let output = format!("{auth:?}");
+        assert!(!output.contains("secret-key"));
+        assert!(output.contains(REDACTED));
+
+        let headers = auth.headers();
+        assert!(!format!("{headers:?}").contains("secret-key"));
+
+        let key = ApiKey::from("secret-key");
+        assert_eq!(format!("{key:?}"), format!("ApiKey({REDACTED:?})"));
+        assert_eq!(key.expose(), "secret-key");
+    }
+
+    #[test]
+    fn test_redact_url() {
+        let mut url =
+            Url::parse("https://finnhub.io/api/v1/quote?symbol=AAPL&token=secret-key").unwrap();
+        redact_url(&mut url);
+        assert!(!url.as_str().contains("secret-key"));
+        assert!(url.as_str().contains("symbol=AAPL"));
+    }
+
+    #[test]
+    fn test_redact_text() {
+        let auth = Auth::new("secret-key");
+        assert_eq!(
+            auth.redact("invalid token secret-key"),
+            format!("invalid token {REDACTED}")
+        );
+    }
 }
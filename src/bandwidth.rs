@@ -0,0 +1,164 @@
+//! Per-endpoint bandwidth accounting.
+//!
+//! Every response the client downloads is counted twice: the bytes that
+//! crossed the network, taken from `Content-Length`, and the bytes of the
+//! decoded body handed to the JSON parser. When the API compresses a response
+//! the first number is the compressed size. Cache hits download nothing and
+//! aren't counted.
+//!
+//! ```rust,no_run
+//! # async fn run(client: finnhub::FinnhubClient) -> finnhub::Result<()> {
+//! client.stock().quote("AAPL").await?;
+//! for (endpoint, usage) in client.bandwidth() {
+//!     println!(
+//!         "{endpoint}: {} requests, {} bytes on the wire, {} bytes decoded",
+//!         usage.requests, usage.wire_bytes, usage.body_bytes
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Responses from clients with a [`tag`](crate::ClientConfig::tag) are also
+//! totalled per tag, so services that hand differently tagged clients to
+//! their teams can attribute usage with
+//! [`FinnhubClient::bandwidth_by_tag`](crate::FinnhubClient::bandwidth_by_tag).
+//!
+//! Each response is also logged at `DEBUG` level with `wire_bytes`,
+//! `body_bytes` and `tag` fields, for collecting sizes through a `tracing`
+//! subscriber.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Bytes downloaded from one endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointBandwidth {
+    /// Responses received.
+    pub requests: u64,
+    /// Bytes received over the network. Uses the decoded size for responses
+    /// without a `Content-Length` header.
+    pub wire_bytes: u64,
+    /// Bytes of the decoded response bodies.
+    pub body_bytes: u64,
+    /// Responses that arrived with a `Content-Encoding` other than
+    /// `identity`.
+    pub compressed: u64,
+}
+
+impl EndpointBandwidth {
+    /// Decoded bytes per byte on the wire; 1.0 when nothing was compressed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compression_ratio(&self) -> f64 {
+        if self.wire_bytes == 0 {
+            return 1.0;
+        }
+        self.body_bytes as f64 / self.wire_bytes as f64
+    }
+}
+
+/// Counters shared by a client and the clients derived from it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BandwidthStats {
+    counters: Arc<Mutex<Counters>>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    endpoints: HashMap<String, EndpointBandwidth>,
+    tags: HashMap<String, EndpointBandwidth>,
+}
+
+impl EndpointBandwidth {
+    fn add(&mut self, wire_bytes: u64, body_bytes: u64, compressed: bool) {
+        self.requests += 1;
+        self.wire_bytes += wire_bytes;
+        self.body_bytes += body_bytes;
+        self.compressed += u64::from(compressed);
+    }
+}
+
+impl BandwidthStats {
+    /// Count a response from `endpoint` (a path and query) to a client
+    /// tagged `tag`.
+    pub(crate) fn record(
+        &self,
+        endpoint: &str,
+        tag: Option<&str>,
+        wire_bytes: Option<u64>,
+        body_bytes: u64,
+        compressed: bool,
+    ) {
+        let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+        let wire_bytes = wire_bytes.unwrap_or(body_bytes);
+        tracing::debug!(
+            endpoint = path,
+            tag,
+            wire_bytes,
+            body_bytes,
+            "response received"
+        );
+
+        let mut counters = self.lock();
+        counters
+            .endpoints
+            .entry(path.to_string())
+            .or_default()
+            .add(wire_bytes, body_bytes, compressed);
+        if let Some(tag) = tag {
+            counters
+                .tags
+                .entry(tag.to_string())
+                .or_default()
+                .add(wire_bytes, body_bytes, compressed);
+        }
+    }
+
+    /// Copy the counters, keyed by endpoint path.
+    pub(crate) fn snapshot(&self) -> HashMap<String, EndpointBandwidth> {
+        self.lock().endpoints.clone()
+    }
+
+    /// Copy the totals of tagged clients, keyed by tag.
+    pub(crate) fn snapshot_by_tag(&self) -> HashMap<String, EndpointBandwidth> {
+        self.lock().tags.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_groups_by_path() {
+        let stats = BandwidthStats::default();
+        stats.record("/quote?symbol=AAPL", Some("research"), Some(100), 400, true);
+        stats.record("/quote?symbol=MSFT", None, None, 300, false);
+        stats.record(
+            "/stock/profile2?symbol=AAPL",
+            Some("research"),
+            Some(50),
+            50,
+            false,
+        );
+
+        let snapshot = stats.snapshot();
+        let quote = snapshot["/quote"];
+        assert_eq!(quote.requests, 2);
+        assert_eq!(quote.wire_bytes, 400);
+        assert_eq!(quote.body_bytes, 700);
+        assert_eq!(quote.compressed, 1);
+        assert!((quote.compression_ratio() - 1.75).abs() < f64::EPSILON);
+        assert_eq!(snapshot["/stock/profile2"].requests, 1);
+
+        let by_tag = stats.snapshot_by_tag();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag["research"].requests, 2);
+        assert_eq!(by_tag["research"].body_bytes, 450);
+    }
+}
@@ -0,0 +1,432 @@
+//! Blocking mirrors of the endpoint groups.
+
+use super::FinnhubClient;
+use crate::{
+    error::Result,
+    models::{
+        calendar::{EarningsCalendar, EarningsFilter, EconomicCalendar, IPOCalendar},
+        index::{IndicesConstituents, IndicesHistoricalConstituents},
+        misc::{
+            AIChatRequest, AIChatResponse, AirlinePriceIndexData, CountryMetadata, CovidInfo,
+            FDACommitteeMeeting, PressRelease, SectorMetric, SymbolLookup, SymbolLookupInfo,
+            TechnicalIndicator,
+        },
+        news::{CompanyNews, MarketNews, NewsCategory, NewsSentiment},
+        scanner::{AggregateIndicators, PatternRecognition, SupportResistance},
+    },
+};
+
+#[cfg(feature = "bond")]
+use crate::models::bond::{BondPrice, BondProfile, BondTickData, BondYieldCurve};
+#[cfg(feature = "crypto")]
+use crate::models::crypto::{CryptoCandles, CryptoExchange, CryptoProfile, CryptoSymbol};
+#[cfg(feature = "economic")]
+use crate::models::economic::{EconomicCode, EconomicData};
+#[cfg(feature = "etf")]
+use crate::models::etf::{ETFCountryExposure, ETFHoldings, ETFProfile, ETFSectorExposure};
+#[cfg(feature = "forex")]
+use crate::models::forex::{ForexCandles, ForexRates, ForexSymbol};
+#[cfg(feature = "mutual-fund")]
+use crate::models::mutual_fund::{
+    MutualFundCountryExposureData, MutualFundEET, MutualFundEETPAI, MutualFundHoldings,
+    MutualFundProfile, MutualFundSectorExposureData,
+};
+#[cfg(any(feature = "stock", feature = "forex", feature = "crypto"))]
+use crate::models::stock::CandleResolution;
+#[cfg(feature = "stock")]
+use crate::{
+    endpoints::stock::company::{ProfileBatch, ProfilesOptions},
+    latency::WithLatency,
+    lenient::Lenient,
+    models::{stock::*, Exchange},
+    partial::PartialResult,
+};
+
+/// Declare a blocking endpoint group whose methods wait for the async group's
+/// method of the same name.
+macro_rules! blocking_group {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $group:ident {
+            $(
+                $(#[$method_meta:meta])*
+                fn $method:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        pub struct $name<'a> {
+            client: &'a FinnhubClient,
+        }
+
+        impl<'a> $name<'a> {
+            pub(super) fn new(client: &'a FinnhubClient) -> Self {
+                Self { client }
+            }
+
+            $(
+                #[doc = concat!(
+                    "Blocking [`", stringify!($method), "`](crate::endpoints::",
+                    stringify!($name), "::", stringify!($method), ")."
+                )]
+                $(#[$method_meta])*
+                pub fn $method(&self, $($arg: $ty),*) -> $ret {
+                    self.client
+                        .block_on(self.client.inner.$group().$method($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(feature = "stock")]
+blocking_group! {
+    /// Blocking [`StockEndpoints`](crate::endpoints::StockEndpoints).
+    StockEndpoints => stock {
+        fn quote(symbol: &str) -> Result<Quote>;
+        fn quotes(symbols: &[&str]) -> PartialResult<Quote>;
+        fn candles(
+            symbol: &str,
+            resolution: CandleResolution,
+            from: i64,
+            to: i64,
+        ) -> Result<StockCandles>;
+        fn quote_with_latency(symbol: &str) -> Result<WithLatency<Quote>>;
+        fn candles_with_latency(
+            symbol: &str,
+            resolution: CandleResolution,
+            from: i64,
+            to: i64,
+        ) -> Result<WithLatency<StockCandles>>;
+        fn bid_ask(symbol: &str) -> Result<BidAsk>;
+        fn tick_data(symbol: &str, date: &str, limit: i64, skip: i64) -> Result<TickData>;
+        fn price_metrics(symbol: &str) -> Result<PriceMetrics>;
+        fn company_profile(symbol: &str) -> Result<CompanyProfile>;
+        fn company_profiles(
+            symbols: &[&str],
+            options: &ProfilesOptions,
+        ) -> ProfileBatch;
+        fn peers(symbol: &str, grouping: Option<&str>) -> Result<Vec<String>>;
+        fn symbols(exchange: &str) -> Result<Vec<Symbol>>;
+        fn symbols_filtered(exchange: &str, filter: &SymbolFilter) -> Result<Vec<Symbol>>;
+        fn symbols_lenient(exchange: &str, filter: &SymbolFilter) -> Result<Lenient<Symbol>>;
+        fn financials(
+            symbol: &str,
+            statement: StatementType,
+            frequency: StatementFrequency,
+        ) -> Result<FinancialStatements>;
+        fn financials_normalized(
+            symbol: &str,
+            statement: StatementType,
+            frequency: StatementFrequency,
+        ) -> Result<NormalizedStatements>;
+        #[cfg(feature = "forex")]
+        fn financials_in_currency(
+            symbol: &str,
+            statement: StatementType,
+            frequency: StatementFrequency,
+            target: &str,
+        ) -> Result<NormalizedStatements>;
+        fn metrics(symbol: &str) -> Result<BasicFinancials>;
+        fn metrics_many(symbols: &[&str]) -> PartialResult<BasicFinancials>;
+        fn earnings(symbol: &str, limit: Option<i64>) -> Result<Vec<Earnings>>;
+        fn financials_reported(
+            symbol: Option<&str>,
+            cik: Option<&str>,
+            access_number: Option<&str>,
+            freq: Option<&str>,
+        ) -> Result<FinancialsAsReported>;
+        fn price_target(symbol: &str) -> Result<PriceTarget>;
+        fn recommendations(symbol: &str) -> Result<Vec<RecommendationTrend>>;
+        fn revenue_breakdown(symbol: &str) -> Result<RevenueBreakdown>;
+        fn upgrade_downgrade(
+            symbol: Option<&str>,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<Vec<UpgradeDowngrade>>;
+        fn insider_transactions(symbol: &str) -> Result<InsiderTransactions>;
+        fn insider_transactions_filtered(
+            symbol: &str,
+            filter: &InsiderFilter,
+        ) -> Result<InsiderTransactions>;
+        fn insider_sentiment(symbol: &str, from: &str, to: &str) -> Result<InsiderSentimentData>;
+        fn dividends(symbol: &str, from: &str, to: &str) -> Result<Vec<Dividend>>;
+        fn splits(symbol: &str, from: &str, to: &str) -> Result<Vec<StockSplit>>;
+        fn dividends_v2(symbol: &str) -> Result<DividendsV2>;
+        fn symbol_changes(from: &str, to: &str) -> Result<SymbolChanges>;
+        fn isin_changes(from: &str, to: &str) -> Result<IsinChanges>;
+        fn historical_market_cap(
+            symbol: &str,
+            from: &str,
+            to: &str,
+        ) -> Result<HistoricalMarketCapData>;
+        fn historical_employee_count(
+            symbol: &str,
+            from: &str,
+            to: &str,
+        ) -> Result<HistoricalEmployeeCount>;
+        fn historical_esg(symbol: &str, from: &str, to: &str) -> Result<HistoricalESG>;
+        fn historical_market_cap_range(
+            symbol: &str,
+            from: &str,
+            to: &str,
+        ) -> Result<HistoricalMarketCapData>;
+        fn historical_employee_count_range(
+            symbol: &str,
+            from: &str,
+            to: &str,
+        ) -> Result<HistoricalEmployeeCount>;
+        fn historical_esg_range(symbol: &str, from: &str, to: &str) -> Result<HistoricalESG>;
+        fn historical_nbbo(
+            symbol: &str,
+            date: &str,
+            limit: i64,
+            skip: i64,
+        ) -> Result<HistoricalNBBO>;
+        fn social_sentiment(symbol: &str, from: &str, to: &str) -> Result<SocialSentiment>;
+        fn filing_sentiment(access_number: &str) -> Result<FilingSentiment>;
+        fn market_status(exchange: &str) -> Result<MarketStatus>;
+        fn market_status_many(
+            exchanges: impl IntoIterator<Item = impl Into<Exchange>>,
+        ) -> PartialResult<MarketStatus, Exchange>;
+        fn market_holiday(exchange: &str) -> Result<MarketHoliday>;
+        fn investment_theme(theme: &str) -> Result<InvestmentTheme>;
+        fn ownership(symbol: &str, limit: Option<i64>) -> Result<OwnershipData>;
+        fn fund_ownership(symbol: &str, limit: Option<i64>) -> Result<FundOwnership>;
+        fn sec_filings(
+            symbol: Option<&str>,
+            cik: Option<&str>,
+            access_number: Option<&str>,
+            form: Option<&str>,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<Vec<Filing>>;
+        fn sec_filings_lenient(
+            symbol: Option<&str>,
+            cik: Option<&str>,
+            access_number: Option<&str>,
+            form: Option<&str>,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<Lenient<Filing>>;
+        fn international_filings(
+            symbol: Option<&str>,
+            country: Option<&str>,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<Vec<InternationalFiling>>;
+        fn transcripts(id: &str) -> Result<EarningsCallTranscript>;
+        fn transcripts_list(symbol: &str) -> Result<EarningsCallTranscriptsList>;
+        fn earnings_call_live(from: &str, to: &str) -> Result<EarningsCallLive>;
+        fn presentations(symbol: &str) -> Result<InvestorPresentations>;
+        fn similarity_index(
+            symbol: Option<&str>,
+            cik: Option<&str>,
+            freq: Option<&str>,
+        ) -> Result<SimilarityIndex>;
+        fn eps_estimates(symbol: &str, freq: Option<&str>) -> Result<EPSEstimates>;
+        fn revenue_estimates(symbol: &str, freq: Option<&str>) -> Result<RevenueEstimates>;
+        fn revenue_series(symbol: &str, frequency: StatementFrequency) -> Result<RevenueSeries>;
+        fn ebitda_estimates(symbol: &str, freq: Option<&str>) -> Result<EBITDAEstimates>;
+        fn ebit_estimates(symbol: &str, freq: Option<&str>) -> Result<EBITEstimates>;
+        fn estimate_accuracy(symbol: &str, periods: usize) -> Result<EstimatesAccuracy>;
+        fn earnings_quality_score(symbol: &str, freq: &str) -> Result<EarningsQualityScore>;
+        fn executives(symbol: &str) -> Result<CompanyExecutives>;
+        fn congressional_trading(
+            symbol: &str,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<CongressionalTrading>;
+        fn lobbying(symbol: &str, from: Option<&str>, to: Option<&str>) -> Result<Lobbying>;
+        fn usa_spending(symbol: &str, from: Option<&str>, to: Option<&str>) -> Result<USASpending>;
+        fn esg(symbol: &str) -> Result<ESGScore>;
+        fn supply_chain(symbol: &str) -> Result<SupplyChainData>;
+        fn uspto_patents(symbol: &str, from: &str, to: &str) -> Result<USPTOPatents>;
+        fn visa_applications(symbol: &str, from: &str, to: &str) -> Result<VisaApplications>;
+        fn timeline(
+            symbol: &str,
+            range: std::ops::RangeInclusive<chrono::NaiveDate>,
+            sources: TimelineSources,
+        ) -> Result<CompanyTimeline>;
+    }
+}
+
+#[cfg(feature = "forex")]
+blocking_group! {
+    /// Blocking [`ForexEndpoints`](crate::endpoints::ForexEndpoints).
+    ForexEndpoints => forex {
+        fn symbols(exchange: &str) -> Result<Vec<ForexSymbol>>;
+        fn candles(
+            symbol: &str,
+            resolution: CandleResolution,
+            from: i64,
+            to: i64,
+        ) -> Result<ForexCandles>;
+        fn rates(base: &str) -> Result<ForexRates>;
+        fn exchanges() -> Result<Vec<String>>;
+    }
+}
+
+#[cfg(feature = "crypto")]
+blocking_group! {
+    /// Blocking [`CryptoEndpoints`](crate::endpoints::CryptoEndpoints).
+    CryptoEndpoints => crypto {
+        fn exchanges() -> Result<Vec<CryptoExchange>>;
+        fn symbols(exchange: &str) -> Result<Vec<CryptoSymbol>>;
+        fn candles(
+            symbol: &str,
+            resolution: CandleResolution,
+            from: i64,
+            to: i64,
+        ) -> Result<CryptoCandles>;
+        fn candles_normalized(
+            symbol: &str,
+            resolution: CandleResolution,
+            from: i64,
+            to: i64,
+        ) -> Result<CryptoCandles>;
+        fn profile(symbol: &str) -> Result<CryptoProfile>;
+    }
+}
+
+blocking_group! {
+    /// Blocking [`NewsEndpoints`](crate::endpoints::NewsEndpoints).
+    NewsEndpoints => news {
+        fn market_news(category: NewsCategory, min_id: Option<i64>) -> Result<Vec<MarketNews>>;
+        fn company_news(symbol: &str, from: &str, to: &str) -> Result<Vec<CompanyNews>>;
+        fn news_sentiment(symbol: &str) -> Result<NewsSentiment>;
+    }
+}
+
+blocking_group! {
+    /// Blocking [`CalendarEndpoints`](crate::endpoints::CalendarEndpoints).
+    CalendarEndpoints => calendar {
+        fn earnings(
+            from: Option<&str>,
+            to: Option<&str>,
+            symbol: Option<&str>,
+        ) -> Result<EarningsCalendar>;
+        fn earnings_filtered(filter: &EarningsFilter) -> Result<EarningsCalendar>;
+        fn economic(from: Option<&str>, to: Option<&str>) -> Result<EconomicCalendar>;
+        fn ipo(from: &str, to: &str) -> Result<IPOCalendar>;
+    }
+}
+
+#[cfg(feature = "etf")]
+blocking_group! {
+    /// Blocking [`ETFEndpoints`](crate::endpoints::ETFEndpoints).
+    ETFEndpoints => etf {
+        fn profile(symbol: Option<&str>, isin: Option<&str>) -> Result<ETFProfile>;
+        fn compare(symbols: &[&str]) -> PartialResult<ETFProfile>;
+        fn holdings(
+            symbol: Option<&str>,
+            isin: Option<&str>,
+            skip: Option<i64>,
+            date: Option<&str>,
+        ) -> Result<ETFHoldings>;
+        fn country_exposure(symbol: Option<&str>, isin: Option<&str>) -> Result<ETFCountryExposure>;
+        fn sector_exposure(symbol: Option<&str>, isin: Option<&str>) -> Result<ETFSectorExposure>;
+    }
+}
+
+#[cfg(feature = "bond")]
+blocking_group! {
+    /// Blocking [`BondEndpoints`](crate::endpoints::BondEndpoints).
+    BondEndpoints => bond {
+        fn profile(
+            figi: Option<&str>,
+            isin: Option<&str>,
+            cusip: Option<&str>,
+        ) -> Result<BondProfile>;
+        fn price(isin: &str) -> Result<BondPrice>;
+        fn tick(
+            isin: &str,
+            date: &str,
+            limit: i64,
+            skip: i64,
+            exchange: &str,
+        ) -> Result<BondTickData>;
+        fn yield_curve(code: &str) -> Result<BondYieldCurve>;
+    }
+}
+
+#[cfg(feature = "mutual-fund")]
+blocking_group! {
+    /// Blocking [`MutualFundEndpoints`](crate::endpoints::MutualFundEndpoints).
+    MutualFundEndpoints => mutual_fund {
+        fn profile(symbol: Option<&str>, isin: Option<&str>) -> Result<MutualFundProfile>;
+        fn holdings(
+            symbol: Option<&str>,
+            isin: Option<&str>,
+            skip: Option<i64>,
+        ) -> Result<MutualFundHoldings>;
+        fn country_exposure(
+            symbol: Option<&str>,
+            isin: Option<&str>,
+        ) -> Result<MutualFundCountryExposureData>;
+        fn sector_exposure(
+            symbol: Option<&str>,
+            isin: Option<&str>,
+        ) -> Result<MutualFundSectorExposureData>;
+        fn eet(isin: &str) -> Result<MutualFundEET>;
+        fn eet_pai(isin: &str) -> Result<MutualFundEETPAI>;
+    }
+}
+
+#[cfg(feature = "economic")]
+blocking_group! {
+    /// Blocking [`EconomicEndpoints`](crate::endpoints::EconomicEndpoints).
+    EconomicEndpoints => economic {
+        fn data(code: &str) -> Result<EconomicData>;
+        fn codes() -> Result<Vec<EconomicCode>>;
+    }
+}
+
+blocking_group! {
+    /// Blocking [`IndexEndpoints`](crate::endpoints::IndexEndpoints).
+    IndexEndpoints => index {
+        fn constituents(symbol: &str) -> Result<IndicesConstituents>;
+        fn historical_constituents(symbol: &str) -> Result<IndicesHistoricalConstituents>;
+    }
+}
+
+blocking_group! {
+    /// Blocking [`MiscEndpoints`](crate::endpoints::MiscEndpoints).
+    MiscEndpoints => misc {
+        fn ai_chat(request: &AIChatRequest) -> Result<AIChatResponse>;
+        fn airline_price_index(
+            airline: &str,
+            from: &str,
+            to: &str,
+        ) -> Result<AirlinePriceIndexData>;
+        fn country() -> Result<Vec<CountryMetadata>>;
+        fn covid19() -> Result<Vec<CovidInfo>>;
+        fn fda_calendar() -> Result<Vec<FDACommitteeMeeting>>;
+        fn technical_indicator(
+            symbol: &str,
+            resolution: &str,
+            from: i64,
+            to: i64,
+            indicator: &str,
+            indicator_fields: Option<serde_json::Value>,
+        ) -> Result<TechnicalIndicator>;
+        fn press_releases(
+            symbol: &str,
+            from: Option<&str>,
+            to: Option<&str>,
+        ) -> Result<PressRelease>;
+        fn symbol_search(query: &str, exchange: Option<&str>) -> Result<SymbolLookup>;
+        fn symbol_search_ranked(query: &str, exchange: Option<&str>) -> Result<SymbolLookup>;
+        fn search_exact(ticker: &str) -> Result<Option<SymbolLookupInfo>>;
+        fn sector_metrics(region: &str) -> Result<SectorMetric>;
+    }
+}
+
+blocking_group! {
+    /// Blocking [`ScannerEndpoints`](crate::endpoints::ScannerEndpoints).
+    ScannerEndpoints => scanner {
+        fn pattern_recognition(symbol: &str, resolution: &str) -> Result<PatternRecognition>;
+        fn support_resistance(symbol: &str, resolution: &str) -> Result<SupportResistance>;
+        fn aggregate_indicators(symbol: &str, resolution: &str) -> Result<AggregateIndicators>;
+    }
+}
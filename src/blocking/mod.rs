@@ -0,0 +1,277 @@
+//! Blocking client for code that isn't async.
+//!
+//! [`FinnhubClient`] mirrors the async [`crate::FinnhubClient`] endpoint
+//! group by group, returning results instead of futures:
+//!
+//! ```rust,no_run
+//! use finnhub::blocking::FinnhubClient;
+//!
+//! fn main() -> finnhub::Result<()> {
+//!     let client = FinnhubClient::new("your-api-key");
+//!     let quote = client.stock().quote("AAPL")?;
+//!     println!("AAPL: {}", quote.current_price);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Like `reqwest::blocking`, it drives the async client on a private runtime
+//! with one worker thread, so the rate limiter, retry budget, cache and error
+//! handling are the async client's own. Streams and watchers have no blocking
+//! form; run them with [`FinnhubClient::block_on`] on
+//! [`as_async`](FinnhubClient::as_async).
+//!
+//! The blocking client must not be created, used or dropped inside an async
+//! runtime; that panics.
+
+mod endpoints;
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::{self, Runtime};
+
+use crate::auth::ApiKey;
+use crate::client::ClientConfig;
+use crate::error::Result;
+
+#[cfg(feature = "bond")]
+pub use endpoints::BondEndpoints;
+#[cfg(feature = "crypto")]
+pub use endpoints::CryptoEndpoints;
+#[cfg(feature = "etf")]
+pub use endpoints::ETFEndpoints;
+#[cfg(feature = "economic")]
+pub use endpoints::EconomicEndpoints;
+#[cfg(feature = "forex")]
+pub use endpoints::ForexEndpoints;
+#[cfg(feature = "mutual-fund")]
+pub use endpoints::MutualFundEndpoints;
+#[cfg(feature = "stock")]
+pub use endpoints::StockEndpoints;
+pub use endpoints::{
+    CalendarEndpoints, IndexEndpoints, MiscEndpoints, NewsEndpoints, ScannerEndpoints,
+};
+
+/// Blocking Finnhub client.
+///
+/// Cloning is cheap; clones share the async client and the runtime.
+#[derive(Clone, Debug)]
+pub struct FinnhubClient {
+    inner: crate::FinnhubClient,
+    runtime: Arc<Runtime>,
+}
+
+impl FinnhubClient {
+    /// Create a new client with the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime can't be started or if called inside an async
+    /// runtime.
+    #[must_use]
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
+        Self::with_config(api_key, ClientConfig::default())
+    }
+
+    /// Create a new client with custom configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base URL or the proxy is invalid, if the runtime can't
+    /// be started or if called inside an async runtime.
+    #[must_use]
+    pub fn with_config(api_key: impl Into<ApiKey>, config: ClientConfig) -> Self {
+        Self::from_async(crate::FinnhubClient::with_config(api_key, config))
+    }
+
+    /// Create a new client with custom configuration, validating it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`](crate::Error::InvalidConfig) if
+    /// [`ClientConfig::validate`] rejects the configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime can't be started or if called inside an async
+    /// runtime.
+    pub fn try_with_config(api_key: impl Into<ApiKey>, config: ClientConfig) -> Result<Self> {
+        crate::FinnhubClient::try_with_config(api_key, config).map(Self::from_async)
+    }
+
+    /// Wrap an async client, sharing its rate limiter, cache and connection
+    /// pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime can't be started or if called inside an async
+    /// runtime.
+    #[must_use]
+    pub fn from_async(client: crate::FinnhubClient) -> Self {
+        assert!(
+            runtime::Handle::try_current().is_err(),
+            "the blocking client can't be used inside an async runtime"
+        );
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("finnhub-blocking")
+            .enable_all()
+            .build()
+            .expect("Failed to start the blocking client's runtime");
+        Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client requests are made with, e.g. for its configuration,
+    /// bandwidth counters or streams.
+    #[must_use]
+    pub fn as_async(&self) -> &crate::FinnhubClient {
+        &self.inner
+    }
+
+    /// Run a future on the client's runtime and wait for its output.
+    ///
+    /// ```rust,no_run
+    /// # let client = finnhub::blocking::FinnhubClient::new("your-api-key");
+    /// let quote = client.block_on(client.as_async().retry(3, || async {
+    ///     client.as_async().stock().quote("AAPL").await
+    /// }))?;
+    /// # Ok::<(), finnhub::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if called inside an async runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Get stock market endpoints.
+    #[cfg(feature = "stock")]
+    #[must_use]
+    pub fn stock(&self) -> StockEndpoints<'_> {
+        StockEndpoints::new(self)
+    }
+
+    /// Get forex market endpoints.
+    #[cfg(feature = "forex")]
+    #[must_use]
+    pub fn forex(&self) -> ForexEndpoints<'_> {
+        ForexEndpoints::new(self)
+    }
+
+    /// Get cryptocurrency endpoints.
+    #[cfg(feature = "crypto")]
+    #[must_use]
+    pub fn crypto(&self) -> CryptoEndpoints<'_> {
+        CryptoEndpoints::new(self)
+    }
+
+    /// Get news endpoints.
+    #[must_use]
+    pub fn news(&self) -> NewsEndpoints<'_> {
+        NewsEndpoints::new(self)
+    }
+
+    /// Get calendar endpoints.
+    #[must_use]
+    pub fn calendar(&self) -> CalendarEndpoints<'_> {
+        CalendarEndpoints::new(self)
+    }
+
+    /// Get ETF endpoints.
+    #[cfg(feature = "etf")]
+    #[must_use]
+    pub fn etf(&self) -> ETFEndpoints<'_> {
+        ETFEndpoints::new(self)
+    }
+
+    /// Get bond endpoints.
+    #[cfg(feature = "bond")]
+    #[must_use]
+    pub fn bond(&self) -> BondEndpoints<'_> {
+        BondEndpoints::new(self)
+    }
+
+    /// Get mutual fund endpoints.
+    #[cfg(feature = "mutual-fund")]
+    #[must_use]
+    pub fn mutual_fund(&self) -> MutualFundEndpoints<'_> {
+        MutualFundEndpoints::new(self)
+    }
+
+    /// Get economic data endpoints.
+    #[cfg(feature = "economic")]
+    #[must_use]
+    pub fn economic(&self) -> EconomicEndpoints<'_> {
+        EconomicEndpoints::new(self)
+    }
+
+    /// Get index endpoints.
+    #[must_use]
+    pub fn index(&self) -> IndexEndpoints<'_> {
+        IndexEndpoints::new(self)
+    }
+
+    /// Get miscellaneous endpoints.
+    #[must_use]
+    pub fn misc(&self) -> MiscEndpoints<'_> {
+        MiscEndpoints::new(self)
+    }
+
+    /// Get scanner/technical analysis endpoints.
+    #[must_use]
+    pub fn scanner(&self) -> ScannerEndpoints<'_> {
+        ScannerEndpoints::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    #[cfg(feature = "stock")]
+    fn test_blocking_quote() {
+        // The mock server needs a runtime of its own; the client must not be
+        // used inside it.
+        let server_runtime = Runtime::new().unwrap();
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v1/quote"))
+                .and(query_param("symbol", "AAPL"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "c": 189.84, "d": -1.33, "dp": -0.70, "h": 191.05,
+                    "l": 189.28, "o": 190.68, "pc": 191.17, "t": 1_714_766_400
+                })))
+                .expect(2)
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        let quote = client.stock().quote("AAPL").unwrap();
+        assert!((quote.current_price - 189.84).abs() < f64::EPSILON);
+
+        // Clones share the async client.
+        let clone = client.clone();
+        clone.stock().quote("AAPL").unwrap();
+        assert!(clone.as_async().ptr_eq(client.as_async()));
+        assert_eq!(client.as_async().bandwidth()["/quote"].requests, 2);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "inside an async runtime")]
+    async fn test_rejects_async_context() {
+        let _ = FinnhubClient::new("test-api-key");
+    }
+}
@@ -0,0 +1,366 @@
+//! Opt-in in-memory response cache.
+//!
+//! The client doesn't cache by default. With the `cache` feature, setting
+//! [`ClientConfig::cache`](crate::ClientConfig::cache) enables a per-client cache
+//! of successful responses, with a time-to-live chosen per endpoint by a
+//! [`CachePolicy`]:
+//!
+//! ```rust,no_run
+//! use finnhub::{cache::CachePolicy, ClientConfig, FinnhubClient};
+//!
+//! let config = ClientConfig {
+//!     cache: Some(CachePolicy::default()),
+//!     ..ClientConfig::default()
+//! };
+//! let client = FinnhubClient::with_config("your-api-key", config);
+//! ```
+//!
+//! TTLs can be set per endpoint path or for a whole [`CacheCategory`] of
+//! endpoints whose data changes at a similar pace.
+//!
+//! Cache hits don't count against the rate limit. To have data ready before
+//! it's needed, e.g. ahead of the market open, warm the cache in the
+//! background with [`FinnhubClient::prefetch`](crate::FinnhubClient::prefetch).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::rt::Instant;
+
+/// Endpoints grouped by how often their data changes, for setting TTLs in
+/// bulk with [`CachePolicy::with_category_ttl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheCategory {
+    /// Quotes and bid/ask, which change constantly.
+    Quotes,
+    /// Company, fund, bond and crypto profiles.
+    Profiles,
+    /// Symbol and exchange lists and symbol lookup.
+    SymbolLists,
+    /// Reported financials and basic financials.
+    Fundamentals,
+    /// Market holidays.
+    Calendars,
+}
+
+impl CacheCategory {
+    /// Every category.
+    pub const ALL: [Self; 5] = [
+        Self::Quotes,
+        Self::Profiles,
+        Self::SymbolLists,
+        Self::Fundamentals,
+        Self::Calendars,
+    ];
+
+    /// Endpoint paths in the category.
+    #[must_use]
+    pub fn paths(self) -> &'static [&'static str] {
+        match self {
+            Self::Quotes => &["/quote", "/stock/bidask"],
+            Self::Profiles => &[
+                "/stock/profile",
+                "/stock/profile2",
+                "/etf/profile",
+                "/mutual-fund/profile",
+                "/bond/profile",
+                "/crypto/profile",
+            ],
+            Self::SymbolLists => &[
+                "/stock/symbol",
+                "/forex/symbol",
+                "/forex/exchange",
+                "/crypto/symbol",
+                "/crypto/exchange",
+                "/search",
+            ],
+            Self::Fundamentals => &[
+                "/stock/financials",
+                "/stock/financials-reported",
+                "/stock/metric",
+            ],
+            Self::Calendars => &["/stock/market-holiday"],
+        }
+    }
+}
+
+/// Time-to-live per endpoint path.
+///
+/// Endpoints without an entry are never cached, so only data known to change
+/// slowly is served from the cache. [`CachePolicy::default`] covers:
+///
+/// | Endpoints | TTL |
+/// |-----------|-----|
+/// | `/quote` | 2 s |
+/// | `/stock/bidask` | 1 s |
+/// | [`CacheCategory::Profiles`] | 24 h |
+/// | [`CacheCategory::SymbolLists`] | 24 h |
+/// | [`CacheCategory::Fundamentals`] | 6 h |
+/// | [`CacheCategory::Calendars`] | 7 days |
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachePolicy {
+    ttls: HashMap<String, Duration>,
+    /// Maximum number of cached responses. When full, expired entries are
+    /// dropped first, then the oldest.
+    pub max_entries: usize,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        let day = Duration::from_secs(24 * 60 * 60);
+        Self::empty()
+            .with_ttl("/quote", Duration::from_secs(2))
+            .with_ttl("/stock/bidask", Duration::from_secs(1))
+            .with_category_ttl(CacheCategory::Profiles, day)
+            .with_category_ttl(CacheCategory::SymbolLists, day)
+            .with_category_ttl(
+                CacheCategory::Fundamentals,
+                Duration::from_secs(6 * 60 * 60),
+            )
+            .with_category_ttl(CacheCategory::Calendars, day * 7)
+    }
+}
+
+impl CachePolicy {
+    /// A policy that caches nothing; add endpoints with [`with_ttl`](Self::with_ttl).
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            ttls: HashMap::new(),
+            max_entries: 10_000,
+        }
+    }
+
+    /// Cache responses from `path` (e.g. `/stock/metric`) for `ttl`,
+    /// replacing any existing entry.
+    #[must_use]
+    pub fn with_ttl(mut self, path: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(path.into(), ttl);
+        self
+    }
+
+    /// Cache responses from every endpoint in `category` for `ttl`. Later
+    /// calls to [`with_ttl`](Self::with_ttl) override single paths:
+    ///
+    /// ```rust
+    /// use finnhub::cache::{CacheCategory, CachePolicy};
+    /// use std::time::Duration;
+    ///
+    /// let policy = CachePolicy::empty()
+    ///     .with_category_ttl(CacheCategory::Profiles, Duration::from_secs(12 * 60 * 60))
+    ///     .with_ttl("/stock/profile2", Duration::from_secs(60 * 60));
+    /// assert_eq!(policy.ttl("/etf/profile"), Some(Duration::from_secs(12 * 60 * 60)));
+    /// assert_eq!(policy.ttl("/stock/profile2"), Some(Duration::from_secs(60 * 60)));
+    /// ```
+    #[must_use]
+    pub fn with_category_ttl(mut self, category: CacheCategory, ttl: Duration) -> Self {
+        for path in category.paths() {
+            self.ttls.insert((*path).to_string(), ttl);
+        }
+        self
+    }
+
+    /// Stop caching responses from `path`.
+    #[must_use]
+    pub fn without(mut self, path: &str) -> Self {
+        self.ttls.remove(path);
+        self
+    }
+
+    /// Time-to-live for responses from `path`, if they are cached.
+    #[must_use]
+    pub fn ttl(&self, path: &str) -> Option<Duration> {
+        self.ttls.get(path).copied().filter(|ttl| !ttl.is_zero())
+    }
+}
+
+/// Outcome of [`FinnhubClient::prefetch`](crate::FinnhubClient::prefetch).
+#[derive(Debug, Default)]
+pub struct PrefetchReport {
+    /// Endpoints fetched and cached.
+    pub fetched: Vec<String>,
+    /// Endpoints skipped because the policy doesn't cache them or a fresh
+    /// response was already cached.
+    pub skipped: Vec<String>,
+    /// Endpoints whose rate limiter refused them or whose request failed.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// Response bodies keyed by endpoint (path and query, without credentials).
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    policy: RwLock<CachePolicy>,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: Vec<u8>,
+    inserted: Instant,
+    expires: Instant,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached body for `endpoint`, if present and fresh.
+    pub(crate) fn get(&self, endpoint: &str) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(endpoint) {
+            Some(entry) if entry.expires > Instant::now() => Some(entry.body.clone()),
+            Some(_) => {
+                entries.remove(endpoint);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Whether the policy caches `endpoint`'s path.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn caches(&self, endpoint: &str) -> bool {
+        self.ttl(endpoint).is_some()
+    }
+
+    fn ttl(&self, endpoint: &str) -> Option<Duration> {
+        self.policy
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .ttl(path_of(endpoint))
+    }
+
+    /// Replace the policy's TTLs, keeping its `max_entries`. Cached responses
+    /// of paths the new TTLs don't cache are dropped; the rest keep the
+    /// expiry they were stored with. Returns whether the TTLs changed.
+    pub(crate) fn set_ttls(&self, ttls: HashMap<String, Duration>) -> bool {
+        let mut policy = self.policy.write().unwrap_or_else(PoisonError::into_inner);
+        if policy.ttls == ttls {
+            return false;
+        }
+        policy.ttls = ttls;
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|endpoint, _| policy.ttl(path_of(endpoint)).is_some());
+        true
+    }
+
+    /// Store `body` if the policy caches `endpoint`'s path.
+    pub(crate) fn insert(&self, endpoint: &str, body: &[u8]) {
+        let Some(ttl) = self.ttl(endpoint) else {
+            return;
+        };
+
+        let max_entries = self
+            .policy
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_entries;
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= max_entries && !entries.contains_key(endpoint) {
+            entries.retain(|_, entry| entry.expires > now);
+            if entries.len() >= max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.inserted)
+                    .map(|(key, _)| key.clone());
+                if let Some(key) = oldest {
+                    entries.remove(&key);
+                }
+            }
+        }
+        if max_entries > 0 {
+            entries.insert(
+                endpoint.to_string(),
+                Entry {
+                    body: body.to_vec(),
+                    inserted: now,
+                    expires: now + ttl,
+                },
+            );
+        }
+    }
+}
+
+/// The path of an endpoint, without its query.
+fn path_of(endpoint: &str) -> &str {
+    endpoint.split_once('?').map_or(endpoint, |(path, _)| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_defaults_and_overrides() {
+        let policy = CachePolicy::default();
+        assert_eq!(policy.ttl("/quote"), Some(Duration::from_secs(2)));
+        assert_eq!(policy.ttl("/stock/candle"), None);
+
+        let policy = policy
+            .with_ttl("/quote", Duration::ZERO)
+            .without("/stock/metric");
+        assert_eq!(policy.ttl("/quote"), None);
+        assert_eq!(policy.ttl("/stock/metric"), None);
+    }
+
+    #[test]
+    fn test_category_paths_are_known_endpoints() {
+        for category in CacheCategory::ALL {
+            for path in category.paths() {
+                let info = crate::coverage::endpoint_info(path);
+                assert!(info.is_some(), "{category:?}: unknown endpoint {path}");
+            }
+        }
+
+        let policy = CachePolicy::empty().with_category_ttl(CacheCategory::Quotes, Duration::ZERO);
+        assert_eq!(policy.ttl("/quote"), None);
+    }
+
+    #[test]
+    fn test_cache_expiry_and_capacity() {
+        let mut policy = CachePolicy::empty().with_ttl("/a", Duration::from_secs(60 * 60));
+        policy.max_entries = 1;
+        let cache = ResponseCache::new(policy.with_ttl("/b", Duration::from_millis(1)));
+
+        cache.insert("/c?x=1", b"{}");
+        assert_eq!(cache.get("/c?x=1"), None);
+
+        cache.insert("/a?x=1", b"1");
+        cache.insert("/a?x=2", b"2");
+        assert_eq!(cache.get("/a?x=1"), None);
+        assert_eq!(cache.get("/a?x=2").as_deref(), Some(&b"2"[..]));
+
+        cache.insert("/b", b"3");
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.get("/b"), None);
+    }
+
+    #[test]
+    fn test_set_ttls_drops_uncached_paths() {
+        let cache = ResponseCache::new(
+            CachePolicy::empty()
+                .with_ttl("/a", Duration::from_secs(60 * 60))
+                .with_ttl("/b", Duration::from_secs(60 * 60)),
+        );
+        cache.insert("/a?x=1", b"1");
+        cache.insert("/b?x=1", b"2");
+
+        let ttls = HashMap::from([("/a".to_string(), Duration::from_secs(60 * 60))]);
+        assert!(cache.set_ttls(ttls.clone()));
+        assert!(!cache.set_ttls(ttls));
+        assert_eq!(cache.get("/a?x=1").as_deref(), Some(&b"1"[..]));
+        assert_eq!(cache.get("/b?x=1"), None);
+
+        cache.insert("/b?x=1", b"2");
+        assert_eq!(cache.get("/b?x=1"), None);
+    }
+}
@@ -1,26 +1,81 @@
 //! Main Finnhub client implementation.
 
-use reqwest::{Client as HttpClient, Response};
-use serde::de::DeserializeOwned;
+use bytes::Bytes;
+use futures::StreamExt;
+use reqwest::{
+    header::{HeaderMap, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    Client as HttpClient, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
 use url::Url;
 
 use crate::{
-    auth::{Auth, AuthMethod},
+    auth::{redact_url, ApiKey, Auth, AuthMethod},
+    bandwidth::{BandwidthStats, EndpointBandwidth},
+    compression,
     endpoints::{
-        BondEndpoints, CalendarEndpoints, CryptoEndpoints, ETFEndpoints, EconomicEndpoints,
-        ForexEndpoints, IndexEndpoints, MiscEndpoints, MutualFundEndpoints, NewsEndpoints,
-        ScannerEndpoints, StockEndpoints,
+        CalendarEndpoints, IndexEndpoints, MiscEndpoints, NewsEndpoints, ScannerEndpoints,
     },
     error::{Error, Result},
-    rate_limiter::RateLimiter,
+    failover::BaseUrls,
+    key_pool::KeyPool,
+    latency::{DataLatency, LatencyPolicy},
+    lenient,
+    limits::RATE_LIMIT_PER_SECOND,
+    proxy::ProxyConfig,
+    rate_limiter::{EndpointBucket, EndpointLimiters, RateLimit},
+    reload::ReloadableConfig,
+    response::{self, RateLimitHeaders, ResponseEnvelope},
+    retry::{RetryBudget, RetryPolicy},
+    rt,
+    symbol::VenueDefaults,
+    validation::{Validate, ValidationMode},
 };
 
+#[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+use crate::cache::PrefetchReport;
+#[cfg(feature = "cache")]
+use crate::cache::{CachePolicy, ResponseCache};
+
+#[cfg(feature = "bond")]
+use crate::endpoints::BondEndpoints;
+#[cfg(feature = "crypto")]
+use crate::endpoints::CryptoEndpoints;
+#[cfg(feature = "etf")]
+use crate::endpoints::ETFEndpoints;
+#[cfg(feature = "economic")]
+use crate::endpoints::EconomicEndpoints;
+#[cfg(feature = "forex")]
+use crate::endpoints::ForexEndpoints;
+#[cfg(feature = "mutual-fund")]
+use crate::endpoints::MutualFundEndpoints;
+#[cfg(feature = "stock")]
+use crate::endpoints::StockEndpoints;
+#[cfg(all(feature = "stock", not(target_arch = "wasm32")))]
+use crate::watch::TickerTape;
+
 const DEFAULT_BASE_URL: &str = "https://finnhub.io/api/v1";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_FAILOVER_AFTER_SECS: u64 = 30;
+/// Longest pause `adaptive_rate_limit` applies when the server reports no
+/// calls left, in case a reset header is far off.
+const MAX_SYNC_PAUSE: Duration = Duration::from_secs(60);
+/// Maximum number of characters of a non-JSON body kept in error messages.
+const SNIPPET_LEN: usize = 200;
+/// Error Finnhub sends with a 403 for endpoints the key's plan doesn't cover.
+const ACCESS_DENIED: &str = "You don't have access to this resource";
 
 /// Rate limiting strategy for the client.
-#[derive(Debug, Clone, Copy)]
+///
+/// Serialized in snake case, e.g. `"per_second"` or
+/// `{"custom": {"capacity": 60, "refill_rate": 60}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RateLimitStrategy {
     /// Standard per-second rate limiting (30 req/s).
     PerSecond,
@@ -41,100 +96,951 @@ impl Default for RateLimitStrategy {
     }
 }
 
+impl RateLimitStrategy {
+    /// Strategy equivalent to the deprecated `ClientConfig::rate_limit` field:
+    /// a bucket of `requests_per_second` tokens refilled at the same rate.
+    #[must_use]
+    pub fn from_legacy(requests_per_second: u32) -> Self {
+        Self::Custom {
+            capacity: requests_per_second,
+            refill_rate: requests_per_second,
+        }
+    }
+
+    /// Bucket capacity and refill rate per second.
+    fn limits(self) -> (u32, u32) {
+        match self {
+            Self::PerSecond => (RATE_LIMIT_PER_SECOND, RATE_LIMIT_PER_SECOND),
+            Self::FifteenSecondWindow => (RATE_LIMIT_PER_SECOND * 15, RATE_LIMIT_PER_SECOND),
+            Self::Custom {
+                capacity,
+                refill_rate,
+            } => (capacity, refill_rate),
+        }
+    }
+
+    /// Reject a custom bucket that would never let a request through.
+    fn check(self, field: &'static str) -> Result<()> {
+        let (capacity, refill_rate) = self.limits();
+        if capacity == 0 {
+            return Err(Error::invalid_config(field, "capacity must be at least 1"));
+        }
+        if refill_rate == 0 {
+            return Err(Error::invalid_config(
+                field,
+                "refill rate must be at least 1 per second",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// HTTP version the client speaks.
+///
+/// Serialized in snake case, e.g. `"http1"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpVersion {
+    /// HTTP/2 when the server offers it in the TLS handshake, as
+    /// `finnhub.io` does, and HTTP/1.1 otherwise.
+    #[default]
+    Auto,
+    /// HTTP/1.1 only, e.g. for a proxy that mishandles HTTP/2.
+    Http1,
+    /// HTTP/2 without negotiating it first, including over plain `http://`
+    /// URLs; fails against servers that only speak HTTP/1.1.
+    Http2,
+}
+
+/// Connection reuse settings of the HTTP client built from a
+/// [`ClientConfig`]. `None` keeps reqwest's default.
+///
+/// Over HTTP/2 all requests to a host share one connection, so a
+/// high-throughput client mostly needs [`HttpVersion::Auto`] and a
+/// keep-alive that holds the connection open between bursts. Over HTTP/1.1
+/// each request in flight needs its own connection; raise
+/// [`pool_max_idle_per_host`](Self::pool_max_idle_per_host) to the
+/// concurrency of your requests to keep them all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// HTTP version to use.
+    pub http_version: HttpVersion,
+    /// Idle connections kept open per host; unlimited by default.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle connection is kept before it is closed; 90 by
+    /// default.
+    pub pool_idle_timeout_secs: Option<u64>,
+    /// Seconds between TCP keep-alive probes on open connections; off by
+    /// default.
+    pub tcp_keepalive_secs: Option<u64>,
+    /// Seconds between HTTP/2 pings on an open connection, which keep it
+    /// alive through proxies and load balancers that drop quiet ones; off
+    /// by default.
+    pub http2_keep_alive_secs: Option<u64>,
+}
+
 /// Configuration for the Finnhub client.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClientConfig {
-    /// Base URL for the API.
+    /// Base URL for the API, including its `/api/v1` path. Endpoint paths
+    /// are appended to it, so a proxy may mount the API under a prefix, as in
+    /// `https://proxy.internal/finnhub/api/v1`.
     pub base_url: String,
-    /// Request timeout in seconds.
+    /// Base URLs to fail over to, in order, such as backup proxies. Empty by
+    /// default.
+    ///
+    /// When the base URL in use keeps failing to connect for
+    /// [`failover_after_secs`](Self::failover_after_secs), requests switch to
+    /// the next one, and the request that found the outage is sent again.
+    /// After the last fallback comes [`base_url`](Self::base_url) again. The
+    /// client stays on a fallback until it fails in turn. HTTP error
+    /// responses never cause a failover.
+    pub fallback_base_urls: Vec<String>,
+    /// How long, in seconds, the base URL in use must keep failing to connect
+    /// before the next of [`fallback_base_urls`](Self::fallback_base_urls)
+    /// takes over; `0` fails over on the first connection error.
+    pub failover_after_secs: u64,
+    /// Time allowed for a whole request, from connecting to reading the last
+    /// byte of the response, in seconds. Raise it for large downloads such as
+    /// tick data, e.g. on a client derived with [`FinnhubClient::reconfigure`].
     pub timeout_secs: u64,
+    /// Time allowed to establish a connection, TLS handshake included, in
+    /// seconds, so an unreachable host fails fast however long
+    /// `timeout_secs` is. Ignored when [`http_client`](Self::http_client) is
+    /// set; configure it on that client instead.
+    pub connect_timeout_secs: u64,
+    /// Ask for compressed responses to GET requests, in the encodings of the
+    /// `gzip` and `brotli` features, and decode them. On by default; has no
+    /// effect without either feature.
+    ///
+    /// Large responses such as `/stock/symbol?exchange=US` shrink several
+    /// times over. [`FinnhubClient::bandwidth`] counts the compressed bytes
+    /// as `wire_bytes`. An `Accept-Encoding` in
+    /// [`default_headers`](Self::default_headers) takes precedence.
+    pub compression: bool,
+    /// HTTP version and connection pool settings. Ignored when
+    /// [`http_client`](Self::http_client) is set; configure that client
+    /// instead.
+    pub connection: ConnectionConfig,
+    /// Largest response body the client reads, in bytes, both as received
+    /// and after decompression; `None`, the default, sets no limit.
+    ///
+    /// A bigger body fails the request with [`Error::ResponseTooLarge`] as
+    /// soon as its size is known, without buffering the rest, so a query
+    /// such as a whole day of tick data can't exhaust memory. Bodies of error
+    /// responses are read within the same limit; past it they are dropped and
+    /// the error carries only the status.
+    pub max_response_bytes: Option<u64>,
     /// Authentication method.
     pub auth_method: AuthMethod,
+    /// Headers sent with every request, such as `X-Client-App: my-service`.
+    /// Empty by default. The auth header is always set by the client and
+    /// can't be overridden here.
+    pub default_headers: HeaderMap,
+    /// Name attributing this client's requests, e.g. to an internal team
+    /// when several services share one process. `None` by default.
+    ///
+    /// The tag is recorded on the client's `tracing` events and totalled in
+    /// [`FinnhubClient::bandwidth_by_tag`]. Give each team a client derived
+    /// with [`FinnhubClient::reconfigure`] to share the rate limit.
+    pub tag: Option<String>,
+    /// HTTP client to send requests with, e.g. one shared with the rest of
+    /// an application for its connection pool and TLS settings. `None`
+    /// builds a default client.
+    ///
+    /// Timeouts and auth headers are applied per request, so the client
+    /// needs no Finnhub-specific settings.
+    pub http_client: Option<HttpClient>,
+    /// Proxy to send requests through; `None` uses the `HTTP_PROXY` and
+    /// `HTTPS_PROXY` environment variables, if set. See [`crate::proxy`].
+    ///
+    /// Only applies to the client built from this configuration, so it
+    /// can't be combined with [`http_client`](Self::http_client); configure
+    /// the proxy on that client instead.
+    pub proxy: Option<ProxyConfig>,
     /// Custom rate limit (requests per second).
-    /// Deprecated: Use rate_limit_strategy instead.
+    ///
+    /// When set, this takes precedence over `rate_limit_strategy` and a warning
+    /// is logged. Use `RateLimitStrategy::from_legacy(n)` for the same behavior.
+    #[deprecated(note = "use `rate_limit_strategy` with `RateLimitStrategy::from_legacy`")]
     pub rate_limit: Option<u32>,
     /// Rate limiting strategy.
     pub rate_limit_strategy: RateLimitStrategy,
+    /// Rate limiter to draw from instead of one of the client's own, so
+    /// several clients built for the same API key share one budget, or to
+    /// use another [`RateLimit`] implementation. `None`, the default, gives
+    /// each client a [`RateLimiter`](crate::RateLimiter) following
+    /// [`rate_limit_strategy`](Self::rate_limit_strategy), which is then
+    /// ignored.
+    ///
+    /// Every key of the client uses this limiter, and 429 pauses and
+    /// reloaded rate limits apply to it, and so to every client sharing it.
+    ///
+    /// ```rust,no_run
+    /// use finnhub::{ClientConfig, FinnhubClient, RateLimiter};
+    /// use std::sync::Arc;
+    ///
+    /// let limiter = Arc::new(RateLimiter::finnhub_default());
+    /// let config = ClientConfig {
+    ///     rate_limiter: Some(limiter),
+    ///     ..ClientConfig::default()
+    /// };
+    /// let clients: Vec<FinnhubClient> = (0..4)
+    ///     .map(|_| FinnhubClient::with_config("your-api-key", config.clone()))
+    ///     .collect();
+    /// ```
+    pub rate_limiter: Option<Arc<dyn RateLimit>>,
+    /// Sanity checks applied to quote and candle responses.
+    pub validation: ValidationMode,
+    /// Latency of the data on your plan, used to label quotes and candles.
+    pub latency: LatencyPolicy,
+    /// Venues prepended to forex and crypto symbols given without one.
+    pub venues: VenueDefaults,
+    /// Retries per minute [`FinnhubClient::retry`] and the
+    /// [`retry_policy`](Self::retry_policy) may spend across all requests;
+    /// `None` leaves retries unlimited. See [`crate::retry`].
+    pub retry_budget: Option<u32>,
+    /// Retry failed GET requests automatically; `None`, the default, returns
+    /// every error to the caller. See [`RetryPolicy`].
+    pub retry_policy: Option<RetryPolicy>,
+    /// Spend each endpoint's [`cost`](crate::coverage::EndpointInfo::cost)
+    /// in rate-limit tokens instead of one per request, so heavy downloads
+    /// leave room for other calls. Off by default.
+    pub weighted_rate_limit: bool,
+    /// Rate-limit tokens spent per request to an endpoint path, e.g.
+    /// `/stock/financials-reported`, overriding both one token and
+    /// [`weighted_rate_limit`](Self::weighted_rate_limit)'s cost. Empty by
+    /// default.
+    pub endpoint_weights: HashMap<String, u32>,
+    /// Separate token buckets for some endpoints or API groups, each also
+    /// spending from the client's rate limit, so a heavy backfill can't
+    /// starve quote calls. Empty by default.
+    ///
+    /// A request uses the first bucket that [matches](EndpointBucket::matches)
+    /// its endpoint, spending the same number of tokens there as from the
+    /// client's rate limit. Like the rate limit, the buckets are shared with
+    /// clients derived with [`FinnhubClient::reconfigure`], and changing
+    /// them there has no effect.
+    pub endpoint_buckets: Vec<EndpointBucket>,
+    /// Pause all requests after a 429 Too Many Requests response, for the
+    /// response's [`retry_after`](Error::retry_after) window, instead of
+    /// letting concurrent tasks keep hitting the limit. Off by default.
+    ///
+    /// The pause holds the rate limiter, so it applies to every clone and to
+    /// clients derived with [`FinnhubClient::reconfigure`]; with
+    /// [several keys](FinnhubClient::with_keys), only the key that got the
+    /// 429 pauses. The response that triggered it still fails with
+    /// [`Error::RateLimitExceeded`].
+    /// Without a `Retry-After` header the pause lasts 60 seconds.
+    pub pause_on_rate_limit: bool,
+    /// Retry a GET request once when it gets a 429, after sleeping for the
+    /// response's [`retry_after`](Error::retry_after), so simple scripts
+    /// don't have to handle [`Error::RateLimitExceeded`] at every call. Off
+    /// by default.
+    ///
+    /// The retry spends from the [`retry_budget`](Self::retry_budget) like
+    /// any other. Ignored when [`retry_policy`](Self::retry_policy) is set,
+    /// which retries 429s itself.
+    pub auto_retry_rate_limit: bool,
+    /// Keep the rate limiter in step with the `X-Ratelimit-Remaining` and
+    /// `X-Ratelimit-Reset` headers of every response, so other processes
+    /// sharing the key, or a plan limit below the configured one, can't make
+    /// the client overrun the server's budget. Off by default.
+    ///
+    /// The bucket is capped at the remaining calls, and with none left the
+    /// key's requests pause until the reset, for at most a minute. See
+    /// [`RateLimiter::sync_with_server`](crate::RateLimiter::sync_with_server).
+    pub adaptive_rate_limit: bool,
+    /// Longest random delay, in milliseconds, added to each wait for the
+    /// rate limiter, so requests queued together don't all go out the moment
+    /// a token is due. `0`, the default, adds none. Ignored when
+    /// [`rate_limiter`](Self::rate_limiter) is set; see
+    /// [`RateLimiter::with_jitter`](crate::RateLimiter::with_jitter).
+    pub rate_limit_jitter_ms: u64,
+    /// Most requests in flight at once, across all tasks using the client;
+    /// `None`, the default, sets no limit.
+    ///
+    /// The rate limit bounds how fast requests start, not how many are open:
+    /// thirty slow tick downloads started in one second can tie up thirty
+    /// sockets. With a limit, further requests wait for a slot before taking
+    /// rate-limit tokens, and hold it until their body has been read. Each
+    /// retry waits for a slot again. Shared with clients derived through
+    /// [`FinnhubClient::reconfigure`].
+    pub max_concurrent_requests: Option<usize>,
+    /// Response cache policy; `None` disables caching.
+    #[cfg(feature = "cache")]
+    pub cache: Option<CachePolicy>,
 }
 
 impl Default for ClientConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
+            fallback_base_urls: Vec::new(),
+            failover_after_secs: DEFAULT_FAILOVER_AFTER_SECS,
             timeout_secs: DEFAULT_TIMEOUT_SECS,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            compression: true,
+            connection: ConnectionConfig::default(),
+            max_response_bytes: None,
             auth_method: AuthMethod::default(),
+            default_headers: HeaderMap::new(),
+            tag: None,
+            http_client: None,
+            proxy: None,
             rate_limit: None,
             rate_limit_strategy: RateLimitStrategy::default(),
+            rate_limiter: None,
+            validation: ValidationMode::default(),
+            latency: LatencyPolicy::default(),
+            venues: VenueDefaults::default(),
+            retry_budget: None,
+            retry_policy: None,
+            weighted_rate_limit: false,
+            endpoint_weights: HashMap::new(),
+            endpoint_buckets: Vec::new(),
+            pause_on_rate_limit: false,
+            auto_retry_rate_limit: false,
+            adaptive_rate_limit: false,
+            rate_limit_jitter_ms: 0,
+            max_concurrent_requests: None,
+            #[cfg(feature = "cache")]
+            cache: None,
         }
     }
 }
 
+impl ClientConfig {
+    /// The rate limit strategy the client will use.
+    ///
+    /// The deprecated `rate_limit` field wins over `rate_limit_strategy` when
+    /// set, so existing configurations keep their behavior.
+    #[must_use]
+    pub fn effective_rate_limit_strategy(&self) -> RateLimitStrategy {
+        #[allow(deprecated)]
+        match self.rate_limit {
+            Some(rate_limit) => RateLimitStrategy::from_legacy(rate_limit),
+            None => self.rate_limit_strategy,
+        }
+    }
+
+    /// Check that every setting is usable.
+    ///
+    /// [`FinnhubClient::try_with_config`] runs this before building a client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] naming the first invalid field: a base
+    /// or fallback URL that isn't an absolute `http` or `https` URL, or is an
+    /// `https` URL in a build without a TLS feature, an auth header in
+    /// `default_headers`, a zero timeout, connect timeout or
+    /// `max_response_bytes`, a proxy with an unsupported URL or set alongside
+    /// `http_client`, a zero rate limit, capacity or refill rate, or a cache
+    /// holding no entries.
+    pub fn validate(&self) -> Result<()> {
+        check_base_url("base_url", &self.base_url)?;
+        for url in &self.fallback_base_urls {
+            check_base_url("fallback_base_urls", url)?;
+        }
+        // The client built from this configuration can only speak TLS with
+        // one of the TLS features; a browser or a given client brings its own.
+        #[cfg(not(any(feature = "rustls", feature = "native-tls", target_arch = "wasm32")))]
+        if self.http_client.is_none() {
+            let urls = std::iter::once(("base_url", &self.base_url)).chain(
+                self.fallback_base_urls
+                    .iter()
+                    .map(|url| ("fallback_base_urls", url)),
+            );
+            for (field, url) in urls {
+                if url.starts_with("https:") {
+                    return Err(Error::invalid_config(
+                        field,
+                        "https needs the `rustls` or `native-tls` feature",
+                    ));
+                }
+            }
+        }
+        if self.default_headers.contains_key("X-Finnhub-Token") {
+            return Err(Error::invalid_config(
+                "default_headers",
+                "must not contain `X-Finnhub-Token`; the client sets it from the API key",
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err(Error::invalid_config(
+                "timeout_secs",
+                "must be at least 1 second",
+            ));
+        }
+        if self.connect_timeout_secs == 0 {
+            return Err(Error::invalid_config(
+                "connect_timeout_secs",
+                "must be at least 1 second",
+            ));
+        }
+        if self.max_response_bytes == Some(0) {
+            return Err(Error::invalid_config(
+                "max_response_bytes",
+                "must be at least 1 byte; set it to `None` for no limit",
+            ));
+        }
+        if let Some(proxy) = &self.proxy {
+            if self.http_client.is_some() {
+                return Err(Error::invalid_config(
+                    "proxy",
+                    "ignored when `http_client` is set; configure the proxy on that client",
+                ));
+            }
+            proxy.check()?;
+        }
+
+        #[allow(deprecated)]
+        let field = if self.rate_limit.is_some() {
+            "rate_limit"
+        } else {
+            "rate_limit_strategy"
+        };
+        self.effective_rate_limit_strategy().check(field)?;
+        if let Some((path, _)) = self.endpoint_weights.iter().find(|(_, &w)| w == 0) {
+            return Err(Error::invalid_config(
+                "endpoint_weights",
+                format!("weight of {path} must be at least 1"),
+            ));
+        }
+        if self
+            .endpoint_buckets
+            .iter()
+            .any(|bucket| bucket.capacity == 0 || bucket.refill_rate == 0)
+        {
+            return Err(Error::invalid_config(
+                "endpoint_buckets",
+                "capacity and refill rate must be at least 1",
+            ));
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err(Error::invalid_config(
+                "max_concurrent_requests",
+                "must be at least 1; set it to `None` for no limit",
+            ));
+        }
+        if let Some(policy) = &self.retry_policy {
+            policy.check()?;
+        }
+
+        #[cfg(feature = "cache")]
+        if self
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.max_entries == 0)
+        {
+            return Err(Error::invalid_config(
+                "cache.max_entries",
+                "must be at least 1; set `cache` to `None` to disable caching",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse the primary and fallback base URLs.
+    fn base_urls(&self) -> std::result::Result<(Url, Vec<Url>), url::ParseError> {
+        let fallbacks = self
+            .fallback_base_urls
+            .iter()
+            .map(|url| Url::parse(url))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok((Url::parse(&self.base_url)?, fallbacks))
+    }
+}
+
+/// Check that `url` is an absolute `http` or `https` URL.
+fn check_base_url(field: &'static str, url: &str) -> Result<()> {
+    match Url::parse(url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(()),
+        Ok(url) => Err(Error::invalid_config(
+            field,
+            format!("expected an http or https URL, got {url}"),
+        )),
+        Err(e) => Err(Error::invalid_config(field, format!("{e}: {url:?}"))),
+    }
+}
+
 /// Main client for interacting with the Finnhub API.
+///
+/// Cloning is cheap: clones share a single allocation holding the HTTP
+/// connection pool, credentials, rate limiter, retry budget, bandwidth
+/// counters and response cache, so every clone draws from the same rate
+/// limit. [`ptr_eq`](Self::ptr_eq) tells whether two handles are clones.
 #[derive(Clone, Debug)]
 pub struct FinnhubClient {
+    inner: Arc<ClientInner>,
+}
+
+#[derive(Debug)]
+struct ClientInner {
     http_client: HttpClient,
-    auth: Arc<Auth>,
-    rate_limiter: Arc<RateLimiter>,
-    base_url: Url,
+    /// The API keys and their rate limiters, shared with clients derived by
+    /// `reconfigure` so a reload rotates the key for all of them. Each client
+    /// sends keys with its own `config.auth_method`.
+    keys: Arc<KeyPool>,
+    /// Limiters of `config.endpoint_buckets`, shared like `keys`.
+    endpoint_limiters: Arc<EndpointLimiters>,
+    /// Slots of `config.max_concurrent_requests`, shared like `keys`.
+    request_slots: Option<Arc<Semaphore>>,
+    retry_budget: Option<RetryBudget>,
+    bandwidth: BandwidthStats,
+    /// Shared with derived clients that keep the same base URLs.
+    base_urls: Arc<BaseUrls>,
+    config: ClientConfig,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl FinnhubClient {
     /// Create a new client with the given API key.
-    pub fn new(api_key: impl Into<String>) -> Self {
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
         Self::with_config(api_key, ClientConfig::default())
     }
 
     /// Create a new client with custom configuration.
-    pub fn with_config(api_key: impl Into<String>, config: ClientConfig) -> Self {
-        let auth = Auth::with_method(api_key, config.auth_method);
+    ///
+    /// Only the base URL is checked; use [`try_with_config`](Self::try_with_config)
+    /// to reject every invalid setting up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a base URL or the proxy is invalid.
+    pub fn with_config(api_key: impl Into<ApiKey>, config: ClientConfig) -> Self {
+        let (primary, fallbacks) = config.base_urls().expect("Invalid base URL");
+        Self::build(vec![api_key.into()], config, primary, fallbacks)
+    }
+
+    /// Create a new client with custom configuration, validating it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if [`ClientConfig::validate`] rejects
+    /// the configuration.
+    pub fn try_with_config(api_key: impl Into<ApiKey>, config: ClientConfig) -> Result<Self> {
+        config.validate()?;
+        let (primary, fallbacks) = config.base_urls()?;
+        Ok(Self::build(
+            vec![api_key.into()],
+            config,
+            primary,
+            fallbacks,
+        ))
+    }
+
+    /// Create a client that spreads requests over several API keys, taking
+    /// them in turn.
+    ///
+    /// Each key gets its own rate limiter with the configured
+    /// [`rate_limit_strategy`](ClientConfig::rate_limit_strategy), so `n`
+    /// keys allow up to `n` times the throughput. A key the server rejects
+    /// with 401, or with a 403 other than Finnhub's "no access to this
+    /// resource" for premium endpoints, is demoted: the request is sent again
+    /// with another key, and later requests skip it for 15 minutes while any
+    /// other key works. See [`demoted_keys`](Self::demoted_keys). Keys paused
+    /// by [`pause_on_rate_limit`](ClientConfig::pause_on_rate_limit) are
+    /// skipped too until the pause ends.
+    ///
+    /// ```rust,no_run
+    /// # use finnhub::{ClientConfig, FinnhubClient};
+    /// let client = FinnhubClient::with_keys(["key-1", "key-2"], ClientConfig::default());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `api_keys` is empty or a base URL or the proxy is invalid.
+    pub fn with_keys<I, S>(api_keys: I, config: ClientConfig) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ApiKey>,
+    {
+        let api_keys: Vec<ApiKey> = api_keys.into_iter().map(Into::into).collect();
+        assert!(!api_keys.is_empty(), "at least one API key is required");
+        let (primary, fallbacks) = config.base_urls().expect("Invalid base URL");
+        Self::build(api_keys, config, primary, fallbacks)
+    }
 
-        let mut builder =
-            HttpClient::builder().timeout(std::time::Duration::from_secs(config.timeout_secs));
+    /// Create a client over several API keys like
+    /// [`with_keys`](Self::with_keys), validating the configuration first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if `api_keys` is empty or
+    /// [`ClientConfig::validate`] rejects the configuration.
+    pub fn try_with_keys<I, S>(api_keys: I, config: ClientConfig) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ApiKey>,
+    {
+        let api_keys: Vec<ApiKey> = api_keys.into_iter().map(Into::into).collect();
+        if api_keys.is_empty() {
+            return Err(Error::invalid_config(
+                "api_keys",
+                "at least one API key is required",
+            ));
+        }
+        config.validate()?;
+        let (primary, fallbacks) = config.base_urls()?;
+        Ok(Self::build(api_keys, config, primary, fallbacks))
+    }
 
-        // Only add headers if using header authentication
-        if matches!(config.auth_method, AuthMethod::Header) {
-            builder = builder.default_headers(auth.headers());
+    fn build(
+        api_keys: Vec<ApiKey>,
+        config: ClientConfig,
+        primary: Url,
+        fallbacks: Vec<Url>,
+    ) -> Self {
+        // Timeout and auth headers are applied per request so that clients
+        // derived with `reconfigure` can share this connection pool.
+        let http_client = config
+            .http_client
+            .clone()
+            .unwrap_or_else(|| new_http_client(&config));
+
+        #[allow(deprecated)]
+        if let Some(rate_limit) = config.rate_limit {
+            tracing::warn!(
+                rate_limit,
+                "ClientConfig::rate_limit is deprecated and overrides rate_limit_strategy; \
+                 use RateLimitStrategy::from_legacy({rate_limit}) instead"
+            );
         }
 
-        let http_client = builder.build().expect("Failed to build HTTP client");
+        let (capacity, refill_rate) = config.effective_rate_limit_strategy().limits();
+        let jitter = Duration::from_millis(config.rate_limit_jitter_ms);
 
-        // Create rate limiter based on strategy
-        let rate_limiter = if let Some(rate_limit) = config.rate_limit {
-            // Legacy support: if rate_limit is set, use it
-            RateLimiter::new(rate_limit, rate_limit)
-        } else {
-            // Use the rate limit strategy
-            match config.rate_limit_strategy {
-                RateLimitStrategy::PerSecond => RateLimiter::finnhub_default(),
-                RateLimitStrategy::FifteenSecondWindow => RateLimiter::finnhub_15s_window(),
-                RateLimitStrategy::Custom {
-                    capacity,
-                    refill_rate,
-                } => RateLimiter::new(capacity, refill_rate),
-            }
+        let inner = ClientInner {
+            http_client,
+            keys: Arc::new(KeyPool::new(
+                api_keys,
+                capacity,
+                refill_rate,
+                jitter,
+                config.rate_limiter.as_ref(),
+            )),
+            endpoint_limiters: Arc::new(EndpointLimiters::new(&config.endpoint_buckets, jitter)),
+            request_slots: config
+                .max_concurrent_requests
+                .map(|max| Arc::new(Semaphore::new(max.min(Semaphore::MAX_PERMITS)))),
+            retry_budget: config.retry_budget.map(RetryBudget::new),
+            bandwidth: BandwidthStats::default(),
+            base_urls: Arc::new(BaseUrls::new(
+                primary,
+                fallbacks,
+                Duration::from_secs(config.failover_after_secs),
+            )),
+            #[cfg(feature = "cache")]
+            cache: new_cache(&config),
+            config,
         };
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
 
-        let base_url = Url::parse(&config.base_url).expect("Invalid base URL");
+    /// Create a client from a modified copy of this client's configuration.
+    ///
+    /// The new client shares the HTTP connection pool (unless the closure sets
+    /// [`ClientConfig::http_client`] or changes [`ClientConfig::proxy`],
+    /// [`ClientConfig::connect_timeout_secs`] or
+    /// [`ClientConfig::connection`]), the API key, the rate limiter, the
+    /// concurrency limit, the retry budget, the response cache (unless the
+    /// closure changes [`ClientConfig::cache`]) and, unless the base URLs
+    /// change, which base URL is in use with `self`, so deriving per-task
+    /// variants (a longer timeout for tick data, a different auth method)
+    /// doesn't duplicate heavyweight state. Rate limit, concurrency limit and
+    /// retry budget settings changed in the closure have no effect since they
+    /// are shared. A client given a different cache policy starts with an
+    /// empty cache of its own.
+    ///
+    /// ```rust,no_run
+    /// # use finnhub::FinnhubClient;
+    /// let client = FinnhubClient::new("your-api-key");
+    /// let slow = client.reconfigure(|cfg| cfg.timeout_secs = 120);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new base URL or proxy is invalid.
+    #[must_use]
+    pub fn reconfigure(&self, f: impl FnOnce(&mut ClientConfig)) -> Self {
+        let mut config = self.inner.config.clone();
+        f(&mut config);
 
+        let (primary, fallbacks) = config.base_urls().expect("Invalid base URL");
+        let failover_after = Duration::from_secs(config.failover_after_secs);
+        let base_urls = if self
+            .inner
+            .base_urls
+            .same_as(&primary, &fallbacks, failover_after)
+        {
+            Arc::clone(&self.inner.base_urls)
+        } else {
+            Arc::new(BaseUrls::new(primary, fallbacks, failover_after))
+        };
+
+        let inner = ClientInner {
+            http_client: match &config.http_client {
+                Some(http_client) => http_client.clone(),
+                None if config.proxy != self.inner.config.proxy
+                    || config.connect_timeout_secs != self.inner.config.connect_timeout_secs
+                    || config.connection != self.inner.config.connection =>
+                {
+                    new_http_client(&config)
+                }
+                None => self.inner.http_client.clone(),
+            },
+            keys: Arc::clone(&self.inner.keys),
+            endpoint_limiters: Arc::clone(&self.inner.endpoint_limiters),
+            request_slots: self.inner.request_slots.clone(),
+            retry_budget: self.inner.retry_budget.clone(),
+            bandwidth: self.inner.bandwidth.clone(),
+            base_urls,
+            #[cfg(feature = "cache")]
+            cache: if config.cache == self.inner.config.cache {
+                self.inner.cache.clone()
+            } else {
+                new_cache(&config)
+            },
+            config,
+        };
         Self {
-            http_client,
-            auth: Arc::new(auth),
-            rate_limiter: Arc::new(rate_limiter),
-            base_url,
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Whether `self` and `other` are clones of the same client, sharing all
+    /// state.
+    ///
+    /// Clients derived through [`reconfigure`](Self::reconfigure) are not
+    /// clones, even though they share the connection pool and rate limiter.
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Apply reloaded settings to the running client without dropping
+    /// in-flight requests; see [`crate::reload`].
+    ///
+    /// Every setting is validated before any is applied, so an invalid update
+    /// changes nothing. Each setting is then swapped atomically: a request
+    /// uses either the old or the new value, never a mix. The API key, rate
+    /// limit and cache TTLs also apply to clients derived with
+    /// [`reconfigure`](Self::reconfigure) that share them.
+    /// [`config`](Self::config) keeps reporting the settings the client was
+    /// built with.
+    ///
+    /// Returns the names of the settings that changed, e.g. `["api_key"]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the API key is empty, the rate
+    /// limit strategy would block every request, or cache TTLs are given to
+    /// a client built without a cache.
+    pub async fn apply_reload(&self, update: &ReloadableConfig) -> Result<Vec<&'static str>> {
+        if update.api_key.as_deref().is_some_and(str::is_empty) {
+            return Err(Error::invalid_config("api_key", "must not be empty"));
         }
+        if update.api_key.is_some() && self.inner.keys.len() > 1 {
+            return Err(Error::invalid_config(
+                "api_key",
+                "the client uses several API keys; build a new client to change them",
+            ));
+        }
+        if let Some(strategy) = update.rate_limit_strategy {
+            strategy.check("rate_limit_strategy")?;
+        }
+        #[cfg(feature = "cache")]
+        if update.cache_ttls.is_some() && self.inner.cache.is_none() {
+            return Err(Error::invalid_config(
+                "cache",
+                "the client was built without a cache; TTLs can't be reloaded",
+            ));
+        }
+
+        let mut changed = Vec::new();
+        if let Some(api_key) = &update.api_key {
+            if self.inner.keys.replace_single(api_key) {
+                changed.push("api_key");
+            }
+        }
+        if let Some(strategy) = update.rate_limit_strategy {
+            let (capacity, refill_rate) = strategy.limits();
+            if self.inner.keys.set_rate(capacity, refill_rate).await {
+                changed.push("rate_limit_strategy");
+            }
+        }
+        #[cfg(feature = "cache")]
+        if let (Some(ttls), Some(cache)) = (&update.cache_ttls, &self.inner.cache) {
+            if cache.set_ttls(ttls.clone()) {
+                changed.push("cache_ttls");
+            }
+        }
+        if !changed.is_empty() {
+            tracing::info!(?changed, "applied reloaded configuration");
+        }
+        Ok(changed)
+    }
+
+    /// Bytes downloaded so far, keyed by endpoint path such as `/quote`.
+    ///
+    /// Shared with clients derived through [`reconfigure`](Self::reconfigure).
+    /// See the [`bandwidth`](crate::bandwidth) module for what is counted.
+    #[must_use]
+    pub fn bandwidth(&self) -> HashMap<String, EndpointBandwidth> {
+        self.inner.bandwidth.snapshot()
+    }
+
+    /// Bytes downloaded so far by tagged clients, keyed by
+    /// [`ClientConfig::tag`], each entry totalled over all endpoints.
+    ///
+    /// Covers this client and every client sharing its counters through
+    /// [`reconfigure`](Self::reconfigure), so one call reports usage per tag.
+    /// Untagged clients are only counted in [`bandwidth`](Self::bandwidth).
+    #[must_use]
+    pub fn bandwidth_by_tag(&self) -> HashMap<String, EndpointBandwidth> {
+        self.inner.bandwidth.snapshot_by_tag()
+    }
+
+    /// The client's [`ClientConfig::tag`].
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.inner.config.tag.as_deref()
+    }
+
+    /// The client's retry budget, if [`ClientConfig::retry_budget`] is set.
+    ///
+    /// Shared with clients derived through [`reconfigure`](Self::reconfigure).
+    #[must_use]
+    pub fn retry_budget(&self) -> Option<&RetryBudget> {
+        self.inner.retry_budget.as_ref()
+    }
+
+    /// Run `f` up to `max_attempts` times while it fails with a retryable
+    /// error, waiting [`Error::retry_after`] seconds (at least one) between
+    /// attempts.
+    ///
+    /// Every retry spends from the client's [`retry_budget`](Self::retry_budget).
+    /// When the budget is used up the error is returned without retrying and
+    /// the budget's [`on_exhausted`](RetryBudget::on_exhausted) hooks are
+    /// called.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: finnhub::FinnhubClient) -> finnhub::Result<()> {
+    /// let quote = client.retry(3, || async { client.stock().quote("AAPL").await }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error from `f`.
+    pub async fn retry<T, F, Fut>(&self, max_attempts: u32, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) if e.is_retryable() && attempt < max_attempts => e,
+                Err(e) => return Err(e),
+            };
+            if !self.spend_retry(&error) {
+                return Err(error);
+            }
+            let delay = error.retry_after().unwrap_or(1).max(1);
+            tracing::debug!(attempt, delay, %error, "retrying request");
+            rt::sleep(Duration::from_secs(delay)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Spend a retry of `error` from the retry budget, if there is one.
+    /// Returns `false` when the budget is used up.
+    fn spend_retry(&self, error: &Error) -> bool {
+        match &self.inner.retry_budget {
+            Some(budget) if !budget.try_acquire() => {
+                budget.notify_exhausted(error);
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Get the configuration this client was built with.
+    #[must_use]
+    pub fn config(&self) -> &ClientConfig {
+        &self.inner.config
+    }
+
+    /// Apply the configured [`ValidationMode`] to a price response.
+    #[cfg_attr(
+        not(any(feature = "stock", feature = "forex", feature = "crypto")),
+        allow(dead_code)
+    )]
+    pub(crate) fn validate<T: Validate>(&self, value: T) -> Result<T> {
+        self.inner.config.validation.apply(value)
+    }
+
+    /// Merged quote and trade updates for `symbols`; see [`TickerTape`].
+    #[cfg(all(feature = "stock", not(target_arch = "wasm32")))]
+    pub fn ticker_tape<I, S>(&self, symbols: I) -> TickerTape
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        TickerTape::new(self.clone(), symbols)
+    }
+
+    #[cfg(any(all(feature = "websocket", feature = "stock"), test))]
+    pub(crate) fn api_key(&self) -> ApiKey {
+        self.inner.keys.api_key(self.inner.keys.pick())
+    }
+
+    /// Credentials of the key at `key` as this client sends them.
+    fn auth(&self, key: usize) -> Auth {
+        Auth::with_method(self.inner.keys.api_key(key), self.inner.config.auth_method)
+    }
+
+    /// Indexes, in the order given to [`with_keys`](Self::with_keys), of the
+    /// API keys demoted after the server rejected them in the last 15
+    /// minutes. Always empty for a client with a single key.
+    ///
+    /// Shared with clients derived through [`reconfigure`](Self::reconfigure).
+    #[must_use]
+    pub fn demoted_keys(&self) -> Vec<usize> {
+        self.inner.keys.demoted()
+    }
+
+    /// Latency of data for `symbol` according to the configured
+    /// [`LatencyPolicy`].
+    #[must_use]
+    pub fn data_latency(&self, symbol: &str) -> DataLatency {
+        self.inner.config.latency.for_symbol(symbol)
     }
 
     /// Get stock market endpoints.
+    #[cfg(feature = "stock")]
     pub fn stock(&self) -> StockEndpoints<'_> {
         StockEndpoints::new(self)
     }
 
     /// Get forex market endpoints.
+    #[cfg(feature = "forex")]
     pub fn forex(&self) -> ForexEndpoints<'_> {
         ForexEndpoints::new(self)
     }
 
     /// Get cryptocurrency endpoints.
+    #[cfg(feature = "crypto")]
     pub fn crypto(&self) -> CryptoEndpoints<'_> {
         CryptoEndpoints::new(self)
     }
@@ -150,21 +1056,25 @@ impl FinnhubClient {
     }
 
     /// Get ETF endpoints.
+    #[cfg(feature = "etf")]
     pub fn etf(&self) -> ETFEndpoints<'_> {
         ETFEndpoints::new(self)
     }
 
     /// Get bond endpoints.
+    #[cfg(feature = "bond")]
     pub fn bond(&self) -> BondEndpoints<'_> {
         BondEndpoints::new(self)
     }
 
     /// Get mutual fund endpoints.
+    #[cfg(feature = "mutual-fund")]
     pub fn mutual_fund(&self) -> MutualFundEndpoints<'_> {
         MutualFundEndpoints::new(self)
     }
 
     /// Get economic data endpoints.
+    #[cfg(feature = "economic")]
     pub fn economic(&self) -> EconomicEndpoints<'_> {
         EconomicEndpoints::new(self)
     }
@@ -184,14 +1094,492 @@ impl FinnhubClient {
         ScannerEndpoints::new(self)
     }
 
+    /// GET `path` with the query `params` and return the response as it
+    /// arrived: status, headers and body.
+    ///
+    /// For endpoints the typed API doesn't cover yet, and for looking at the
+    /// JSON behind a deserialization error. The request goes through the
+    /// rate limiter, authentication and failover like any other, but not
+    /// the cache or retries, and asks for an uncompressed body. Error
+    /// statuses are returned rather than turned into errors.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: finnhub::FinnhubClient) -> finnhub::Result<()> {
+    /// let (status, _headers, body) = client
+    ///     .get_raw("/stock/profile2", &[("symbol", "AAPL")])
+    ///     .await?;
+    /// println!("{status}: {}", String::from_utf8_lossy(&body));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParameter`] if `path` doesn't start with `/`,
+    /// [`Error::ResponseTooLarge`] for a body over
+    /// [`ClientConfig::max_response_bytes`], and the rate limiter's and HTTP
+    /// client's errors, wrapped in [`Error::Endpoint`].
+    pub async fn get_raw(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<(StatusCode, HeaderMap, Bytes)> {
+        check_path(path)?;
+        async {
+            let _slot = self.request_slot().await;
+            let key = self.acquire_rate_limit(path).await?;
+            let response = self
+                .send("GET", path, key, |url| {
+                    self.inner.http_client.get(url).query(params)
+                })
+                .await?;
+            let (status, headers) = (response.status(), response.headers().clone());
+            let body = read_body(response, self.inner.config.max_response_bytes).await?;
+            self.inner.bandwidth.record(
+                path,
+                self.tag(),
+                None,
+                body.len() as u64,
+                headers.contains_key(CONTENT_ENCODING),
+            );
+            Ok((status, headers, Bytes::from(body)))
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), path, err))
+    }
+
+    /// GET `path` with the query `params` and return the deserialized body
+    /// with the response's status, headers, [rate limit
+    /// state](crate::response::RateLimitHeaders), server time and latency.
+    ///
+    /// Takes a path and parameters like [`get_raw`](Self::get_raw) and goes
+    /// through the same rate limiter, authentication and failover, skipping
+    /// the cache and retries so the metadata always describes a fresh
+    /// response. Bodies are decoded and checked as the endpoint methods do;
+    /// see [`response`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParameter`] if `path` doesn't start with `/`,
+    /// and otherwise the errors of the endpoint methods, wrapped in
+    /// [`Error::Endpoint`].
+    pub async fn get_enveloped<T>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<ResponseEnvelope<T>>
+    where
+        T: DeserializeOwned,
+    {
+        check_path(path)?;
+        async {
+            let _slot = self.request_slot().await;
+            let key = self.acquire_rate_limit(path).await?;
+            let started = rt::Instant::now();
+            let response = self
+                .send("GET", path, key, |url| self.get_request(url).query(params))
+                .await?;
+            let (status, headers) = (response.status(), response.headers().clone());
+            let body = self.handle_response(path, key, response).await?;
+            let latency = started.elapsed();
+            Ok(ResponseEnvelope::new(
+                lenient::from_slice(&body)?,
+                status,
+                headers,
+                latency,
+            ))
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), path, err))
+    }
+
     /// Make a GET request to the API.
+    ///
+    /// Errors are wrapped in [`Error::Endpoint`] with the endpoint path and
+    /// symbol.
     pub(crate) async fn get<T>(&self, endpoint: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        self.rate_limiter.acquire().await?;
+        self.get_decoded(endpoint, lenient::from_slice).await
+    }
+
+    /// GET an endpoint and return the raw JSON body, through the cache and
+    /// rate limiter like [`get`](Self::get). The body is checked to be JSON,
+    /// as it isn't decoded here.
+    pub(crate) async fn get_json_body(&self, endpoint: &str) -> Result<Vec<u8>> {
+        self.get_decoded(endpoint, |body| {
+            serde_json::from_slice::<serde::de::IgnoredAny>(body).map(|_| body.to_vec())
+        })
+        .await
+    }
+
+    /// GET an endpoint through the cache and rate limiter and `decode` its
+    /// body. Only bodies that decode are cached.
+    async fn get_decoded<V>(
+        &self,
+        endpoint: &str,
+        decode: impl FnOnce(&[u8]) -> serde_json::Result<V>,
+    ) -> Result<V> {
+        async {
+            #[cfg(feature = "cache")]
+            if let Some(body) = self.inner.cache.as_ref().and_then(|c| c.get(endpoint)) {
+                tracing::debug!(endpoint, "cache hit");
+                return Ok(decode(&body)?);
+            }
+
+            let body = self.fetch_with_retries(endpoint).await?;
+            let value = decode(&body)?;
+
+            #[cfg(feature = "cache")]
+            if let Some(cache) = &self.inner.cache {
+                cache.insert(endpoint, &body);
+            }
+
+            Ok(value)
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), endpoint, err))
+    }
+
+    /// GET an endpoint past the response cache and return the raw body, for
+    /// checks that must see what the API returns now.
+    pub(crate) async fn get_fresh_body(&self, endpoint: &str) -> Result<Vec<u8>> {
+        self.fetch_with_retries(endpoint)
+            .await
+            .map_err(|err| endpoint_error(self.tag(), endpoint, err))
+    }
+
+    /// POST a JSON body to an endpoint and return the raw JSON response body.
+    pub(crate) async fn post_json_body<B>(&self, endpoint: &str, body: &B) -> Result<Vec<u8>>
+    where
+        B: Serialize + ?Sized,
+    {
+        async {
+            let _slot = self.request_slot().await;
+            let (key, response) = self.send_post(endpoint, body).await?;
+            self.handle_response(endpoint, key, response).await
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), endpoint, err))
+    }
+
+    /// Make a POST request with a JSON body to the API.
+    ///
+    /// Responses are never cached. Errors are wrapped in [`Error::Endpoint`]
+    /// like those of [`get`](Self::get).
+    pub(crate) async fn post<T, B>(&self, endpoint: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        async {
+            let _slot = self.request_slot().await;
+            let (key, response) = self.send_post(endpoint, body).await?;
+            let body = self.handle_response(endpoint, key, response).await?;
+            Ok(lenient::from_slice(&body)?)
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), endpoint, err))
+    }
+
+    /// Make a POST request with a JSON body and return the successful
+    /// response without reading its body, for streamed replies. The
+    /// request's concurrency slot is released once the response arrives.
+    pub(crate) async fn post_streaming<B>(&self, endpoint: &str, body: &B) -> Result<Response>
+    where
+        B: Serialize + ?Sized,
+    {
+        async {
+            let _slot = self.request_slot().await;
+            let (key, response) = self.send_post(endpoint, body).await?;
+            if response.status().is_success() {
+                Ok(response)
+            } else {
+                Err(self.error_response(key, response).await)
+            }
+        }
+        .await
+        .map_err(|err: Error| endpoint_error(self.tag(), endpoint, err))
+    }
+
+    /// Send a POST request, returning the key it was sent with and the
+    /// response.
+    async fn send_post<B>(&self, endpoint: &str, body: &B) -> Result<(usize, Response)>
+    where
+        B: Serialize + ?Sized,
+    {
+        let key = self.acquire_rate_limit(endpoint).await?;
+        let response = self
+            .send("POST", endpoint, key, |url| {
+                self.inner.http_client.post(url).json(body)
+            })
+            .await?;
+        Ok((key, response))
+    }
+
+    /// Wait for the endpoint's bucket, if it has one, then pick the key for
+    /// the next request and wait for its rate limiter, spending the
+    /// endpoint's [`request_cost`](Self::request_cost) from both. Returns the
+    /// key's index.
+    async fn acquire_rate_limit(&self, endpoint: &str) -> Result<usize> {
+        let cost = self.request_cost(endpoint);
+        #[cfg(feature = "metrics")]
+        let started = rt::Instant::now();
+        if let Some(bucket) = self.inner.endpoint_limiters.for_endpoint(endpoint) {
+            bucket.acquire_tagged(cost, self.tag()).await?;
+        }
+        let key = self.inner.keys.pick();
+        self.inner
+            .keys
+            .limiter(key)
+            .acquire_tagged(cost, self.tag())
+            .await?;
+        #[cfg(feature = "metrics")]
+        crate::metrics::rate_limit_wait(endpoint, self.tag(), started.elapsed());
+        Ok(key)
+    }
+
+    /// Wait for a slot under [`ClientConfig::max_concurrent_requests`], held
+    /// until the returned permit is dropped; `None` without a limit.
+    async fn request_slot(&self) -> Option<SemaphorePermit<'_>> {
+        // The semaphore is never closed, so acquiring only fails without one.
+        self.inner.request_slots.as_ref()?.acquire().await.ok()
+    }
+
+    /// Rate-limit tokens a request to `endpoint` spends: its
+    /// [`endpoint_weights`](ClientConfig::endpoint_weights) entry, else its
+    /// cost with [`ClientConfig::weighted_rate_limit`], else one.
+    fn request_cost(&self, endpoint: &str) -> u32 {
+        let config = &self.inner.config;
+        let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+        match config.endpoint_weights.get(path) {
+            Some(&weight) => weight,
+            None if config.weighted_rate_limit => crate::coverage::cost(endpoint),
+            None => 1,
+        }
+    }
 
-        let mut url = self.base_url.clone();
+    /// Warm the response cache in the background.
+    ///
+    /// Each endpoint is a path and query as the endpoint methods build them,
+    /// e.g. `/stock/profile2?symbol=AAPL`. Endpoints the cache policy doesn't
+    /// cover, or with a fresh cached response, are skipped. Requests spend
+    /// their weight, as for foreground requests (from
+    /// [`ClientConfig::endpoint_weights`], else the documented cost when
+    /// [`ClientConfig::weighted_rate_limit`] is set, else one), from the
+    /// endpoint's bucket, if it has one, and the key's limiter through
+    /// [`RateLimit::acquire_low_priority_weighted`], which with the default
+    /// limiter leaves half of each for foreground requests. An endpoint whose
+    /// limiter returns an error is not requested and is reported as failed.
+    /// Without a configured cache nothing is fetched. The task is spawned on the
+    /// current Tokio runtime, so this is not available on wasm32.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: finnhub::FinnhubClient) {
+    /// let warmup = client.prefetch(
+    ///     ["AAPL", "MSFT"].map(|symbol| format!("/stock/profile2?symbol={symbol}")),
+    /// );
+    /// let report = warmup.await.expect("prefetch task panicked");
+    /// println!("warmed {} endpoints", report.fetched.len());
+    /// # }
+    /// ```
+    #[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+    pub fn prefetch(
+        &self,
+        endpoints: impl IntoIterator<Item = impl Into<String>>,
+    ) -> rt::JoinHandle<PrefetchReport> {
+        let client = self.clone();
+        let endpoints: Vec<String> = endpoints.into_iter().map(Into::into).collect();
+        rt::spawn(async move {
+            let mut report = PrefetchReport::default();
+            let Some(cache) = client.inner.cache.clone() else {
+                report.skipped = endpoints;
+                return report;
+            };
+
+            for endpoint in endpoints {
+                if !cache.caches(&endpoint) || cache.get(&endpoint).is_some() {
+                    report.skipped.push(endpoint);
+                    continue;
+                }
+                let slot = client.request_slot().await;
+                let cost = client.request_cost(&endpoint);
+                if let Some(bucket) = client.inner.endpoint_limiters.for_endpoint(&endpoint) {
+                    bucket.acquire_low_priority_weighted(cost).await;
+                }
+                let key = client.inner.keys.pick();
+                let result = match client
+                    .inner
+                    .keys
+                    .limiter(key)
+                    .acquire_low_priority_weighted(cost)
+                    .await
+                {
+                    Ok(()) => client.fetch(&endpoint, key).await.and_then(|body| {
+                        serde_json::from_slice::<serde::de::IgnoredAny>(&body)?;
+                        Ok(body)
+                    }),
+                    Err(err) => Err(err),
+                };
+                drop(slot);
+                match result {
+                    Ok(body) => {
+                        cache.insert(&endpoint, &body);
+                        report.fetched.push(endpoint);
+                    }
+                    Err(err) => {
+                        tracing::debug!(endpoint, error = %err, "prefetch failed");
+                        report.failed.push((endpoint, err));
+                    }
+                }
+            }
+            report
+        })
+    }
+
+    /// Wait for the rate limiter and fetch `endpoint`, retrying according to
+    /// the configured [`RetryPolicy`], or once after a 429 with
+    /// [`ClientConfig::auto_retry_rate_limit`]. Each attempt waits for the
+    /// rate limiter again.
+    async fn fetch_with_retries(&self, endpoint: &str) -> Result<Vec<u8>> {
+        let rate_limit_once;
+        let policy = match &self.inner.config.retry_policy {
+            Some(policy) => policy,
+            None if self.inner.config.auto_retry_rate_limit => {
+                rate_limit_once = RetryPolicy::rate_limit_once();
+                &rate_limit_once
+            }
+            None => return self.fetch_with_any_key(endpoint).await,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let error = match self.fetch_with_any_key(endpoint).await {
+                Err(e) if policy.should_retry(attempt, &e) => e,
+                result => return result,
+            };
+            if !self.spend_retry(&error) {
+                return Err(error);
+            }
+            let delay = policy.backoff(attempt, &error);
+            tracing::debug!(endpoint, attempt, ?delay, %error, "retrying request");
+            rt::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Wait for the rate limiter of the next key and fetch `endpoint`. When
+    /// the server rejects the key and another one is usable, the request is
+    /// sent again with that one.
+    async fn fetch_with_any_key(&self, endpoint: &str) -> Result<Vec<u8>> {
+        loop {
+            let _slot = self.request_slot().await;
+            let key = self.acquire_rate_limit(endpoint).await?;
+            match self.fetch(endpoint, key).await {
+                Err(err) if rejects_key(&err) && self.inner.keys.can_switch() => {}
+                result => return result,
+            }
+        }
+    }
+
+    /// Send a GET request with the key at `key` and return the body of a
+    /// successful JSON response.
+    async fn fetch(&self, endpoint: &str, key: usize) -> Result<Vec<u8>> {
+        let response = self
+            .send("GET", endpoint, key, |url| self.get_request(url))
+            .await?;
+        self.handle_response(endpoint, key, response).await
+    }
+
+    /// A GET request for `url`, asking for a compressed body unless
+    /// compression is off or the default headers set `Accept-Encoding`.
+    fn get_request(&self, url: Url) -> reqwest::RequestBuilder {
+        let request = self.inner.http_client.get(url);
+        match compression::ACCEPT_ENCODING {
+            Some(encodings)
+                if self.inner.config.compression
+                    && !self
+                        .inner
+                        .config
+                        .default_headers
+                        .contains_key(ACCEPT_ENCODING) =>
+            {
+                request.header(ACCEPT_ENCODING, encodings)
+            }
+            _ => request,
+        }
+    }
+
+    /// Send the request `request` builds for `endpoint` to the active base
+    /// URL, with the key at `key` and the timeout applied. When the base URL
+    /// can't be reached and the client fails over, the request goes to the
+    /// next one, trying each base URL at most once.
+    async fn send(
+        &self,
+        method: &'static str,
+        endpoint: &str,
+        key: usize,
+        request: impl Fn(Url) -> reqwest::RequestBuilder,
+    ) -> Result<Response> {
+        let auth = self.auth(key);
+        let mut tried = 0;
+        loop {
+            #[cfg(feature = "metrics")]
+            let _in_flight = crate::metrics::request(method, endpoint, self.tag());
+            let (index, base_url) = self.inner.base_urls.active();
+            let result = request(self.request_url(base_url, method, endpoint, &auth))
+                .headers(self.inner.config.default_headers.clone())
+                .headers(auth.headers())
+                .timeout(Duration::from_secs(self.inner.config.timeout_secs))
+                .send()
+                .await;
+            match result {
+                Ok(response) => {
+                    self.inner.base_urls.reachable(index);
+                    if self.inner.config.adaptive_rate_limit {
+                        self.sync_rate_limit(key, response.headers()).await;
+                    }
+                    return Ok(response);
+                }
+                Err(err) if is_connect_error(&err) => {
+                    tried += 1;
+                    if !self.inner.base_urls.unreachable(index)
+                        || tried >= self.inner.base_urls.len()
+                    {
+                        return Err(redact_http_error(err));
+                    }
+                }
+                Err(err) => return Err(redact_http_error(err)),
+            }
+        }
+    }
+
+    /// Apply the `X-Ratelimit-*` headers of a response to the rate limiter
+    /// of the key at `key`.
+    async fn sync_rate_limit(&self, key: usize, headers: &HeaderMap) {
+        let rate_limit = RateLimitHeaders::from_headers(headers);
+        let Some(remaining) = rate_limit.remaining else {
+            return;
+        };
+        let now = response::server_time(headers).unwrap_or_else(chrono::Utc::now);
+        let reset_in = rate_limit
+            .reset_in(now)
+            .map(|reset_in| reset_in.min(MAX_SYNC_PAUSE));
+        if remaining == 0 {
+            tracing::debug!(?reset_in, "server reports no calls left; pausing");
+        }
+        self.inner
+            .keys
+            .limiter(key)
+            .sync_with_server(remaining, reset_in)
+            .await;
+    }
+
+    /// Build the URL for an endpoint (a path and query) on `base_url`, with
+    /// URL auth applied, and log it with the key redacted.
+    fn request_url(&self, base_url: &Url, method: &str, endpoint: &str, auth: &Auth) -> Url {
+        let mut url = base_url.clone();
 
         // Split endpoint into path and query parts
         let (path, query) = if let Some(query_start) = endpoint.find('?') {
@@ -200,7 +1588,13 @@ impl FinnhubClient {
             (endpoint, None)
         };
 
-        url.set_path(&format!("/api/v1{}", path));
+        // Join onto the base URL's own path, so a proxy mounting the API
+        // under a prefix keeps it.
+        url.set_path(&format!(
+            "{}{}",
+            base_url.path().trim_end_matches('/'),
+            path
+        ));
 
         // Add any existing query parameters from the endpoint
         if let Some(query_str) = query {
@@ -213,58 +1607,1486 @@ impl FinnhubClient {
         }
 
         // Apply auth to URL if using URL parameter method
-        self.auth.apply_to_url(&mut url);
+        auth.apply_to_url(&mut url);
 
-        let response = self.http_client.get(url).send().await?;
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            let mut redacted = url.clone();
+            redact_url(&mut redacted);
+            tracing::debug!(url = %redacted, tag = self.tag(), "{method}");
+        }
 
-        self.handle_response(response).await
+        url
     }
 
-    /// Handle API response.
-    async fn handle_response<T>(&self, response: Response) -> Result<T>
-    where
-        T: DeserializeOwned,
-    {
+    /// Handle API response, returning the JSON body of a successful response.
+    async fn handle_response(
+        &self,
+        endpoint: &str,
+        key: usize,
+        response: Response,
+    ) -> Result<Vec<u8>> {
         let status = response.status();
+        let headers = response.headers();
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
 
         if status.is_success() {
-            response.json::<T>().await.map_err(Into::into)
+            let wire_bytes = headers
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+            let encoding = content_encoding(headers);
+            let compressed = encoding.as_deref().is_some_and(|v| v != "identity");
+            let limit = self.inner.config.max_response_bytes;
+            let raw = read_body(response, limit).await?;
+            let wire_bytes = wire_bytes.unwrap_or(raw.len() as u64);
+            let body = compression::decode(encoding.as_deref(), raw, limit)?;
+            self.inner.bandwidth.record(
+                endpoint,
+                self.tag(),
+                Some(wire_bytes),
+                body.len() as u64,
+                compressed,
+            );
+            if !is_json(content_type.as_deref()) || body.trim_ascii().is_empty() {
+                return Err(self.unexpected_content_type(
+                    key,
+                    status.as_u16(),
+                    content_type,
+                    &body,
+                ));
+            }
+            Ok(body)
         } else {
-            match status.as_u16() {
-                401 => Err(Error::Unauthorized),
-                429 => {
-                    let retry_after = response
-                        .headers()
-                        .get("retry-after")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|v| v.parse::<u64>().ok())
-                        .unwrap_or(60);
-
-                    Err(Error::RateLimitExceeded { retry_after })
+            Err(self.error_response(key, response).await)
+        }
+    }
+
+    /// Turn an unsuccessful response to a request sent with the key at `key`
+    /// into an error, demoting the key if the server rejected it.
+    async fn error_response(&self, key: usize, response: Response) -> Error {
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+        let encoding = content_encoding(response.headers());
+        let limit = self.inner.config.max_response_bytes;
+        let error = match status.as_u16() {
+            401 => Error::Unauthorized,
+            429 => {
+                let retry_after = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                if self.inner.config.pause_on_rate_limit {
+                    tracing::warn!(retry_after, "rate limited; pausing all requests");
+                    self.inner
+                        .keys
+                        .pause_for(key, Duration::from_secs(retry_after))
+                        .await;
                 }
-                _ => {
-                    let message = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| format!("HTTP error {}", status.as_u16()));
-
-                    Err(Error::ApiError {
-                        status: status.as_u16(),
-                        message,
-                    })
+                Error::RateLimitExceeded { retry_after }
+            }
+            _ if content_type.as_deref().is_some_and(is_html) => {
+                let body = read_decoded(response, encoding.as_deref(), limit)
+                    .await
+                    .unwrap_or_default();
+                self.unexpected_content_type(key, status.as_u16(), content_type, &body)
+            }
+            _ => {
+                let message = read_decoded(response, encoding.as_deref(), limit)
+                    .await
+                    .map_or_else(
+                        |_| format!("HTTP error {}", status.as_u16()),
+                        |body| String::from_utf8_lossy(&body).into_owned(),
+                    );
+
+                Error::ApiError {
+                    status: status.as_u16(),
+                    message: self.auth(key).redact(&message),
                 }
             }
+        };
+        if rejects_key(&error) {
+            self.inner.keys.demote(key);
+        }
+        error
+    }
+}
+
+impl FinnhubClient {
+    fn unexpected_content_type(
+        &self,
+        key: usize,
+        status: u16,
+        content_type: Option<String>,
+        body: &[u8],
+    ) -> Error {
+        let text = String::from_utf8_lossy(body);
+        let snippet: String = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(SNIPPET_LEN)
+            .collect();
+
+        Error::UnexpectedContentType {
+            status,
+            content_type,
+            snippet: self.auth(key).redact(&snippet),
         }
     }
 }
 
+/// Body of a response as it arrives. reqwest's wasm `Response` has no
+/// `chunk()`, so bodies are read through `bytes_stream()` on every target.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type BodyStream = futures::stream::BoxStream<'static, reqwest::Result<Bytes>>;
+#[cfg(target_arch = "wasm32")]
+pub(crate) type BodyStream = futures::stream::LocalBoxStream<'static, reqwest::Result<Bytes>>;
+
+pub(crate) fn body_stream(response: Response) -> BodyStream {
+    Box::pin(response.bytes_stream())
+}
+
+/// Read the body of `response`, failing with [`Error::ResponseTooLarge`] as
+/// soon as it is known to exceed `limit` bytes.
+async fn read_body(response: Response, limit: Option<u64>) -> Result<Vec<u8>> {
+    let Some(limit) = limit else {
+        return Ok(response.bytes().await.map_err(redact_http_error)?.into());
+    };
+    if response.content_length().is_some_and(|len| len > limit) {
+        return Err(Error::ResponseTooLarge { limit });
+    }
+    let mut body = Vec::new();
+    let mut chunks = body_stream(response);
+    while let Some(chunk) = chunks.next().await.transpose().map_err(redact_http_error)? {
+        if (body.len() + chunk.len()) as u64 > limit {
+            return Err(Error::ResponseTooLarge { limit });
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Read the body of `response` and decode its `encoding`, both within `limit`
+/// bytes.
+async fn read_decoded(
+    response: Response,
+    encoding: Option<&str>,
+    limit: Option<u64>,
+) -> Result<Vec<u8>> {
+    let body = read_body(response, limit).await?;
+    compression::decode(encoding, body, limit)
+}
+
+/// Check a path given to [`FinnhubClient::get_raw`] or
+/// [`FinnhubClient::get_enveloped`], whose query goes in separate parameters.
+/// Attach `endpoint` to `err`, counting the failure in the error metrics of a
+/// client tagged `tag` unless an inner call already did.
+fn endpoint_error(tag: Option<&str>, endpoint: &str, err: Error) -> Error {
+    #[cfg(feature = "metrics")]
+    if !matches!(err, Error::Endpoint { .. }) {
+        crate::metrics::error(endpoint, tag, &err);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = tag;
+    err.at_endpoint(endpoint)
+}
+
+fn check_path(path: &str) -> Result<()> {
+    if !path.starts_with('/') || path.contains('?') {
+        return Err(Error::invalid_parameter(format!(
+            "path must start with `/` and pass its query in `params`, got {path:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// The `Content-Encoding` of a response, if any.
+fn content_encoding(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+}
+
+/// Build the HTTP client for a configuration without an injected one.
+#[cfg(not(target_arch = "wasm32"))]
+fn new_http_client(config: &ClientConfig) -> HttpClient {
+    let connection = &config.connection;
+    let secs = |secs: Option<u64>| secs.map(Duration::from_secs);
+    let mut builder = HttpClient::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .tcp_keepalive(secs(connection.tcp_keepalive_secs))
+        .http2_keep_alive_interval(secs(connection.http2_keep_alive_secs));
+    if let Some(max_idle) = connection.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(idle_timeout) = connection.pool_idle_timeout_secs {
+        builder = builder.pool_idle_timeout(Duration::from_secs(idle_timeout));
+    }
+    builder = match connection.http_version {
+        HttpVersion::Auto => builder,
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2 => builder.http2_prior_knowledge(),
+    };
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(proxy.to_reqwest().expect("Invalid proxy"));
+    }
+    builder.build().expect("Failed to build HTTP client")
+}
+
+/// Build the HTTP client for a configuration without an injected one. The
+/// browser's `fetch` makes the connections, so there is no connect timeout,
+/// proxy or connection pool to set.
+#[cfg(target_arch = "wasm32")]
+fn new_http_client(_config: &ClientConfig) -> HttpClient {
+    HttpClient::new()
+}
+
+#[cfg(feature = "cache")]
+fn new_cache(config: &ClientConfig) -> Option<Arc<ResponseCache>> {
+    config
+        .cache
+        .clone()
+        .map(|policy| Arc::new(ResponseCache::new(policy)))
+}
+
+/// Whether a `Content-Type` denotes JSON. A missing header is given the benefit
+/// of the doubt.
+fn is_json(content_type: Option<&str>) -> bool {
+    content_type.is_none_or(|ct| ct.to_ascii_lowercase().contains("json"))
+}
+
+fn is_html(content_type: &str) -> bool {
+    content_type.to_ascii_lowercase().contains("html")
+}
+
+/// Whether the server rejected the API key a request was sent with. Finnhub
+/// also answers 403 for premium endpoints called with a free key; that says
+/// nothing about the key, and another free key would get the same answer.
+fn rejects_key(err: &Error) -> bool {
+    match err.root() {
+        Error::Unauthorized => true,
+        Error::ApiError {
+            status: 403,
+            message,
+        } => !message.contains(ACCESS_DENIED),
+        _ => false,
+    }
+}
+
+/// Whether the request failed before reaching the server.
+fn is_connect_error(err: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    return err.is_connect();
+    // The fetch API doesn't say why a request failed.
+    #[cfg(target_arch = "wasm32")]
+    return err.is_request();
+}
+
+/// Strip the API key from the URL carried by a reqwest error so it can't leak
+/// through `Display` or `Debug`.
+pub(crate) fn redact_http_error(mut err: reqwest::Error) -> Error {
+    if let Some(url) = err.url_mut() {
+        redact_url(url);
+    }
+    Error::Http(err)
+}
+
+/// A client with the default configuration sending requests to a mock server.
+#[cfg(test)]
+pub(crate) fn mock_client(server: &wiremock::MockServer) -> FinnhubClient {
+    let config = ClientConfig {
+        base_url: format!("{}/api/v1", server.uri()),
+        ..ClientConfig::default()
+    };
+    FinnhubClient::with_config("test-api-key", config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::RateLimiter;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn test_client_creation() {
         let client = FinnhubClient::new("test-api-key");
-        assert!(client.auth.api_key() == "test-api-key");
+        assert!(client.auth(0).api_key() == "test-api-key");
+    }
+
+    #[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+    #[test]
+    fn test_https_needs_a_tls_feature() {
+        assert!(matches!(
+            ClientConfig::default().validate(),
+            Err(Error::InvalidConfig {
+                field: "base_url",
+                ..
+            })
+        ));
+        let config = ClientConfig {
+            base_url: "http://localhost:8080/api/v1".to_string(),
+            ..ClientConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[cfg(any(feature = "rustls", feature = "native-tls"))]
+    #[test]
+    fn test_config_validation() {
+        assert!(ClientConfig::default().validate().is_ok());
+
+        let invalid = |config: ClientConfig| match FinnhubClient::try_with_config("key", config) {
+            Err(Error::InvalidConfig { field, .. }) => field,
+            other => panic!("expected InvalidConfig, got {other:?}"),
+        };
+        assert_eq!(
+            invalid(ClientConfig {
+                base_url: "not a url".to_string(),
+                ..ClientConfig::default()
+            }),
+            "base_url"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                base_url: "ftp://finnhub.io/api/v1".to_string(),
+                ..ClientConfig::default()
+            }),
+            "base_url"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                fallback_base_urls: vec!["backup.example".to_string()],
+                ..ClientConfig::default()
+            }),
+            "fallback_base_urls"
+        );
+        let mut token_header = HeaderMap::new();
+        token_header.insert("x-finnhub-token", "other-key".parse().unwrap());
+        assert_eq!(
+            invalid(ClientConfig {
+                default_headers: token_header,
+                ..ClientConfig::default()
+            }),
+            "default_headers"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                timeout_secs: 0,
+                ..ClientConfig::default()
+            }),
+            "timeout_secs"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                connect_timeout_secs: 0,
+                ..ClientConfig::default()
+            }),
+            "connect_timeout_secs"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                rate_limit_strategy: RateLimitStrategy::Custom {
+                    capacity: 0,
+                    refill_rate: 30,
+                },
+                ..ClientConfig::default()
+            }),
+            "rate_limit_strategy"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                retry_policy: Some(RetryPolicy {
+                    jitter: 1.5,
+                    ..RetryPolicy::default()
+                }),
+                ..ClientConfig::default()
+            }),
+            "retry_policy"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                endpoint_buckets: vec![EndpointBucket::new(0, 1).with_path("/stock/tick")],
+                ..ClientConfig::default()
+            }),
+            "endpoint_buckets"
+        );
+        assert_eq!(
+            invalid(ClientConfig {
+                max_concurrent_requests: Some(0),
+                ..ClientConfig::default()
+            }),
+            "max_concurrent_requests"
+        );
+        #[allow(deprecated)]
+        let legacy = ClientConfig {
+            rate_limit: Some(0),
+            ..ClientConfig::default()
+        };
+        assert_eq!(invalid(legacy), "rate_limit");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_legacy_rate_limit_takes_precedence() {
+        let config = ClientConfig {
+            rate_limit: Some(60),
+            rate_limit_strategy: RateLimitStrategy::FifteenSecondWindow,
+            ..ClientConfig::default()
+        };
+        assert!(matches!(
+            config.effective_rate_limit_strategy(),
+            RateLimitStrategy::Custom {
+                capacity: 60,
+                refill_rate: 60
+            }
+        ));
+        assert!(matches!(
+            ClientConfig::default().effective_rate_limit_strategy(),
+            RateLimitStrategy::PerSecond
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "runtime-agnostic")]
+    fn test_retry_without_tokio_runtime() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let client = FinnhubClient::new("test-api-key");
+        let calls = AtomicU32::new(0);
+        let start = rt::Instant::now();
+        let result: Result<u32> = futures::executor::block_on(client.retry(2, || {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match call {
+                    0 => Err(Error::RateLimitExceeded { retry_after: 1 }),
+                    _ => Ok(call),
+                }
+            }
+        }));
+
+        assert_eq!(result.unwrap(), 1);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_when_budget_exhausted() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let client = FinnhubClient::with_config(
+            "test-api-key",
+            ClientConfig {
+                retry_budget: Some(0),
+                ..ClientConfig::default()
+            },
+        );
+        let exhausted = Arc::new(AtomicU32::new(0));
+        let hook_count = Arc::clone(&exhausted);
+        client.retry_budget().unwrap().on_exhausted(move |error| {
+            assert!(matches!(error, Error::Timeout));
+            hook_count.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = client
+            .retry(5, || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Err(Error::Timeout) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(exhausted.load(Ordering::SeqCst), 1);
+        assert_eq!(client.retry_budget().unwrap().denied(), 1);
+        // Derived clients spend from the same budget.
+        let derived = client.reconfigure(|cfg| cfg.timeout_secs = 60);
+        assert_eq!(derived.retry_budget().unwrap().denied(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_retries_get_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/peers"))
+            .respond_with(ResponseTemplate::new(503).set_body_raw("<html>busy</html>", "text/html"))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/peers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["AAPL"]))
+            .mount(&server)
+            .await;
+
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(10),
+            ..RetryPolicy::default()
+        };
+        let client = mock_client(&server).reconfigure(|cfg| cfg.retry_policy = Some(policy));
+        let peers: Vec<String> = client.get("/stock/peers?symbol=AAPL").await.unwrap();
+        assert_eq!(peers, ["AAPL"]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        // Without a policy the first error is returned.
+        server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503).set_body_raw("<html>busy</html>", "text/html"))
+            .mount(&server)
+            .await;
+        let err = mock_client(&server)
+            .get::<Vec<String>>("/stock/peers?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_recorded_per_endpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"c": 1.5})))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let derived = client.reconfigure(|cfg| cfg.timeout_secs = 60);
+        let _: serde_json::Value = client.get("/quote?symbol=AAPL").await.unwrap();
+        let _: serde_json::Value = derived.get("/quote?symbol=MSFT").await.unwrap();
+
+        let quote = client.bandwidth()["/quote"];
+        assert_eq!(quote.requests, 2);
+        assert_eq!(quote.body_bytes, 2 * br#"{"c":1.5}"#.len() as u64);
+        assert_eq!(quote.wire_bytes, quote.body_bytes);
+        assert_eq!(quote.compressed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_raw_returns_any_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/new-endpoint"))
+            .and(query_param("q", "a&b=c"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-remaining", "29")
+                    .set_body_raw("not quite json", "application/json"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such endpoint"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let (status, headers, body) = client
+            .get_raw("/stock/new-endpoint", &[("q", "a&b=c")])
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["x-ratelimit-remaining"], "29");
+        assert_eq!(&body[..], b"not quite json");
+
+        let (status, _, body) = client.get_raw("/stock/missing", &[]).await.unwrap();
+        assert_eq!(
+            (status.as_u16(), &body[..]),
+            (404, &b"no such endpoint"[..])
+        );
+        assert_eq!(client.bandwidth()["/stock/missing"].requests, 1);
+
+        assert!(matches!(
+            client.get_raw("/quote?symbol=AAPL", &[]).await,
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_enveloped_returns_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(query_param("symbol", "AAPL"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-limit", "30")
+                    .insert_header("x-ratelimit-remaining", "12")
+                    .set_body_json(serde_json::json!({"c": 261.74})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("no such endpoint"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let envelope = client
+            .get_enveloped::<serde_json::Value>("/quote", &[("symbol", "AAPL")])
+            .await
+            .unwrap();
+        assert_eq!(envelope.status, StatusCode::OK);
+        assert_eq!(envelope.body["c"], 261.74);
+        assert_eq!(envelope.rate_limit.limit, Some(30));
+        assert_eq!(envelope.rate_limit.remaining, Some(12));
+        // wiremock's server sends a `Date` header.
+        assert!(envelope.server_time.is_some());
+
+        assert!(matches!(
+            client
+                .get_enveloped::<serde_json::Value>("/stock/missing", &[])
+                .await,
+            Err(Error::Endpoint { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_response_size_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/tick"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"p": vec![1.5; 100]})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| cfg.max_response_bytes = Some(100));
+        let err = client
+            .get::<serde_json::Value>("/stock/tick?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err.root(), Error::ResponseTooLarge { limit: 100 }),
+            "{err}"
+        );
+
+        let client = client.reconfigure(|cfg| cfg.max_response_bytes = Some(1 << 20));
+        let value: serde_json::Value = client.get("/stock/tick?symbol=AAPL").await.unwrap();
+        assert_eq!(value["p"][99], 1.5);
+    }
+
+    #[tokio::test]
+    async fn test_error_body_size_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("x".repeat(1000)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_raw("<html>".repeat(1000), "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| cfg.max_response_bytes = Some(100));
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        match err.root() {
+            Error::ApiError { status, message } => {
+                assert_eq!((*status, message.as_str()), (500, "HTTP error 500"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        let err = client
+            .get::<serde_json::Value>("/stock/profile2?symbol=AAPL")
+            .await
+            .unwrap_err();
+        match err.root() {
+            Error::UnexpectedContentType {
+                status, snippet, ..
+            } => {
+                assert_eq!((*status, snippet.as_str()), (503, ""));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_settings() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"c": 1.5})))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| {
+            cfg.connection = ConnectionConfig {
+                http_version: HttpVersion::Http2,
+                pool_max_idle_per_host: Some(4),
+                pool_idle_timeout_secs: Some(30),
+                tcp_keepalive_secs: Some(60),
+                http2_keep_alive_secs: Some(20),
+            };
+        });
+        let _: serde_json::Value = client.get("/quote?symbol=AAPL").await.unwrap();
+        let http1 = client.reconfigure(|cfg| cfg.connection.http_version = HttpVersion::Http1);
+        let _: serde_json::Value = http1.get("/quote?symbol=AAPL").await.unwrap();
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn test_gzip_response_decoded() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        use wiremock::matchers::header_regex;
+
+        let body = serde_json::to_vec(&serde_json::json!({"symbols": vec!["AAPL"; 200]})).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&body).unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/symbol"))
+            .and(header_regex("accept-encoding", "gzip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(encoder.finish().unwrap(), "application/json"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/symbol"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body.clone(), "application/json"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let value: serde_json::Value = client.get("/stock/symbol?exchange=US").await.unwrap();
+        assert_eq!(value["symbols"][199], "AAPL");
+        let usage = client.bandwidth()["/stock/symbol"];
+        assert_eq!((usage.compressed, usage.body_bytes), (1, body.len() as u64));
+        assert!(usage.wire_bytes < usage.body_bytes / 10);
+
+        // Turned off, the request asks for nothing and gets the plain body.
+        let plain = client.reconfigure(|cfg| cfg.compression = false);
+        let _: serde_json::Value = plain.get("/stock/symbol?exchange=US").await.unwrap();
+        assert_eq!(client.bandwidth()["/stock/symbol"].compressed, 1);
+    }
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_cached_response_skips_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"n": 1})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| {
+            cfg.cache = Some(CachePolicy::default());
+        });
+        for _ in 0..2 {
+            let value: serde_json::Value = client.get("/stock/profile2?symbol=AAPL").await.unwrap();
+            assert_eq!(value["n"], 1);
+        }
+
+        // Derived clients with the same policy share the cache.
+        let derived = client.reconfigure(|cfg| cfg.timeout_secs = 5);
+        let value: serde_json::Value = derived.get("/stock/profile2?symbol=AAPL").await.unwrap();
+        assert_eq!(value["n"], 1);
+    }
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_prefetch_warms_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"n": 1})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| {
+            cfg.cache = Some(CachePolicy::default());
+        });
+        let report = client
+            .prefetch(["/stock/profile2?symbol=AAPL", "/stock/candle?symbol=AAPL"])
+            .await
+            .unwrap();
+        assert_eq!(report.fetched, ["/stock/profile2?symbol=AAPL"]);
+        assert_eq!(report.skipped, ["/stock/candle?symbol=AAPL"]);
+        assert!(report.failed.is_empty());
+
+        let value: serde_json::Value = client.get("/stock/profile2?symbol=AAPL").await.unwrap();
+        assert_eq!(value["n"], 1);
+    }
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_prefetch_spends_endpoint_bucket_and_weight() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            cache: Some(CachePolicy::default()),
+            endpoint_weights: HashMap::from([("/stock/profile2".to_string(), 3)]),
+            endpoint_buckets: vec![EndpointBucket::new(10, 1).with_path("/stock/profile2")],
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        client
+            .prefetch(["/stock/profile2?symbol=AAPL"])
+            .await
+            .unwrap();
+        let bucket = client
+            .inner
+            .endpoint_limiters
+            .for_endpoint("/stock/profile2")
+            .unwrap();
+        assert_eq!(bucket.available_tokens().await, 7);
+    }
+
+    #[cfg(feature = "cache")]
+    #[tokio::test]
+    async fn test_prefetch_skips_endpoints_the_limiter_refuses() {
+        #[derive(Debug)]
+        struct Refusing;
+
+        #[async_trait::async_trait]
+        impl RateLimit for Refusing {
+            async fn acquire_weighted(&self, _cost: u32) -> Result<()> {
+                Err(Error::RateLimitExceeded { retry_after: 1 })
+            }
+            async fn try_acquire(&self) -> Result<()> {
+                Err(Error::RateLimitExceeded { retry_after: 1 })
+            }
+            async fn available(&self) -> u32 {
+                0
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            cache: Some(CachePolicy::default()),
+            rate_limiter: Some(Arc::new(Refusing)),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        let report = client
+            .prefetch(["/stock/profile2?symbol=AAPL"])
+            .await
+            .unwrap();
+        assert!(report.fetched.is_empty());
+        assert!(matches!(
+            report.failed[..],
+            [(_, Error::RateLimitExceeded { .. })]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_buckets_and_weights() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            endpoint_weights: HashMap::from([("/stock/metric".to_string(), 3)]),
+            endpoint_buckets: vec![EndpointBucket::new(1, 4).with_path("/stock/tick")],
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        let limiter = client.inner.keys.limiter(0);
+
+        client
+            .get::<serde_json::Value>("/stock/metric?symbol=AAPL")
+            .await
+            .unwrap();
+        // 27 left, or 28 if a token was refilled meanwhile.
+        assert!(limiter.available().await <= 28);
+
+        // The second tick page waits for the bucket; quotes don't.
+        let start = rt::Instant::now();
+        client.get::<serde_json::Value>("/quote").await.unwrap();
+        client
+            .get::<serde_json::Value>("/stock/tick")
+            .await
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_millis(150));
+        client
+            .get::<serde_json::Value>("/stock/tick")
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            max_concurrent_requests: Some(2),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        let derived = client.reconfigure(|cfg| cfg.max_concurrent_requests = None);
+
+        // Four slow requests, two at a time, across both clients.
+        let start = rt::Instant::now();
+        let results = futures::future::join_all([
+            client.get::<serde_json::Value>("/quote?symbol=AAPL"),
+            client.get::<serde_json::Value>("/quote?symbol=MSFT"),
+            derived.get::<serde_json::Value>("/quote?symbol=NVDA"),
+            derived.get::<serde_json::Value>("/quote?symbol=AMZN"),
+        ])
+        .await;
+        assert!(results.iter().all(Result::is_ok));
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_clients_share_a_rate_limiter() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let limiter = Arc::new(RateLimiter::new(10, 1));
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            rate_limiter: Some(limiter.clone()),
+            ..ClientConfig::default()
+        };
+        let first = FinnhubClient::with_config("test-api-key", config.clone());
+        let second = FinnhubClient::with_config("test-api-key", config);
+        first.get::<serde_json::Value>("/quote").await.unwrap();
+        second.get::<serde_json::Value>("/quote").await.unwrap();
+        assert_eq!(limiter.available_tokens().await, 8);
+    }
+
+    #[tokio::test]
+    async fn test_html_body_is_unexpected_content_type() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html>\n  <title>Just a moment...</title>\n</html>",
+                "text/html; charset=UTF-8",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        match err.into_root() {
+            Error::UnexpectedContentType {
+                status,
+                content_type,
+                snippet,
+            } => {
+                assert_eq!(status, 200);
+                assert_eq!(content_type.as_deref(), Some("text/html; charset=UTF-8"));
+                assert_eq!(snippet, "<html> <title>Just a moment...</title> </html>");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_is_unexpected_content_type() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("content-type", "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=%5EGSPC")
+            .await
+            .unwrap_err();
+        assert_eq!(err.endpoint(), Some("/quote"));
+        assert_eq!(err.symbol(), Some("^GSPC"));
+        assert!(err
+            .to_string()
+            .starts_with("/quote (^GSPC): Unexpected content type"));
+        assert!(matches!(err.root(), Error::UnexpectedContentType { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_html_error_page_is_retryable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(502).set_body_raw("<html>Bad gateway</html>", "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            Error::UnexpectedContentType { status: 502, .. }
+        ));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_injected_http_client_is_used() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(header("x-service", "pricing"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-service", HeaderValue::from_static("pricing"));
+        let shared = HttpClient::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            http_client: Some(shared),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_proxy() {
+        use wiremock::matchers::header;
+
+        // The mock server stands in for the proxy; the API host doesn't resolve.
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(header("proxy-authorization", "Basic c3ZjOnNlY3JldA=="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let config = ClientConfig {
+            base_url: "http://finnhub.invalid/api/v1".to_string(),
+            proxy: Some(ProxyConfig::new(proxy.uri()).with_credentials("svc", "secret")),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::try_with_config("test-api-key", config).unwrap();
+        client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap();
+
+        let conflicting = ClientConfig {
+            proxy: Some(ProxyConfig::new(proxy.uri())),
+            http_client: Some(HttpClient::new()),
+            ..ClientConfig::default()
+        };
+        assert!(matches!(
+            conflicting.validate(),
+            Err(Error::InvalidConfig { field: "proxy", .. })
+        ));
+    }
+
+    #[test]
+    fn test_reconfigure_shares_state() {
+        let client = FinnhubClient::new("test-api-key");
+        let derived = client.reconfigure(|cfg| {
+            cfg.timeout_secs = 120;
+            cfg.auth_method = AuthMethod::UrlParameter;
+        });
+
+        assert!(Arc::ptr_eq(&client.inner.keys, &derived.inner.keys));
+        assert_eq!(derived.config().timeout_secs, 120);
+        assert_eq!(client.config().timeout_secs, DEFAULT_TIMEOUT_SECS);
+        assert!(matches!(derived.auth(0).method(), AuthMethod::UrlParameter));
+        assert_eq!(derived.auth(0).api_key(), "test-api-key");
+
+        assert!(client.clone().ptr_eq(&client));
+        assert!(!derived.ptr_eq(&client));
+    }
+
+    #[test]
+    fn test_debug_does_not_leak_api_key() {
+        let config = ClientConfig {
+            auth_method: AuthMethod::UrlParameter,
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("secret-key", config);
+        assert!(!format!("{client:?}").contains("secret-key"));
+
+        let client = FinnhubClient::new("secret-key");
+        assert!(!format!("{client:?}").contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_redacts_token() {
+        let config = ClientConfig {
+            // Nothing listens on port 9, so the request fails before reaching any server.
+            base_url: "http://127.0.0.1:9/api/v1".to_string(),
+            auth_method: AuthMethod::UrlParameter,
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("secret-key", config);
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert!(!err.to_string().contains("secret-key"));
+        assert!(!format!("{err:?}").contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_fallback_base_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: "http://127.0.0.1:9/api/v1".to_string(),
+            fallback_base_urls: vec![format!("{}/api/v1", server.uri())],
+            failover_after_secs: 0,
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::try_with_config("test-api-key", config).unwrap();
+        client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap();
+
+        // Derived clients stay on the fallback.
+        let derived = client.reconfigure(|cfg| cfg.timeout_secs = 5);
+        derived
+            .get::<serde_json::Value>("/quote?symbol=MSFT")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_prefixed_fallback_base_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/finnhub/api/v1/quote"))
+            .and(query_param("symbol", "AAPL"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: "http://127.0.0.1:9/api/v1".to_string(),
+            fallback_base_urls: vec![format!("{}/finnhub/api/v1/", server.uri())],
+            failover_after_secs: 0,
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::try_with_config("test-api-key", config).unwrap();
+        client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_every_base_url_is_down() {
+        let config = ClientConfig {
+            // Nothing listens on ports 9 or 19.
+            base_url: "http://127.0.0.1:9/api/v1".to_string(),
+            fallback_base_urls: vec!["http://127.0.0.1:19/api/v1".to_string()],
+            failover_after_secs: 0,
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::try_with_config("test-api-key", config).unwrap();
+        for symbol in ["AAPL", "MSFT"] {
+            let result = tokio::time::timeout(
+                Duration::from_secs(5),
+                client.get::<serde_json::Value>(&format!("/quote?symbol={symbol}")),
+            )
+            .await
+            .expect("request should give up once every base URL was tried");
+            assert!(matches!(result.unwrap_err().root(), Error::Http(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_and_tags() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(header("X-Client-App", "my-service"))
+            .and(header("X-Finnhub-Token", "test-api-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert("X-Client-App", "my-service".parse().unwrap());
+        let client = FinnhubClient::with_config(
+            "test-api-key",
+            ClientConfig {
+                base_url: format!("{}/api/v1", server.uri()),
+                default_headers,
+                ..ClientConfig::default()
+            },
+        );
+        let research = client.reconfigure(|cfg| cfg.tag = Some("research".to_string()));
+        assert_eq!(client.tag(), None);
+        assert_eq!(research.tag(), Some("research"));
+
+        for client in [&client, &research, &research] {
+            client
+                .get::<serde_json::Value>("/quote?symbol=AAPL")
+                .await
+                .unwrap();
+        }
+
+        let by_tag = client.bandwidth_by_tag();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag["research"].requests, 2);
+        assert_eq!(client.bandwidth()["/quote"].requests, 3);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response_pauses_all_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| cfg.pause_on_rate_limit = true);
+        let other = client.reconfigure(|cfg| cfg.tag = Some("other".to_string()));
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert_eq!(err.retry_after(), Some(1));
+
+        // A client sharing the rate limiter waits out the pause too.
+        let start = rt::Instant::now();
+        other
+            .get::<serde_json::Value>("/quote?symbol=MSFT")
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_rate_limit_follows_headers() {
+        let server = MockServer::start().await;
+        let reset = chrono::Utc::now().timestamp() + 2;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-Ratelimit-Remaining", "0")
+                    .insert_header("X-Ratelimit-Reset", reset.to_string().as_str())
+                    .set_body_json(serde_json::json!({})),
+            )
+            .mount(&server)
+            .await;
+
+        let limiter = Arc::new(RateLimiter::finnhub_default());
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            rate_limiter: Some(limiter.clone()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        client.get::<serde_json::Value>("/quote").await.unwrap();
+        assert_eq!(limiter.paused_for().await, None);
+
+        let client = client.reconfigure(|cfg| cfg.adaptive_rate_limit = true);
+        client.get::<serde_json::Value>("/quote").await.unwrap();
+        let paused = limiter.paused_for().await;
+        assert!(paused.is_some_and(|pause| pause <= Duration::from_secs(3)));
+    }
+
+    #[tokio::test]
+    async fn test_auto_retry_rate_limit_retries_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).reconfigure(|cfg| cfg.auto_retry_rate_limit = true);
+        client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // Only once, and only for 429s.
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/peers"))
+            .respond_with(ResponseTemplate::new(503).set_body_raw("<html>busy</html>", "text/html"))
+            .mount(&server)
+            .await;
+        let err = client
+            .get::<serde_json::Value>("/quote?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert!(matches!(err.root(), Error::RateLimitExceeded { .. }));
+        client
+            .get::<Vec<String>>("/stock/peers?symbol=AAPL")
+            .await
+            .unwrap_err();
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_key_pool_round_robin_and_demotion() {
+        let server = MockServer::start().await;
+        for key in ["key-a", "key-b"] {
+            Mock::given(method("GET"))
+                .and(header("X-Finnhub-Token", key))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+                .expect(1..)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(header("X-Finnhub-Token", "revoked"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_keys(["key-a", "revoked", "key-b"], config);
+        // The revoked key's request is sent again with the next key.
+        for _ in 0..4 {
+            client
+                .get::<serde_json::Value>("/quote?symbol=AAPL")
+                .await
+                .unwrap();
+        }
+        assert_eq!(client.demoted_keys(), [1]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 5);
+        assert!(!format!("{client:?}").contains("key-a"));
+
+        let update = ReloadableConfig {
+            api_key: Some("key-c".to_string()),
+            ..ReloadableConfig::default()
+        };
+        assert!(client.apply_reload(&update).await.is_err());
+        assert!(
+            FinnhubClient::try_with_keys(Vec::<String>::new(), ClientConfig::default()).is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_premium_403_keeps_keys() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(
+                serde_json::json!({"error": "You don't have access to this resource."}),
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_keys(["key-a", "key-b"], config);
+        for _ in 0..2 {
+            let err = client
+                .get::<serde_json::Value>("/stock/tick?symbol=AAPL")
+                .await
+                .unwrap_err();
+            assert!(matches!(err.root(), Error::ApiError { status: 403, .. }));
+        }
+        // Neither key is demoted, and neither request was re-sent.
+        assert!(client.demoted_keys().is_empty());
     }
 }
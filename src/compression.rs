@@ -0,0 +1,123 @@
+//! Compressed responses.
+//!
+//! With the `gzip` or `brotli` feature and [`ClientConfig::compression`] on,
+//! GET requests advertise the encodings the client can decode in
+//! `Accept-Encoding`, and bodies are decoded here rather than by reqwest, so
+//! [bandwidth accounting](crate::bandwidth) still sees the compressed size.
+//! On wasm32 the browser negotiates and decodes compression itself.
+//!
+//! [`ClientConfig::compression`]: crate::ClientConfig::compression
+
+#[cfg(all(any(feature = "gzip", feature = "brotli"), not(target_arch = "wasm32")))]
+use std::io::Read;
+
+#[cfg(all(any(feature = "gzip", feature = "brotli"), not(target_arch = "wasm32")))]
+use crate::error::Error;
+use crate::error::Result;
+
+/// `Accept-Encoding` value for the enabled features; `None` without any.
+#[cfg(all(feature = "gzip", feature = "brotli", not(target_arch = "wasm32")))]
+pub(crate) const ACCEPT_ENCODING: Option<&str> = Some("br, gzip, deflate");
+#[cfg(all(feature = "gzip", not(feature = "brotli"), not(target_arch = "wasm32")))]
+pub(crate) const ACCEPT_ENCODING: Option<&str> = Some("gzip, deflate");
+#[cfg(all(not(feature = "gzip"), feature = "brotli", not(target_arch = "wasm32")))]
+pub(crate) const ACCEPT_ENCODING: Option<&str> = Some("br");
+#[cfg(any(not(any(feature = "gzip", feature = "brotli")), target_arch = "wasm32"))]
+pub(crate) const ACCEPT_ENCODING: Option<&str> = None;
+
+/// Decode a body sent with `Content-Encoding: encoding`, failing with
+/// [`Error::ResponseTooLarge`] once it decodes to more than `limit` bytes.
+///
+/// Bodies without an encoding, or with one the enabled features can't
+/// decode, are returned as they are.
+#[cfg(all(any(feature = "gzip", feature = "brotli"), not(target_arch = "wasm32")))]
+pub(crate) fn decode(encoding: Option<&str>, body: Vec<u8>, limit: Option<u64>) -> Result<Vec<u8>> {
+    let Some(encoding) = encoding.map(str::trim) else {
+        return Ok(body);
+    };
+    let reader: Box<dyn Read + '_> = match encoding.to_ascii_lowercase().as_str() {
+        #[cfg(feature = "gzip")]
+        "gzip" | "x-gzip" => Box::new(flate2::read::MultiGzDecoder::new(body.as_slice())),
+        #[cfg(feature = "gzip")]
+        "deflate" => Box::new(flate2::read::ZlibDecoder::new(body.as_slice())),
+        #[cfg(feature = "brotli")]
+        "br" => Box::new(brotli_decompressor::Decompressor::new(
+            body.as_slice(),
+            4096,
+        )),
+        _ => return Ok(body),
+    };
+    // Read one byte past the limit to tell a body of exactly `limit` bytes
+    // from a larger one.
+    let max_read = limit.map_or(u64::MAX, |limit| limit.saturating_add(1));
+    // Never reserve more than may be read, so a small compressed body can't
+    // allocate past the limit before decoding starts.
+    let capacity = body
+        .len()
+        .saturating_mul(4)
+        .min(usize::try_from(max_read).unwrap_or(usize::MAX));
+    let mut decoded = Vec::with_capacity(capacity);
+    reader
+        .take(max_read)
+        .read_to_end(&mut decoded)
+        .map_err(|err| Error::Internal(format!("failed to decode {encoding} response: {err}")))?;
+    match limit {
+        Some(limit) if decoded.len() as u64 > limit => Err(Error::ResponseTooLarge { limit }),
+        _ => Ok(decoded),
+    }
+}
+
+/// Without a compression feature nothing is requested compressed, and the
+/// browser hands over decoded bodies, so bodies are returned as they are.
+#[cfg(any(not(any(feature = "gzip", feature = "brotli")), target_arch = "wasm32"))]
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn decode(
+    _encoding: Option<&str>,
+    body: Vec<u8>,
+    _limit: Option<u64>,
+) -> Result<Vec<u8>> {
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_encodings_pass_through() {
+        let body = br#"{"c":1.0}"#.to_vec();
+        assert_eq!(decode(None, body.clone(), None).unwrap(), body);
+        assert_eq!(decode(Some("identity"), body.clone(), None).unwrap(), body);
+        assert_eq!(decode(Some("zstd"), body.clone(), None).unwrap(), body);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_round_trip() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let body = br#"{"c":261.74,"h":263.31}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(
+            decode(Some("gzip"), compressed.clone(), None).unwrap(),
+            body
+        );
+        let limit = body.len() as u64;
+        assert_eq!(
+            decode(Some("gzip"), compressed.clone(), Some(limit)).unwrap(),
+            body
+        );
+        assert!(matches!(
+            decode(Some("gzip"), compressed, Some(limit - 1)),
+            Err(crate::Error::ResponseTooLarge { limit: 22 })
+        ));
+        assert!(matches!(
+            decode(Some("gzip"), body.to_vec(), None),
+            Err(crate::Error::Internal(_))
+        ));
+    }
+}
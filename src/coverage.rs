@@ -0,0 +1,729 @@
+//! Machine-readable list of Finnhub API endpoints and their support in this
+//! crate.
+//!
+//! [`coverage`](crate::coverage()) lists every REST endpoint the crate
+//! implements, plus known endpoints it doesn't yet, so tools can render a
+//! capability matrix:
+//!
+//! ```
+//! let endpoints = finnhub::coverage();
+//! let implemented = endpoints.iter().filter(|e| e.implemented).count();
+//! println!("{implemented}/{} endpoints implemented", endpoints.len());
+//!
+//! for endpoint in endpoints.iter().filter(|e| e.available() && !e.premium) {
+//!     println!("{:<10} {:<40} {}", endpoint.category, endpoint.name, endpoint.path);
+//! }
+//! ```
+//!
+//! The `premium` flag follows Finnhub's documentation when the table was last
+//! updated; plans change, so treat it as a hint. Tests check that every path
+//! requested by the endpoint modules is listed as implemented, and that the
+//! endpoint counts in `README.md` and `TEST_COVERAGE.md` match the table.
+//!
+//! Each endpoint also has a [`cost`](EndpointInfo::cost): a rough weight
+//! relative to a quote, for planning batch jobs. Finnhub counts every call
+//! against the same limit, but tick pages, candles over long ranges and
+//! full symbol lists take far longer to serve and download. With
+//! `ClientConfig::weighted_rate_limit` the client spends `cost` rate-limit
+//! tokens per request instead of one:
+//!
+//! ```
+//! use finnhub::coverage::endpoint_info;
+//!
+//! let tick = endpoint_info("/stock/tick?symbol=AAPL&date=2024-03-01").unwrap();
+//! assert!(tick.premium && tick.cost > 1);
+//! ```
+
+use std::fmt;
+
+/// API group an endpoint belongs to, matching the client accessor and cargo
+/// feature that expose it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Category {
+    /// `client.stock()`.
+    Stock,
+    /// `client.forex()`.
+    Forex,
+    /// `client.crypto()`.
+    Crypto,
+    /// `client.etf()`.
+    Etf,
+    /// `client.bond()`.
+    Bond,
+    /// `client.mutual_fund()`.
+    MutualFund,
+    /// `client.economic()`.
+    Economic,
+    /// `client.news()`.
+    News,
+    /// `client.calendar()`.
+    Calendar,
+    /// `client.index()`.
+    Index,
+    /// `client.scanner()`.
+    Scanner,
+    /// `client.misc()`.
+    Misc,
+}
+
+impl Category {
+    /// Cargo feature required for the category, or `None` if it is always
+    /// available with the `client` feature.
+    #[must_use]
+    pub fn feature(&self) -> Option<&'static str> {
+        match self {
+            Category::Stock => Some("stock"),
+            Category::Forex => Some("forex"),
+            Category::Crypto => Some("crypto"),
+            Category::Etf => Some("etf"),
+            Category::Bond => Some("bond"),
+            Category::MutualFund => Some("mutual-fund"),
+            Category::Economic => Some("economic"),
+            Category::News
+            | Category::Calendar
+            | Category::Index
+            | Category::Scanner
+            | Category::Misc => None,
+        }
+    }
+
+    /// Whether the category's endpoints are compiled into this build.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        let feature = match self {
+            Category::Stock => cfg!(feature = "stock"),
+            Category::Forex => cfg!(feature = "forex"),
+            Category::Crypto => cfg!(feature = "crypto"),
+            Category::Etf => cfg!(feature = "etf"),
+            Category::Bond => cfg!(feature = "bond"),
+            Category::MutualFund => cfg!(feature = "mutual-fund"),
+            Category::Economic => cfg!(feature = "economic"),
+            Category::News
+            | Category::Calendar
+            | Category::Index
+            | Category::Scanner
+            | Category::Misc => true,
+        };
+        cfg!(feature = "client") && feature
+    }
+
+    /// Lowercase name, as shown by `Display`, e.g. `"mutual-fund"`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Stock => "stock",
+            Category::Forex => "forex",
+            Category::Crypto => "crypto",
+            Category::Etf => "etf",
+            Category::Bond => "bond",
+            Category::MutualFund => "mutual-fund",
+            Category::Economic => "economic",
+            Category::News => "news",
+            Category::Calendar => "calendar",
+            Category::Index => "index",
+            Category::Scanner => "scanner",
+            Category::Misc => "misc",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One Finnhub REST endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EndpointInfo {
+    /// API group.
+    pub category: Category,
+    /// Name as in Finnhub's documentation.
+    pub name: &'static str,
+    /// Path below the API base URL, e.g. `/stock/profile2`.
+    pub path: &'static str,
+    /// Whether the crate has a method for the endpoint.
+    pub implemented: bool,
+    /// Whether the endpoint needs a paid plan.
+    pub premium: bool,
+    /// Relative weight of a request, 1 for a quote.
+    pub cost: u32,
+}
+
+impl EndpointInfo {
+    /// Whether the endpoint can be called from this build: implemented and
+    /// its category's feature enabled.
+    #[must_use]
+    pub fn available(&self) -> bool {
+        self.implemented && self.category.enabled()
+    }
+
+    const fn with_cost(mut self, cost: u32) -> Self {
+        self.cost = cost;
+        self
+    }
+}
+
+const fn endpoint(
+    category: Category,
+    name: &'static str,
+    path: &'static str,
+    premium: bool,
+) -> EndpointInfo {
+    EndpointInfo {
+        category,
+        name,
+        path,
+        implemented: true,
+        premium,
+        cost: 1,
+    }
+}
+
+const fn planned(
+    category: Category,
+    name: &'static str,
+    path: &'static str,
+    premium: bool,
+) -> EndpointInfo {
+    EndpointInfo {
+        category,
+        name,
+        path,
+        implemented: false,
+        premium,
+        cost: 1,
+    }
+}
+
+const ENDPOINTS: &[EndpointInfo] = &[
+    endpoint(Category::Stock, "Quote", "/quote", false),
+    endpoint(Category::Stock, "Stock candles", "/stock/candle", true).with_cost(2),
+    endpoint(Category::Stock, "Last bid/ask", "/stock/bidask", true),
+    endpoint(Category::Stock, "Tick data", "/stock/tick", true).with_cost(5),
+    endpoint(
+        Category::Stock,
+        "Price metrics",
+        "/stock/price-metric",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Company profile 2",
+        "/stock/profile2",
+        false,
+    ),
+    endpoint(Category::Stock, "Company peers", "/stock/peers", false),
+    endpoint(Category::Stock, "Stock symbols", "/stock/symbol", false).with_cost(2),
+    endpoint(
+        Category::Stock,
+        "Company executives",
+        "/stock/executive",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Congressional trading",
+        "/stock/congressional-trading",
+        true,
+    ),
+    endpoint(Category::Stock, "Senate lobbying", "/stock/lobbying", false),
+    endpoint(
+        Category::Stock,
+        "USA spending",
+        "/stock/usa-spending",
+        false,
+    ),
+    endpoint(Category::Stock, "Company ESG scores", "/stock/esg", true),
+    endpoint(
+        Category::Stock,
+        "Supply chain relationships",
+        "/stock/supply-chain",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "USPTO patents",
+        "/stock/uspto-patent",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "H1-B visa applications",
+        "/stock/visa-application",
+        false,
+    ),
+    endpoint(Category::Stock, "Dividends", "/stock/dividend", true),
+    endpoint(
+        Category::Stock,
+        "Dividends 2 (basic)",
+        "/stock/dividend2",
+        true,
+    ),
+    endpoint(Category::Stock, "Stock splits", "/stock/split", true),
+    endpoint(Category::Stock, "Symbol change", "/ca/symbol-change", true),
+    endpoint(Category::Stock, "ISIN change", "/ca/isin-change", true),
+    endpoint(
+        Category::Stock,
+        "EPS estimates",
+        "/stock/eps-estimate",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Revenue estimates",
+        "/stock/revenue-estimate",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "EBITDA estimates",
+        "/stock/ebitda-estimate",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "EBIT estimates",
+        "/stock/ebit-estimate",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Earnings quality score",
+        "/stock/earnings-quality-score",
+        true,
+    ),
+    endpoint(Category::Stock, "SEC filings", "/stock/filings", false).with_cost(2),
+    endpoint(
+        Category::Stock,
+        "International filings",
+        "/stock/international-filings",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Earnings call transcripts",
+        "/stock/transcripts",
+        true,
+    )
+    .with_cost(2),
+    endpoint(
+        Category::Stock,
+        "Earnings call transcripts list",
+        "/stock/transcripts/list",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Earnings call live",
+        "/stock/earnings-call-live",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Stock presentations",
+        "/stock/presentation",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Similarity index",
+        "/stock/similarity-index",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Financial statements",
+        "/stock/financials",
+        true,
+    ),
+    endpoint(Category::Stock, "Basic financials", "/stock/metric", false),
+    endpoint(
+        Category::Stock,
+        "Earnings surprises",
+        "/stock/earnings",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Financials as reported",
+        "/stock/financials-reported",
+        false,
+    )
+    .with_cost(2),
+    endpoint(
+        Category::Stock,
+        "Historical market cap",
+        "/stock/historical-market-cap",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Historical employee count",
+        "/stock/historical-employee-count",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Historical ESG scores",
+        "/stock/historical-esg",
+        true,
+    ),
+    endpoint(Category::Stock, "Historical NBBO", "/stock/bbo", true).with_cost(5),
+    endpoint(
+        Category::Stock,
+        "Insider transactions",
+        "/stock/insider-transactions",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Insider sentiment",
+        "/stock/insider-sentiment",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Market status",
+        "/stock/market-status",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Market holidays",
+        "/stock/market-holiday",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Investment themes",
+        "/stock/investment-theme",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Institutional ownership",
+        "/stock/ownership",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Fund ownership",
+        "/stock/fund-ownership",
+        true,
+    ),
+    endpoint(Category::Stock, "Price target", "/stock/price-target", true),
+    endpoint(
+        Category::Stock,
+        "Recommendation trends",
+        "/stock/recommendation",
+        false,
+    ),
+    endpoint(
+        Category::Stock,
+        "Revenue breakdown",
+        "/stock/revenue-breakdown",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Upgrades and downgrades",
+        "/stock/upgrade-downgrade",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "Social sentiment",
+        "/stock/social-sentiment",
+        true,
+    ),
+    endpoint(
+        Category::Stock,
+        "SEC filing sentiment",
+        "/stock/filings-sentiment",
+        true,
+    ),
+    planned(Category::Stock, "Company profile", "/stock/profile", true),
+    planned(
+        Category::Stock,
+        "Institutional profile",
+        "/institutional/profile",
+        true,
+    ),
+    planned(
+        Category::Stock,
+        "Institutional portfolio",
+        "/institutional/portfolio",
+        true,
+    ),
+    planned(
+        Category::Stock,
+        "Institutional ownership changes",
+        "/institutional/ownership",
+        true,
+    ),
+    endpoint(Category::Forex, "Forex exchanges", "/forex/exchange", false),
+    endpoint(Category::Forex, "Forex symbols", "/forex/symbol", false).with_cost(2),
+    endpoint(Category::Forex, "Forex candles", "/forex/candle", true).with_cost(2),
+    endpoint(Category::Forex, "Forex rates", "/forex/rates", true),
+    endpoint(
+        Category::Crypto,
+        "Crypto exchanges",
+        "/crypto/exchange",
+        false,
+    ),
+    endpoint(Category::Crypto, "Crypto symbols", "/crypto/symbol", false).with_cost(2),
+    endpoint(Category::Crypto, "Crypto candles", "/crypto/candle", true).with_cost(2),
+    endpoint(Category::Crypto, "Crypto profile", "/crypto/profile", true),
+    endpoint(Category::Etf, "ETF profile", "/etf/profile", true),
+    endpoint(Category::Etf, "ETF holdings", "/etf/holdings", true).with_cost(2),
+    endpoint(Category::Etf, "ETF country exposure", "/etf/country", true),
+    endpoint(Category::Etf, "ETF sector exposure", "/etf/sector", true),
+    endpoint(Category::Bond, "Bond profile", "/bond/profile", true),
+    endpoint(Category::Bond, "Bond price", "/bond/price", true),
+    endpoint(Category::Bond, "Bond tick data", "/bond/tick", true).with_cost(5),
+    endpoint(
+        Category::Bond,
+        "Bond yield curve",
+        "/bond/yield-curve",
+        true,
+    ),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund profile",
+        "/mutual-fund/profile",
+        true,
+    ),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund holdings",
+        "/mutual-fund/holdings",
+        true,
+    )
+    .with_cost(2),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund country exposure",
+        "/mutual-fund/country",
+        true,
+    ),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund sector exposure",
+        "/mutual-fund/sector",
+        true,
+    ),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund EET",
+        "/mutual-fund/eet",
+        true,
+    ),
+    endpoint(
+        Category::MutualFund,
+        "Mutual fund EET PAI",
+        "/mutual-fund/eet-pai",
+        true,
+    ),
+    endpoint(Category::Economic, "Economic data", "/economic", true),
+    endpoint(Category::Economic, "Economic codes", "/economic/code", true),
+    endpoint(Category::News, "Market news", "/news", false),
+    endpoint(Category::News, "Company news", "/company-news", false),
+    endpoint(Category::News, "News sentiment", "/news-sentiment", true),
+    endpoint(
+        Category::Calendar,
+        "Earnings calendar",
+        "/calendar/earnings",
+        false,
+    ),
+    endpoint(
+        Category::Calendar,
+        "Economic calendar",
+        "/calendar/economic",
+        true,
+    ),
+    endpoint(Category::Calendar, "IPO calendar", "/calendar/ipo", false),
+    endpoint(
+        Category::Index,
+        "Index constituents",
+        "/index/constituents",
+        true,
+    ),
+    endpoint(
+        Category::Index,
+        "Historical index constituents",
+        "/index/historical-constituents",
+        true,
+    )
+    .with_cost(2),
+    endpoint(
+        Category::Scanner,
+        "Pattern recognition",
+        "/scan/pattern",
+        true,
+    ),
+    endpoint(
+        Category::Scanner,
+        "Support/resistance",
+        "/scan/support-resistance",
+        true,
+    ),
+    endpoint(
+        Category::Scanner,
+        "Aggregate indicators",
+        "/scan/technical-indicator",
+        true,
+    ),
+    endpoint(Category::Misc, "Symbol lookup", "/search", false),
+    endpoint(Category::Misc, "Country metadata", "/country", false),
+    endpoint(Category::Misc, "COVID-19", "/covid19/us", false),
+    endpoint(
+        Category::Misc,
+        "FDA committee meeting calendar",
+        "/fda-advisory-committee-calendar",
+        false,
+    ),
+    endpoint(
+        Category::Misc,
+        "Airline price index",
+        "/airline/price-index",
+        true,
+    ),
+    endpoint(Category::Misc, "Technical indicators", "/indicator", true),
+    endpoint(Category::Misc, "Press releases", "/press-releases", true),
+    endpoint(Category::Misc, "Sector metrics", "/sector/metrics", true),
+    endpoint(Category::Misc, "AI chat", "/ai-chat", true).with_cost(5),
+];
+
+/// Every known Finnhub REST endpoint, grouped by category. Endpoints of
+/// categories whose feature is disabled are listed too; see
+/// [`EndpointInfo::available`].
+#[must_use]
+pub fn coverage() -> &'static [EndpointInfo] {
+    ENDPOINTS
+}
+
+/// Look up an endpoint by path. A query string is ignored, so the paths and
+/// queries the endpoint methods build can be passed as they are.
+#[must_use]
+pub fn endpoint_info(endpoint: &str) -> Option<&'static EndpointInfo> {
+    let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+    ENDPOINTS.iter().find(|e| e.path == path)
+}
+
+/// Rate-limit tokens a request to `endpoint` costs; 1 for unknown endpoints.
+#[cfg(feature = "client")]
+pub(crate) fn cost(endpoint: &str) -> u32 {
+    endpoint_info(endpoint).map_or(1, |e| e.cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    /// Path literals in the endpoint modules, outside their tests.
+    fn requested_paths(dir: &Path, paths: &mut HashSet<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                requested_paths(&path, paths);
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            let source = source.split("#[cfg(test)]").next().unwrap();
+            for literal in source.split('"').skip(1).step_by(2) {
+                if literal.starts_with('/') {
+                    let end = literal.find(['?', '{']).unwrap_or(literal.len());
+                    paths.insert(literal[..end].to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_coverage_matches_endpoint_modules() {
+        let mut requested = HashSet::new();
+        requested_paths(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/endpoints"),
+            &mut requested,
+        );
+        let implemented: HashSet<String> = coverage()
+            .iter()
+            .filter(|e| e.implemented)
+            .map(|e| e.path.to_string())
+            .collect();
+        assert_eq!(requested, implemented);
+
+        let unique: HashSet<_> = coverage().iter().map(|e| e.path).collect();
+        assert_eq!(unique.len(), coverage().len(), "duplicate paths");
+        assert_eq!(implemented.len(), 98);
+    }
+
+    /// `implemented/total` endpoints of `category`, or of every category.
+    fn counts(category: Option<Category>) -> String {
+        let endpoints: Vec<_> = coverage()
+            .iter()
+            .filter(|e| category.is_none_or(|c| e.category == c))
+            .collect();
+        let implemented = endpoints.iter().filter(|e| e.implemented).count();
+        format!("{implemented}/{}", endpoints.len())
+    }
+
+    /// The first `N/M` after `label` in `doc`.
+    fn documented<'a>(doc: &'a str, label: &str) -> &'a str {
+        let at = doc
+            .find(label)
+            .unwrap_or_else(|| panic!("{label:?} not found"));
+        let rest = &doc[at + label.len()..];
+        let rest = &rest[rest.find(|c: char| c.is_ascii_digit()).unwrap()..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '/'))
+            .unwrap_or(rest.len());
+        &rest[..end]
+    }
+
+    #[test]
+    fn test_docs_match_coverage() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let readme = std::fs::read_to_string(root.join("README.md")).unwrap();
+        let mut checks = vec![
+            (&readme, "Extensive API coverage", None),
+            (&readme, "### Stock Market Data", Some(Category::Stock)),
+            (&readme, "**ETFs**", Some(Category::Etf)),
+            (&readme, "**Forex**", Some(Category::Forex)),
+            (&readme, "**Crypto**", Some(Category::Crypto)),
+            (&readme, "**Bonds**", Some(Category::Bond)),
+            (&readme, "**Mutual Funds**", Some(Category::MutualFund)),
+            (&readme, "**Indices**", Some(Category::Index)),
+            (&readme, "**Economic Data**", Some(Category::Economic)),
+            (&readme, "**News**", Some(Category::News)),
+            (&readme, "**Calendar**", Some(Category::Calendar)),
+            (&readme, "**Technical Analysis**", Some(Category::Scanner)),
+        ];
+        // Not part of the published package.
+        let test_coverage = std::fs::read_to_string(root.join("TEST_COVERAGE.md")).ok();
+        if let Some(doc) = &test_coverage {
+            checks.extend([
+                (doc, "**Total Endpoints**", None),
+                (doc, "**Stock Endpoints**", Some(Category::Stock)),
+                (doc, "### Stock Endpoints", Some(Category::Stock)),
+                (doc, "### Forex Endpoints", Some(Category::Forex)),
+                (doc, "### Crypto Endpoints", Some(Category::Crypto)),
+                (doc, "### ETF Endpoints", Some(Category::Etf)),
+                (doc, "### Bond Endpoints", Some(Category::Bond)),
+                (doc, "### Mutual Fund Endpoints", Some(Category::MutualFund)),
+                (doc, "### News Endpoints", Some(Category::News)),
+                (doc, "### Calendar Endpoints", Some(Category::Calendar)),
+                (doc, "### Economic Data Endpoints", Some(Category::Economic)),
+                (doc, "### Index Endpoints", Some(Category::Index)),
+                (doc, "### Scanner Endpoints", Some(Category::Scanner)),
+                (doc, "### Miscellaneous Endpoints", Some(Category::Misc)),
+            ]);
+        }
+        for (doc, label, category) in checks {
+            assert_eq!(documented(doc, label), counts(category), "{label}");
+        }
+    }
+}
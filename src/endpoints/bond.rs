@@ -3,6 +3,7 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
+    limits::check_tick_limit,
     models::bond::{BondPrice, BondProfile, BondTickData, BondYieldCurve},
 };
 
@@ -70,7 +71,7 @@ impl<'a> BondEndpoints<'a> {
     /// # Arguments
     /// * `isin` - ISIN identifier
     /// * `date` - Date in YYYY-MM-DD format
-    /// * `limit` - Limit number of ticks returned (max 25000)
+    /// * `limit` - Limit number of ticks returned (max [`TICK_MAX_LIMIT`](crate::limits::TICK_MAX_LIMIT))
     /// * `skip` - Number of ticks to skip
     /// * `exchange` - Exchange (e.g., "trace")
     pub async fn tick(
@@ -81,6 +82,7 @@ impl<'a> BondEndpoints<'a> {
         skip: i64,
         exchange: &str,
     ) -> Result<BondTickData> {
+        check_tick_limit(limit)?;
         self.client
             .get(&format!(
                 "/bond/tick?isin={}&date={}&limit={}&skip={}&exchange={}",
@@ -94,8 +96,10 @@ impl<'a> BondEndpoints<'a> {
     /// Returns yield curve data for Treasury bonds.
     ///
     /// # Arguments
-    /// * `code` - Bond's code (e.g., "10y" for 10-year Treasury)
-    pub async fn yield_curve(&self, code: &str) -> Result<BondYieldCurve> {
+    /// * `code` - Bond's code (e.g., "10y", or
+    ///   [`TreasuryTenor::TenYears`](crate::models::bond::TreasuryTenor::TenYears),
+    ///   for 10-year Treasury)
+    pub async fn yield_curve(&self, code: impl std::fmt::Display) -> Result<BondYieldCurve> {
         self.client
             .get(&format!("/bond/yield-curve?code={}", code))
             .await
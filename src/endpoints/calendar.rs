@@ -3,7 +3,7 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::calendar::{EarningsCalendar, EconomicCalendar, IPOCalendar},
+    models::calendar::{EarningsCalendar, EarningsFilter, EconomicCalendar, IPOCalendar},
 };
 
 /// Calendar-related API endpoints.
@@ -31,17 +31,32 @@ impl<'a> CalendarEndpoints<'a> {
         to: Option<&str>,
         symbol: Option<&str>,
     ) -> Result<EarningsCalendar> {
+        let filter = EarningsFilter {
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+            symbol: symbol.map(str::to_string),
+            international: false,
+        };
+        self.earnings_filtered(&filter).await
+    }
+
+    /// Get earnings calendar matching `filter`, optionally including
+    /// international companies.
+    pub async fn earnings_filtered(&self, filter: &EarningsFilter) -> Result<EarningsCalendar> {
         let mut params = vec![];
 
-        if let Some(f) = from {
+        if let Some(f) = &filter.from {
             params.push(format!("from={}", f));
         }
-        if let Some(t) = to {
+        if let Some(t) = &filter.to {
             params.push(format!("to={}", t));
         }
-        if let Some(s) = symbol {
+        if let Some(s) = &filter.symbol {
             params.push(format!("symbol={}", s));
         }
+        if filter.international {
+            params.push("international=true".to_string());
+        }
 
         let query = if params.is_empty() {
             String::from("/calendar/earnings")
@@ -31,7 +31,8 @@ impl<'a> CryptoEndpoints<'a> {
 
     /// Get crypto candlestick data.
     ///
-    /// Get OHLCV data for crypto symbols.
+    /// Get OHLCV data for crypto symbols. Symbols without a `VENUE:` prefix get
+    /// the one configured in `ClientConfig::venues`, if any.
     pub async fn candles(
         &self,
         symbol: &str,
@@ -39,12 +40,44 @@ impl<'a> CryptoEndpoints<'a> {
         from: i64,
         to: i64,
     ) -> Result<CryptoCandles> {
-        self.client
-            .get(&format!(
-                "/crypto/candle?symbol={}&resolution={}&from={}&to={}",
-                symbol, resolution, from, to
-            ))
-            .await
+        let (_, endpoint) = self.candle_request(symbol, resolution, from, to);
+        let candles = self.client.get(&endpoint).await?;
+        self.client.validate(candles)
+    }
+
+    /// Get crypto candlestick data aligned to UTC bar boundaries.
+    ///
+    /// Like [`candles`](Self::candles), but the response is passed through
+    /// [`CryptoCandles::normalized`] with the venue's [`CandleQuirks`] before
+    /// validation, so bars from different venues share timestamps and
+    /// duplicate bars are resolved.
+    pub async fn candles_normalized(
+        &self,
+        symbol: &str,
+        resolution: CandleResolution,
+        from: i64,
+        to: i64,
+    ) -> Result<CryptoCandles> {
+        let (symbol, endpoint) = self.candle_request(symbol, resolution, from, to);
+        let candles: CryptoCandles = self.client.get(&endpoint).await?;
+        let quirks = CandleQuirks::for_symbol(&symbol);
+        self.client.validate(candles.normalized(resolution, quirks))
+    }
+
+    /// The venue-qualified symbol and the endpoint of a candle request.
+    fn candle_request(
+        &self,
+        symbol: &str,
+        resolution: CandleResolution,
+        from: i64,
+        to: i64,
+    ) -> (String, String) {
+        let symbol = self.client.config().venues.crypto_symbol(symbol);
+        let endpoint = format!(
+            "/crypto/candle?symbol={}&resolution={}&from={}&to={}",
+            symbol, resolution, from, to
+        );
+        (symbol.into_owned(), endpoint)
     }
 
     /// Get crypto profile data.
@@ -3,7 +3,7 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::economic::{EconomicCode, EconomicData},
+    models::economic::{EconomicCode, EconomicCodes, EconomicData},
 };
 
 /// Economic data API endpoints.
@@ -33,6 +33,17 @@ impl<'a> EconomicEndpoints<'a> {
     pub async fn codes(&self) -> Result<Vec<EconomicCode>> {
         self.client.get("/economic/code").await
     }
+
+    /// Get the indicator code list as a registry, for looking up codes of
+    /// common indicators with [`EconomicCodes::find`] and searching by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fetching the code list fails, as for
+    /// [`codes`](Self::codes).
+    pub async fn code_registry(&self) -> Result<EconomicCodes> {
+        self.codes().await.map(EconomicCodes::new)
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +74,14 @@ mod tests {
         assert!(!codes.is_empty());
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_code_registry() {
+        let client = test_client().await;
+        let registry = client.economic().code_registry().await.unwrap();
+        assert!(!registry.search("unemployment").is_empty());
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_data() {
@@ -4,6 +4,7 @@ use crate::{
     client::FinnhubClient,
     error::Result,
     models::etf::{ETFCountryExposure, ETFHoldings, ETFProfile, ETFSectorExposure},
+    partial::{self, PartialResult},
 };
 
 /// ETF-related API endpoints.
@@ -44,6 +45,31 @@ impl<'a> ETFEndpoints<'a> {
         self.client.get(&query).await
     }
 
+    /// Fetch the profiles of several ETFs for comparison.
+    ///
+    /// Profiles are fetched concurrently and keyed by symbol; a failed symbol
+    /// doesn't stop the others. Build the table with
+    /// [`ETFComparison::from_profiles`](crate::models::etf::ETFComparison::from_profiles):
+    ///
+    /// ```rust,no_run
+    /// # use finnhub::{models::etf::ETFComparison, FinnhubClient};
+    /// # async fn run(client: FinnhubClient) {
+    /// let profiles = client.etf().compare(&["SPY", "VOO", "IVV"]).await;
+    /// print!("{}", ETFComparison::from_profiles(profiles.ok_values().cloned()));
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    /// * `symbols` - ETF symbols
+    pub async fn compare(&self, symbols: &[&str]) -> PartialResult<ETFProfile> {
+        partial::fetch_all(
+            symbols.iter().map(|symbol| (*symbol).to_string()),
+            partial::DEFAULT_CONCURRENCY,
+            |symbol| async move { self.profile(Some(&symbol), None).await },
+        )
+        .await
+    }
+
     /// Get ETF holdings/constituents.
     ///
     /// Returns full ETF holdings data.
@@ -150,7 +176,7 @@ impl<'a> ETFEndpoints<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use crate::{models::etf::ETFComparison, ClientConfig, FinnhubClient, RateLimitStrategy};
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -176,6 +202,17 @@ mod tests {
         assert!(profile.profile.name.is_some());
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_compare() {
+        let client = test_client().await;
+        let result = client.etf().compare(&["SPY", "VOO", "IVV"]).await;
+        assert!(result.is_complete(), "Failed to compare ETFs: {:?}", result.errors);
+
+        let comparison = ETFComparison::from_profiles(result.values.into_values());
+        assert_eq!(comparison.rows.len(), 3);
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_holdings() {
@@ -26,7 +26,8 @@ impl<'a> ForexEndpoints<'a> {
 
     /// Get forex candlestick data.
     ///
-    /// Get OHLCV data for forex symbols.
+    /// Get OHLCV data for forex symbols. Symbols without a `VENUE:` prefix get
+    /// the one configured in `ClientConfig::venues`, if any.
     pub async fn candles(
         &self,
         symbol: &str,
@@ -34,12 +35,15 @@ impl<'a> ForexEndpoints<'a> {
         from: i64,
         to: i64,
     ) -> Result<ForexCandles> {
-        self.client
+        let symbol = self.client.config().venues.forex_symbol(symbol);
+        let candles = self
+            .client
             .get(&format!(
                 "/forex/candle?symbol={}&resolution={}&from={}&to={}",
                 symbol, resolution, from, to
             ))
-            .await
+            .await?;
+        self.client.validate(candles)
     }
 
     /// Get forex exchange rates.
@@ -1,14 +1,21 @@
 //! Miscellaneous API endpoints.
 
+use futures::{stream, Stream, StreamExt};
+use reqwest::header::CONTENT_TYPE;
+use std::collections::VecDeque;
+
 use crate::{
-    client::FinnhubClient,
-    error::Result,
+    client::{body_stream, redact_http_error, BodyStream, FinnhubClient},
+    error::{Error, Result},
     models::misc::{
-        AIChatRequest, AIChatResponse, AirlinePriceIndexData, CountryMetadata, CovidInfo,
-        FDACommitteeMeeting, PressRelease, SectorMetric, SymbolLookup, TechnicalIndicator,
+        AIChatChunk, AIChatRequest, AIChatResponse, AIChatStreamDecoder, AirlinePriceIndexData,
+        CountryMetadata, CovidInfo, FDACommitteeMeeting, PressRelease, SectorMetric, SymbolLookup,
+        SymbolLookupInfo, TechnicalIndicator,
     },
 };
 
+const AI_CHAT: &str = "/ai-chat";
+
 /// Miscellaneous API endpoints.
 pub struct MiscEndpoints<'a> {
     client: &'a FinnhubClient,
@@ -21,10 +28,67 @@ impl<'a> MiscEndpoints<'a> {
     }
 
     /// Chat with AI copilot powered by Neyman AI.
-    pub async fn ai_chat(&self, _request: &AIChatRequest) -> Result<AIChatResponse> {
-        // Note: This is a POST endpoint, which would require implementing POST support in the client
-        // For now, this is a placeholder
-        unimplemented!("POST endpoints not yet implemented")
+    ///
+    /// Waits for the whole reply; `request.stream` is ignored. Continue the
+    /// conversation with [`AIChatRequest::push_reply`].
+    pub async fn ai_chat(&self, request: &AIChatRequest) -> Result<AIChatResponse> {
+        let request = AIChatRequest {
+            stream: None,
+            ..request.clone()
+        };
+        self.client.post(AI_CHAT, &request).await
+    }
+
+    /// Chat with AI copilot, receiving the reply as it is generated.
+    ///
+    /// Each item is the next piece of the reply's text. If the API answers
+    /// with a complete JSON reply instead of a stream, it is yielded as a
+    /// single chunk. The stream ends after the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request error if the reply can't be started; errors while
+    /// reading it are yielded by the stream.
+    ///
+    /// ```rust,no_run
+    /// use finnhub::models::misc::AIChatRequest;
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run(client: finnhub::FinnhubClient) -> finnhub::Result<()> {
+    /// let request = AIChatRequest::new().user("What moved NVDA today?");
+    /// let mut reply = Box::pin(client.misc().ai_chat_stream(&request).await?);
+    /// while let Some(chunk) = reply.next().await {
+    ///     print!("{}", chunk?.content);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ai_chat_stream(
+        &self,
+        request: &AIChatRequest,
+    ) -> Result<impl Stream<Item = Result<AIChatChunk>>> {
+        let request = request.clone().streaming();
+        let response = self.client.post_streaming(AI_CHAT, &request).await?;
+        let whole_reply = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.trim_start().starts_with("application/json"));
+
+        let state = ChatStream {
+            body: body_stream(response),
+            decoder: AIChatStreamDecoder::new(),
+            pending: VecDeque::new(),
+            whole_reply,
+        };
+        Ok(stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.next().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(state))),
+                Ok(None) => None,
+                Err(err) => Some((Err(err.at_endpoint(AI_CHAT)), None)),
+            }
+        }))
     }
 
     /// Get airline ticket price index.
@@ -115,6 +179,25 @@ impl<'a> MiscEndpoints<'a> {
         self.client.get(&url).await
     }
 
+    /// Search for symbols, ranked client-side: exact ticker matches first,
+    /// then ticker prefixes, then description matches.
+    pub async fn symbol_search_ranked(
+        &self,
+        query: &str,
+        exchange: Option<&str>,
+    ) -> Result<SymbolLookup> {
+        let mut lookup = self.symbol_search(query, exchange).await?;
+        lookup.rank(query);
+        Ok(lookup)
+    }
+
+    /// Resolve a ticker to the search result whose symbol matches it exactly,
+    /// if there is one.
+    pub async fn search_exact(&self, ticker: &str) -> Result<Option<SymbolLookupInfo>> {
+        let lookup = self.symbol_search(ticker, None).await?;
+        Ok(lookup.into_exact(ticker))
+    }
+
     /// Get ratios for different sectors and regions/indices.
     pub async fn sector_metrics(&self, region: &str) -> Result<SectorMetric> {
         self.client
@@ -123,9 +206,61 @@ impl<'a> MiscEndpoints<'a> {
     }
 }
 
+/// Reads chunks from a streamed AI chat response.
+struct ChatStream {
+    body: BodyStream,
+    decoder: AIChatStreamDecoder,
+    pending: VecDeque<AIChatChunk>,
+    whole_reply: bool,
+}
+
+impl ChatStream {
+    async fn next(&mut self) -> Result<Option<AIChatChunk>> {
+        if self.whole_reply {
+            self.whole_reply = false;
+            let mut body = Vec::new();
+            while let Some(bytes) = self
+                .body
+                .next()
+                .await
+                .transpose()
+                .map_err(redact_http_error)?
+            {
+                body.extend_from_slice(&bytes);
+            }
+            let reply: AIChatResponse = serde_json::from_slice(&body)?;
+            return Ok(Some(AIChatChunk {
+                chat_id: Some(reply.chat_id),
+                content: reply.content,
+            }));
+        }
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Ok(Some(chunk));
+            }
+            let Some(bytes) = self
+                .body
+                .next()
+                .await
+                .transpose()
+                .map_err(redact_http_error)?
+            else {
+                let decoder = std::mem::take(&mut self.decoder);
+                return decoder.finish().map_err(Error::from);
+            };
+            self.pending.extend(self.decoder.push(&bytes)?);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::client::mock_client;
+    use crate::models::misc::AIChatRequest;
     use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use futures::StreamExt;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -248,6 +383,22 @@ mod tests {
         assert!(!results.result.is_empty());
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_search_exact() {
+        let client = test_client().await;
+        let result = client.misc().search_exact("AAPL").await;
+        assert!(
+            result.is_ok(),
+            "Failed to resolve ticker: {:?}",
+            result.err()
+        );
+        assert_eq!(
+            result.unwrap().map(|info| info.symbol).as_deref(),
+            Some("AAPL")
+        );
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_sector_metrics() {
@@ -259,4 +410,88 @@ mod tests {
             result.err()
         );
     }
+
+    #[tokio::test]
+    async fn test_ai_chat_posts_conversation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/ai-chat"))
+            .and(header("X-Finnhub-Token", "test-api-key"))
+            .and(body_json(serde_json::json!({
+                "messages": [
+                    {"role": "system", "content": "Be concise."},
+                    {"role": "user", "content": "NVDA price?"},
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "chatId": "c1",
+                "content": "$124.92",
+                "querySummary": "NVDA Stock Price",
+                "relatedQueries": [],
+                "tickers": [],
+                "sources": [],
+                "widgets": [],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let mut request = AIChatRequest::new()
+            .system("Be concise.")
+            .user("NVDA price?")
+            .streaming();
+        let response = client.misc().ai_chat(&request).await.unwrap();
+        assert_eq!(response.content, "$124.92");
+
+        request.push_reply(&response);
+        assert_eq!(request.messages.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ai_chat_stream_yields_chunks() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/ai-chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "data: {\"chatId\":\"c1\",\"content\":\"NVDA is \"}\n\n\
+                 data: {\"content\":\"up 4%.\"}\n\n\
+                 data: [DONE]\n\n",
+                "text/event-stream",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = AIChatRequest::new().user("What moved NVDA?");
+        let chunks: Vec<_> = client
+            .misc()
+            .ai_chat_stream(&request)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        let text: String = chunks.into_iter().map(|c| c.unwrap().content).collect();
+        assert_eq!(text, "NVDA is up 4%.");
+    }
+
+    #[tokio::test]
+    async fn test_ai_chat_error_names_endpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("premium required"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let request = AIChatRequest::new().user("Hi");
+        let Err(err) = client.misc().ai_chat_stream(&request).await else {
+            panic!("expected an error");
+        };
+        assert_eq!(err.endpoint(), Some("/ai-chat"));
+        assert!(matches!(
+            err.root(),
+            crate::Error::ApiError { status: 403, .. }
+        ));
+    }
 }
@@ -1,27 +1,44 @@
 //! API endpoint implementations.
+//!
+//! Asset-class endpoint groups are gated behind the cargo feature of the same
+//! name (`stock`, `forex`, `crypto`, `etf`, `bond`, `mutual-fund`, `economic`).
 
+#[cfg(feature = "bond")]
 pub mod bond;
 pub mod calendar;
+#[cfg(feature = "crypto")]
 pub mod crypto;
+#[cfg(feature = "economic")]
 pub mod economic;
+#[cfg(feature = "etf")]
 pub mod etf;
+#[cfg(feature = "forex")]
 pub mod forex;
 pub mod index;
 pub mod misc;
+#[cfg(feature = "mutual-fund")]
 pub mod mutual_fund;
 pub mod news;
 pub mod scanner;
+#[cfg(feature = "stock")]
 pub mod stock;
 
+#[cfg(feature = "bond")]
 pub use bond::BondEndpoints;
 pub use calendar::CalendarEndpoints;
+#[cfg(feature = "crypto")]
 pub use crypto::CryptoEndpoints;
+#[cfg(feature = "economic")]
 pub use economic::EconomicEndpoints;
+#[cfg(feature = "etf")]
 pub use etf::ETFEndpoints;
+#[cfg(feature = "forex")]
 pub use forex::ForexEndpoints;
 pub use index::IndexEndpoints;
 pub use misc::MiscEndpoints;
+#[cfg(feature = "mutual-fund")]
 pub use mutual_fund::MutualFundEndpoints;
 pub use news::NewsEndpoints;
 pub use scanner::ScannerEndpoints;
+#[cfg(feature = "stock")]
 pub use stock::StockEndpoints;
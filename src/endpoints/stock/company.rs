@@ -1,11 +1,72 @@
 //! Company information endpoints.
 
+use futures::stream::{self, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::stock::{CompanyProfile, Symbol},
+    lenient::Lenient,
+    models::stock::{CompanyProfile, Symbol, SymbolFilter},
+    partial::PartialResult,
+    persist::{self, StateStore},
 };
 
+/// Options for [`CompanyEndpoints::profiles`].
+#[derive(Clone)]
+pub struct ProfilesOptions {
+    /// Maximum number of profile requests in flight. Requests still go
+    /// through the rate limiter.
+    pub concurrency: usize,
+    /// Store caching profiles under `profiles/<SYMBOL>`, e.g. a
+    /// [`FileStore`](crate::persist::FileStore) to keep them on disk between
+    /// runs.
+    pub cache: Option<Arc<dyn StateStore>>,
+    /// How long a cached profile is used before it is fetched again.
+    pub max_age: Duration,
+}
+
+impl Default for ProfilesOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            cache: None,
+            max_age: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl fmt::Debug for ProfilesOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProfilesOptions")
+            .field("concurrency", &self.concurrency)
+            .field("cache", &self.cache.is_some())
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
+
+/// Outcome of [`CompanyEndpoints::profiles`].
+#[derive(Debug, Default)]
+pub struct ProfileBatch {
+    /// Resolved profiles keyed by symbol, and the symbols whose request or
+    /// cache access failed.
+    pub profiles: PartialResult<CompanyProfile>,
+    /// Symbols served from the cache.
+    pub cached: Vec<String>,
+}
+
+/// Cached profile with the time it was fetched.
+#[derive(Serialize, Deserialize)]
+struct CachedProfile {
+    /// UNIX seconds.
+    fetched_at: i64,
+    profile: CompanyProfile,
+}
+
 /// Company information endpoints.
 pub struct CompanyEndpoints<'a> {
     client: &'a FinnhubClient,
@@ -24,6 +85,65 @@ impl<'a> CompanyEndpoints<'a> {
             .await
     }
 
+    /// Resolve profiles for many symbols at once.
+    ///
+    /// Requests run concurrently up to `options.concurrency`. With a cache
+    /// store, profiles fetched less than `options.max_age` ago are read from
+    /// it and new ones are written back, so a cold start only pays for the
+    /// symbols it hasn't seen recently. Failures are reported per symbol and
+    /// don't stop the batch. Duplicate symbols are resolved once.
+    pub async fn profiles(&self, symbols: &[&str], options: &ProfilesOptions) -> ProfileBatch {
+        let mut unique: Vec<&str> = symbols.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+
+        let results: Vec<_> = stream::iter(unique)
+            .map(|symbol| async move { (symbol, self.resolve_profile(symbol, options).await) })
+            .buffer_unordered(options.concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut batch = ProfileBatch::default();
+        for (symbol, result) in results {
+            let profile = result.map(|(profile, cached)| {
+                if cached {
+                    batch.cached.push(symbol.to_string());
+                }
+                profile
+            });
+            batch.profiles.push(symbol.to_string(), profile);
+        }
+        batch
+    }
+
+    /// Profile for `symbol` and whether it came from the cache.
+    async fn resolve_profile(
+        &self,
+        symbol: &str,
+        options: &ProfilesOptions,
+    ) -> Result<(CompanyProfile, bool)> {
+        let Some(store) = options.cache.as_deref() else {
+            return Ok((self.profile(symbol).await?, false));
+        };
+
+        let key = format!("profiles/{symbol}");
+        let now = chrono::Utc::now().timestamp();
+        let max_age = i64::try_from(options.max_age.as_secs()).unwrap_or(i64::MAX);
+        if let Some(cached) = persist::load::<CachedProfile>(store, &key).await? {
+            if now.saturating_sub(cached.fetched_at) < max_age {
+                return Ok((cached.profile, true));
+            }
+        }
+
+        let profile = self.profile(symbol).await?;
+        let entry = CachedProfile {
+            fetched_at: now,
+            profile,
+        };
+        persist::save(store, &key, &entry).await?;
+        Ok((entry.profile, false))
+    }
+
     /// Get company peers.
     ///
     /// Returns a list of peers operating in the same country and sector/industry.
@@ -40,15 +160,69 @@ impl<'a> CompanyEndpoints<'a> {
     ///
     /// List all supported stocks for a given exchange.
     pub async fn symbols(&self, exchange: &str) -> Result<Vec<Symbol>> {
-        self.client
-            .get(&format!("/stock/symbol?exchange={}", exchange))
+        self.symbols_filtered(exchange, &SymbolFilter::default())
             .await
     }
+
+    /// Get supported stocks matching `filter`.
+    ///
+    /// Filtering happens server-side, so only matching listings are downloaded.
+    ///
+    /// # Arguments
+    /// * `exchange` - Exchange code, e.g. `US`
+    /// * `filter` - Optional MIC, security type and currency filters
+    pub async fn symbols_filtered(
+        &self,
+        exchange: &str,
+        filter: &SymbolFilter,
+    ) -> Result<Vec<Symbol>> {
+        self.symbols_as(exchange, filter).await
+    }
+
+    /// Get supported stocks matching `filter`, skipping entries that fail to
+    /// deserialize.
+    ///
+    /// Exchange listings run to tens of thousands of entries; this keeps one
+    /// malformed entry from failing the whole download. Skipped entries are
+    /// reported in [`Lenient::warnings`].
+    pub async fn symbols_lenient(
+        &self,
+        exchange: &str,
+        filter: &SymbolFilter,
+    ) -> Result<Lenient<Symbol>> {
+        self.symbols_as(exchange, filter).await
+    }
+
+    async fn symbols_as<T: DeserializeOwned>(
+        &self,
+        exchange: &str,
+        filter: &SymbolFilter,
+    ) -> Result<T> {
+        let mut params = vec![format!("exchange={}", exchange)];
+
+        if let Some(mic) = &filter.mic {
+            params.push(format!("mic={}", mic));
+        }
+        if let Some(security_type) = &filter.security_type {
+            params.push(format!("securityType={}", security_type));
+        }
+        if let Some(currency) = &filter.currency {
+            params.push(format!("currency={}", currency));
+        }
+
+        let query = format!("/stock/symbol?{}", params.join("&"));
+        self.client.get(&query).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use super::*;
+    use crate::{
+        models::stock::SecurityType, persist::MemoryStore, ClientConfig, RateLimitStrategy,
+    };
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -59,6 +233,53 @@ mod tests {
         FinnhubClient::with_config(api_key, config)
     }
 
+    #[tokio::test]
+    async fn test_profiles_uses_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .and(query_param("symbol", "AAPL"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"name": "Apple Inc"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .and(query_param("symbol", "BAD"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test-api-key", config);
+        let options = ProfilesOptions {
+            cache: Some(Arc::new(MemoryStore::new())),
+            ..ProfilesOptions::default()
+        };
+
+        let company = CompanyEndpoints::new(&client);
+        let first = company.profiles(&["AAPL", "BAD", "AAPL"], &options).await;
+        assert_eq!(
+            first.profiles.values["AAPL"].name.as_deref(),
+            Some("Apple Inc")
+        );
+        assert!(first.cached.is_empty());
+        assert_eq!(first.profiles.error_count(), 1);
+        assert_eq!(first.profiles.errors[0].0, "BAD");
+
+        let second = company.profiles(&["AAPL"], &options).await;
+        assert_eq!(second.cached, ["AAPL"]);
+        assert_eq!(
+            second.profiles.values["AAPL"].name.as_deref(),
+            Some("Apple Inc")
+        );
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_company_profile() {
@@ -102,4 +323,26 @@ mod tests {
             assert!(!symbol.description.is_empty());
         }
     }
+
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_symbols_filtered() {
+        let client = test_client().await;
+        let filter = SymbolFilter {
+            mic: Some("XNAS".to_string()),
+            security_type: Some(SecurityType::CommonStock),
+            currency: Some("USD".to_string()),
+        };
+        let symbols = client
+            .stock()
+            .symbols_filtered("US", &filter)
+            .await
+            .unwrap();
+
+        assert!(!symbols.is_empty());
+        for symbol in symbols.iter().take(50) {
+            assert_eq!(symbol.mic.as_deref(), Some("XNAS"));
+            assert_eq!(symbol.symbol_type.as_deref(), Some("Common Stock"));
+        }
+    }
 }
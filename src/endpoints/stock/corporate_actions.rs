@@ -3,7 +3,7 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::stock::{Dividend, DividendsV2, StockSplit},
+    models::stock::{Dividend, DividendsV2, IsinChanges, StockSplit, SymbolChanges},
 };
 
 /// Corporate actions endpoints.
@@ -52,6 +52,33 @@ impl<'a> CorporateActionsEndpoints<'a> {
             .get(&format!("/stock/dividend2?symbol={}", symbol))
             .await
     }
+
+    /// Get ticker changes across US, EU, NSE and ASX listings.
+    ///
+    /// Returns at most 2000 changes; split longer ranges. Use
+    /// [`SymbolChanges::latest_symbol`] to map old tickers to current ones.
+    ///
+    /// # Arguments
+    /// * `from` - From date (YYYY-MM-DD)
+    /// * `to` - To date (YYYY-MM-DD)
+    pub async fn symbol_changes(&self, from: &str, to: &str) -> Result<SymbolChanges> {
+        self.client
+            .get(&format!("/ca/symbol-change?from={}&to={}", from, to))
+            .await
+    }
+
+    /// Get ISIN changes of EU listings.
+    ///
+    /// Returns at most 2000 changes; split longer ranges.
+    ///
+    /// # Arguments
+    /// * `from` - From date (YYYY-MM-DD)
+    /// * `to` - To date (YYYY-MM-DD)
+    pub async fn isin_changes(&self, from: &str, to: &str) -> Result<IsinChanges> {
+        self.client
+            .get(&format!("/ca/isin-change?from={}&to={}", from, to))
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -125,4 +152,36 @@ mod tests {
             result.err()
         );
     }
+
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_symbol_changes() {
+        let client = test_client().await;
+        match client
+            .stock()
+            .symbol_changes("2022-06-01", "2022-06-30")
+            .await
+        {
+            Ok(changes) => assert_eq!(changes.latest_symbol("FB"), "META"),
+            // Premium endpoint
+            Err(e) if matches!(e.root(), crate::Error::ApiError { status: 403, .. }) => {}
+            Err(e) => panic!("Failed to get symbol changes: {e:?}"),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_isin_changes() {
+        let client = test_client().await;
+        match client
+            .stock()
+            .isin_changes("2021-08-07", "2021-10-07")
+            .await
+        {
+            Ok(_) => {}
+            // Premium endpoint
+            Err(e) if matches!(e.root(), crate::Error::ApiError { status: 403, .. }) => {}
+            Err(e) => panic!("Failed to get ISIN changes: {e:?}"),
+        }
+    }
 }
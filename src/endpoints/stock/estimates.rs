@@ -1,10 +1,15 @@
 //! Earnings and revenue estimates endpoints.
 
+use chrono::{Duration, Utc};
+
 use crate::{
     client::FinnhubClient,
-    error::Result,
+    endpoints::{stock::financials::FinancialsEndpoints, CalendarEndpoints},
+    error::{Error, Result},
     models::stock::{
-        EBITDAEstimates, EBITEstimates, EPSEstimates, EarningsQualityScore, RevenueEstimates,
+        EBITDAEstimates, EBITEstimates, EPSEstimates, EarningsQualityScore, EstimateAccuracy,
+        EstimateComparison, EstimatesAccuracy, RevenueEstimates, RevenueSeries, StatementFrequency,
+        StatementType,
     },
 };
 
@@ -55,6 +60,34 @@ impl<'a> EstimatesEndpoints<'a> {
         self.client.get(&query).await
     }
 
+    /// Revenue estimates alongside reported revenue, ready to chart.
+    ///
+    /// Fetches [`revenue`](Self::revenue) estimates and the income statement
+    /// at the same frequency and aligns them by fiscal period; see
+    /// [`RevenueSeries::from_parts`].
+    ///
+    /// # Arguments
+    /// * `symbol` - Stock symbol
+    /// * `frequency` - Annual or quarterly; estimates have no TTM variant
+    pub async fn revenue_series(
+        &self,
+        symbol: &str,
+        frequency: StatementFrequency,
+    ) -> Result<RevenueSeries> {
+        if matches!(frequency, StatementFrequency::TTM) {
+            return Err(Error::invalid_parameter(
+                "revenue estimates are only available annually or quarterly",
+            ));
+        }
+        let freq = frequency.to_string();
+        let financials = FinancialsEndpoints::new(self.client);
+        let (estimates, statements) = futures::try_join!(
+            self.revenue(symbol, Some(&freq)),
+            financials.statements(symbol, StatementType::IncomeStatement, frequency),
+        )?;
+        Ok(RevenueSeries::from_parts(&estimates, &statements))
+    }
+
     /// Get EBITDA estimates.
     ///
     /// Returns analysts' EBITDA estimates for a company.
@@ -110,11 +143,82 @@ impl<'a> EstimatesEndpoints<'a> {
             ))
             .await
     }
+
+    /// Compare quarterly consensus estimates with reported results.
+    ///
+    /// Joins [`eps`](Self::eps) estimates with reported EPS from the earnings
+    /// endpoint, and [`revenue`](Self::revenue) estimates with reported revenue
+    /// from the earnings calendar, over the last `periods` reported quarters.
+    ///
+    /// # Arguments
+    /// * `symbol` - Stock symbol
+    /// * `periods` - Number of most recent reported quarters to evaluate
+    pub async fn accuracy(&self, symbol: &str, periods: usize) -> Result<EstimatesAccuracy> {
+        let limit = i64::try_from(periods).unwrap_or(i64::MAX);
+        let today = Utc::now().date_naive();
+        // Roughly one quarter per period, plus one for reporting lag.
+        let from = today - Duration::days(limit.saturating_add(1).saturating_mul(92));
+        let (from, to) = (from.to_string(), today.to_string());
+
+        let financials = FinancialsEndpoints::new(self.client);
+        let calendar = CalendarEndpoints::new(self.client);
+        let (eps_estimates, revenue_estimates, earnings, releases) = futures::try_join!(
+            self.eps(symbol, Some("quarterly")),
+            self.revenue(symbol, Some("quarterly")),
+            financials.earnings(symbol, Some(limit)),
+            calendar.earnings(Some(&from), Some(&to), Some(symbol)),
+        )?;
+
+        let eps = earnings
+            .iter()
+            .filter_map(|reported| {
+                let actual = reported.actual?;
+                let estimate = eps_estimates
+                    .data
+                    .iter()
+                    .find(|e| e.period == reported.period)?
+                    .eps_avg?;
+                Some(EstimateComparison {
+                    period: reported.period.clone(),
+                    estimate,
+                    actual,
+                })
+            })
+            .take(periods)
+            .collect();
+
+        let mut releases = releases.earnings_calendar;
+        releases.sort_by(|a, b| b.date.cmp(&a.date));
+        let revenue = releases
+            .iter()
+            .filter_map(|release| {
+                let actual = release.revenue_actual?;
+                let (year, quarter) = (release.year?, release.quarter?);
+                let estimate = revenue_estimates.data.iter().find(|e| {
+                    e.year.map(i64::from) == Some(year) && e.quarter.map(i64::from) == Some(quarter)
+                })?;
+                Some(EstimateComparison {
+                    period: estimate.period.clone(),
+                    estimate: estimate.revenue_avg?,
+                    actual,
+                })
+            })
+            .take(periods)
+            .collect();
+
+        Ok(EstimatesAccuracy {
+            symbol: symbol.to_string(),
+            eps: EstimateAccuracy::from_comparisons(eps),
+            revenue: EstimateAccuracy::from_comparisons(revenue),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use crate::{
+        models::stock::StatementFrequency, ClientConfig, FinnhubClient, RateLimitStrategy,
+    };
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -125,6 +229,19 @@ mod tests {
         FinnhubClient::with_config(api_key, config)
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_estimate_accuracy() {
+        let client = test_client().await;
+        let accuracy = client.stock().estimate_accuracy("AAPL", 4).await.unwrap();
+
+        assert_eq!(accuracy.symbol, "AAPL");
+        assert!(accuracy.eps.periods.len() <= 4);
+        if let Some(rate) = accuracy.eps.beat_rate {
+            assert!((0.0..=1.0).contains(&rate));
+        }
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_eps_estimates() {
@@ -207,6 +324,23 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_revenue_series() {
+        let client = test_client().await;
+        let series = client
+            .stock()
+            .revenue_series("AAPL", StatementFrequency::Quarterly)
+            .await
+            .unwrap();
+
+        assert!(!series.points.is_empty());
+        assert!(series
+            .points
+            .windows(2)
+            .all(|w| (w[0].year, w[0].quarter) < (w[1].year, w[1].quarter)));
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_ebitda_estimates() {
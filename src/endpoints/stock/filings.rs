@@ -1,8 +1,11 @@
 //! SEC filings and document endpoints.
 
+use serde::de::DeserializeOwned;
+
 use crate::{
     client::FinnhubClient,
     error::Result,
+    lenient::Lenient,
     models::stock::{
         EarningsCallLive, EarningsCallTranscript, EarningsCallTranscriptsList, Filing,
         InternationalFiling, InvestorPresentations, SimilarityIndex,
@@ -40,6 +43,36 @@ impl<'a> FilingsEndpoints<'a> {
         from: Option<&str>,
         to: Option<&str>,
     ) -> Result<Vec<Filing>> {
+        self.sec_as(symbol, cik, access_number, form, from, to)
+            .await
+    }
+
+    /// Get SEC filings, skipping entries that fail to deserialize.
+    ///
+    /// Takes the same arguments as [`sec`](Self::sec). Skipped entries are
+    /// reported in [`Lenient::warnings`].
+    pub async fn sec_lenient(
+        &self,
+        symbol: Option<&str>,
+        cik: Option<&str>,
+        access_number: Option<&str>,
+        form: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Lenient<Filing>> {
+        self.sec_as(symbol, cik, access_number, form, from, to)
+            .await
+    }
+
+    async fn sec_as<T: DeserializeOwned>(
+        &self,
+        symbol: Option<&str>,
+        cik: Option<&str>,
+        access_number: Option<&str>,
+        form: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<T> {
         let mut params = vec![];
 
         if let Some(s) = symbol {
@@ -72,7 +105,8 @@ impl<'a> FilingsEndpoints<'a> {
 
     /// Get international filings.
     ///
-    /// List filings for international companies. Limit to 500 documents at a time.
+    /// List filings for international companies. Limited to
+    /// [`INTERNATIONAL_FILINGS_MAX_DOCUMENTS`](crate::limits::INTERNATIONAL_FILINGS_MAX_DOCUMENTS) documents at a time.
     ///
     /// # Arguments
     /// * `symbol` - Stock symbol (optional)
@@ -1,13 +1,22 @@
 //! Financial data endpoints.
 
+use super::company::CompanyEndpoints;
 use crate::{
     client::FinnhubClient,
     error::Result,
     models::stock::{
-        BasicFinancials, Earnings, FinancialStatements, FinancialsAsReported, StatementFrequency,
-        StatementType,
+        BasicFinancials, Earnings, FinancialStatements, FinancialsAsReported, NormalizedStatements,
+        StatementFrequency, StatementType, UnitScale,
     },
+    partial::{self, PartialResult},
 };
+#[cfg(feature = "forex")]
+use crate::{error::Error, models::forex::ForexRates};
+#[cfg(feature = "forex")]
+use std::collections::{BTreeSet, HashMap};
+
+/// Unit of the amounts in standardized statements.
+const STATEMENT_SCALE: UnitScale = UnitScale::Millions;
 
 /// Financial data endpoints.
 pub struct FinancialsEndpoints<'a> {
@@ -37,6 +46,85 @@ impl<'a> FinancialsEndpoints<'a> {
             .await
     }
 
+    /// Standardized statements in absolute units, tagged with the reporting
+    /// currency from the company profile.
+    pub async fn normalized(
+        &self,
+        symbol: &str,
+        statement: StatementType,
+        frequency: StatementFrequency,
+    ) -> Result<NormalizedStatements> {
+        let company = CompanyEndpoints::new(self.client);
+        let (statements, profile) = futures::try_join!(
+            self.statements(symbol, statement, frequency),
+            company.profile(symbol),
+        )?;
+        Ok(statements.normalized(
+            STATEMENT_SCALE,
+            reporting_currency(profile.currency.as_deref()),
+        ))
+    }
+
+    /// Standardized statements in absolute units of `target` currency.
+    ///
+    /// Each period is converted at the forex rate for its end date, fetched
+    /// from `/forex/rates`. Periods without a rate keep the reporting
+    /// currency; find them with [`NormalizedStatements::not_in`].
+    ///
+    /// # Errors
+    ///
+    /// Returns request errors, or [`Error::InvalidRequest`] if the company
+    /// profile doesn't name a reporting currency.
+    #[cfg(feature = "forex")]
+    pub async fn normalized_to(
+        &self,
+        symbol: &str,
+        statement: StatementType,
+        frequency: StatementFrequency,
+        target: &str,
+    ) -> Result<NormalizedStatements> {
+        let company = CompanyEndpoints::new(self.client);
+        let (statements, profile) = futures::try_join!(
+            self.statements(symbol, statement, frequency),
+            company.profile(symbol),
+        )?;
+        let currency = reporting_currency(profile.currency.as_deref())
+            .ok_or_else(|| {
+                Error::InvalidRequest(format!("no reporting currency known for {symbol}"))
+            })?
+            .to_ascii_uppercase();
+        let target = target.to_ascii_uppercase();
+
+        let mut rates = HashMap::new();
+        if currency != target {
+            let dates: BTreeSet<&str> = statements
+                .financials
+                .iter()
+                .filter_map(|period| period.get("period")?.as_str())
+                .collect();
+            let requests = dates.into_iter().map(|date| {
+                let (currency, target) = (&currency, &target);
+                async move {
+                    let rates: ForexRates = self
+                        .client
+                        .get(&format!("/forex/rates?base={currency}&date={date}"))
+                        .await?;
+                    Ok::<_, Error>((date.to_string(), rates.quote.get(target).copied()))
+                }
+            });
+            for (date, rate) in futures::future::try_join_all(requests).await? {
+                if let Some(rate) = rate {
+                    rates.insert(date, rate);
+                }
+            }
+        }
+        Ok(
+            statements.normalized_to(STATEMENT_SCALE, &currency, &target, |date| {
+                rates.get(date).copied()
+            }),
+        )
+    }
+
     /// Get basic financials metrics.
     ///
     /// Returns key metrics such as P/E ratio, market cap, 52-week high/low, etc.
@@ -46,6 +134,20 @@ impl<'a> FinancialsEndpoints<'a> {
             .await
     }
 
+    /// Get basic financials for many symbols, keyed by symbol, e.g. to screen
+    /// a universe.
+    ///
+    /// Up to [`partial::DEFAULT_CONCURRENCY`] requests run at once, still
+    /// through the rate limiter. A failed symbol doesn't stop the others.
+    pub async fn metrics_many(&self, symbols: &[&str]) -> PartialResult<BasicFinancials> {
+        partial::fetch_all(
+            symbols.iter().map(|s| (*s).to_string()),
+            partial::DEFAULT_CONCURRENCY,
+            |symbol| async move { self.metrics(&symbol).await },
+        )
+        .await
+    }
+
     /// Get company earnings.
     pub async fn earnings(&self, symbol: &str, limit: Option<i64>) -> Result<Vec<Earnings>> {
         let url = if let Some(limit) = limit {
@@ -97,6 +199,11 @@ impl<'a> FinancialsEndpoints<'a> {
     }
 }
 
+/// The profile's filing currency, if it names one.
+fn reporting_currency(currency: Option<&str>) -> Option<&str> {
+    currency.filter(|c| !c.trim().is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -219,4 +326,70 @@ mod tests {
             result.err()
         );
     }
+
+    #[cfg(feature = "forex")]
+    #[tokio::test]
+    async fn test_normalized_to_converts_at_period_rates() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/financials"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbol": "SAP",
+                "financials": [
+                    {"period": "2023-12-31", "year": 2023, "revenue": 31207},
+                    {"period": "2022-12-31", "year": 2022, "revenue": 30871},
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"currency": "EUR"})),
+            )
+            .mount(&server)
+            .await;
+        for (date, rate) in [("2023-12-31", 1.1), ("2022-12-31", 1.07)] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/forex/rates"))
+                .and(query_param("base", "EUR"))
+                .and(query_param("date", date))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "base": "EUR",
+                    "quote": {"USD": rate, "GBP": 0.86}
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = FinnhubClient::with_config(
+            "test-api-key",
+            ClientConfig {
+                base_url: format!("{}/api/v1", server.uri()),
+                ..ClientConfig::default()
+            },
+        );
+        let normalized = client
+            .stock()
+            .financials_in_currency(
+                "SAP",
+                StatementType::IncomeStatement,
+                StatementFrequency::Annual,
+                "usd",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(normalized.not_in("USD").count(), 0);
+        let revenue: Vec<_> = normalized
+            .periods
+            .iter()
+            .map(|p| p.get("revenue").unwrap().round())
+            .collect();
+        assert_eq!(revenue, [31_207e6 * 1.1, 30_871e6 * 1.07].map(f64::round));
+    }
 }
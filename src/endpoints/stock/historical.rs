@@ -1,13 +1,23 @@
 //! Historical data endpoints.
 
+use chrono::{Duration, NaiveDate};
+use std::future::Future;
+
 use crate::{
     client::FinnhubClient,
-    error::Result,
-    models::stock::{
-        HistoricalESG, HistoricalEmployeeCount, HistoricalMarketCapData, HistoricalNBBO,
+    error::{Error, Result},
+    limits::check_tick_limit,
+    models::{
+        stock::{HistoricalESG, HistoricalEmployeeCount, HistoricalMarketCapData, HistoricalNBBO},
+        time_series::parse_date,
     },
 };
 
+/// Days covered by one request of the `*_range` methods. The history
+/// endpoints cut long ranges short without saying so; a year per request
+/// stays well inside what they return in full.
+const RANGE_CHUNK_DAYS: i64 = 365;
+
 /// Historical data endpoints.
 pub struct HistoricalEndpoints<'a> {
     client: &'a FinnhubClient,
@@ -65,6 +75,74 @@ impl<'a> HistoricalEndpoints<'a> {
             .await
     }
 
+    /// Get historical market capitalization data over a range of any length.
+    ///
+    /// Splits `from..=to` into one-year windows, requests them concurrently
+    /// and merges the results oldest first, dropping rows repeated at window
+    /// boundaries. Rows that share a date but differ are all kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParameter`] if a date isn't `YYYY-MM-DD` or
+    /// `from` is after `to`.
+    ///
+    /// # Arguments
+    /// * `symbol` - Stock symbol
+    /// * `from` - Start date in YYYY-MM-DD format
+    /// * `to` - End date in YYYY-MM-DD format
+    pub async fn market_cap_range(
+        &self,
+        symbol: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalMarketCapData> {
+        let chunks = chunked(from, to, |from, to| async move {
+            self.market_cap(symbol, &from, &to).await
+        })
+        .await?;
+        let currency = chunks
+            .iter()
+            .map(|chunk| chunk.currency.clone())
+            .find(|currency| !currency.is_empty())
+            .unwrap_or_default();
+        Ok(HistoricalMarketCapData {
+            symbol: symbol.to_string(),
+            currency,
+            data: merge_by_date(chunks.into_iter().map(|c| c.data), |p| &p.at_date),
+        })
+    }
+
+    /// Get historical employee counts over a range of any length; see
+    /// [`market_cap_range`](Self::market_cap_range).
+    pub async fn employee_count_range(
+        &self,
+        symbol: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalEmployeeCount> {
+        let chunks = chunked(from, to, |from, to| async move {
+            self.employee_count(symbol, &from, &to).await
+        })
+        .await?;
+        Ok(HistoricalEmployeeCount {
+            symbol: symbol.to_string(),
+            data: merge_by_date(chunks.into_iter().map(|c| c.data), |p| &p.at_date),
+        })
+    }
+
+    /// Get historical ESG scores over a range of any length; see
+    /// [`market_cap_range`](Self::market_cap_range).
+    pub async fn esg_range(&self, symbol: &str, from: &str, to: &str) -> Result<HistoricalESG> {
+        let chunks = chunked(from, to, |from, to| async move {
+            self.esg(symbol, &from, &to).await
+        })
+        .await?;
+        Ok(HistoricalESG {
+            symbol: symbol.to_string(),
+            data: merge_by_date(chunks.into_iter().map(|c| c.data), |p| &p.at_date),
+        })
+    }
+
     /// Get historical NBBO (National Best Bid and Offer) data.
     ///
     /// Returns historical best bid and offer for US stocks, LSE, TSX, Euronext and Deutsche Borse.
@@ -72,7 +150,7 @@ impl<'a> HistoricalEndpoints<'a> {
     /// # Arguments
     /// * `symbol` - Stock symbol
     /// * `date` - Date in YYYY-MM-DD format
-    /// * `limit` - Limit number of ticks returned (max 25000)
+    /// * `limit` - Limit number of ticks returned (max [`TICK_MAX_LIMIT`](crate::limits::TICK_MAX_LIMIT))
     /// * `skip` - Number of ticks to skip
     pub async fn nbbo(
         &self,
@@ -81,6 +159,7 @@ impl<'a> HistoricalEndpoints<'a> {
         limit: i64,
         skip: i64,
     ) -> Result<HistoricalNBBO> {
+        check_tick_limit(limit)?;
         self.client
             .get(&format!(
                 "/stock/bbo?symbol={}&date={}&limit={}&skip={}",
@@ -90,9 +169,125 @@ impl<'a> HistoricalEndpoints<'a> {
     }
 }
 
+/// Consecutive inclusive windows of at most `days` days covering `from..=to`.
+fn date_windows(from: NaiveDate, to: NaiveDate, days: i64) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut windows = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = (start + Duration::days(days - 1)).min(to);
+        windows.push((start, end));
+        start = end + Duration::days(1);
+    }
+    windows
+}
+
+/// Run `fetch` for each window of `from..=to` concurrently.
+async fn chunked<T, F, Fut>(from: &str, to: &str, fetch: F) -> Result<Vec<T>>
+where
+    F: Fn(String, String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let parse = |date: &str| {
+        parse_date(date).ok_or_else(|| {
+            Error::invalid_parameter(format!("expected a YYYY-MM-DD date, got {date:?}"))
+        })
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    if from > to {
+        return Err(Error::invalid_parameter(format!(
+            "from ({from}) is after to ({to})"
+        )));
+    }
+    let requests = date_windows(from, to, RANGE_CHUNK_DAYS)
+        .into_iter()
+        .map(|(from, to)| fetch(from.to_string(), to.to_string()));
+    futures::future::try_join_all(requests).await
+}
+
+/// Concatenate rows, sort them oldest first and drop rows equal to an earlier
+/// one.
+fn merge_by_date<T: PartialEq>(
+    chunks: impl IntoIterator<Item = Vec<T>>,
+    date: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let mut rows: Vec<T> = chunks.into_iter().flatten().collect();
+    // Stable, so rows of one date keep their arrival order.
+    rows.sort_by(|a, b| date(a).cmp(date(b)));
+    let mut merged: Vec<T> = Vec::with_capacity(rows.len());
+    // Start of the rows in `merged` with the current row's date.
+    let mut same_date = 0;
+    for row in rows {
+        if merged
+            .get(same_date)
+            .is_some_and(|first| date(first) != date(&row))
+        {
+            same_date = merged.len();
+        }
+        if !merged[same_date..].contains(&row) {
+            merged.push(row);
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{chunked, date_windows, merge_by_date};
+    use crate::models::stock::EmployeeCountData;
     use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_date_windows() {
+        let d = |y, m, day| NaiveDate::from_ymd_opt(y, m, day).unwrap();
+        let windows = date_windows(d(2020, 1, 1), d(2022, 6, 30), 365);
+        assert_eq!(
+            windows,
+            [
+                (d(2020, 1, 1), d(2020, 12, 30)),
+                (d(2020, 12, 31), d(2021, 12, 30)),
+                (d(2021, 12, 31), d(2022, 6, 30)),
+            ]
+        );
+        assert_eq!(date_windows(d(2020, 1, 2), d(2020, 1, 1), 365), []);
+    }
+
+    #[test]
+    fn test_merge_by_date_drops_boundary_duplicates() {
+        let row = |date: &str, total| EmployeeCountData {
+            at_date: date.to_string(),
+            employee_total: total,
+        };
+        let merged = merge_by_date(
+            [
+                vec![
+                    row("2021-12-31", 2),
+                    row("2021-12-31", 5),
+                    row("2021-06-30", 1),
+                ],
+                vec![row("2022-06-30", 3), row("2021-12-31", 2)],
+            ],
+            |r| &r.at_date,
+        );
+        assert_eq!(
+            merged,
+            [
+                row("2021-06-30", 1),
+                row("2021-12-31", 2),
+                row("2021-12-31", 5),
+                row("2022-06-30", 3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunked_rejects_reversed_range() {
+        let result = chunked("2022-01-01", "2021-01-01", |_, _| async {
+            Ok::<_, crate::Error>(())
+        })
+        .await;
+        assert!(matches!(result, Err(crate::Error::InvalidParameter(_))));
+    }
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -118,6 +313,22 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_historical_market_cap_range() {
+        let client = test_client().await;
+        let history = client
+            .stock()
+            .historical_market_cap_range("AAPL", "2019-01-01", "2023-12-31")
+            .await
+            .unwrap();
+
+        assert!(history
+            .data
+            .windows(2)
+            .all(|pair| pair[0].at_date < pair[1].at_date));
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_historical_employee_count() {
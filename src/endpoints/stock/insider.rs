@@ -3,7 +3,7 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::stock::{InsiderSentimentData, InsiderTransactions},
+    models::stock::{InsiderFilter, InsiderSentimentData, InsiderTransactions},
 };
 
 /// Insider trading endpoints.
@@ -26,6 +26,30 @@ impl<'a> InsiderEndpoints<'a> {
             .await
     }
 
+    /// Get insider transactions matching `filter`.
+    ///
+    /// The date range is applied by the API, transaction codes client-side.
+    pub async fn transactions_filtered(
+        &self,
+        symbol: &str,
+        filter: &InsiderFilter,
+    ) -> Result<InsiderTransactions> {
+        let mut params = vec![format!("symbol={}", symbol)];
+        if let Some(from) = &filter.from {
+            params.push(format!("from={}", from));
+        }
+        if let Some(to) = &filter.to {
+            params.push(format!("to={}", to));
+        }
+
+        let mut transactions: InsiderTransactions = self
+            .client
+            .get(&format!("/stock/insider-transactions?{}", params.join("&")))
+            .await?;
+        transactions.data.retain(|t| filter.matches(t));
+        Ok(transactions)
+    }
+
     /// Get insider sentiment data.
     ///
     /// Returns aggregated insider trading sentiment by month.
@@ -46,7 +70,10 @@ impl<'a> InsiderEndpoints<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use crate::{
+        models::stock::{InsiderFilter, InsiderTransaction, TransactionCode},
+        ClientConfig, FinnhubClient, RateLimitStrategy,
+    };
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -70,6 +97,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_insider_transactions_filtered() {
+        let client = test_client().await;
+        let filter = InsiderFilter::new()
+            .from("2024-01-01")
+            .codes([TransactionCode::Sale]);
+        let result = client
+            .stock()
+            .insider_transactions_filtered("AAPL", &filter)
+            .await;
+
+        assert!(
+            result.is_ok(),
+            "Failed to get insider transactions: {:?}",
+            result.err()
+        );
+        assert!(result
+            .unwrap()
+            .data
+            .iter()
+            .all(InsiderTransaction::is_open_market_sale));
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_insider_sentiment() {
@@ -3,7 +3,11 @@
 use crate::{
     client::FinnhubClient,
     error::Result,
-    models::stock::{InvestmentTheme, MarketHoliday, MarketStatus},
+    models::{
+        stock::{InvestmentTheme, MarketHoliday, MarketStatus},
+        Exchange,
+    },
+    partial::{self, PartialResult},
 };
 
 /// Market data endpoints.
@@ -26,6 +30,25 @@ impl<'a> MarketEndpoints<'a> {
             .await
     }
 
+    /// Get the market status of several exchanges concurrently.
+    ///
+    /// Requests still go through the rate limiter. A failed exchange doesn't
+    /// stop the others.
+    ///
+    /// # Arguments
+    /// * `exchanges` - Exchange codes, e.g. `["US", "L", "T"]`
+    pub async fn status_many(
+        &self,
+        exchanges: impl IntoIterator<Item = impl Into<Exchange>>,
+    ) -> PartialResult<MarketStatus, Exchange> {
+        let exchanges: Vec<Exchange> = exchanges.into_iter().map(Into::into).collect();
+        let concurrency = exchanges.len();
+        partial::fetch_all(exchanges, concurrency, |exchange| async move {
+            self.status(exchange.as_str()).await
+        })
+        .await
+    }
+
     /// Get market holidays.
     ///
     /// Returns a list of holidays for global exchanges.
@@ -53,7 +76,7 @@ impl<'a> MarketEndpoints<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    use crate::{models::Exchange, ClientConfig, FinnhubClient, RateLimitStrategy};
 
     async fn test_client() -> FinnhubClient {
         dotenv::dotenv().ok();
@@ -77,6 +100,17 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_market_status_many() {
+        let client = test_client().await;
+        let result = client.stock().market_status_many(["US", "L"]).await;
+
+        assert!(result.is_complete(), "{:?}", result.errors);
+        assert!(result.get(&Exchange::new("US")).is_some());
+        assert!(result.get(&Exchange::new("L")).is_some());
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_market_holiday() {
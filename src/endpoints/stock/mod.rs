@@ -13,8 +13,16 @@ pub mod market;
 pub mod ownership;
 pub mod price;
 pub mod sentiment;
+pub mod timeline;
 
-use crate::{client::FinnhubClient, error::Result, models::stock::*};
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    latency::WithLatency,
+    lenient::Lenient,
+    models::{stock::*, Exchange},
+    partial::PartialResult,
+};
 
 /// Stock-related API endpoints with a flat API structure.
 pub struct StockEndpoints<'a> {
@@ -34,6 +42,13 @@ impl<'a> StockEndpoints<'a> {
         price::PriceEndpoints::new(self.client).quote(symbol).await
     }
 
+    /// Get quotes for many symbols; failed symbols don't stop the others.
+    pub async fn quotes(&self, symbols: &[&str]) -> PartialResult<Quote> {
+        price::PriceEndpoints::new(self.client)
+            .quotes(symbols)
+            .await
+    }
+
     /// Get candlestick data (OHLCV) for stocks.
     pub async fn candles(
         &self,
@@ -47,6 +62,26 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get a quote labelled with its [`DataLatency`](crate::latency::DataLatency).
+    pub async fn quote_with_latency(&self, symbol: &str) -> Result<WithLatency<Quote>> {
+        price::PriceEndpoints::new(self.client)
+            .quote_with_latency(symbol)
+            .await
+    }
+
+    /// Get candles labelled with their [`DataLatency`](crate::latency::DataLatency).
+    pub async fn candles_with_latency(
+        &self,
+        symbol: &str,
+        resolution: CandleResolution,
+        from: i64,
+        to: i64,
+    ) -> Result<WithLatency<StockCandles>> {
+        price::PriceEndpoints::new(self.client)
+            .candles_with_latency(symbol, resolution, from, to)
+            .await
+    }
+
     /// Get last bid-ask data.
     pub async fn bid_ask(&self, symbol: &str) -> Result<BidAsk> {
         price::PriceEndpoints::new(self.client)
@@ -67,6 +102,15 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Stream every tick for a symbol and date, paging through the results.
+    pub fn tick_stream(
+        &self,
+        symbol: &'a str,
+        date: &'a str,
+    ) -> impl futures::Stream<Item = Result<TickData>> + 'a {
+        price::PriceEndpoints::new(self.client).tick_stream(symbol, date)
+    }
+
     /// Get price metrics.
     pub async fn price_metrics(&self, symbol: &str) -> Result<PriceMetrics> {
         price::PriceEndpoints::new(self.client)
@@ -83,6 +127,17 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Resolve profiles for many symbols at once, optionally cached.
+    pub async fn company_profiles(
+        &self,
+        symbols: &[&str],
+        options: &company::ProfilesOptions,
+    ) -> company::ProfileBatch {
+        company::CompanyEndpoints::new(self.client)
+            .profiles(symbols, options)
+            .await
+    }
+
     /// Get company peers.
     pub async fn peers(&self, symbol: &str, grouping: Option<&str>) -> Result<Vec<String>> {
         company::CompanyEndpoints::new(self.client)
@@ -97,6 +152,28 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get supported stocks filtered by MIC, security type or currency.
+    pub async fn symbols_filtered(
+        &self,
+        exchange: &str,
+        filter: &SymbolFilter,
+    ) -> Result<Vec<Symbol>> {
+        company::CompanyEndpoints::new(self.client)
+            .symbols_filtered(exchange, filter)
+            .await
+    }
+
+    /// Get supported stocks, skipping entries that fail to deserialize.
+    pub async fn symbols_lenient(
+        &self,
+        exchange: &str,
+        filter: &SymbolFilter,
+    ) -> Result<Lenient<Symbol>> {
+        company::CompanyEndpoints::new(self.client)
+            .symbols_lenient(exchange, filter)
+            .await
+    }
+
     // ===== Financial endpoints =====
 
     /// Get standardized financial statements.
@@ -111,6 +188,34 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Standardized financial statements in absolute units, tagged with the
+    /// reporting currency.
+    pub async fn financials_normalized(
+        &self,
+        symbol: &str,
+        statement: StatementType,
+        frequency: StatementFrequency,
+    ) -> Result<NormalizedStatements> {
+        financials::FinancialsEndpoints::new(self.client)
+            .normalized(symbol, statement, frequency)
+            .await
+    }
+
+    /// Standardized financial statements in absolute units of `target`
+    /// currency, converted at historical forex rates.
+    #[cfg(feature = "forex")]
+    pub async fn financials_in_currency(
+        &self,
+        symbol: &str,
+        statement: StatementType,
+        frequency: StatementFrequency,
+        target: &str,
+    ) -> Result<NormalizedStatements> {
+        financials::FinancialsEndpoints::new(self.client)
+            .normalized_to(symbol, statement, frequency, target)
+            .await
+    }
+
     /// Get basic financials metrics.
     pub async fn metrics(&self, symbol: &str) -> Result<BasicFinancials> {
         financials::FinancialsEndpoints::new(self.client)
@@ -118,6 +223,14 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get basic financials for many symbols; failed symbols don't stop the
+    /// others.
+    pub async fn metrics_many(&self, symbols: &[&str]) -> PartialResult<BasicFinancials> {
+        financials::FinancialsEndpoints::new(self.client)
+            .metrics_many(symbols)
+            .await
+    }
+
     /// Get company earnings.
     pub async fn earnings(&self, symbol: &str, limit: Option<i64>) -> Result<Vec<Earnings>> {
         financials::FinancialsEndpoints::new(self.client)
@@ -182,6 +295,17 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get insider transactions matching `filter`.
+    pub async fn insider_transactions_filtered(
+        &self,
+        symbol: &str,
+        filter: &InsiderFilter,
+    ) -> Result<InsiderTransactions> {
+        insider::InsiderEndpoints::new(self.client)
+            .transactions_filtered(symbol, filter)
+            .await
+    }
+
     /// Get insider sentiment data.
     pub async fn insider_sentiment(
         &self,
@@ -217,6 +341,20 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get ticker changes, e.g. to follow `FB` to `META`.
+    pub async fn symbol_changes(&self, from: &str, to: &str) -> Result<SymbolChanges> {
+        corporate_actions::CorporateActionsEndpoints::new(self.client)
+            .symbol_changes(from, to)
+            .await
+    }
+
+    /// Get ISIN changes of EU listings.
+    pub async fn isin_changes(&self, from: &str, to: &str) -> Result<IsinChanges> {
+        corporate_actions::CorporateActionsEndpoints::new(self.client)
+            .isin_changes(from, to)
+            .await
+    }
+
     // ===== Historical endpoints =====
 
     /// Get historical market capitalization data.
@@ -255,6 +393,44 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get historical market capitalization data, splitting long ranges into
+    /// several requests.
+    pub async fn historical_market_cap_range(
+        &self,
+        symbol: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalMarketCapData> {
+        historical::HistoricalEndpoints::new(self.client)
+            .market_cap_range(symbol, from, to)
+            .await
+    }
+
+    /// Get historical employee count data, splitting long ranges into several
+    /// requests.
+    pub async fn historical_employee_count_range(
+        &self,
+        symbol: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalEmployeeCount> {
+        historical::HistoricalEndpoints::new(self.client)
+            .employee_count_range(symbol, from, to)
+            .await
+    }
+
+    /// Get historical ESG scores, splitting long ranges into several requests.
+    pub async fn historical_esg_range(
+        &self,
+        symbol: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HistoricalESG> {
+        historical::HistoricalEndpoints::new(self.client)
+            .esg_range(symbol, from, to)
+            .await
+    }
+
     /// Get historical NBBO data.
     pub async fn historical_nbbo(
         &self,
@@ -298,6 +474,16 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get the market status of several exchanges concurrently.
+    pub async fn market_status_many(
+        &self,
+        exchanges: impl IntoIterator<Item = impl Into<Exchange>>,
+    ) -> PartialResult<MarketStatus, Exchange> {
+        market::MarketEndpoints::new(self.client)
+            .status_many(exchanges)
+            .await
+    }
+
     /// Get market holidays.
     pub async fn market_holiday(&self, exchange: &str) -> Result<MarketHoliday> {
         market::MarketEndpoints::new(self.client)
@@ -345,6 +531,21 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Get SEC filings, skipping entries that fail to deserialize.
+    pub async fn sec_filings_lenient(
+        &self,
+        symbol: Option<&str>,
+        cik: Option<&str>,
+        access_number: Option<&str>,
+        form: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Lenient<Filing>> {
+        filings::FilingsEndpoints::new(self.client)
+            .sec_lenient(symbol, cik, access_number, form, from, to)
+            .await
+    }
+
     /// Get international filings.
     pub async fn international_filings(
         &self,
@@ -418,6 +619,17 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Revenue estimates aligned with reported revenue, oldest period first.
+    pub async fn revenue_series(
+        &self,
+        symbol: &str,
+        frequency: StatementFrequency,
+    ) -> Result<RevenueSeries> {
+        estimates::EstimatesEndpoints::new(self.client)
+            .revenue_series(symbol, frequency)
+            .await
+    }
+
     /// Get EBITDA estimates.
     pub async fn ebitda_estimates(
         &self,
@@ -436,6 +648,18 @@ impl<'a> StockEndpoints<'a> {
             .await
     }
 
+    /// Compare EPS and revenue estimates with reported results over the last
+    /// `periods` quarters.
+    pub async fn estimate_accuracy(
+        &self,
+        symbol: &str,
+        periods: usize,
+    ) -> Result<EstimatesAccuracy> {
+        estimates::EstimatesEndpoints::new(self.client)
+            .accuracy(symbol, periods)
+            .await
+    }
+
     /// Get earnings quality score.
     pub async fn earnings_quality_score(
         &self,
@@ -524,4 +748,19 @@ impl<'a> StockEndpoints<'a> {
             .visa_applications(symbol, from, to)
             .await
     }
+
+    // ===== Timeline =====
+
+    /// Build a chronological timeline of earnings, dividends, splits, filings,
+    /// rating changes and news.
+    pub async fn timeline(
+        &self,
+        symbol: &str,
+        range: std::ops::RangeInclusive<chrono::NaiveDate>,
+        sources: TimelineSources,
+    ) -> Result<CompanyTimeline> {
+        timeline::TimelineEndpoints::new(self.client)
+            .timeline(symbol, range, sources)
+            .await
+    }
 }
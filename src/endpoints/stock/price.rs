@@ -1,9 +1,14 @@
 //! Price-related stock endpoints.
 
+use futures::stream::{self, Stream};
+
 use crate::{
     client::FinnhubClient,
     error::Result,
+    latency::WithLatency,
+    limits::{check_tick_limit, TICK_MAX_LIMIT},
     models::stock::{BidAsk, CandleResolution, PriceMetrics, Quote, StockCandles, TickData},
+    partial::{self, PartialResult},
 };
 
 /// Price-related endpoints for stocks.
@@ -19,7 +24,24 @@ impl<'a> PriceEndpoints<'a> {
 
     /// Get real-time quote data.
     pub async fn quote(&self, symbol: &str) -> Result<Quote> {
-        self.client.get(&format!("/quote?symbol={}", symbol)).await
+        let quote = self
+            .client
+            .get(&format!("/quote?symbol={}", symbol))
+            .await?;
+        self.client.validate(quote)
+    }
+
+    /// Get quotes for many symbols, keyed by symbol.
+    ///
+    /// Up to [`partial::DEFAULT_CONCURRENCY`] requests run at once, still
+    /// through the rate limiter. A failed symbol doesn't stop the others.
+    pub async fn quotes(&self, symbols: &[&str]) -> PartialResult<Quote> {
+        partial::fetch_all(
+            symbols.iter().map(|s| (*s).to_string()),
+            partial::DEFAULT_CONCURRENCY,
+            |symbol| async move { self.quote(&symbol).await },
+        )
+        .await
     }
 
     /// Get candlestick data (OHLCV) for stocks.
@@ -33,12 +55,36 @@ impl<'a> PriceEndpoints<'a> {
         from: i64,
         to: i64,
     ) -> Result<StockCandles> {
-        self.client
+        let candles = self
+            .client
             .get(&format!(
                 "/stock/candle?symbol={}&resolution={}&from={}&to={}",
                 symbol, resolution, from, to
             ))
-            .await
+            .await?;
+        self.client.validate(candles)
+    }
+
+    /// Get a quote labelled with the latency configured for its exchange.
+    pub async fn quote_with_latency(&self, symbol: &str) -> Result<WithLatency<Quote>> {
+        Ok(WithLatency {
+            data: self.quote(symbol).await?,
+            latency: self.client.data_latency(symbol),
+        })
+    }
+
+    /// Get candles labelled with the latency configured for their exchange.
+    pub async fn candles_with_latency(
+        &self,
+        symbol: &str,
+        resolution: CandleResolution,
+        from: i64,
+        to: i64,
+    ) -> Result<WithLatency<StockCandles>> {
+        Ok(WithLatency {
+            data: self.candles(symbol, resolution, from, to).await?,
+            latency: self.client.data_latency(symbol),
+        })
     }
 
     /// Get last bid-ask data.
@@ -57,7 +103,7 @@ impl<'a> PriceEndpoints<'a> {
     /// # Arguments
     /// * `symbol` - Stock symbol
     /// * `date` - Date in YYYY-MM-DD format
-    /// * `limit` - Limit number of ticks returned (max 25000)
+    /// * `limit` - Limit number of ticks returned (max [`crate::limits::TICK_MAX_LIMIT`])
     /// * `skip` - Number of ticks to skip
     pub async fn tick_data(
         &self,
@@ -66,6 +112,7 @@ impl<'a> PriceEndpoints<'a> {
         limit: i64,
         skip: i64,
     ) -> Result<TickData> {
+        check_tick_limit(limit)?;
         self.client
             .get(&format!(
                 "/stock/tick?symbol={}&date={}&limit={}&skip={}",
@@ -74,6 +121,30 @@ impl<'a> PriceEndpoints<'a> {
             .await
     }
 
+    /// Stream every tick for `symbol` on `date`, one page at a time.
+    ///
+    /// Pages hold up to [`TICK_MAX_LIMIT`] ticks and are requested lazily, so
+    /// only one page is in memory at a time. The stream ends after the page
+    /// that reaches the day's `total`, or after the first error.
+    pub fn tick_stream(
+        &self,
+        symbol: &'a str,
+        date: &'a str,
+    ) -> impl Stream<Item = Result<TickData>> + 'a {
+        let client = self.client;
+        stream::try_unfold(Some(0), move |skip| async move {
+            let Some(skip) = skip else {
+                return Ok(None);
+            };
+            let page = PriceEndpoints::new(client)
+                .tick_data(symbol, date, TICK_MAX_LIMIT, skip)
+                .await?;
+            let next = skip + page.count;
+            let more = page.count > 0 && next < page.total;
+            Ok(Some((page, more.then_some(next))))
+        })
+    }
+
     /// Get price metrics.
     ///
     /// Get advanced price performance metrics for a stock.
@@ -158,6 +229,41 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tick_stream_pages_until_total() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::{path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (skip, count) in [(0, 2), (2, 1)] {
+            let body = serde_json::json!({
+                "s": "AAPL", "skip": skip, "count": count, "total": 3,
+                "v": vec![1.0; count], "p": vec![100.0; count],
+                "t": vec![1_704_207_600_000_i64; count], "x": vec!["Q"; count],
+            });
+            Mock::given(path("/api/v1/stock/tick"))
+                .and(query_param("skip", skip.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("test_key", config);
+        let pages: Vec<TickData> = client
+            .stock()
+            .tick_stream("AAPL", "2024-01-02")
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(pages.iter().map(|p| p.count).collect::<Vec<_>>(), [2, 1]);
+    }
+
     #[tokio::test]
     #[ignore = "requires API key"]
     async fn test_price_metrics() {
@@ -0,0 +1,140 @@
+//! Company event timeline.
+
+use chrono::NaiveDate;
+use std::future::Future;
+use std::ops::RangeInclusive;
+
+use crate::{
+    client::FinnhubClient,
+    endpoints::{
+        stock::{
+            analytics::AnalyticsEndpoints, corporate_actions::CorporateActionsEndpoints,
+            filings::FilingsEndpoints,
+        },
+        CalendarEndpoints, NewsEndpoints,
+    },
+    error::Result,
+    models::stock::{CompanyEventKind, CompanyTimeline, TimelineSources},
+};
+
+/// Company event timeline endpoints.
+pub struct TimelineEndpoints<'a> {
+    client: &'a FinnhubClient,
+}
+
+impl<'a> TimelineEndpoints<'a> {
+    /// Create a new timeline endpoints instance.
+    pub fn new(client: &'a FinnhubClient) -> Self {
+        Self { client }
+    }
+
+    /// Build a chronological timeline of company events.
+    ///
+    /// Merges earnings releases, dividends, splits, SEC filings, analyst
+    /// rating changes and news within `range`. Each enabled source costs one
+    /// request; the requests run concurrently and any failure fails the whole
+    /// timeline.
+    ///
+    /// # Arguments
+    /// * `symbol` - Stock symbol
+    /// * `range` - Dates to include
+    /// * `sources` - Which event sources to fetch
+    pub async fn timeline(
+        &self,
+        symbol: &str,
+        range: RangeInclusive<NaiveDate>,
+        sources: TimelineSources,
+    ) -> Result<CompanyTimeline> {
+        let (from, to) = (range.start().to_string(), range.end().to_string());
+        let (from, to) = (from.as_str(), to.as_str());
+
+        let calendar = CalendarEndpoints::new(self.client);
+        let corporate_actions = CorporateActionsEndpoints::new(self.client);
+        let filings = FilingsEndpoints::new(self.client);
+        let analytics = AnalyticsEndpoints::new(self.client);
+        let news = NewsEndpoints::new(self.client);
+
+        let (earnings, dividends, splits, sec, ratings, articles) = futures::try_join!(
+            fetch(sources.earnings, async {
+                let calendar = calendar
+                    .earnings(Some(from), Some(to), Some(symbol))
+                    .await?;
+                Ok(calendar.earnings_calendar)
+            }),
+            fetch(
+                sources.dividends,
+                corporate_actions.dividends(symbol, from, to)
+            ),
+            fetch(sources.splits, corporate_actions.splits(symbol, from, to)),
+            fetch(
+                sources.filings,
+                filings.sec(Some(symbol), None, None, None, Some(from), Some(to))
+            ),
+            fetch(
+                sources.rating_changes,
+                analytics.upgrade_downgrade(Some(symbol), Some(from), Some(to))
+            ),
+            fetch(sources.news, news.company_news(symbol, from, to)),
+        )?;
+
+        let records = earnings
+            .into_iter()
+            .map(CompanyEventKind::Earnings)
+            .chain(dividends.into_iter().map(CompanyEventKind::Dividend))
+            .chain(splits.into_iter().map(CompanyEventKind::Split))
+            .chain(sec.into_iter().map(CompanyEventKind::Filing))
+            .chain(ratings.into_iter().map(CompanyEventKind::RatingChange))
+            .chain(articles.into_iter().map(CompanyEventKind::News));
+
+        Ok(CompanyTimeline::new(records, &range))
+    }
+}
+
+/// Await `request` if the source is enabled. Requests are boxed to keep the
+/// joined future small.
+fn fetch<T>(
+    enabled: bool,
+    request: impl Future<Output = Result<Vec<T>>>,
+) -> impl Future<Output = Result<Vec<T>>> {
+    let request = enabled.then(|| Box::pin(request));
+    async move {
+        match request {
+            Some(request) => request.await,
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientConfig, FinnhubClient, RateLimitStrategy};
+
+    async fn test_client() -> FinnhubClient {
+        dotenv::dotenv().ok();
+        let api_key = std::env::var("FINNHUB_API_KEY").unwrap_or_else(|_| "test_key".to_string());
+
+        let mut config = ClientConfig::default();
+        config.rate_limit_strategy = RateLimitStrategy::FifteenSecondWindow;
+        FinnhubClient::with_config(api_key, config)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires API key"]
+    async fn test_timeline() {
+        let client = test_client().await;
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let result = client
+            .stock()
+            .timeline("AAPL", from..=to, TimelineSources::default())
+            .await;
+        assert!(result.is_ok(), "Failed to get timeline: {:?}", result.err());
+
+        let timeline = result.unwrap();
+        assert!(timeline
+            .events
+            .windows(2)
+            .all(|pair| pair[0].time <= pair[1].time));
+    }
+}
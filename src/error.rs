@@ -9,6 +9,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug, Error)]
 pub enum Error {
     /// HTTP request failed.
+    #[cfg(feature = "client")]
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
@@ -32,10 +33,34 @@ pub enum Error {
         message: String,
     },
 
+    /// The API answered with something other than JSON, such as an HTML error
+    /// page from a proxy or an empty body.
+    #[error("Unexpected content type {content_type:?} (status {status}): {snippet}")]
+    UnexpectedContentType {
+        /// HTTP status code.
+        status: u16,
+        /// Value of the `Content-Type` header, if any.
+        content_type: Option<String>,
+        /// Start of the response body, whitespace collapsed.
+        snippet: String,
+    },
+
+    /// A response body was larger than `ClientConfig::max_response_bytes`,
+    /// on the wire or decoded; the rest of it was not read.
+    #[error("Response body exceeds the limit of {limit} bytes")]
+    ResponseTooLarge {
+        /// The configured limit, in bytes.
+        limit: u64,
+    },
+
     /// Failed to deserialize response.
     #[error("Deserialization error: {0}")]
     Deserialization(#[from] serde_json::Error),
 
+    /// Response data failed a sanity check, e.g. a candle with high below low.
+    #[error("Data invariant violation: {0}")]
+    DataInvariantViolation(String),
+
     /// Invalid parameter provided.
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
@@ -44,15 +69,30 @@ pub enum Error {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// A client configuration setting is invalid.
+    #[error("Invalid configuration: {field}: {message}")]
+    InvalidConfig {
+        /// Name of the `ClientConfig` field, e.g. `timeout_secs`, or of the
+        /// reloaded setting, e.g. `api_key`.
+        field: &'static str,
+        /// What is wrong with it.
+        message: String,
+    },
+
     /// WebSocket error.
     #[cfg(feature = "websocket")]
     #[error("WebSocket error: {0}")]
     WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
 
     /// URL parsing error.
+    #[cfg(feature = "client")]
     #[error("URL parse error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    /// A state store or export sink failed to read or write.
+    #[error("Storage error: {0}")]
+    Storage(String),
+
     /// Timeout error.
     #[error("Request timeout")]
     Timeout,
@@ -60,6 +100,28 @@ pub enum Error {
     /// Generic error for unexpected cases.
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// A request to an endpoint failed; `source` is the underlying error.
+    ///
+    /// Every error returned by an endpoint method is wrapped in this variant.
+    /// Use [`Error::root`] to match on the underlying error.
+    #[error("{}: {source}", endpoint_label(name, symbol.as_deref()))]
+    Endpoint {
+        /// Endpoint path, e.g. `/stock/profile2`.
+        name: String,
+        /// The `symbol` query parameter of the request, if it had one.
+        symbol: Option<String>,
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+fn endpoint_label(name: &str, symbol: Option<&str>) -> String {
+    match symbol {
+        Some(symbol) => format!("{name} ({symbol})"),
+        None => name.to_string(),
+    }
 }
 
 impl Error {
@@ -68,22 +130,90 @@ impl Error {
         Self::InvalidParameter(param.into())
     }
 
+    /// Create a new invalid configuration error.
+    pub fn invalid_config(field: &'static str, message: impl Into<String>) -> Self {
+        Self::InvalidConfig {
+            field,
+            message: message.into(),
+        }
+    }
+
     /// Create a new internal error.
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal(message.into())
     }
 
+    /// Attach the endpoint and symbol of a request, given as a path and
+    /// query such as `/quote?symbol=AAPL`. Errors that already carry an
+    /// endpoint are returned unchanged.
+    #[cfg(feature = "client")]
+    #[must_use]
+    pub(crate) fn at_endpoint(self, endpoint: &str) -> Self {
+        if matches!(self, Self::Endpoint { .. }) {
+            return self;
+        }
+        let (name, query) = endpoint.split_once('?').unwrap_or((endpoint, ""));
+        let symbol = url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "symbol")
+            .map(|(_, symbol)| symbol.into_owned());
+        Self::Endpoint {
+            name: name.to_string(),
+            symbol,
+            source: Box::new(self),
+        }
+    }
+
+    /// The underlying error, without endpoint context.
+    #[must_use]
+    pub fn root(&self) -> &Error {
+        match self {
+            Self::Endpoint { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+
+    /// Consume the error and return the underlying error, without endpoint
+    /// context.
+    #[must_use]
+    pub fn into_root(self) -> Error {
+        match self {
+            Self::Endpoint { source, .. } => source.into_root(),
+            _ => self,
+        }
+    }
+
+    /// Endpoint path of the failed request, if known.
+    #[must_use]
+    pub fn endpoint(&self) -> Option<&str> {
+        match self {
+            Self::Endpoint { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Symbol of the failed request, if it had one.
+    #[must_use]
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            Self::Endpoint { symbol, .. } => symbol.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Check if this error is retryable.
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            Self::RateLimitExceeded { .. } | Self::Timeout | Self::Http(_)
-        )
+        match self.root() {
+            Self::RateLimitExceeded { .. } | Self::Timeout => true,
+            Self::UnexpectedContentType { status, .. } => *status >= 500,
+            #[cfg(feature = "client")]
+            Self::Http(_) => true,
+            _ => false,
+        }
     }
 
     /// Get the retry delay in seconds if applicable.
     pub fn retry_after(&self) -> Option<u64> {
-        match self {
+        match self.root() {
             Self::RateLimitExceeded { retry_after } => Some(*retry_after),
             Self::Timeout => Some(5), // Default retry after 5 seconds for timeout
             _ => None,
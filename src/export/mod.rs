@@ -0,0 +1,201 @@
+//! Columnar export of tick data.
+//!
+//! Historical ticks from [`tick_stream`](crate::endpoints::stock::StockEndpoints::tick_stream)
+//! and WebSocket trades share one row shape, [`TickRecord`], which converts
+//! into Arrow record batches with [`to_record_batch`]. With the `parquet`
+//! feature, [`ParquetSink`] writes them to Parquet files partitioned by symbol
+//! and date.
+
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, Float64Array, ListBuilder, RecordBatch, StringArray, StringBuilder,
+    TimestampMillisecondArray,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, NaiveDate};
+
+use crate::error::{Error, Result};
+use crate::models::stock::TickData;
+
+#[cfg(feature = "parquet")]
+mod sink;
+
+#[cfg(feature = "parquet")]
+pub use sink::{ExportSummary, ParquetSink, ParquetSinkConfig};
+
+/// One trade, as written by the exporters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickRecord {
+    /// Symbol.
+    pub symbol: String,
+    /// UNIX timestamp in ms.
+    pub timestamp: i64,
+    /// Price.
+    pub price: f64,
+    /// Volume.
+    pub volume: f64,
+    /// Venue, if reported.
+    pub exchange: Option<String>,
+    /// Trade conditions, if reported.
+    pub conditions: Option<Vec<String>>,
+}
+
+impl TickRecord {
+    /// UTC trading date of the tick, used for partitioning.
+    #[must_use]
+    pub fn date(&self) -> Option<NaiveDate> {
+        DateTime::from_timestamp_millis(self.timestamp).map(|t| t.date_naive())
+    }
+}
+
+/// Types that can be flattened into [`TickRecord`]s.
+pub trait IntoTickRecords {
+    /// Convert into tick rows.
+    fn into_tick_records(self) -> Vec<TickRecord>;
+}
+
+impl IntoTickRecords for TickRecord {
+    fn into_tick_records(self) -> Vec<TickRecord> {
+        vec![self]
+    }
+}
+
+impl IntoTickRecords for Vec<TickRecord> {
+    fn into_tick_records(self) -> Vec<TickRecord> {
+        self
+    }
+}
+
+impl IntoTickRecords for TickData {
+    fn into_tick_records(self) -> Vec<TickRecord> {
+        self.ticks()
+            .map(|tick| TickRecord {
+                symbol: self.symbol.clone(),
+                timestamp: tick.timestamp,
+                price: tick.price,
+                volume: tick.volume.as_f64(),
+                exchange: tick.exchange.map(str::to_string),
+                conditions: tick.conditions.map(<[String]>::to_vec),
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl IntoTickRecords for crate::websocket::TradeData {
+    fn into_tick_records(self) -> Vec<TickRecord> {
+        vec![TickRecord {
+            symbol: self.symbol,
+            timestamp: self.timestamp,
+            price: self.price,
+            volume: self.volume,
+            exchange: None,
+            conditions: self.conditions,
+        }]
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl IntoTickRecords for crate::websocket::WebSocketMessage {
+    /// Trades in the message; other messages yield nothing.
+    fn into_tick_records(self) -> Vec<TickRecord> {
+        match self {
+            Self::Trade { data } => data
+                .into_iter()
+                .flat_map(IntoTickRecords::into_tick_records)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Arrow schema of [`to_record_batch`] output.
+#[must_use]
+pub fn tick_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("symbol", DataType::Utf8, false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("price", DataType::Float64, false),
+        Field::new("volume", DataType::Float64, false),
+        Field::new("exchange", DataType::Utf8, true),
+        Field::new(
+            "conditions",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ]))
+}
+
+/// Convert ticks into an Arrow record batch with [`tick_schema`].
+///
+/// # Errors
+///
+/// Returns [`Error::Storage`] if Arrow rejects the batch.
+pub fn to_record_batch(records: &[TickRecord]) -> Result<RecordBatch> {
+    let mut conditions = ListBuilder::new(StringBuilder::new());
+    for record in records {
+        match &record.conditions {
+            Some(values) => {
+                for value in values {
+                    conditions.values().append_value(value);
+                }
+                conditions.append(true);
+            }
+            None => conditions.append(false),
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.symbol.as_str()),
+        )),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(records.iter().map(|r| r.timestamp))
+                .with_timezone("UTC"),
+        ),
+        Arc::new(Float64Array::from_iter_values(
+            records.iter().map(|r| r.price),
+        )),
+        Arc::new(Float64Array::from_iter_values(
+            records.iter().map(|r| r.volume),
+        )),
+        Arc::new(
+            records
+                .iter()
+                .map(|r| r.exchange.as_deref())
+                .collect::<StringArray>(),
+        ),
+        Arc::new(conditions.finish()),
+    ];
+
+    RecordBatch::try_new(tick_schema(), columns).map_err(|err| Error::Storage(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_data_to_record_batch() {
+        let ticks: TickData = serde_json::from_value(serde_json::json!({
+            "s": "AAPL", "skip": 0, "count": 2, "total": 2,
+            "v": [10.0, 5.0], "p": [185.1, 185.2],
+            "t": [1_704_207_600_000_i64, 1_704_207_600_500_i64],
+            "x": ["Q", "N"], "c": [["1"], ["1", "12"]],
+        }))
+        .unwrap();
+
+        let records = ticks.into_tick_records();
+        assert_eq!(records[1].conditions.as_deref().map(<[_]>::len), Some(2));
+        assert_eq!(records[0].date(), NaiveDate::from_ymd_opt(2024, 1, 2));
+
+        let batch = to_record_batch(&records).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema(), tick_schema());
+    }
+}
@@ -0,0 +1,321 @@
+//! Partitioned Parquet writer for tick records.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use futures::{Stream, StreamExt};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use super::{tick_schema, to_record_batch, IntoTickRecords, TickRecord};
+use crate::error::{Error, Result};
+use crate::persist::file_name;
+
+/// Buffering limits for [`ParquetSink`].
+#[derive(Debug, Clone)]
+pub struct ParquetSinkConfig {
+    /// Rows buffered per partition before they are written as a row group.
+    pub row_group_size: usize,
+    /// Partitions kept open at once. When another partition is needed the
+    /// least recently written one is flushed and its file closed; later rows
+    /// for it go to a new part file.
+    pub max_open_partitions: usize,
+}
+
+impl Default for ParquetSinkConfig {
+    fn default() -> Self {
+        Self {
+            row_group_size: 65_536,
+            max_open_partitions: 16,
+        }
+    }
+}
+
+/// Files written by a [`ParquetSink`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    /// Closed Parquet files, in the order they were finished.
+    pub files: Vec<PathBuf>,
+    /// Rows written across all files.
+    pub rows: u64,
+}
+
+/// Writes tick records to Parquet files laid out as
+/// `<root>/symbol=<SYMBOL>/date=<YYYY-MM-DD>/part-<N>.parquet`.
+///
+/// At most [`max_open_partitions`](ParquetSinkConfig::max_open_partitions)
+/// partitions hold buffered rows at a time, each at most
+/// [`row_group_size`](ParquetSinkConfig::row_group_size) rows, so memory stays
+/// bounded no matter how many symbols and days pass through. Existing part
+/// files are never overwritten.
+///
+/// Files are only valid after [`finish`](Self::finish); dropping the sink
+/// leaves the open ones without a footer. Writes are synchronous file I/O.
+///
+/// ```no_run
+/// use finnhub::export::{ParquetSink, ParquetSinkConfig};
+/// use finnhub::FinnhubClient;
+///
+/// # async fn run() -> finnhub::Result<()> {
+/// let client = FinnhubClient::new("your-api-key");
+/// let mut sink = ParquetSink::new("ticks", ParquetSinkConfig::default());
+/// sink.write_stream(client.stock().tick_stream("AAPL", "2024-01-02")).await?;
+/// let summary = sink.finish()?;
+/// println!("wrote {} rows to {} files", summary.rows, summary.files.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ParquetSink {
+    root: PathBuf,
+    config: ParquetSinkConfig,
+    partitions: HashMap<(String, NaiveDate), Partition>,
+    clock: u64,
+    summary: ExportSummary,
+}
+
+struct Partition {
+    buffer: Vec<TickRecord>,
+    writer: Option<(ArrowWriter<File>, PathBuf)>,
+    last_used: u64,
+}
+
+impl std::fmt::Debug for ParquetSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetSink")
+            .field("root", &self.root)
+            .field("config", &self.config)
+            .field("open_partitions", &self.partitions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ParquetSink {
+    /// Create a sink writing under `root`, which is created as needed.
+    pub fn new(root: impl Into<PathBuf>, config: ParquetSinkConfig) -> Self {
+        Self {
+            root: root.into(),
+            config,
+            partitions: HashMap::new(),
+            clock: 0,
+            summary: ExportSummary::default(),
+        }
+    }
+
+    /// Buffer ticks, writing row groups as partitions fill up.
+    ///
+    /// Records whose timestamp is out of range are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Storage`] if a file can't be created or written.
+    pub fn write(&mut self, ticks: impl IntoTickRecords) -> Result<()> {
+        for record in ticks.into_tick_records() {
+            let Some(date) = record.date() else {
+                tracing::warn!(
+                    symbol = %record.symbol,
+                    timestamp = record.timestamp,
+                    "skipping tick with invalid timestamp"
+                );
+                continue;
+            };
+            let key = (record.symbol.clone(), date);
+
+            if !self.partitions.contains_key(&key)
+                && self.partitions.len() >= self.config.max_open_partitions.max(1)
+            {
+                self.evict()?;
+            }
+
+            self.clock += 1;
+            let partition = self
+                .partitions
+                .entry(key.clone())
+                .or_insert_with(|| Partition {
+                    buffer: Vec::new(),
+                    writer: None,
+                    last_used: 0,
+                });
+            partition.last_used = self.clock;
+            partition.buffer.push(record);
+
+            if partition.buffer.len() >= self.config.row_group_size.max(1) {
+                flush(&self.root, &key, partition)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drain a stream such as
+    /// [`tick_stream`](crate::endpoints::stock::StockEndpoints::tick_stream)
+    /// into the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the stream or from writing.
+    pub async fn write_stream<S, T>(&mut self, stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<T>>,
+        T: IntoTickRecords,
+    {
+        futures::pin_mut!(stream);
+        while let Some(item) = stream.next().await {
+            self.write(item?)?;
+        }
+        Ok(())
+    }
+
+    /// Flush every partition, close all files and report what was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Storage`] if a file can't be written or closed.
+    pub fn finish(mut self) -> Result<ExportSummary> {
+        let mut keys: Vec<_> = self.partitions.keys().cloned().collect();
+        keys.sort();
+        for key in keys {
+            self.close(&key)?;
+        }
+        Ok(self.summary)
+    }
+
+    /// Close the least recently written partition.
+    fn evict(&mut self) -> Result<()> {
+        let oldest = self
+            .partitions
+            .iter()
+            .min_by_key(|(_, p)| p.last_used)
+            .map(|(key, _)| key.clone());
+        match oldest {
+            Some(key) => self.close(&key),
+            None => Ok(()),
+        }
+    }
+
+    fn close(&mut self, key: &(String, NaiveDate)) -> Result<()> {
+        let Some(mut partition) = self.partitions.remove(key) else {
+            return Ok(());
+        };
+        flush(&self.root, key, &mut partition)?;
+        if let Some((writer, path)) = partition.writer {
+            let metadata = writer.close().map_err(storage)?;
+            self.summary.rows += u64::try_from(metadata.num_rows).unwrap_or_default();
+            self.summary.files.push(path);
+        }
+        Ok(())
+    }
+}
+
+/// Write a partition's buffered rows as one row group, opening its file first
+/// if needed.
+fn flush(root: &Path, key: &(String, NaiveDate), partition: &mut Partition) -> Result<()> {
+    if partition.buffer.is_empty() {
+        return Ok(());
+    }
+    let batch = to_record_batch(&partition.buffer)?;
+
+    let (writer, _) = match &mut partition.writer {
+        Some(open) => open,
+        None => partition.writer.insert(open(root, key)?),
+    };
+    writer.write(&batch).map_err(storage)?;
+    writer.flush().map_err(storage)?;
+
+    partition.buffer.clear();
+    Ok(())
+}
+
+/// Create the next unused part file for a partition.
+fn open(root: &Path, (symbol, date): &(String, NaiveDate)) -> Result<(ArrowWriter<File>, PathBuf)> {
+    let dir = root
+        .join(format!("symbol={}", file_name(symbol)))
+        .join(format!("date={date}"));
+    std::fs::create_dir_all(&dir).map_err(storage)?;
+
+    let mut part = 0;
+    let path = loop {
+        let path = dir.join(format!("part-{part:05}.parquet"));
+        if !path.exists() {
+            break path;
+        }
+        part += 1;
+    };
+    let file = File::create_new(&path).map_err(storage)?;
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let writer = ArrowWriter::try_new(file, tick_schema(), Some(props)).map_err(storage)?;
+    Ok((writer, path))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn storage(err: impl std::fmt::Display) -> Error {
+    Error::Storage(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn tick(symbol: &str, timestamp: i64) -> TickRecord {
+        TickRecord {
+            symbol: symbol.to_string(),
+            timestamp,
+            price: 100.0,
+            volume: 1.0,
+            exchange: Some("Q".to_string()),
+            conditions: None,
+        }
+    }
+
+    #[test]
+    fn test_partitions_and_bounded_buffers() {
+        let root = std::env::temp_dir().join(format!("finnhub-parquet-{}", std::process::id()));
+        let config = ParquetSinkConfig {
+            row_group_size: 2,
+            max_open_partitions: 1,
+        };
+        let mut sink = ParquetSink::new(&root, config);
+
+        let day = 86_400_000;
+        sink.write(vec![
+            tick("AAPL", 0),
+            tick("AAPL", 1),
+            tick("AAPL", 2),
+            tick("BINANCE:BTCUSDT", 0),
+            tick("AAPL", day),
+        ])
+        .unwrap();
+        let summary = sink.finish().unwrap();
+
+        assert_eq!(summary.rows, 5);
+        let names: Vec<_> = summary
+            .files
+            .iter()
+            .map(|p| {
+                p.strip_prefix(&root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "symbol=AAPL/date=1970-01-01/part-00000.parquet",
+                "symbol=BINANCE_BTCUSDT/date=1970-01-01/part-00000.parquet",
+                "symbol=AAPL/date=1970-01-02/part-00000.parquet",
+            ]
+        );
+
+        // Two row groups: one full flush and the remainder at eviction.
+        let reader = SerializedFileReader::new(File::open(&summary.files[0]).unwrap()).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 2);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,137 @@
+//! Failover between the primary base URL and its fallbacks.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use url::Url;
+
+use crate::rt::Instant;
+
+/// The base URLs of a client, primary first, and which one is in use.
+#[derive(Debug)]
+pub(crate) struct BaseUrls {
+    urls: Vec<Url>,
+    /// How long the active URL must keep failing to connect before the next
+    /// one takes over.
+    failover_after: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Index of the URL in use.
+    active: usize,
+    /// Start of the current run of connection errors on the active URL.
+    failing_since: Option<Instant>,
+}
+
+impl BaseUrls {
+    pub(crate) fn new(primary: Url, fallbacks: Vec<Url>, failover_after: Duration) -> Self {
+        let mut urls = vec![primary];
+        urls.extend(fallbacks);
+        Self {
+            urls,
+            failover_after,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Whether these were built from `primary`, `fallbacks` and
+    /// `failover_after`, so derived clients can share the failover state.
+    pub(crate) fn same_as(
+        &self,
+        primary: &Url,
+        fallbacks: &[Url],
+        failover_after: Duration,
+    ) -> bool {
+        self.urls[0] == *primary
+            && self.urls[1..] == *fallbacks
+            && self.failover_after == failover_after
+    }
+
+    /// Number of base URLs, primary included.
+    pub(crate) fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    /// Index and URL of the base URL to send the next request to.
+    pub(crate) fn active(&self) -> (usize, &Url) {
+        let active = self.lock().active;
+        (active, &self.urls[active])
+    }
+
+    /// Record that the URL at `index` answered.
+    pub(crate) fn reachable(&self, index: usize) {
+        let mut state = self.lock();
+        if state.active == index {
+            state.failing_since = None;
+        }
+    }
+
+    /// Record that the URL at `index` couldn't be connected to. Returns
+    /// whether the client switched to another URL, so the request should be
+    /// sent again.
+    pub(crate) fn unreachable(&self, index: usize) -> bool {
+        let mut state = self.lock();
+        if state.active != index {
+            // Another request already failed over.
+            return true;
+        }
+        if self.urls.len() == 1 {
+            return false;
+        }
+        let now = Instant::now();
+        let since = *state.failing_since.get_or_insert(now);
+        if now.duration_since(since) < self.failover_after {
+            return false;
+        }
+
+        state.active = (index + 1) % self.urls.len();
+        state.failing_since = None;
+        tracing::warn!(
+            from = %self.urls[index],
+            to = %self.urls[state.active],
+            "base URL unreachable, failing over"
+        );
+        true
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(failover_after: Duration) -> BaseUrls {
+        BaseUrls::new(
+            Url::parse("https://primary.example/api/v1").unwrap(),
+            vec![Url::parse("https://backup.example/api/v1").unwrap()],
+            failover_after,
+        )
+    }
+
+    #[test]
+    fn test_fails_over_after_period() {
+        let urls = urls(Duration::from_millis(50));
+        assert!(!urls.unreachable(0));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(urls.unreachable(0));
+        assert_eq!(urls.active().1.host_str(), Some("backup.example"));
+
+        // A request still in flight to the old URL retries on the new one.
+        assert!(urls.unreachable(0));
+        assert_eq!(urls.active().0, 1);
+    }
+
+    #[test]
+    fn test_success_resets_failure_run() {
+        let urls = urls(Duration::from_millis(50));
+        assert!(!urls.unreachable(0));
+        std::thread::sleep(Duration::from_millis(60));
+        urls.reachable(0);
+        assert!(!urls.unreachable(0));
+        assert_eq!(urls.active().0, 0);
+    }
+}
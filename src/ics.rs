@@ -0,0 +1,326 @@
+//! iCalendar (RFC 5545) export of economic and earnings calendars.
+//!
+//! Turns calendar responses into `.ics` files that calendar apps and
+//! scheduling tools can subscribe to or import.
+//!
+//! ```
+//! use finnhub::ics::IcsCalendar;
+//! use finnhub::models::calendar::{EconomicCalendar, EconomicEvent};
+//!
+//! let calendar = EconomicCalendar {
+//!     economic_calendar: vec![EconomicEvent {
+//!         actual: None,
+//!         prev: Some(3.1),
+//!         country: Some("US".to_string()),
+//!         unit: Some("%".to_string()),
+//!         estimate: Some(3.2),
+//!         event: Some("CPI YoY".to_string()),
+//!         impact: Some("high".to_string()),
+//!         time: Some("2024-01-11 13:30:00".to_string()),
+//!     }],
+//! };
+//!
+//! let ics = IcsCalendar::new().with_economic(&calendar).to_string();
+//! assert!(ics.contains("SUMMARY:US CPI YoY\r\n"));
+//! assert!(ics.contains("DTSTART:20240111T133000Z\r\n"));
+//! ```
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::fmt::{self, Write};
+
+use crate::models::{
+    calendar::{EarningsCalendar, EarningsRelease, EconomicCalendar, EconomicEvent, ReportingTime},
+    time_series::parse_date,
+};
+
+/// Longest content line allowed by RFC 5545, in octets, before folding.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// An iCalendar document built from calendar responses.
+///
+/// Economic events become timed events at their release time (UTC).
+/// Earnings releases become all-day events, since the API only reports
+/// whether they fall before the open, after the close or during market hours.
+/// Entries without a usable date are left out. `Display` renders the
+/// document with CRLF line endings.
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    name: Option<String>,
+    stamp: DateTime<Utc>,
+    events: Vec<IcsEvent>,
+}
+
+#[derive(Debug, Clone)]
+struct IcsEvent {
+    uid: String,
+    start: Start,
+    summary: String,
+    description: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Start {
+    Time(DateTime<Utc>),
+    Date(chrono::NaiveDate),
+}
+
+impl Default for IcsCalendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IcsCalendar {
+    /// Create an empty calendar stamped with the current time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            stamp: Utc::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Set the calendar's display name (`X-WR-CALNAME`).
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the `DTSTAMP` written on every event, for reproducible output.
+    #[must_use]
+    pub fn with_stamp(mut self, stamp: DateTime<Utc>) -> Self {
+        self.stamp = stamp;
+        self
+    }
+
+    /// Add the events of an economic calendar.
+    #[must_use]
+    pub fn with_economic(mut self, calendar: &EconomicCalendar) -> Self {
+        self.events
+            .extend(calendar.economic_calendar.iter().filter_map(economic_event));
+        self
+    }
+
+    /// Add the releases of an earnings calendar.
+    #[must_use]
+    pub fn with_earnings(mut self, calendar: &EarningsCalendar) -> Self {
+        self.events
+            .extend(calendar.earnings_calendar.iter().filter_map(earnings_event));
+        self
+    }
+
+    /// Number of events in the calendar.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether the calendar has no events.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+fn economic_event(event: &EconomicEvent) -> Option<IcsEvent> {
+    let time = event.time.as_deref()?;
+    let start = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S")
+        .ok()?
+        .and_utc();
+    let name = event.event.as_deref().unwrap_or("Economic release");
+    let summary = match event.country.as_deref() {
+        Some(country) => format!("{country} {name}"),
+        None => name.to_string(),
+    };
+
+    let unit = event.unit.as_deref().unwrap_or_default();
+    let mut description = Vec::new();
+    if let Some(impact) = &event.impact {
+        description.push(format!("Impact: {impact}"));
+    }
+    for (label, value) in [
+        ("Estimate", event.estimate),
+        ("Previous", event.prev),
+        ("Actual", event.actual),
+    ] {
+        if let Some(value) = value {
+            description.push(format!("{label}: {value}{unit}"));
+        }
+    }
+
+    Some(IcsEvent {
+        uid: uid(
+            "economic",
+            &format!("{}-{summary}", start.format("%Y%m%dT%H%M%SZ")),
+        ),
+        start: Start::Time(start),
+        summary,
+        description: description.join("\n"),
+    })
+}
+
+fn earnings_event(release: &EarningsRelease) -> Option<IcsEvent> {
+    let date = parse_date(release.date.as_deref()?)?;
+    let symbol = release.symbol.as_deref().unwrap_or("Unknown");
+    let mut summary = format!("{symbol} earnings");
+    if let (Some(quarter), Some(year)) = (release.quarter, release.year) {
+        summary = format!("{symbol} Q{quarter} {year} earnings");
+    }
+    match release.hour {
+        Some(ReportingTime::BeforeOpen) => summary.push_str(" (before open)"),
+        Some(ReportingTime::AfterClose) => summary.push_str(" (after close)"),
+        Some(ReportingTime::DuringMarketHours) => summary.push_str(" (during market hours)"),
+        _ => {}
+    }
+
+    let mut description = Vec::new();
+    for (label, estimate, actual) in [
+        ("EPS", release.eps_estimate, release.eps_actual),
+        ("Revenue", release.revenue_estimate, release.revenue_actual),
+    ] {
+        if let Some(estimate) = estimate {
+            description.push(format!("{label} estimate: {estimate}"));
+        }
+        if let Some(actual) = actual {
+            description.push(format!("{label} actual: {actual}"));
+        }
+    }
+
+    Some(IcsEvent {
+        uid: uid("earnings", &format!("{}-{symbol}", date.format("%Y%m%d"))),
+        start: Start::Date(date),
+        summary,
+        description: description.join("\n"),
+    })
+}
+
+/// Stable identifier, so re-imported events update instead of duplicating.
+fn uid(kind: &str, key: &str) -> String {
+    let key: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("{kind}-{key}@finnhub")
+}
+
+impl fmt::Display for IcsCalendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stamp = self.stamp.format("%Y%m%dT%H%M%SZ").to_string();
+        line(f, "BEGIN:VCALENDAR")?;
+        line(f, "VERSION:2.0")?;
+        line(f, "PRODID:-//finnhub-rs//calendar export//EN")?;
+        line(f, "CALSCALE:GREGORIAN")?;
+        if let Some(name) = &self.name {
+            line(f, &format!("X-WR-CALNAME:{}", escape(name)))?;
+        }
+        for event in &self.events {
+            line(f, "BEGIN:VEVENT")?;
+            line(f, &format!("UID:{}", event.uid))?;
+            line(f, &format!("DTSTAMP:{stamp}"))?;
+            match event.start {
+                Start::Time(time) => {
+                    line(f, &format!("DTSTART:{}", time.format("%Y%m%dT%H%M%SZ")))?;
+                }
+                Start::Date(date) => {
+                    line(f, &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")))?;
+                    if let Some(next) = date.succ_opt() {
+                        line(f, &format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")))?;
+                    }
+                }
+            }
+            line(f, &format!("SUMMARY:{}", escape(&event.summary)))?;
+            if !event.description.is_empty() {
+                line(f, &format!("DESCRIPTION:{}", escape(&event.description)))?;
+            }
+            line(f, "END:VEVENT")?;
+        }
+        line(f, "END:VCALENDAR")
+    }
+}
+
+/// Escape a TEXT value.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Write a content line, folding it at [`MAX_LINE_OCTETS`] without splitting
+/// a UTF-8 character.
+fn line(f: &mut fmt::Formatter<'_>, content: &str) -> fmt::Result {
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            f.write_str("\r\n ")?;
+            width = 1;
+        }
+        f.write_char(c)?;
+        width += c.len_utf8();
+    }
+    f.write_str("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_earnings_all_day_event_and_folding() {
+        let calendar = EarningsCalendar {
+            earnings_calendar: vec![
+                EarningsRelease {
+                    symbol: Some("AAPL".to_string()),
+                    date: Some("2024-02-01".to_string()),
+                    hour: Some(ReportingTime::AfterClose),
+                    year: Some(2024),
+                    quarter: Some(1),
+                    eps_estimate: Some(2.1),
+                    eps_actual: None,
+                    revenue_estimate: None,
+                    revenue_actual: None,
+                },
+                EarningsRelease {
+                    symbol: Some("MSFT".to_string()),
+                    date: None,
+                    hour: None,
+                    year: None,
+                    quarter: None,
+                    eps_estimate: None,
+                    eps_actual: None,
+                    revenue_estimate: None,
+                    revenue_actual: None,
+                },
+            ],
+        };
+        let stamp = DateTime::from_timestamp(0, 0).unwrap();
+        let ics = IcsCalendar::new()
+            .with_name(format!("Earnings, {}", "x".repeat(80)))
+            .with_stamp(stamp)
+            .with_earnings(&calendar);
+        assert_eq!(ics.len(), 1);
+
+        let text = ics.to_string();
+        assert!(text.contains("UID:earnings-20240201-AAPL@finnhub\r\n"));
+        assert!(text.contains("DTSTAMP:19700101T000000Z\r\n"));
+        assert!(text.contains("DTSTART;VALUE=DATE:20240201\r\nDTEND;VALUE=DATE:20240202\r\n"));
+        assert!(text.contains("SUMMARY:AAPL Q1 2024 earnings (after close)\r\n"));
+        assert!(text.contains("DESCRIPTION:EPS estimate: 2.1\r\n"));
+        assert!(text.contains("X-WR-CALNAME:Earnings\\, xxx"));
+        assert!(text
+            .lines()
+            .all(|l| l.trim_end_matches('\r').len() <= MAX_LINE_OCTETS));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+    }
+}
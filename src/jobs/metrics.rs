@@ -0,0 +1,362 @@
+//! Snapshots of basic financials for a whole universe of symbols.
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use super::MetricsSink;
+use crate::client::{FinnhubClient, RateLimitStrategy};
+use crate::error::{Error, Result};
+use crate::models::stock::SymbolFilter;
+use crate::partial::DEFAULT_CONCURRENCY;
+use crate::persist::{self, StateStore};
+
+/// Basic financials of one symbol, as written by a [`MetricsSnapshotJob`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// Symbol.
+    pub symbol: String,
+    /// When the metrics were fetched, in UNIX seconds.
+    pub fetched_at: i64,
+    /// The `metric` map of
+    /// [`BasicFinancials`](crate::models::stock::BasicFinancials); the
+    /// `series` are not kept.
+    pub metric: HashMap<String, serde_json::Value>,
+}
+
+/// Outcome of [`MetricsSnapshotJob::run`].
+#[derive(Debug, Default)]
+pub struct MetricsJobReport {
+    /// Symbols written in this run.
+    pub written: usize,
+    /// Symbols skipped because an interrupted earlier run already wrote them.
+    pub skipped: usize,
+    /// Symbols whose request failed, with their errors.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// Symbols a job covers.
+#[derive(Debug, Clone)]
+enum Universe {
+    Exchange {
+        exchange: String,
+        filter: SymbolFilter,
+    },
+    Symbols(Vec<String>),
+}
+
+/// Progress of a run, saved so an interrupted run can resume.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    /// Symbols of the run, resolved once so a resumed run covers the same
+    /// universe.
+    symbols: Vec<String>,
+    /// Symbols written and flushed to the sink.
+    done: BTreeSet<String>,
+    /// Whether the run wrote every symbol; the next run starts over.
+    complete: bool,
+}
+
+/// Fetches [`metrics`](crate::endpoints::StockEndpoints::metrics) for every
+/// symbol of an exchange or a list and writes them to a [`MetricsSink`].
+///
+/// Requests go through the client's rate limiter. A universe of thousands of
+/// symbols takes a while at 30 requests per second, so build the client with
+/// [`RateLimitStrategy::FifteenSecondWindow`] to use the full burst
+/// allowance. Failed symbols are reported and don't stop the run.
+///
+/// With [`with_checkpoint`](Self::with_checkpoint), progress is saved every
+/// [`checkpoint_every`](Self::with_checkpoint_every) symbols, after flushing
+/// the sink. Running the job again with the same key skips the symbols
+/// already written, including after a crash, and retries the ones that
+/// failed; once every symbol is written, the next run starts over. Include
+/// the date in the key to keep each night's snapshot separate. Snapshots
+/// written after the last checkpoint of a crashed run are written again on
+/// resume, so a sink may see a symbol twice.
+pub struct MetricsSnapshotJob {
+    client: FinnhubClient,
+    universe: Universe,
+    checkpoint: Option<(Arc<dyn StateStore>, String)>,
+    concurrency: usize,
+    checkpoint_every: usize,
+}
+
+impl fmt::Debug for MetricsSnapshotJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsSnapshotJob")
+            .field("universe", &self.universe)
+            .field("checkpoint", &self.checkpoint.as_ref().map(|(_, key)| key))
+            .field("concurrency", &self.concurrency)
+            .field("checkpoint_every", &self.checkpoint_every)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MetricsSnapshotJob {
+    /// Cover the symbols listed for `exchange`, e.g. `US`, that match
+    /// `filter`.
+    #[must_use]
+    pub fn for_exchange(
+        client: FinnhubClient,
+        exchange: impl Into<String>,
+        filter: SymbolFilter,
+    ) -> Self {
+        Self::new(
+            client,
+            Universe::Exchange {
+                exchange: exchange.into(),
+                filter,
+            },
+        )
+    }
+
+    /// Cover `symbols`. Duplicates are fetched once.
+    #[must_use]
+    pub fn for_symbols<I, S>(client: FinnhubClient, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(
+            client,
+            Universe::Symbols(symbols.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    fn new(client: FinnhubClient, universe: Universe) -> Self {
+        Self {
+            client,
+            universe,
+            checkpoint: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            checkpoint_every: 100,
+        }
+    }
+
+    /// Save progress under `key` in `store`, and resume from it.
+    #[must_use]
+    pub fn with_checkpoint(mut self, store: Arc<dyn StateStore>, key: impl Into<String>) -> Self {
+        self.checkpoint = Some((store, key.into()));
+        self
+    }
+
+    /// Maximum number of requests in flight; 8 by default.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Symbols written between checkpoints; 100 by default.
+    #[must_use]
+    pub fn with_checkpoint_every(mut self, symbols: usize) -> Self {
+        self.checkpoint_every = symbols;
+        self
+    }
+
+    /// Fetch and write the metrics of every symbol not written yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the exchange's symbol list request, of the
+    /// checkpoint store or of the sink. Errors of single symbols are
+    /// reported in [`MetricsJobReport::failed`] instead.
+    pub async fn run(&self, sink: &mut dyn MetricsSink) -> Result<MetricsJobReport> {
+        if self.client.config().effective_rate_limit_strategy()
+            != RateLimitStrategy::FifteenSecondWindow
+        {
+            tracing::info!("metrics job running without the 15-second window rate limit strategy");
+        }
+
+        let mut checkpoint = self.load_checkpoint().await?;
+        if checkpoint.symbols.is_empty() {
+            checkpoint.symbols = self.symbols().await?;
+            self.save_checkpoint(&checkpoint).await?;
+        }
+
+        let pending: Vec<String> = checkpoint
+            .symbols
+            .iter()
+            .filter(|symbol| !checkpoint.done.contains(*symbol))
+            .cloned()
+            .collect();
+        let mut report = MetricsJobReport {
+            skipped: checkpoint.symbols.len() - pending.len(),
+            ..MetricsJobReport::default()
+        };
+
+        let mut results = stream::iter(pending)
+            .map(|symbol| async move {
+                let result = self.client.stock().metrics(&symbol).await;
+                (symbol, result)
+            })
+            .buffer_unordered(self.concurrency.max(1));
+        let mut unsaved = 0;
+        while let Some((symbol, result)) = results.next().await {
+            match result {
+                Ok(financials) => {
+                    sink.write(&MetricsSnapshot {
+                        symbol: symbol.clone(),
+                        fetched_at: chrono::Utc::now().timestamp(),
+                        metric: financials.metric,
+                    })?;
+                    checkpoint.done.insert(symbol);
+                    report.written += 1;
+                    unsaved += 1;
+                    if unsaved >= self.checkpoint_every.max(1) {
+                        sink.flush()?;
+                        self.save_checkpoint(&checkpoint).await?;
+                        unsaved = 0;
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(%symbol, error = %err, "metrics request failed");
+                    report.failed.push((symbol, err));
+                }
+            }
+        }
+
+        sink.flush()?;
+        checkpoint.complete = report.failed.is_empty();
+        self.save_checkpoint(&checkpoint).await?;
+        Ok(report)
+    }
+
+    /// Symbols of the universe, sorted and without duplicates.
+    async fn symbols(&self) -> Result<Vec<String>> {
+        let mut symbols = match &self.universe {
+            Universe::Exchange { exchange, filter } => self
+                .client
+                .stock()
+                .symbols_filtered(exchange, filter)
+                .await?
+                .into_iter()
+                .map(|symbol| symbol.symbol)
+                .collect(),
+            Universe::Symbols(symbols) => symbols.clone(),
+        };
+        symbols.sort_unstable();
+        symbols.dedup();
+        Ok(symbols)
+    }
+
+    /// The checkpoint of an unfinished run, or an empty one.
+    async fn load_checkpoint(&self) -> Result<Checkpoint> {
+        let Some((store, key)) = &self.checkpoint else {
+            return Ok(Checkpoint::default());
+        };
+        match persist::load::<Checkpoint>(store.as_ref(), key).await? {
+            Some(checkpoint) if !checkpoint.complete => {
+                tracing::info!(
+                    key = %key,
+                    done = checkpoint.done.len(),
+                    total = checkpoint.symbols.len(),
+                    "resuming metrics job"
+                );
+                Ok(checkpoint)
+            }
+            _ => Ok(Checkpoint::default()),
+        }
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &Checkpoint) -> Result<()> {
+        match &self.checkpoint {
+            Some((store, key)) => persist::save(store.as_ref(), key, checkpoint).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock_client;
+    use crate::persist::MemoryStore;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Default)]
+    struct VecSink {
+        rows: Vec<MetricsSnapshot>,
+        flushes: usize,
+    }
+
+    impl MetricsSink for VecSink {
+        fn write(&mut self, snapshot: &MetricsSnapshot) -> Result<()> {
+            self.rows.push(snapshot.clone());
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    fn metrics(symbol: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "symbol": symbol,
+            "metric": { "peTTM": 28.5 },
+            "metricType": "all",
+            "series": {},
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_resumes_and_retries_failed_symbols() {
+        let server = MockServer::start().await;
+        for symbol in ["AAPL", "MSFT"] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/stock/metric"))
+                .and(query_param("symbol", symbol))
+                .respond_with(metrics(symbol))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/metric"))
+            .and(query_param("symbol", "NVDA"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/metric"))
+            .and(query_param("symbol", "NVDA"))
+            .respond_with(metrics("NVDA"))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+        let job = MetricsSnapshotJob::for_symbols(client, ["NVDA", "AAPL", "MSFT", "AAPL"])
+            .with_checkpoint(store.clone(), "metrics/test")
+            .with_checkpoint_every(1);
+
+        let mut sink = VecSink::default();
+        let report = job.run(&mut sink).await.unwrap();
+        assert_eq!(report.written, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "NVDA");
+        assert_eq!(sink.rows.len(), 2);
+        assert!(sink.flushes >= 3);
+
+        // The second run only retries the failed symbol.
+        let mut sink = VecSink::default();
+        let report = job.run(&mut sink).await.unwrap();
+        assert_eq!((report.written, report.skipped), (1, 2));
+        assert!(report.failed.is_empty());
+        assert_eq!(sink.rows[0].symbol, "NVDA");
+        assert_eq!(sink.rows[0].metric["peTTM"], serde_json::json!(28.5));
+
+        let checkpoint = persist::load::<Checkpoint>(store.as_ref(), "metrics/test")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(checkpoint.complete);
+        assert_eq!(checkpoint.done.len(), 3);
+    }
+}
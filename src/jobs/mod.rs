@@ -0,0 +1,39 @@
+//! Ready-made batch jobs for common bulk workloads.
+//!
+//! [`MetricsSnapshotJob`] fetches basic financials for every symbol of an
+//! exchange or a list, typically overnight, and writes them through a
+//! [`MetricsSink`]: [`JsonlSink`], [`CsvSink`], or with the `parquet` feature
+//! `MetricsParquetSink`. With a checkpoint store, an interrupted run resumes
+//! where it stopped:
+//!
+//! ```rust,no_run
+//! use finnhub::jobs::{JsonlSink, MetricsSnapshotJob};
+//! use finnhub::models::stock::SymbolFilter;
+//! use finnhub::persist::FileStore;
+//! use finnhub::{ClientConfig, FinnhubClient, RateLimitStrategy};
+//! use std::sync::Arc;
+//!
+//! # async fn run() -> finnhub::Result<()> {
+//! let config = ClientConfig {
+//!     rate_limit_strategy: RateLimitStrategy::FifteenSecondWindow,
+//!     ..ClientConfig::default()
+//! };
+//! let client = FinnhubClient::with_config("your-api-key", config);
+//!
+//! let mut sink = JsonlSink::append("metrics-2024-06-01.jsonl")?;
+//! let report = MetricsSnapshotJob::for_exchange(client, "US", SymbolFilter::default())
+//!     .with_checkpoint(Arc::new(FileStore::new("state")), "metrics/2024-06-01")
+//!     .run(&mut sink)
+//!     .await?;
+//! println!("{} written, {} failed", report.written, report.failed.len());
+//! # Ok(())
+//! # }
+//! ```
+
+mod metrics;
+mod sink;
+
+pub use metrics::{MetricsJobReport, MetricsSnapshot, MetricsSnapshotJob};
+#[cfg(feature = "parquet")]
+pub use sink::MetricsParquetSink;
+pub use sink::{CsvSink, JsonlSink, MetricsSink};
@@ -0,0 +1,336 @@
+//! Destinations for metrics snapshots.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::MetricsSnapshot;
+use crate::error::{Error, Result};
+
+/// Where a [`MetricsSnapshotJob`](super::MetricsSnapshotJob) writes its
+/// snapshots. Implement it to load them straight into a database.
+pub trait MetricsSink: Send {
+    /// Write or buffer one snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot can't be written; the job stops.
+    fn write(&mut self, snapshot: &MetricsSnapshot) -> Result<()>;
+
+    /// Make every snapshot written so far durable. Called before each
+    /// checkpoint and at the end of a run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if buffered snapshots can't be written; the job
+    /// stops without saving the checkpoint.
+    fn flush(&mut self) -> Result<()>;
+}
+
+/// Writes one JSON object per line, as serialized by [`MetricsSnapshot`].
+///
+/// Keeps every metric, numeric or not.
+#[derive(Debug)]
+pub struct JsonlSink {
+    writer: BufWriter<File>,
+}
+
+impl JsonlSink {
+    /// Append to the file at `path`, creating it if needed, so a resumed
+    /// run adds to what the interrupted one wrote.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Storage`] if the file can't be opened.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(open_append(path.as_ref())?),
+        })
+    }
+}
+
+impl MetricsSink for JsonlSink {
+    fn write(&mut self, snapshot: &MetricsSnapshot) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, snapshot)?;
+        self.writer.write_all(b"\n").map_err(storage)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(storage)?;
+        self.writer.get_ref().sync_data().map_err(storage)
+    }
+}
+
+/// Writes numeric metrics as CSV rows of `symbol,fetched_at,metric,value`,
+/// one row per metric.
+///
+/// The long layout doesn't depend on which metrics a symbol has. Metrics
+/// that aren't numbers, such as dates, are skipped; use [`JsonlSink`] to
+/// keep them.
+#[derive(Debug)]
+pub struct CsvSink {
+    writer: BufWriter<File>,
+}
+
+impl CsvSink {
+    /// Append to the file at `path`, creating it if needed. The header row
+    /// is written when the file is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Storage`] if the file can't be opened or written.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self> {
+        let file = open_append(path.as_ref())?;
+        let empty = file.metadata().map_err(storage)?.len() == 0;
+        let mut writer = BufWriter::new(file);
+        if empty {
+            writer
+                .write_all(b"symbol,fetched_at,metric,value\n")
+                .map_err(storage)?;
+        }
+        Ok(Self { writer })
+    }
+}
+
+impl MetricsSink for CsvSink {
+    fn write(&mut self, snapshot: &MetricsSnapshot) -> Result<()> {
+        for (metric, value) in numeric_metrics(snapshot) {
+            writeln!(
+                self.writer,
+                "{},{},{},{value}",
+                csv_field(&snapshot.symbol),
+                snapshot.fetched_at,
+                csv_field(metric),
+            )
+            .map_err(storage)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(storage)?;
+        self.writer.get_ref().sync_data().map_err(storage)
+    }
+}
+
+/// Numeric metrics of a snapshot, sorted by name.
+fn numeric_metrics(snapshot: &MetricsSnapshot) -> Vec<(&str, f64)> {
+    let mut metrics: Vec<_> = snapshot
+        .metric
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.as_f64()?)))
+        .collect();
+    metrics.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    metrics
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(storage)
+}
+
+fn storage(err: impl std::fmt::Display) -> Error {
+    Error::Storage(err.to_string())
+}
+
+#[cfg(feature = "parquet")]
+pub use parquet_sink::MetricsParquetSink;
+
+#[cfg(feature = "parquet")]
+mod parquet_sink {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, RecordBatch, StringArray, TimestampSecondArray};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+
+    use super::{numeric_metrics, storage, MetricsSink, MetricsSnapshot};
+    use crate::error::Result;
+
+    /// Writes numeric metrics to Parquet files with columns `symbol`,
+    /// `fetched_at`, `metric` and `value`, one row per metric.
+    ///
+    /// Each [`flush`](MetricsSink::flush) closes a file
+    /// `<root>/part-<N>.parquet`, so every checkpoint leaves only complete
+    /// files behind; existing files are never overwritten. As with
+    /// [`CsvSink`](super::CsvSink), metrics that aren't numbers are skipped.
+    #[derive(Debug)]
+    pub struct MetricsParquetSink {
+        root: PathBuf,
+        symbols: Vec<String>,
+        fetched_at: Vec<i64>,
+        metrics: Vec<String>,
+        values: Vec<f64>,
+        files: Vec<PathBuf>,
+    }
+
+    impl MetricsParquetSink {
+        /// Write files under `root`, which is created as needed.
+        pub fn new(root: impl Into<PathBuf>) -> Self {
+            Self {
+                root: root.into(),
+                symbols: Vec::new(),
+                fetched_at: Vec::new(),
+                metrics: Vec::new(),
+                values: Vec::new(),
+                files: Vec::new(),
+            }
+        }
+
+        /// Files written so far.
+        #[must_use]
+        pub fn files(&self) -> &[PathBuf] {
+            &self.files
+        }
+
+        fn schema() -> SchemaRef {
+            Arc::new(Schema::new(vec![
+                Field::new("symbol", DataType::Utf8, false),
+                Field::new(
+                    "fetched_at",
+                    DataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+                    false,
+                ),
+                Field::new("metric", DataType::Utf8, false),
+                Field::new("value", DataType::Float64, false),
+            ]))
+        }
+    }
+
+    impl MetricsSink for MetricsParquetSink {
+        fn write(&mut self, snapshot: &MetricsSnapshot) -> Result<()> {
+            for (metric, value) in numeric_metrics(snapshot) {
+                self.symbols.push(snapshot.symbol.clone());
+                self.fetched_at.push(snapshot.fetched_at);
+                self.metrics.push(metric.to_string());
+                self.values.push(value);
+            }
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            if self.values.is_empty() {
+                return Ok(());
+            }
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(StringArray::from(std::mem::take(&mut self.symbols))),
+                Arc::new(
+                    TimestampSecondArray::from(std::mem::take(&mut self.fetched_at))
+                        .with_timezone("UTC"),
+                ),
+                Arc::new(StringArray::from(std::mem::take(&mut self.metrics))),
+                Arc::new(Float64Array::from(std::mem::take(&mut self.values))),
+            ];
+            let batch = RecordBatch::try_new(Self::schema(), columns).map_err(storage)?;
+
+            std::fs::create_dir_all(&self.root).map_err(storage)?;
+            let mut part = self.files.len();
+            let path = loop {
+                let path = self.root.join(format!("part-{part:05}.parquet"));
+                if !path.exists() {
+                    break path;
+                }
+                part += 1;
+            };
+            let file = std::fs::File::create_new(&path).map_err(storage)?;
+            let props = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let mut writer =
+                ArrowWriter::try_new(file, Self::schema(), Some(props)).map_err(storage)?;
+            writer.write(&batch).map_err(storage)?;
+            writer.close().map_err(storage)?;
+            self.files.push(path);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn snapshot(symbol: &str) -> MetricsSnapshot {
+        MetricsSnapshot {
+            symbol: symbol.to_string(),
+            fetched_at: 1_717_200_000,
+            metric: HashMap::from([
+                ("peTTM".to_string(), serde_json::json!(28.5)),
+                ("beta".to_string(), serde_json::json!(1.2)),
+                (
+                    "52WeekHighDate".to_string(),
+                    serde_json::json!("2024-05-31"),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_file_sinks_append() {
+        let dir = std::env::temp_dir().join(format!("finnhub-metrics-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (csv, jsonl) = (dir.join("metrics.csv"), dir.join("metrics.jsonl"));
+
+        for symbol in ["AAPL", "BRK,B"] {
+            let mut sink = CsvSink::append(&csv).unwrap();
+            sink.write(&snapshot(symbol)).unwrap();
+            sink.flush().unwrap();
+            let mut sink = JsonlSink::append(&jsonl).unwrap();
+            sink.write(&snapshot(symbol)).unwrap();
+            sink.flush().unwrap();
+        }
+
+        assert_eq!(
+            std::fs::read_to_string(&csv).unwrap(),
+            "symbol,fetched_at,metric,value\n\
+             AAPL,1717200000,beta,1.2\n\
+             AAPL,1717200000,peTTM,28.5\n\
+             \"BRK,B\",1717200000,beta,1.2\n\
+             \"BRK,B\",1717200000,peTTM,28.5\n"
+        );
+        let lines: Vec<MetricsSnapshot> = std::fs::read_to_string(&jsonl)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, [snapshot("AAPL"), snapshot("BRK,B")]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_sink_closes_a_file_per_flush() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let root = std::env::temp_dir().join(format!("finnhub-metrics-pq-{}", std::process::id()));
+        let mut sink = MetricsParquetSink::new(&root);
+        sink.write(&snapshot("AAPL")).unwrap();
+        sink.flush().unwrap();
+        sink.flush().unwrap();
+        sink.write(&snapshot("MSFT")).unwrap();
+        sink.flush().unwrap();
+
+        assert_eq!(sink.files().len(), 2);
+        let reader = SerializedFileReader::new(File::open(&sink.files()[1]).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
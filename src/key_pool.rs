@@ -0,0 +1,284 @@
+//! Round-robin over the API keys of a client, each with its own rate limiter.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::Duration;
+
+use crate::auth::ApiKey;
+use crate::rate_limiter::{RateLimit, RateLimiter};
+use crate::rt::Instant;
+
+/// How long a rejected key is skipped before it is tried again, so a key
+/// restored on the server, or one hit by a transient rejection, comes back.
+const DEMOTION_PERIOD: Duration = Duration::from_secs(15 * 60);
+
+/// The API keys of a client, in the order given, and their rate limiters.
+///
+/// A client built with one key has a pool of one, so the single-key and
+/// multi-key paths are the same.
+pub(crate) struct KeyPool {
+    keys: Vec<PooledKey>,
+    /// Counter for the round-robin; taken modulo the number of candidates.
+    next: AtomicUsize,
+    /// How long a demoted key stays demoted.
+    demotion_period: Duration,
+}
+
+struct PooledKey {
+    /// Behind a lock so a reload can rotate the key of a single-key pool.
+    api_key: RwLock<ApiKey>,
+    /// The key's own limiter, or one shared with other clients.
+    limiter: Arc<dyn RateLimit>,
+    state: Mutex<KeyState>,
+}
+
+/// Why a key is skipped by [`KeyPool::pick`] while another one is usable.
+#[derive(Debug, Default)]
+struct KeyState {
+    /// When the server rejected the key; demoted keys are skipped for the
+    /// demotion period.
+    demoted_at: Option<Instant>,
+    /// End of a pause after a 429 response with the key.
+    paused_until: Option<Instant>,
+}
+
+impl PooledKey {
+    fn state(&self) -> MutexGuard<'_, KeyState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl KeyPool {
+    /// A pool of `api_keys`, each with a bucket of `capacity` tokens refilled
+    /// at `refill_rate` per second and waits jittered by up to `jitter`, or
+    /// all drawing from `shared` when given. `api_keys` must not be empty.
+    pub(crate) fn new(
+        api_keys: Vec<ApiKey>,
+        capacity: u32,
+        refill_rate: u32,
+        jitter: Duration,
+        shared: Option<&Arc<dyn RateLimit>>,
+    ) -> Self {
+        debug_assert!(!api_keys.is_empty());
+        Self {
+            keys: api_keys
+                .into_iter()
+                .map(|api_key| PooledKey {
+                    api_key: RwLock::new(api_key),
+                    limiter: shared.map_or_else(
+                        || -> Arc<dyn RateLimit> {
+                            Arc::new(RateLimiter::new(capacity, refill_rate).with_jitter(jitter))
+                        },
+                        Arc::clone,
+                    ),
+                    state: Mutex::default(),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            demotion_period: DEMOTION_PERIOD,
+        }
+    }
+
+    /// Number of keys.
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Index of the key to send the next request with: the next key in turn
+    /// that is neither demoted nor paused after a 429, else the next one that
+    /// isn't demoted, else the next of all keys.
+    pub(crate) fn pick(&self) -> usize {
+        let turn = self.next.fetch_add(1, Ordering::Relaxed);
+        if self.keys.len() == 1 {
+            return 0;
+        }
+        let now = Instant::now();
+        let mut ready = Vec::with_capacity(self.keys.len());
+        let mut paused = Vec::new();
+        for (index, key) in self.keys.iter().enumerate() {
+            let state = key.state();
+            if self.is_demoted(&state, now) {
+                continue;
+            }
+            if state.paused_until.is_some_and(|until| until > now) {
+                paused.push(index);
+            } else {
+                ready.push(index);
+            }
+        }
+        if !ready.is_empty() {
+            ready[turn % ready.len()]
+        } else if !paused.is_empty() {
+            paused[turn % paused.len()]
+        } else {
+            turn % self.keys.len()
+        }
+    }
+
+    /// The key at `index`.
+    pub(crate) fn api_key(&self, index: usize) -> ApiKey {
+        self.keys[index]
+            .api_key
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Rate limiter of the key at `index`.
+    pub(crate) fn limiter(&self, index: usize) -> &dyn RateLimit {
+        &*self.keys[index].limiter
+    }
+
+    /// Record that the server rejected the key at `index`. The key is
+    /// skipped for the demotion period, then tried again. A single key is
+    /// never demoted, since there is nothing to switch to.
+    pub(crate) fn demote(&self, index: usize) {
+        if self.keys.len() == 1 {
+            return;
+        }
+        let now = Instant::now();
+        let mut state = self.keys[index].state();
+        if !self.is_demoted(&state, now) {
+            tracing::warn!(key = index, "API key rejected; demoting it");
+        }
+        state.demoted_at = Some(now);
+    }
+
+    /// Pause the key at `index` for `duration` after a 429 response: its
+    /// limiter holds requests, and [`pick`](Self::pick) skips it while
+    /// another key is usable.
+    pub(crate) async fn pause_for(&self, index: usize, duration: Duration) {
+        self.keys[index].state().paused_until = Some(Instant::now() + duration);
+        self.keys[index].limiter.pause_for(duration).await;
+    }
+
+    /// Whether a request rejected with one key can be sent again with
+    /// another: there are several keys and one of them isn't demoted.
+    pub(crate) fn can_switch(&self) -> bool {
+        let now = Instant::now();
+        self.keys.len() > 1
+            && self
+                .keys
+                .iter()
+                .any(|key| !self.is_demoted(&key.state(), now))
+    }
+
+    /// Indexes of the demoted keys.
+    pub(crate) fn demoted(&self) -> Vec<usize> {
+        let now = Instant::now();
+        (0..self.keys.len())
+            .filter(|&index| self.is_demoted(&self.keys[index].state(), now))
+            .collect()
+    }
+
+    fn is_demoted(&self, state: &KeyState, now: Instant) -> bool {
+        state
+            .demoted_at
+            .is_some_and(|at| now.duration_since(at) < self.demotion_period)
+    }
+
+    /// Replace the key of a single-key pool. Returns whether it changed.
+    pub(crate) fn replace_single(&self, api_key: &str) -> bool {
+        debug_assert_eq!(self.keys.len(), 1);
+        let mut current = self.keys[0]
+            .api_key
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if current.expose() == api_key {
+            return false;
+        }
+        *current = ApiKey::from(api_key);
+        self.keys[0].state().demoted_at = None;
+        true
+    }
+
+    /// Change every key's limiter; see [`RateLimiter::set_rate`]. Returns
+    /// whether anything changed.
+    pub(crate) async fn set_rate(&self, capacity: u32, refill_rate: u32) -> bool {
+        let mut changed = false;
+        for key in &self.keys {
+            changed |= key.limiter.set_rate(capacity, refill_rate).await;
+        }
+        changed
+    }
+}
+
+impl fmt::Debug for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPool")
+            .field("keys", &self.keys.len())
+            .field("demoted", &self.demoted())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(keys: &[&str]) -> KeyPool {
+        KeyPool::new(
+            keys.iter().map(|&key| ApiKey::from(key)).collect(),
+            1,
+            1,
+            Duration::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_round_robin_skips_demoted_keys() {
+        let keys = pool(&["a", "b", "c"]);
+        let picks: Vec<usize> = (0..4).map(|_| keys.pick()).collect();
+        assert_eq!(picks, [0, 1, 2, 0]);
+
+        keys.demote(1);
+        assert!((0..4).all(|_| keys.pick() != 1));
+        assert_eq!(keys.demoted(), [1]);
+
+        // Once every key is demoted, all are used again.
+        keys.demote(0);
+        assert!(keys.can_switch());
+        keys.demote(2);
+        assert!(!keys.can_switch());
+        let mut picks: Vec<usize> = (0..3).map(|_| keys.pick()).collect();
+        picks.sort_unstable();
+        assert_eq!(picks, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_demoted_keys_come_back() {
+        let mut keys = pool(&["a", "b"]);
+        keys.demotion_period = Duration::from_millis(50);
+        keys.demote(0);
+        assert_eq!(keys.demoted(), [0]);
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(keys.demoted().is_empty());
+        let mut picks: Vec<usize> = (0..2).map(|_| keys.pick()).collect();
+        picks.sort_unstable();
+        assert_eq!(picks, [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_skips_paused_keys() {
+        let keys = pool(&["a", "b", "c"]);
+        keys.pause_for(1, Duration::from_secs(60)).await;
+        assert!((0..4).all(|_| keys.pick() != 1));
+
+        // A paused key is still used before a demoted one.
+        keys.demote(0);
+        keys.demote(2);
+        assert_eq!(keys.pick(), 1);
+    }
+
+    #[test]
+    fn test_single_key_is_never_demoted() {
+        let keys = pool(&["secret-key"]);
+        keys.demote(0);
+        assert!(keys.demoted().is_empty() && !keys.can_switch());
+        assert!(keys.replace_single("new-key"));
+        assert_eq!(keys.api_key(keys.pick()).expose(), "new-key");
+        assert!(!format!("{keys:?}").contains("new-key"));
+    }
+}
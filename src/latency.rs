@@ -0,0 +1,169 @@
+//! Real-time versus delayed data labelling.
+//!
+//! Finnhub serves real-time data for US listings but delayed or end-of-day
+//! data for some exchanges, depending on the subscription plan. The API
+//! doesn't say which a response is, so the client infers it from a
+//! [`LatencyPolicy`] describing the plan, set in `ClientConfig::latency`.
+//!
+//! ```
+//! use finnhub::latency::{DataLatency, LatencyPolicy};
+//!
+//! let policy = LatencyPolicy::default().with_exchange("TO", DataLatency::EndOfDay);
+//! assert_eq!(policy.for_symbol("AAPL"), DataLatency::RealTime);
+//! assert_eq!(policy.for_symbol("VOD.L"), DataLatency::Delayed(15));
+//! assert_eq!(policy.for_symbol("SHOP.TO"), DataLatency::EndOfDay);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::symbol::{self, Instrument, Vendor};
+
+/// How far behind the market a value may be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataLatency {
+    /// Real-time data.
+    RealTime,
+    /// Data delayed by this many minutes.
+    Delayed(u32),
+    /// Only updated after the close.
+    EndOfDay,
+}
+
+impl DataLatency {
+    /// Whether the data is real-time.
+    #[must_use]
+    pub fn is_real_time(self) -> bool {
+        self == Self::RealTime
+    }
+}
+
+impl fmt::Display for DataLatency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataLatency::RealTime => write!(f, "real-time"),
+            DataLatency::Delayed(minutes) => write!(f, "delayed {minutes} min"),
+            DataLatency::EndOfDay => write!(f, "end of day"),
+        }
+    }
+}
+
+/// Latency of the data available on a subscription plan.
+///
+/// Equities are looked up by exchange suffix (`L` in `VOD.L`), forex and
+/// crypto pairs by venue (`OANDA`, `BINANCE`). Anything without an override
+/// falls back to the per-asset-class defaults. [`LatencyPolicy::default`]
+/// assumes US listings, forex and crypto are real-time and other exchanges
+/// are delayed 15 minutes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyPolicy {
+    /// US listings (symbols without an exchange suffix).
+    pub us: DataLatency,
+    /// Listings on other exchanges.
+    pub international: DataLatency,
+    /// Forex pairs.
+    pub forex: DataLatency,
+    /// Crypto pairs.
+    pub crypto: DataLatency,
+    exchanges: HashMap<String, DataLatency>,
+}
+
+impl Default for LatencyPolicy {
+    fn default() -> Self {
+        Self {
+            us: DataLatency::RealTime,
+            international: DataLatency::Delayed(15),
+            forex: DataLatency::RealTime,
+            crypto: DataLatency::RealTime,
+            exchanges: HashMap::new(),
+        }
+    }
+}
+
+impl LatencyPolicy {
+    /// Use `latency` for an exchange suffix or venue, replacing any existing
+    /// entry.
+    #[must_use]
+    pub fn with_exchange(mut self, exchange: &str, latency: DataLatency) -> Self {
+        self.exchanges
+            .insert(exchange.to_ascii_uppercase(), latency);
+        self
+    }
+
+    /// Latency of data for a Finnhub symbol. Symbols that can't be parsed are
+    /// treated as international listings.
+    #[must_use]
+    pub fn for_symbol(&self, symbol: &str) -> DataLatency {
+        let Ok(parsed) = symbol::parse(symbol, Vendor::Finnhub) else {
+            return self.international;
+        };
+        let (exchange, fallback) = match &parsed.instrument {
+            Instrument::Equity { exchange: None, .. } => (None, self.us),
+            Instrument::Equity {
+                exchange: Some(exchange),
+                ..
+            } => (Some(exchange.as_str()), self.international),
+            Instrument::Forex { .. } => (parsed.venue.as_deref(), self.forex),
+            Instrument::Crypto { .. } => (parsed.venue.as_deref(), self.crypto),
+        };
+        exchange
+            .and_then(|exchange| self.exchanges.get(&exchange.to_ascii_uppercase()))
+            .copied()
+            .unwrap_or(fallback)
+    }
+}
+
+/// A value labelled with its [`DataLatency`].
+///
+/// Dereferences to the wrapped value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithLatency<T> {
+    /// The data.
+    pub data: T,
+    /// How delayed the data may be.
+    pub latency: DataLatency,
+}
+
+impl<T> WithLatency<T> {
+    /// Unwrap the data, dropping the label.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T> Deref for WithLatency<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_lookup() {
+        let policy = LatencyPolicy {
+            forex: DataLatency::Delayed(1),
+            ..LatencyPolicy::default()
+        }
+        .with_exchange("l", DataLatency::EndOfDay)
+        .with_exchange("BINANCE", DataLatency::Delayed(5));
+
+        assert_eq!(policy.for_symbol("BRK.B"), DataLatency::RealTime);
+        assert_eq!(policy.for_symbol("VOD.L"), DataLatency::EndOfDay);
+        assert_eq!(policy.for_symbol("SAP.DE"), DataLatency::Delayed(15));
+        assert_eq!(policy.for_symbol("OANDA:EUR_USD"), DataLatency::Delayed(1));
+        assert_eq!(
+            policy.for_symbol("BINANCE:BTCUSDT"),
+            DataLatency::Delayed(5)
+        );
+        assert_eq!(policy.for_symbol(""), DataLatency::Delayed(15));
+        assert_eq!(DataLatency::Delayed(15).to_string(), "delayed 15 min");
+    }
+}
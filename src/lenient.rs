@@ -0,0 +1,160 @@
+//! Error-tolerant deserialization of large arrays.
+//!
+//! A single malformed element normally fails a whole response. For bulk
+//! ingestion it is often better to keep the valid elements and record the rest.
+//! [`Lenient`] does that: it deserializes a JSON array element by element and
+//! collects the failures in [`Lenient::warnings`].
+//!
+//! ```
+//! use finnhub::lenient::Lenient;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Row {
+//!     id: u32,
+//! }
+//!
+//! let rows: Lenient<Row> = serde_json::from_str(r#"[{"id": 1}, {"id": "x"}, {"id": 3}]"#).unwrap();
+//! assert_eq!(rows.items.len(), 2);
+//! assert_eq!(rows.warnings[0].index, 1);
+//! ```
+//!
+//! Errors name the path of the field that failed, both in the warnings and
+//! from the strict [`from_slice`] the client decodes responses with, so a
+//! change in the API's output points straight at the affected field:
+//!
+//! ```
+//! #[derive(Debug, serde::Deserialize)]
+//! struct Candles {
+//!     c: Vec<f64>,
+//! }
+//!
+//! let err = finnhub::lenient::from_slice::<Candles>(br#"{"c": [1.5, null]}"#).unwrap_err();
+//! assert!(err.to_string().starts_with("c[1]: invalid type: null, expected f64"));
+//! ```
+
+use serde::de::{DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Deserialize `T` strictly from JSON, prefixing data errors with the path
+/// of the offending field, e.g. `financials[0].revenue: invalid type: ...`.
+///
+/// # Errors
+///
+/// Returns the `serde_json` error if `bytes` isn't valid JSON or doesn't
+/// match `T`.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> serde_json::Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(with_path)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Fold the path into data errors; syntax errors keep their position only.
+fn with_path(err: serde_path_to_error::Error<serde_json::Error>) -> serde_json::Error {
+    let path = err.path().to_string();
+    let err = err.into_inner();
+    if err.is_data() && path != "." {
+        serde::de::Error::custom(format!("{path}: {err}"))
+    } else {
+        err
+    }
+}
+
+/// An array element that failed to deserialize and was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedElement {
+    /// Position of the element in the original array.
+    pub index: usize,
+    /// Deserialization error message, starting with the path of the failing
+    /// field within the element.
+    pub message: String,
+}
+
+/// The valid elements of an array, plus a warning for each skipped element.
+#[derive(Debug, Clone)]
+pub struct Lenient<T> {
+    /// Elements that deserialized successfully, in their original order.
+    pub items: Vec<T>,
+    /// Elements that were skipped.
+    pub warnings: Vec<SkippedElement>,
+}
+
+impl<T> Lenient<T> {
+    /// Whether every element deserialized.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Discard the warnings.
+    #[must_use]
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Lenient<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(LenientVisitor(PhantomData))
+    }
+}
+
+struct LenientVisitor<T>(PhantomData<T>);
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for LenientVisitor<T> {
+    type Value = Lenient<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        let mut warnings = Vec::new();
+
+        // Buffer each element so a bad one can't desynchronize the array.
+        while let Some(value) = seq.next_element::<serde_json::Value>()? {
+            let index = items.len() + warnings.len();
+            match serde_path_to_error::deserialize(value) {
+                Ok(item) => items.push(item),
+                Err(err) => warnings.push(SkippedElement {
+                    index,
+                    message: with_path(err).to_string(),
+                }),
+            }
+        }
+
+        Ok(Lenient { items, warnings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        id: u32,
+    }
+
+    #[test]
+    fn test_skips_bad_elements() {
+        let rows: Lenient<Row> =
+            serde_json::from_str(r#"[{"id": 1}, null, {"id": -1}, {"id": 2}]"#).unwrap();
+        assert_eq!(rows.items, vec![Row { id: 1 }, Row { id: 2 }]);
+        assert_eq!(
+            rows.warnings.iter().map(|w| w.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(rows.warnings[1].message.starts_with("id: invalid value"));
+        assert!(!rows.is_complete());
+    }
+
+    #[test]
+    fn test_rejects_non_array() {
+        assert!(serde_json::from_str::<Lenient<Row>>(r#"{"id": 1}"#).is_err());
+    }
+}
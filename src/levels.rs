@@ -0,0 +1,192 @@
+//! Support/resistance history and confluence ranking.
+//!
+//! The support/resistance scanner only returns today's levels. A
+//! [`LevelHistory`] records each response in a
+//! [`crate::persist::StateStore`] so that levels recurring across days and
+//! resolutions can be ranked with [`crate::models::scanner::confluence`], e.g.
+//! for charting overlays.
+//!
+//! ```rust,no_run
+//! use finnhub::{levels::LevelHistory, persist::FileStore, FinnhubClient};
+//! use std::sync::Arc;
+//!
+//! # async fn run() -> finnhub::Result<()> {
+//! let client = FinnhubClient::new("your-api-key");
+//! let history = LevelHistory::new(Arc::new(FileStore::new("state")));
+//!
+//! // Once a day:
+//! history.fetch(&client, "AAPL", &["D", "W"]).await?;
+//!
+//! for level in history.ranked("AAPL", 0.005).await?.iter().take(5) {
+//!     println!("{:.2} strength {:.2} on {:?}", level.price, level.strength, level.resolutions);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{NaiveDate, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    models::scanner::{confluence, ConfluenceLevel, LevelSnapshot, SupportResistance},
+    persist::{self, StateStore},
+};
+
+/// Snapshots kept per symbol unless changed with
+/// [`LevelHistory::with_limit`]: about a year of daily fetches.
+const DEFAULT_LIMIT: usize = 250;
+
+/// Support/resistance snapshots per symbol, kept in a [`StateStore`] under
+/// `levels/<SYMBOL>`.
+pub struct LevelHistory {
+    store: Arc<dyn StateStore>,
+    limit: usize,
+}
+
+impl fmt::Debug for LevelHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LevelHistory")
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LevelHistory {
+    /// Keep history in `store`.
+    pub fn new(store: Arc<dyn StateStore>) -> Self {
+        Self {
+            store,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// Keep at most `limit` snapshots per symbol, dropping the oldest.
+    #[must_use]
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Fetch today's levels for each resolution and record them.
+    ///
+    /// # Errors
+    ///
+    /// Returns request or storage errors. Resolutions fetched before the
+    /// failure are not recorded.
+    pub async fn fetch(
+        &self,
+        client: &FinnhubClient,
+        symbol: &str,
+        resolutions: &[&str],
+    ) -> Result<()> {
+        let date = Utc::now().date_naive();
+        let mut snapshots = Vec::with_capacity(resolutions.len());
+        for &resolution in resolutions {
+            let response = client
+                .scanner()
+                .support_resistance(symbol, resolution)
+                .await?;
+            snapshots.push((resolution, response));
+        }
+        for (resolution, response) in snapshots {
+            self.record(symbol, date, resolution, &response).await?;
+        }
+        Ok(())
+    }
+
+    /// Record a response fetched on `date`, replacing any snapshot for the
+    /// same date and resolution.
+    ///
+    /// # Errors
+    ///
+    /// Returns storage errors.
+    pub async fn record(
+        &self,
+        symbol: &str,
+        date: NaiveDate,
+        resolution: &str,
+        response: &SupportResistance,
+    ) -> Result<()> {
+        let mut snapshots = self.snapshots(symbol).await?;
+        snapshots.retain(|s| s.date != date || s.resolution != resolution);
+        snapshots.push(LevelSnapshot {
+            date,
+            resolution: resolution.to_string(),
+            levels: response.levels.clone(),
+        });
+        snapshots.sort_by(|a, b| a.date.cmp(&b.date).then(a.resolution.cmp(&b.resolution)));
+        let excess = snapshots.len().saturating_sub(self.limit);
+        snapshots.drain(..excess);
+
+        persist::save(self.store.as_ref(), &key(symbol), &snapshots).await
+    }
+
+    /// Recorded snapshots for `symbol`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns storage errors.
+    pub async fn snapshots(&self, symbol: &str) -> Result<Vec<LevelSnapshot>> {
+        Ok(persist::load(self.store.as_ref(), &key(symbol))
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Recorded levels for `symbol` ranked by confluence; see
+    /// [`confluence`] for `tolerance`.
+    ///
+    /// # Errors
+    ///
+    /// Returns storage errors.
+    pub async fn ranked(&self, symbol: &str, tolerance: f64) -> Result<Vec<ConfluenceLevel>> {
+        Ok(confluence(&self.snapshots(symbol).await?, tolerance))
+    }
+}
+
+fn key(symbol: &str) -> String {
+    format!("levels/{symbol}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persist::MemoryStore;
+
+    #[tokio::test]
+    async fn test_record_replaces_and_limits() {
+        let history = LevelHistory::new(Arc::new(MemoryStore::new())).with_limit(2);
+        let d = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        let levels = |levels: &[f64]| SupportResistance {
+            levels: levels.to_vec(),
+        };
+
+        history
+            .record("AAPL", d(1), "D", &levels(&[100.0]))
+            .await
+            .unwrap();
+        history
+            .record("AAPL", d(2), "D", &levels(&[101.0]))
+            .await
+            .unwrap();
+        history
+            .record("AAPL", d(2), "D", &levels(&[100.2]))
+            .await
+            .unwrap();
+        history
+            .record("AAPL", d(3), "D", &levels(&[100.1]))
+            .await
+            .unwrap();
+
+        let snapshots = history.snapshots("AAPL").await.unwrap();
+        let dates: Vec<_> = snapshots.iter().map(|s| s.date).collect();
+        assert_eq!(dates, [d(2), d(3)]);
+        assert_eq!(snapshots[0].levels, [100.2]);
+
+        let ranked = history.ranked("AAPL", 0.005).await.unwrap();
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].snapshots, 2);
+    }
+}
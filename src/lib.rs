@@ -32,11 +32,36 @@
 //! }
 //! ```
 //!
+//! ## Cargo Features
+//!
+//! Asset-class endpoint groups and their models are behind features so that
+//! applications only compile what they use. The default `full` feature enables
+//! all of them.
+//!
+//! - `stock`, `forex`, `crypto`, `etf`, `bond`, `mutual-fund`, `economic`
+//! - `client`: HTTP client, rate limiter and endpoint groups (on by default)
+//! - `models-only`: every asset-class model without the transport stack; combine
+//!   with `default-features = false` to share the serde types with server or WASM
+//!   code without pulling in reqwest or tokio
+//! - `blocking`: synchronous `blocking::FinnhubClient` for code that isn't async (off by default)
+//! - `websocket`: real-time WebSocket client (off by default)
+//! - `socks`: SOCKS5 proxies in `ClientConfig::proxy` (off by default)
+//! - `metrics`: request and rate limiter metrics through the `metrics` facade (off by default)
+//! - `cache`: opt-in in-memory response cache with per-endpoint TTLs (off by default)
+//! - `arrow`: convert tick data into Arrow record batches (off by default)
+//! - `parquet`: write tick streams to partitioned Parquet files (off by default)
+//! - `ics`: export economic and earnings calendars as iCalendar files (off by default)
+//!
+//! News, calendar, index, scanner and miscellaneous endpoints are always available.
+//!
 //! ## Design Philosophy
 //!
 //! This library follows a minimalist design philosophy:
-//! - **No automatic retries**: Applications implement context-aware retry logic
-//! - **No response caching**: Applications manage cache based on their needs
+//! - **No automatic retries by default**: Applications implement context-aware retry logic, or opt
+//!   into a `RetryPolicy` on `ClientConfig` or `FinnhubClient::retry`, with a client-wide retry
+//!   budget
+//! - **No response caching by default**: Applications manage cache based on their needs, or opt
+//!   into the `cache` feature's per-endpoint TTL cache
 //! - **Flexible rate limiting**: Choose between strict per-second or burst-friendly strategies
 //!
 //! The library provides the tools (`is_retryable()`, `retry_after()`) but lets you decide how to use them.
@@ -46,22 +71,127 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+#[cfg(all(
+    target_arch = "wasm32",
+    any(feature = "websocket", feature = "blocking", feature = "parquet")
+))]
+compile_error!("the `websocket`, `blocking` and `parquet` features are not available on wasm32");
+
+#[cfg(all(
+    feature = "client",
+    any(feature = "stock", feature = "forex", feature = "crypto")
+))]
+pub mod analytics;
+#[cfg(feature = "client")]
 pub mod auth;
+#[cfg(feature = "client")]
+pub mod bandwidth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "client")]
+mod compression;
+pub mod coverage;
+#[cfg(feature = "client")]
 pub mod endpoints;
 pub mod error;
+#[cfg(feature = "arrow")]
+pub mod export;
+#[cfg(feature = "client")]
+mod failover;
+#[cfg(feature = "ics")]
+pub mod ics;
+#[cfg(all(feature = "client", feature = "stock"))]
+pub mod jobs;
+#[cfg(feature = "client")]
+mod key_pool;
+pub mod latency;
+pub mod lenient;
+#[cfg(feature = "client")]
+pub mod levels;
+pub mod limits;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod models;
+#[cfg(feature = "client")]
+pub mod partial;
+#[cfg(feature = "client")]
+pub mod persist;
+#[cfg(feature = "config-file")]
+pub mod profile;
+#[cfg(feature = "client")]
+pub mod proxy;
+#[cfg(feature = "client")]
 pub mod rate_limiter;
+#[cfg(feature = "client")]
+pub mod reload;
+#[cfg(feature = "client")]
+pub mod response;
+#[cfg(feature = "client")]
+pub mod retry;
+#[cfg(feature = "client")]
+mod rt;
+#[cfg(feature = "client")]
+pub mod service;
+pub mod symbol;
+pub mod toolkit;
+pub mod validation;
+#[cfg(feature = "client")]
+pub mod watch;
 
 #[cfg(feature = "websocket")]
 pub mod websocket;
 
-pub use client::{ClientConfig, FinnhubClient, RateLimitStrategy};
+#[cfg(feature = "client")]
+pub use client::{ClientConfig, ConnectionConfig, FinnhubClient, HttpVersion, RateLimitStrategy};
+pub use coverage::coverage;
 pub use error::{Error, Result};
-pub use rate_limiter::RateLimiter;
+#[cfg(feature = "client")]
+pub use rate_limiter::{RateLimit, RateLimiter};
+#[cfg(feature = "client")]
+pub use response::ResponseEnvelope;
 
-#[doc(hidden)]
+/// The types most programs need, for a single glob import.
+///
+/// ```rust,no_run
+/// use finnhub::prelude::*;
+///
+/// # async fn run() -> Result<()> {
+/// let client = FinnhubClient::new("your-api-key");
+/// let quote: Quote = client.stock().quote("AAPL").await?;
+/// let news: Vec<MarketNews> = client.news().market_news(NewsCategory::General, None).await?;
+/// let income = client
+///     .stock()
+///     .financials("AAPL", StatementType::IncomeStatement, StatementFrequency::Annual)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Items are added here in minor releases but only removed in major ones,
+/// so the glob import keeps compiling across upgrades. Models for an asset
+/// class are only included when its feature is enabled.
 pub mod prelude {
-    pub use crate::client::FinnhubClient;
+    #[cfg(feature = "client")]
+    pub use crate::client::{ClientConfig, FinnhubClient, RateLimitStrategy};
+    #[cfg(all(feature = "client", feature = "stock"))]
+    pub use crate::endpoints::stock::company::ProfilesOptions;
     pub use crate::error::{Error, Result};
+    pub use crate::models::calendar::{EarningsCalendar, EarningsFilter, EarningsRelease};
+    #[cfg(feature = "crypto")]
+    pub use crate::models::crypto::CryptoCandles;
+    #[cfg(feature = "forex")]
+    pub use crate::models::forex::ForexCandles;
+    pub use crate::models::news::{CompanyNews, MarketNews, NewsCategory};
+    pub use crate::models::stock::CandleResolution;
+    #[cfg(feature = "stock")]
+    pub use crate::models::stock::{
+        BasicFinancials, CompanyProfile, Quote, StatementFrequency, StatementType, StockCandles,
+        SymbolFilter,
+    };
+    #[cfg(feature = "client")]
+    pub use crate::partial::PartialResult;
 }
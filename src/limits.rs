@@ -0,0 +1,39 @@
+//! Documented Finnhub API limits.
+//!
+//! The crate validates requests and sizes its rate limiter presets with these
+//! values; applications can use them for the same purpose.
+
+/// Maximum sustained request rate for every plan, in requests per second.
+pub const RATE_LIMIT_PER_SECOND: u32 = 30;
+
+/// Maximum `limit` for tick endpoints (stock ticks, NBBO, bond ticks).
+pub const TICK_MAX_LIMIT: i64 = 25_000;
+
+/// Maximum number of documents returned by one international filings request.
+pub const INTERNATIONAL_FILINGS_MAX_DOCUMENTS: usize = 500;
+
+/// Check a tick endpoint `limit` against [`TICK_MAX_LIMIT`].
+#[cfg(feature = "client")]
+#[cfg_attr(not(any(feature = "stock", feature = "bond")), allow(dead_code))]
+pub(crate) fn check_tick_limit(limit: i64) -> crate::Result<()> {
+    if (1..=TICK_MAX_LIMIT).contains(&limit) {
+        Ok(())
+    } else {
+        Err(crate::Error::invalid_parameter(format!(
+            "limit must be between 1 and {TICK_MAX_LIMIT}, got {limit}"
+        )))
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_tick_limit() {
+        assert!(check_tick_limit(1).is_ok());
+        assert!(check_tick_limit(TICK_MAX_LIMIT).is_ok());
+        assert!(check_tick_limit(0).is_err());
+        assert!(check_tick_limit(TICK_MAX_LIMIT + 1).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Request metrics through the [`metrics`] facade.
+//!
+//! With the `metrics` feature the client records the metrics below into
+//! whichever recorder the application installs; nothing is recorded without
+//! one. `group` is the endpoint's [category](crate::coverage::Category),
+//! such as `stock` or `forex`, or `other` for paths the crate doesn't list.
+//! `tag` is the client's [`ClientConfig::tag`](crate::ClientConfig::tag),
+//! or empty when it has none, so usage can be attributed per team.
+//!
+//! | Name | Type | Labels | |
+//! |------|------|--------|---|
+//! | [`REQUESTS`] | counter | `group`, `method`, `tag` | HTTP requests sent, retries and failovers included |
+//! | [`ERRORS`] | counter | `group`, `kind`, `tag` | Failed calls, by [`error_kind`] |
+//! | [`RATE_LIMIT_WAIT`] | histogram | `group`, `tag` | Seconds spent waiting for the rate limiter |
+//! | [`IN_FLIGHT`] | gauge | | Requests waiting for their response |
+//!
+//! For Prometheus, install `metrics-exporter-prometheus` and call
+//! [`describe`] once to attach help texts:
+//!
+//! ```rust,ignore
+//! metrics_exporter_prometheus::PrometheusBuilder::new()
+//!     .with_http_listener(([0, 0, 0, 0], 9000))
+//!     .install()?;
+//! finnhub::metrics::describe();
+//! ```
+
+use std::time::Duration;
+
+use ::metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
+
+use crate::coverage;
+use crate::error::Error;
+
+/// Counter of HTTP requests sent.
+pub const REQUESTS: &str = "finnhub_requests_total";
+/// Counter of calls that failed.
+pub const ERRORS: &str = "finnhub_errors_total";
+/// Histogram of the time spent waiting for the rate limiter, in seconds.
+pub const RATE_LIMIT_WAIT: &str = "finnhub_rate_limit_wait_seconds";
+/// Gauge of requests sent and not yet answered.
+pub const IN_FLIGHT: &str = "finnhub_requests_in_flight";
+
+/// Register units and help texts for the client's metrics with the
+/// installed recorder.
+pub fn describe() {
+    describe_counter!(REQUESTS, "HTTP requests sent to the Finnhub API");
+    describe_counter!(ERRORS, "Finnhub API calls that failed, by error kind");
+    describe_histogram!(
+        RATE_LIMIT_WAIT,
+        ::metrics::Unit::Seconds,
+        "Time spent waiting for the client's rate limiter"
+    );
+    describe_gauge!(IN_FLIGHT, "Finnhub API requests waiting for a response");
+}
+
+/// The `kind` label [`ERRORS`] gives `error`: the snake-case name of its
+/// [root](Error::root) variant, e.g. `rate_limit_exceeded`.
+#[must_use]
+pub fn error_kind(error: &Error) -> &'static str {
+    match error.root() {
+        Error::Http(err) if err.is_timeout() => "timeout",
+        Error::Http(_) => "http",
+        Error::RateLimitExceeded { .. } => "rate_limit_exceeded",
+        Error::Unauthorized => "unauthorized",
+        Error::ApiError { .. } => "api_error",
+        Error::UnexpectedContentType { .. } => "unexpected_content_type",
+        Error::ResponseTooLarge { .. } => "response_too_large",
+        Error::Deserialization(_) => "deserialization",
+        Error::DataInvariantViolation(_) => "data_invariant_violation",
+        Error::InvalidParameter(_) => "invalid_parameter",
+        Error::InvalidRequest(_) => "invalid_request",
+        Error::InvalidConfig { .. } => "invalid_config",
+        #[cfg(feature = "websocket")]
+        Error::WebSocket(_) => "websocket",
+        Error::UrlParse(_) => "url_parse",
+        Error::Storage(_) => "storage",
+        Error::Timeout => "timeout",
+        Error::Internal(_) | Error::Endpoint { .. } => "internal",
+    }
+}
+
+/// The `group` label of a request to `endpoint`, a path and query.
+fn group(endpoint: &str) -> &'static str {
+    coverage::endpoint_info(endpoint).map_or("other", |info| info.category.name())
+}
+
+/// Count a request by a client tagged `tag` and track it as in flight until
+/// the guard is dropped.
+pub(crate) fn request(method: &'static str, endpoint: &str, tag: Option<&str>) -> InFlight {
+    counter!(
+        REQUESTS,
+        "group" => group(endpoint),
+        "method" => method,
+        "tag" => tag_label(tag),
+    )
+    .increment(1);
+    gauge!(IN_FLIGHT).increment(1.0);
+    InFlight
+}
+
+/// Count a failed call to `endpoint` by a client tagged `tag`.
+pub(crate) fn error(endpoint: &str, tag: Option<&str>, error: &Error) {
+    counter!(
+        ERRORS,
+        "group" => group(endpoint),
+        "kind" => error_kind(error),
+        "tag" => tag_label(tag),
+    )
+    .increment(1);
+}
+
+/// Record how long a request to `endpoint` by a client tagged `tag` waited
+/// for the rate limiter.
+pub(crate) fn rate_limit_wait(endpoint: &str, tag: Option<&str>, waited: Duration) {
+    histogram!(RATE_LIMIT_WAIT, "group" => group(endpoint), "tag" => tag_label(tag))
+        .record(waited.as_secs_f64());
+}
+
+/// The `tag` label: the tag, or empty for an untagged client.
+fn tag_label(tag: Option<&str>) -> String {
+    tag.unwrap_or_default().to_owned()
+}
+
+/// Decrements [`IN_FLIGHT`] when dropped.
+#[must_use]
+pub(crate) struct InFlight;
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        gauge!(IN_FLIGHT).decrement(1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::metrics::{
+        Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata,
+        Recorder, SharedString, Unit,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps every value recorded, with the metric's name and labels.
+    #[derive(Default)]
+    struct Log(Arc<Mutex<Vec<(String, f64)>>>);
+
+    struct Handle(String, Arc<Mutex<Vec<(String, f64)>>>);
+
+    impl Log {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            let labels: Vec<String> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            Arc::new(Handle(
+                format!("{}{{{}}}", key.name(), labels.join(",")),
+                self.0.clone(),
+            ))
+        }
+    }
+
+    impl Handle {
+        fn push(&self, value: f64) {
+            self.1.lock().unwrap().push((self.0.clone(), value));
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.push(value as f64);
+        }
+        fn absolute(&self, value: u64) {
+            self.push(value as f64);
+        }
+    }
+
+    impl GaugeFn for Handle {
+        fn increment(&self, value: f64) {
+            self.push(value);
+        }
+        fn decrement(&self, value: f64) {
+            self.push(-value);
+        }
+        fn set(&self, value: f64) {
+            self.push(value);
+        }
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, value: f64) {
+            self.push(value);
+        }
+    }
+
+    impl Recorder for Log {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.handle(key))
+        }
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.handle(key))
+        }
+    }
+
+    #[test]
+    fn test_metrics_recorded() {
+        let log = Log::default();
+        ::metrics::with_local_recorder(&log, || {
+            describe();
+            let in_flight = request("GET", "/forex/rates?base=USD", Some("research"));
+            rate_limit_wait(
+                "/stock/profile2?symbol=AAPL",
+                None,
+                Duration::from_millis(250),
+            );
+            error(
+                "/not-listed",
+                None,
+                &Error::RateLimitExceeded { retry_after: 1 },
+            );
+            drop(in_flight);
+        });
+
+        let recorded = log.0.lock().unwrap().clone();
+        assert_eq!(
+            recorded,
+            [
+                (
+                    "finnhub_requests_total{group=forex,method=GET,tag=research}".to_string(),
+                    1.0
+                ),
+                ("finnhub_requests_in_flight{}".to_string(), 1.0),
+                (
+                    "finnhub_rate_limit_wait_seconds{group=stock,tag=}".to_string(),
+                    0.25
+                ),
+                (
+                    "finnhub_errors_total{group=other,kind=rate_limit_exceeded,tag=}".to_string(),
+                    1.0
+                ),
+                ("finnhub_requests_in_flight{}".to_string(), -1.0),
+            ]
+        );
+    }
+}
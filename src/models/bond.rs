@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::Count;
+
 /// Bond profile data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BondProfile {
@@ -91,7 +93,7 @@ pub struct BondTickData {
     pub total: i64,
     /// List of volume data.
     #[serde(rename = "v")]
-    pub volume: Vec<f64>,
+    pub volume: Vec<Count>,
     /// List of price data.
     #[serde(rename = "p")]
     pub price: Vec<f64>,
@@ -106,6 +108,14 @@ pub struct BondTickData {
     pub conditions: Option<Vec<Vec<String>>>,
 }
 
+impl BondTickData {
+    /// [`volume`](Self::volume) as `f64`s, for arithmetic with prices.
+    #[must_use]
+    pub fn volume_f64(&self) -> Vec<f64> {
+        self.volume.iter().map(|volume| volume.as_f64()).collect()
+    }
+}
+
 /// Yield curve data point.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YieldCurvePoint {
@@ -125,3 +135,68 @@ pub struct BondYieldCurve {
     /// Array of yield curve data points.
     pub data: Vec<YieldCurvePoint>,
 }
+
+/// US Treasury yield curve tenors, the codes accepted by
+/// `bond().yield_curve()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TreasuryTenor {
+    /// 1 month.
+    #[serde(rename = "1m")]
+    OneMonth,
+    /// 3 months.
+    #[serde(rename = "3m")]
+    ThreeMonths,
+    /// 6 months.
+    #[serde(rename = "6m")]
+    SixMonths,
+    /// 1 year.
+    #[serde(rename = "1y")]
+    OneYear,
+    /// 2 years.
+    #[serde(rename = "2y")]
+    TwoYears,
+    /// 3 years.
+    #[serde(rename = "3y")]
+    ThreeYears,
+    /// 5 years.
+    #[serde(rename = "5y")]
+    FiveYears,
+    /// 7 years.
+    #[serde(rename = "7y")]
+    SevenYears,
+    /// 10 years.
+    #[serde(rename = "10y")]
+    TenYears,
+    /// 20 years.
+    #[serde(rename = "20y")]
+    TwentyYears,
+    /// 30 years.
+    #[serde(rename = "30y")]
+    ThirtyYears,
+}
+
+impl TreasuryTenor {
+    /// The yield curve code, e.g. `10y`.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            TreasuryTenor::OneMonth => "1m",
+            TreasuryTenor::ThreeMonths => "3m",
+            TreasuryTenor::SixMonths => "6m",
+            TreasuryTenor::OneYear => "1y",
+            TreasuryTenor::TwoYears => "2y",
+            TreasuryTenor::ThreeYears => "3y",
+            TreasuryTenor::FiveYears => "5y",
+            TreasuryTenor::SevenYears => "7y",
+            TreasuryTenor::TenYears => "10y",
+            TreasuryTenor::TwentyYears => "20y",
+            TreasuryTenor::ThirtyYears => "30y",
+        }
+    }
+}
+
+impl std::fmt::Display for TreasuryTenor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
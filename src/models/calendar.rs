@@ -1,16 +1,19 @@
 //! Calendar-related data models.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
 
 /// Earnings release data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EarningsRelease {
     /// Symbol.
     pub symbol: Option<String>,
     /// Date.
     pub date: Option<String>,
-    /// Indicates whether the earnings is announced before market open(bmo), after market close(amc), or during market hour(dmh).
-    pub hour: Option<String>,
+    /// When in the trading day the release is scheduled. Empty values are
+    /// read as `None`.
+    #[serde(default, deserialize_with = "reporting_time")]
+    pub hour: Option<ReportingTime>,
     /// Earnings year.
     pub year: Option<i64>,
     /// Earnings quarter.
@@ -29,6 +32,76 @@ pub struct EarningsRelease {
     pub revenue_actual: Option<f64>,
 }
 
+/// When an earnings release is scheduled relative to the trading session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ReportingTime {
+    /// Before market open (`bmo`).
+    BeforeOpen,
+    /// After market close (`amc`).
+    AfterClose,
+    /// During market hours (`dmh`).
+    DuringMarketHours,
+    /// Any other value, as reported.
+    Other(String),
+}
+
+impl ReportingTime {
+    /// The API's spelling: `bmo`, `amc` or `dmh`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            ReportingTime::BeforeOpen => "bmo",
+            ReportingTime::AfterClose => "amc",
+            ReportingTime::DuringMarketHours => "dmh",
+            ReportingTime::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for ReportingTime {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "bmo" => ReportingTime::BeforeOpen,
+            "amc" => ReportingTime::AfterClose,
+            "dmh" => ReportingTime::DuringMarketHours,
+            _ => ReportingTime::Other(value),
+        }
+    }
+}
+
+impl From<ReportingTime> for String {
+    fn from(value: ReportingTime) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for ReportingTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn reporting_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ReportingTime>, D::Error> {
+    let hour = Option::<String>::deserialize(deserializer)?;
+    Ok(hour.filter(|h| !h.is_empty()).map(ReportingTime::from))
+}
+
+/// Parameters for the earnings calendar.
+#[derive(Debug, Clone, Default)]
+pub struct EarningsFilter {
+    /// From date in `YYYY-MM-DD` format.
+    pub from: Option<String>,
+    /// To date in `YYYY-MM-DD` format.
+    pub to: Option<String>,
+    /// Only releases for this symbol.
+    pub symbol: Option<String>,
+    /// Include international (non-US) companies.
+    pub international: bool,
+}
+
 /// Earnings calendar response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EarningsCalendar {
@@ -96,3 +169,39 @@ pub struct IPOCalendar {
     #[serde(rename = "ipoCalendar")]
     pub ipo_calendar: Vec<IPOEvent>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reporting_time() {
+        let calendar: EarningsCalendar = serde_json::from_value(serde_json::json!({
+            "earningsCalendar": [
+                {"symbol": "AAPL", "hour": "amc"},
+                {"symbol": "MSFT", "hour": ""},
+                {"symbol": "IBM", "hour": "tns"},
+                {"symbol": "XOM"}
+            ]
+        }))
+        .unwrap();
+        let hours: Vec<_> = calendar
+            .earnings_calendar
+            .iter()
+            .map(|e| e.hour.clone())
+            .collect();
+        assert_eq!(
+            hours,
+            [
+                Some(ReportingTime::AfterClose),
+                None,
+                Some(ReportingTime::Other("tns".to_string())),
+                None
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&calendar.earnings_calendar[0]).unwrap()["hour"],
+            "amc"
+        );
+    }
+}
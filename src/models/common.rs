@@ -2,6 +2,7 @@
 
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents a timestamp in the API responses.
 pub type Timestamp = DateTime<Utc>;
@@ -85,10 +86,21 @@ impl Resolution {
 }
 
 /// Exchange codes.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Exchange(pub String);
 
 impl Exchange {
+    /// Wrap an exchange code.
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(code.into())
+    }
+
+    /// The exchange code.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
     /// US exchanges.
     pub const US: &'static str = "US";
     /// NYSE.
@@ -101,6 +113,24 @@ impl Exchange {
     pub const TSE: &'static str = "TSE";
 }
 
+impl fmt::Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Exchange {
+    fn from(code: &str) -> Self {
+        Self::new(code)
+    }
+}
+
+impl From<String> for Exchange {
+    fn from(code: String) -> Self {
+        Self(code)
+    }
+}
+
 /// Currency codes.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Currency(pub String);
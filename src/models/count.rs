@@ -0,0 +1,258 @@
+//! Exact integers for count-like fields.
+//!
+//! Share counts and volumes are whole numbers, but the API sometimes sends
+//! them as floats (`1.5e10`) or strings, and an `f64` only holds integers
+//! exactly up to 2^53. [`Count`] parses any of those forms into an `i128`.
+//! JSON integers up to `u64::MAX` and strings of digits are exact;
+//! `serde_json` reads larger integers and floats as `f64`, so those are only
+//! as exact as an `f64` is:
+//!
+//! ```
+//! use finnhub::models::Count;
+//!
+//! let counts: Vec<Count> =
+//!     serde_json::from_str(r#"[15441883000, 1.5e10, "123456789012345678901"]"#).unwrap();
+//! assert_eq!(counts[0].get(), 15_441_883_000);
+//! assert_eq!(counts[1].to_i64(), Some(15_000_000_000));
+//! assert_eq!(counts[2].get(), 123_456_789_012_345_678_901);
+//! ```
+//!
+//! Stock and bond volumes and [`CompanyProfile::shares_outstanding`] are
+//! [`Count`]s. Crypto and forex volumes stay `f64`, since a trade can move a
+//! fraction of a coin. Fields typed `i64` in the models go through the same
+//! parsing with [`deserialize_i64`], so a float or string count no longer
+//! fails the whole response.
+//!
+//! [`CompanyProfile::shares_outstanding`]: crate::models::stock::CompanyProfile::shares_outstanding
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A whole number of shares, contracts or trades.
+///
+/// Deserializes from JSON integers, floats (rounded to the nearest integer)
+/// and strings holding either.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Count(i128);
+
+impl Count {
+    /// Wrap `value`.
+    #[must_use]
+    pub const fn new(value: i128) -> Self {
+        Self(value)
+    }
+
+    /// The count.
+    #[must_use]
+    pub const fn get(self) -> i128 {
+        self.0
+    }
+
+    /// The count, if it fits in an `i64`.
+    #[must_use]
+    pub fn to_i64(self) -> Option<i64> {
+        i64::try_from(self.0).ok()
+    }
+
+    /// The count as an `f64`, for arithmetic with prices; inexact above
+    /// 2^53.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64
+    }
+
+    /// Round `value` to the nearest count; `None` if it isn't finite or is
+    /// out of range.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_f64(value: f64) -> Option<Self> {
+        // 2^127: the first float past `i128::MAX`.
+        const LIMIT: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+        let rounded = value.round();
+        (rounded.is_finite() && rounded.abs() < LIMIT).then_some(Self(rounded as i128))
+    }
+}
+
+impl From<i64> for Count {
+    fn from(value: i64) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u64> for Count {
+    fn from(value: u64) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Count> for i128 {
+    fn from(count: Count) -> Self {
+        count.0
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Count {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i128(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CountVisitor)
+    }
+}
+
+struct CountVisitor;
+
+impl Visitor<'_> for CountVisitor {
+    type Value = Count;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a whole number, as an integer, float or string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Count, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Count, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Count, E> {
+        Ok(Count(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Count, E> {
+        Count::from_f64(value).ok_or_else(|| E::custom(format!("count out of range: {value}")))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Count, E> {
+        let value = value.trim();
+        if let Ok(count) = value.parse::<i128>() {
+            return Ok(Count(count));
+        }
+        match value.parse::<f64>() {
+            Ok(float) => self.visit_f64(float),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+/// Deserialize an `i64` count the way [`Count`] does, for
+/// `#[serde(deserialize_with = "...")]`.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the value isn't a whole number or
+/// doesn't fit in an `i64`.
+pub fn deserialize_i64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    let count = Count::deserialize(deserializer)?;
+    count
+        .to_i64()
+        .ok_or_else(|| de::Error::custom(format!("count out of range for i64: {count}")))
+}
+
+/// Deserialize an optional `i64` count the way [`Count`] does; `null` is
+/// `None`. Pair it with `#[serde(default)]` for fields that may be missing.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the value isn't `null` or a whole
+/// number fitting in an `i64`.
+pub fn deserialize_option_i64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<i64>, D::Error> {
+    match Option::<Count>::deserialize(deserializer)? {
+        Some(count) => count
+            .to_i64()
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("count out of range for i64: {count}"))),
+        None => Ok(None),
+    }
+}
+
+/// Counts sent in millions, such as `shareOutstanding`, for
+/// `#[serde(with = "...")]` on an `Option<Count>` field. Serializes back to
+/// millions.
+#[cfg(any(feature = "stock", test))]
+pub(crate) mod millions {
+    use super::Count;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub(crate) fn serialize<S: Serializer>(
+        count: &Option<Count>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        count
+            .map(|count| count.as_f64() / 1e6)
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Count>, D::Error> {
+        // Whole millions would pass through `Count` exactly, but fractional
+        // ones can't, so go through `f64`; its 15 digits cover any listed
+        // company's share count.
+        match Option::<f64>::deserialize(deserializer)? {
+            Some(millions) => Count::from_f64(millions * 1e6).map(Some).ok_or_else(|| {
+                serde::de::Error::custom(format!("count out of range: {millions} million"))
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Holding {
+        #[serde(deserialize_with = "deserialize_i64")]
+        share: i64,
+        #[serde(default, deserialize_with = "deserialize_option_i64")]
+        change: Option<i64>,
+    }
+
+    #[test]
+    fn test_count_parsing() {
+        let count: Count = serde_json::from_str("2.4999e3").unwrap();
+        assert_eq!(count.get(), 2500);
+        assert!(serde_json::from_str::<Count>(r#""12 shares""#).is_err());
+        assert_eq!(serde_json::to_string(&Count::new(-7)).unwrap(), "-7");
+
+        let holding: Holding = serde_json::from_str(r#"{"share": "1200"}"#).unwrap();
+        assert_eq!((holding.share, holding.change), (1200, None));
+        let holding: Holding = serde_json::from_str(r#"{"share": 1e3, "change": -2.0}"#).unwrap();
+        assert_eq!((holding.share, holding.change), (1000, Some(-2)));
+        assert!(serde_json::from_str::<Holding>(r#"{"share": 1e30}"#).is_err());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Profile {
+        #[serde(default, with = "millions")]
+        shares: Option<Count>,
+    }
+
+    #[test]
+    fn test_millions_round_trip() {
+        let profile: Profile = serde_json::from_str(r#"{"shares": 15441.883}"#).unwrap();
+        assert_eq!(profile.shares, Some(Count::new(15_441_883_000)));
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(json, r#"{"shares":15441.883}"#);
+        let profile: Profile = serde_json::from_str("{}").unwrap();
+        assert_eq!(profile.shares, None);
+    }
+}
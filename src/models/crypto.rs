@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::stock::CandleResolution;
+
 /// Crypto symbol information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +51,140 @@ pub struct CryptoCandles {
     pub status: String,
 }
 
+/// Timestamps above this are taken to be milliseconds: as seconds they would
+/// fall after the year 5000.
+const MILLISECOND_THRESHOLD: i64 = 100_000_000_000;
+
+/// Known ways a venue's candles deviate from bars stamped in seconds at the
+/// bar's open, oldest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CandleQuirks {
+    /// Timestamps are in milliseconds. Millisecond timestamps are also
+    /// detected by magnitude, so this only matters for very old bars.
+    pub millisecond_timestamps: bool,
+    /// Bars are stamped at their close rather than their open.
+    pub stamped_at_close: bool,
+    /// Bars arrive newest first. Bars are always sorted; this decides which
+    /// of two copies of the same bar is the later revision.
+    pub newest_first: bool,
+}
+
+impl CandleQuirks {
+    /// Quirks of the venue in a `VENUE:PAIR` symbol.
+    ///
+    /// Finnhub relays some venues' candles in the shape of the venue's own
+    /// API:
+    ///
+    /// - `BITFINEX` stamps candles in milliseconds.
+    /// - `COINBASE` lists candles newest first.
+    ///
+    /// Other venues, and symbols without a venue, have no known quirks.
+    #[must_use]
+    pub fn for_symbol(symbol: &str) -> Self {
+        let venue = symbol.split_once(':').map_or("", |(venue, _)| venue);
+        match venue.to_ascii_uppercase().as_str() {
+            "BITFINEX" => Self {
+                millisecond_timestamps: true,
+                ..Self::default()
+            },
+            "COINBASE" => Self {
+                newest_first: true,
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+impl CryptoCandles {
+    /// Align bars to UTC bucket starts and resolve duplicates.
+    ///
+    /// Timestamps are converted to seconds, moved to the bar's open if the
+    /// venue stamps bars at their close, and floored to the start of their
+    /// [`resolution`](CandleResolution::bar_start) bucket, so bars from
+    /// different venues line up. Bars are then sorted oldest first. Two bars
+    /// with the same original timestamp are revisions of one bar and the later
+    /// one wins; bars with different timestamps in the same bucket are
+    /// partial bars and are merged (first open, highest high, lowest low, last
+    /// close, summed volume). Columns longer than `timestamp` are truncated.
+    #[must_use]
+    pub fn normalized(self, resolution: CandleResolution, quirks: CandleQuirks) -> Self {
+        struct Bar {
+            time: i64,
+            start: i64,
+            open: f64,
+            high: f64,
+            low: f64,
+            close: f64,
+            volume: f64,
+        }
+
+        let mut bars: Vec<Bar> = self
+            .timestamp
+            .iter()
+            .zip(&self.open)
+            .zip(&self.high)
+            .zip(&self.low)
+            .zip(&self.close)
+            .zip(&self.volume)
+            .map(|(((((&raw, &open), &high), &low), &close), &volume)| {
+                let mut seconds = raw;
+                if quirks.millisecond_timestamps || raw.abs() > MILLISECOND_THRESHOLD {
+                    seconds = raw.div_euclid(1000);
+                }
+                if quirks.stamped_at_close {
+                    // The bucket containing the second before the close.
+                    seconds -= 1;
+                }
+                Bar {
+                    time: seconds,
+                    start: resolution.bar_start(seconds),
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                }
+            })
+            .collect();
+        if quirks.newest_first {
+            bars.reverse();
+        }
+        // Stable, so revisions keep their arrival order.
+        bars.sort_by_key(|bar| (bar.start, bar.time));
+
+        let mut unique: Vec<Bar> = Vec::with_capacity(bars.len());
+        for bar in bars {
+            match unique.last_mut() {
+                Some(last) if last.time == bar.time => *last = bar,
+                _ => unique.push(bar),
+            }
+        }
+        let mut merged: Vec<Bar> = Vec::with_capacity(unique.len());
+        for bar in unique {
+            match merged.last_mut() {
+                Some(last) if last.start == bar.start => {
+                    last.high = last.high.max(bar.high);
+                    last.low = last.low.min(bar.low);
+                    last.close = bar.close;
+                    last.volume += bar.volume;
+                }
+                _ => merged.push(bar),
+            }
+        }
+
+        Self {
+            open: merged.iter().map(|b| b.open).collect(),
+            high: merged.iter().map(|b| b.high).collect(),
+            low: merged.iter().map(|b| b.low).collect(),
+            close: merged.iter().map(|b| b.close).collect(),
+            volume: merged.iter().map(|b| b.volume).collect(),
+            timestamp: merged.iter().map(|b| b.start).collect(),
+            status: self.status,
+        }
+    }
+}
+
 /// Crypto profile data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -66,3 +202,53 @@ pub struct CryptoProfile {
     /// Logo URL.
     pub logo: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn fixture(json: &str) -> CryptoCandles {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_misaligned_daily_bars_merge() {
+        // Bars stamped at 00:00 and 08:00 on the same UTC day, plus a weekly
+        // check: 2024-01-03 was a Wednesday, so the bar belongs to the week of
+        // Monday 2024-01-01.
+        let candles = fixture(
+            r#"{"o":[100.0,104.0,110.0],"h":[105.0,112.0,111.0],"l":[99.0,103.0,108.0],
+                "c":[104.0,109.0,109.5],"v":[5.0,7.0,4.0],
+                "t":[1704067200,1704096000,1704153600],"s":"ok"}"#,
+        );
+        let daily = candles
+            .clone()
+            .normalized(CandleResolution::Daily, CandleQuirks::default());
+        assert_eq!(daily.timestamp, [1_704_067_200, 1_704_067_200 + DAY]);
+        assert_eq!(daily.open, [100.0, 110.0]);
+        assert_eq!(daily.high, [112.0, 111.0]);
+        assert_eq!(daily.low, [99.0, 108.0]);
+        assert_eq!(daily.close, [109.0, 109.5]);
+        assert_eq!(daily.volume, [12.0, 4.0]);
+
+        let weekly = candles.normalized(CandleResolution::Weekly, CandleQuirks::default());
+        assert_eq!(weekly.timestamp, [1_704_067_200]);
+        assert_eq!(weekly.close, [109.5]);
+    }
+
+    #[test]
+    fn test_stamped_at_close() {
+        // An hourly bar stamped at its 01:00 close belongs to the 00:00 bucket.
+        let candles = fixture(
+            r#"{"o":[1.0],"h":[1.0],"l":[1.0],"c":[1.0],"v":[1.0],"t":[1704070800],"s":"ok"}"#,
+        );
+        let quirks = CandleQuirks {
+            stamped_at_close: true,
+            ..CandleQuirks::default()
+        };
+        let normalized = candles.normalized(CandleResolution::SixtyMinutes, quirks);
+        assert_eq!(normalized.timestamp, [1_704_067_200]);
+    }
+}
@@ -1,6 +1,9 @@
 //! Economic data models.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::models::TimeSeries;
 
 /// Economic data point.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +23,12 @@ pub struct EconomicData {
     pub data: Vec<EconomicDataPoint>,
 }
 
+impl From<EconomicData> for TimeSeries<f64> {
+    fn from(data: EconomicData) -> Self {
+        TimeSeries::from_date_strings(data.data.into_iter().map(|point| (point.date, point.value)))
+    }
+}
+
 /// Economic indicator code.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconomicCode {
@@ -32,3 +41,250 @@ pub struct EconomicCode {
     /// Unit.
     pub unit: String,
 }
+
+/// Widely followed indicators, resolved to Finnhub codes with
+/// [`EconomicCodes::find`] instead of hardcoding opaque code strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Indicator {
+    /// Consumer price index, headline.
+    ConsumerPriceIndex,
+    /// Consumer price index excluding food and energy.
+    CoreConsumerPriceIndex,
+    /// Producer price index.
+    ProducerPriceIndex,
+    /// Gross domestic product.
+    Gdp,
+    /// Gross domestic product growth rate.
+    GdpGrowthRate,
+    /// Unemployment rate.
+    UnemploymentRate,
+    /// Non-farm payrolls.
+    NonFarmPayrolls,
+    /// Central bank policy rate.
+    InterestRate,
+    /// Retail sales.
+    RetailSales,
+    /// Industrial production.
+    IndustrialProduction,
+    /// Balance of trade.
+    TradeBalance,
+    /// Consumer confidence.
+    ConsumerConfidence,
+}
+
+impl Indicator {
+    /// Every indicator.
+    pub const ALL: [Indicator; 12] = [
+        Indicator::ConsumerPriceIndex,
+        Indicator::CoreConsumerPriceIndex,
+        Indicator::ProducerPriceIndex,
+        Indicator::Gdp,
+        Indicator::GdpGrowthRate,
+        Indicator::UnemploymentRate,
+        Indicator::NonFarmPayrolls,
+        Indicator::InterestRate,
+        Indicator::RetailSales,
+        Indicator::IndustrialProduction,
+        Indicator::TradeBalance,
+        Indicator::ConsumerConfidence,
+    ];
+
+    /// Lowercase phrases one of which an indicator name must contain, and
+    /// phrases it must not contain.
+    fn patterns(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Indicator::ConsumerPriceIndex => (
+                &["consumer price index", "cpi", "inflation rate"],
+                &["core", "excluding", "ex food"],
+            ),
+            Indicator::CoreConsumerPriceIndex => {
+                (&["core consumer price", "core cpi", "core inflation"], &[])
+            }
+            Indicator::ProducerPriceIndex => (&["producer price", "ppi"], &["core"]),
+            Indicator::Gdp => (
+                &["gdp", "gross domestic product"],
+                &["growth", "per capita", "deflator"],
+            ),
+            Indicator::GdpGrowthRate => (&["gdp growth", "gross domestic product growth"], &[]),
+            Indicator::UnemploymentRate => (&["unemployment rate"], &["youth"]),
+            Indicator::NonFarmPayrolls => {
+                (&["non farm payrolls", "nonfarm payrolls"], &["private"])
+            }
+            Indicator::InterestRate => (&["interest rate", "policy rate"], &["mortgage"]),
+            Indicator::RetailSales => (&["retail sales"], &["ex autos", "excluding"]),
+            Indicator::IndustrialProduction => (&["industrial production"], &[]),
+            Indicator::TradeBalance => (&["balance of trade", "trade balance"], &[]),
+            Indicator::ConsumerConfidence => (&["consumer confidence"], &[]),
+        }
+    }
+
+    /// Whether `name` describes this indicator.
+    fn matches(self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        let (include, exclude) = self.patterns();
+        include.iter().any(|phrase| name.contains(phrase))
+            && !exclude.iter().any(|phrase| name.contains(phrase))
+    }
+}
+
+impl fmt::Display for Indicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Indicator::ConsumerPriceIndex => "consumer price index",
+            Indicator::CoreConsumerPriceIndex => "core consumer price index",
+            Indicator::ProducerPriceIndex => "producer price index",
+            Indicator::Gdp => "GDP",
+            Indicator::GdpGrowthRate => "GDP growth rate",
+            Indicator::UnemploymentRate => "unemployment rate",
+            Indicator::NonFarmPayrolls => "non-farm payrolls",
+            Indicator::InterestRate => "interest rate",
+            Indicator::RetailSales => "retail sales",
+            Indicator::IndustrialProduction => "industrial production",
+            Indicator::TradeBalance => "balance of trade",
+            Indicator::ConsumerConfidence => "consumer confidence",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The economic code list, indexed for lookup and search.
+///
+/// Build it from `economic().codes()`, or fetch it with
+/// `economic().code_registry()`. The list rarely changes, so keep it around
+/// rather than refetching.
+///
+/// ```rust
+/// use finnhub::models::economic::{EconomicCode, EconomicCodes, Indicator};
+///
+/// let codes = EconomicCodes::new(vec![EconomicCode {
+///     code: "US-UNEMP".to_string(),
+///     country: "US".to_string(),
+///     name: "Unemployment Rate".to_string(),
+///     unit: "%".to_string(),
+/// }]);
+/// let unemployment = codes.find(Indicator::UnemploymentRate, "us").unwrap();
+/// assert_eq!(unemployment.code, "US-UNEMP");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EconomicCodes {
+    codes: Vec<EconomicCode>,
+}
+
+impl EconomicCodes {
+    /// Index `codes`.
+    #[must_use]
+    pub fn new(codes: Vec<EconomicCode>) -> Self {
+        Self { codes }
+    }
+
+    /// The entry for `code`.
+    #[must_use]
+    pub fn get(&self, code: &str) -> Option<&EconomicCode> {
+        self.codes.iter().find(|entry| entry.code == code)
+    }
+
+    /// The code of `indicator` for `country`, compared case-insensitively
+    /// with [`EconomicCode::country`].
+    ///
+    /// When several names match, the shortest wins, since variants such as
+    /// "Unemployment Rate SA" add words to the headline series. Use
+    /// [`search`](Self::search) to pick among them yourself.
+    #[must_use]
+    pub fn find(&self, indicator: Indicator, country: &str) -> Option<&EconomicCode> {
+        self.codes
+            .iter()
+            .filter(|entry| entry.country.eq_ignore_ascii_case(country))
+            .filter(|entry| indicator.matches(&entry.name))
+            .min_by_key(|entry| entry.name.len())
+    }
+
+    /// Entries whose code, country, name or unit contain every word of
+    /// `query`, ignoring case.
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<&EconomicCode> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.codes
+            .iter()
+            .filter(|entry| {
+                let text = format!(
+                    "{} {} {} {}",
+                    entry.code, entry.country, entry.name, entry.unit
+                )
+                .to_lowercase();
+                words.iter().all(|word| text.contains(word.as_str()))
+            })
+            .collect()
+    }
+
+    /// Entries for `country`, compared case-insensitively.
+    pub fn for_country<'a>(&'a self, country: &'a str) -> impl Iterator<Item = &'a EconomicCode> {
+        self.codes
+            .iter()
+            .filter(move |entry| entry.country.eq_ignore_ascii_case(country))
+    }
+
+    /// Every entry.
+    pub fn iter(&self) -> impl Iterator<Item = &EconomicCode> {
+        self.codes.iter()
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Whether there are no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+impl From<Vec<EconomicCode>> for EconomicCodes {
+    fn from(codes: Vec<EconomicCode>) -> Self {
+        Self::new(codes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(code: &str, country: &str, name: &str) -> EconomicCode {
+        EconomicCode {
+            code: code.to_string(),
+            country: country.to_string(),
+            name: name.to_string(),
+            unit: "%".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_and_search() {
+        let codes = EconomicCodes::new(vec![
+            code("US-1", "US", "Core Consumer Price Index"),
+            code("US-2", "US", "Consumer Price Index CPI"),
+            code("US-3", "US", "GDP Growth Rate"),
+            code("US-4", "US", "GDP"),
+            code("DE-1", "DE", "Consumer Price Index CPI"),
+            code("US-5", "US", "Unemployment Rate SA"),
+            code("US-6", "US", "Unemployment Rate"),
+        ]);
+
+        let find = |indicator, country| codes.find(indicator, country).map(|c| c.code.as_str());
+        assert_eq!(find(Indicator::ConsumerPriceIndex, "us"), Some("US-2"));
+        assert_eq!(find(Indicator::CoreConsumerPriceIndex, "US"), Some("US-1"));
+        assert_eq!(find(Indicator::ConsumerPriceIndex, "DE"), Some("DE-1"));
+        assert_eq!(find(Indicator::Gdp, "US"), Some("US-4"));
+        assert_eq!(find(Indicator::GdpGrowthRate, "US"), Some("US-3"));
+        assert_eq!(find(Indicator::UnemploymentRate, "US"), Some("US-6"));
+        assert_eq!(find(Indicator::RetailSales, "US"), None);
+
+        assert_eq!(codes.search("consumer price").len(), 3);
+        assert_eq!(codes.search("cpi de-").len(), 1);
+        assert_eq!(codes.for_country("us").count(), 6);
+        assert_eq!(codes.get("US-3").unwrap().name, "GDP Growth Rate");
+    }
+}
@@ -0,0 +1,249 @@
+//! Uniform access to responses that wrap a list.
+//!
+//! Some endpoints return a bare JSON array, others wrap it in an object such
+//! as `{"symbol": "AAPL", "data": [...]}`. [`DataEnvelope`] is implemented
+//! for both, so code that only wants the rows doesn't care which shape an
+//! endpoint uses:
+//!
+//! ```rust
+//! use finnhub::models::stock::{InsiderTransaction, InsiderTransactions};
+//! use finnhub::models::DataEnvelope;
+//!
+//! fn rows<E: DataEnvelope>(response: E) -> Vec<E::Item> {
+//!     response.into_inner()
+//! }
+//!
+//! let wrapped = InsiderTransactions { symbol: "AAPL".to_string(), data: Vec::new() };
+//! assert_eq!(wrapped.symbol(), Some("AAPL"));
+//! let bare: Vec<InsiderTransaction> = Vec::new();
+//! assert!(rows(wrapped).is_empty() && rows(bare).is_empty());
+//! ```
+//!
+//! Wrapped responses also have an `iter` method and implement
+//! `IntoIterator`, by value and by reference, so `for row in &response`
+//! iterates their `data`.
+
+use super::PaginatedResponse;
+
+/// A response whose payload is a list of `Item`s, bare or wrapped.
+pub trait DataEnvelope {
+    /// Element of the list.
+    type Item;
+
+    /// Symbol the response is for, if the envelope names one.
+    fn symbol(&self) -> Option<&str>;
+
+    /// The list.
+    fn data(&self) -> &[Self::Item];
+
+    /// Take the list, dropping the envelope.
+    fn into_inner(self) -> Vec<Self::Item>;
+}
+
+impl<T> DataEnvelope for Vec<T> {
+    type Item = T;
+
+    fn symbol(&self) -> Option<&str> {
+        None
+    }
+
+    fn data(&self) -> &[T] {
+        self
+    }
+
+    fn into_inner(self) -> Vec<T> {
+        self
+    }
+}
+
+impl<T> DataEnvelope for PaginatedResponse<T> {
+    type Item = T;
+
+    fn symbol(&self) -> Option<&str> {
+        None
+    }
+
+    fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    fn into_inner(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T> IntoIterator for PaginatedResponse<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Iterate over the items.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PaginatedResponse<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// Implement [`DataEnvelope`] and `IntoIterator` for a response with a
+/// `data` list, reading the symbol from its `symbol` field, an optional
+/// `symbol` field, or nowhere.
+macro_rules! data_envelope {
+    ($envelope:ty, $item:ty) => {
+        data_envelope!($envelope, $item, none);
+    };
+    (@symbol $this:ident, symbol) => {
+        Some($this.symbol.as_str())
+    };
+    (@symbol $this:ident, optional_symbol) => {
+        $this.symbol.as_deref()
+    };
+    (@symbol $this:ident, none) => {
+        None
+    };
+    ($envelope:ty, $item:ty, $symbol:ident) => {
+        impl DataEnvelope for $envelope {
+            type Item = $item;
+
+            fn symbol(&self) -> Option<&str> {
+                data_envelope!(@symbol self, $symbol)
+            }
+
+            fn data(&self) -> &[$item] {
+                &self.data
+            }
+
+            fn into_inner(self) -> Vec<$item> {
+                self.data
+            }
+        }
+
+        impl IntoIterator for $envelope {
+            type Item = $item;
+            type IntoIter = std::vec::IntoIter<$item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.data.into_iter()
+            }
+        }
+
+        impl $envelope {
+            /// Iterate over `data`.
+            pub fn iter(&self) -> std::slice::Iter<'_, $item> {
+                self.data.iter()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $envelope {
+            type Item = &'a $item;
+            type IntoIter = std::slice::Iter<'a, $item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.data.iter()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "bond")]
+data_envelope!(super::bond::BondYieldCurve, super::bond::YieldCurvePoint);
+#[cfg(feature = "economic")]
+data_envelope!(
+    super::economic::EconomicData,
+    super::economic::EconomicDataPoint
+);
+data_envelope!(super::misc::SectorMetric, super::misc::SectorMetricData);
+data_envelope!(
+    super::misc::AirlinePriceIndexData,
+    super::misc::AirlinePriceIndex
+);
+
+#[cfg(feature = "stock")]
+mod stock {
+    use super::DataEnvelope;
+    use crate::models::stock::{
+        CongressionalTrade, CongressionalTrading, DividendV2, DividendsV2, EBITDAEstimate,
+        EBITDAEstimates, EBITEstimate, EBITEstimates, EPSEstimate, EPSEstimates, ESGData,
+        EarningsQualityScore, EarningsQualityScoreData, EmployeeCountData, FinancialReport,
+        FinancialsAsReported, HistoricalESG, HistoricalEmployeeCount, HistoricalMarketCapData,
+        Holiday, InsiderSentiment, InsiderSentimentData, InsiderTransaction, InsiderTransactions,
+        InvestmentTheme, IsinChange, IsinChanges, Lobbying, LobbyingData, MarketCapData,
+        MarketHoliday, PatentApplication, RevenueBreakdown, RevenueEstimate, RevenueEstimates,
+        SocialSentiment, SocialSentimentData, SupplyChainData, SupplyChainRelationship,
+        SymbolChange, SymbolChanges, ThemeStock, USASpending, USASpendingData, USPTOPatents,
+        VisaApplication, VisaApplications,
+    };
+    use std::collections::HashMap;
+
+    data_envelope!(
+        RevenueBreakdown,
+        HashMap<String, serde_json::Value>,
+        symbol
+    );
+    data_envelope!(USPTOPatents, PatentApplication, symbol);
+    data_envelope!(VisaApplications, VisaApplication, symbol);
+    data_envelope!(SupplyChainData, SupplyChainRelationship, symbol);
+    data_envelope!(CongressionalTrading, CongressionalTrade, symbol);
+    data_envelope!(Lobbying, LobbyingData, symbol);
+    data_envelope!(USASpending, USASpendingData, symbol);
+    data_envelope!(DividendsV2, DividendV2, symbol);
+    data_envelope!(SymbolChanges, SymbolChange);
+    data_envelope!(IsinChanges, IsinChange);
+    data_envelope!(EPSEstimates, EPSEstimate, symbol);
+    data_envelope!(RevenueEstimates, RevenueEstimate, symbol);
+    data_envelope!(EBITDAEstimates, EBITDAEstimate, symbol);
+    data_envelope!(EBITEstimates, EBITEstimate, symbol);
+    data_envelope!(EarningsQualityScore, EarningsQualityScoreData, symbol);
+    data_envelope!(FinancialsAsReported, FinancialReport, optional_symbol);
+    data_envelope!(HistoricalMarketCapData, MarketCapData, symbol);
+    data_envelope!(HistoricalEmployeeCount, EmployeeCountData, symbol);
+    data_envelope!(HistoricalESG, ESGData, symbol);
+    data_envelope!(InsiderTransactions, InsiderTransaction, symbol);
+    data_envelope!(InsiderSentimentData, InsiderSentiment, symbol);
+    data_envelope!(SocialSentiment, SocialSentimentData, symbol);
+    data_envelope!(MarketHoliday, Holiday);
+    data_envelope!(InvestmentTheme, ThemeStock);
+}
+
+#[cfg(all(test, feature = "stock"))]
+mod tests {
+    use super::*;
+    use crate::models::stock::{FinancialsAsReported, Holiday, MarketHoliday};
+
+    #[test]
+    fn test_envelopes_iterate_their_data() {
+        let holidays = MarketHoliday {
+            exchange: "US".to_string(),
+            data: vec![Holiday {
+                event_name: "Christmas".to_string(),
+                at_date: "2024-12-25".to_string(),
+                trading_hour: String::new(),
+            }],
+            timezone: "America/New_York".to_string(),
+        };
+        assert_eq!(holidays.symbol(), None);
+        let names: Vec<&str> = (&holidays)
+            .into_iter()
+            .map(|h| h.event_name.as_str())
+            .collect();
+        assert_eq!(names, ["Christmas"]);
+        assert_eq!(holidays.into_iter().count(), 1);
+
+        let reported: FinancialsAsReported =
+            serde_json::from_str(r#"{"symbol": null, "cik": "320193", "data": []}"#).unwrap();
+        assert_eq!(reported.symbol(), None);
+        assert!(reported.data().is_empty());
+    }
+}
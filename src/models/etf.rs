@@ -1,6 +1,11 @@
 //! ETF-related data models.
 
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::models::time_series::parse_date;
 
 /// ETF profile data (inner profile object).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,17 +28,19 @@ pub struct ETFProfileData {
     /// Expense ratio.
     #[serde(rename = "expenseRatio")]
     pub expense_ratio: Option<f64>,
-    /// Tracking index.
-    #[serde(rename = "trackingIndex")]
-    pub tracking_index: Option<String>,
+    /// Tracking index. Empty values are read as `None`.
+    #[serde(rename = "trackingIndex", default, deserialize_with = "non_empty")]
+    pub tracking_index: Option<TrackingIndex>,
     /// ETF issuer.
     #[serde(rename = "etfCompany")]
     pub etf_company: Option<String>,
-    /// ETF domicile.
+    /// ETF domicile as an upper-case country code, e.g. `US` or `IE`. Empty
+    /// values are read as `None`.
+    #[serde(default, deserialize_with = "domicile")]
     pub domicile: Option<String>,
-    /// Inception date.
-    #[serde(rename = "inceptionDate")]
-    pub inception_date: Option<String>,
+    /// Inception date. Empty or unparseable values are read as `None`.
+    #[serde(rename = "inceptionDate", default, deserialize_with = "inception_date")]
+    pub inception_date: Option<NaiveDate>,
     /// ETF's website.
     pub website: Option<String>,
     /// Logo URL.
@@ -76,6 +83,165 @@ pub struct ETFProfile {
     pub profile: ETFProfileData,
 }
 
+/// The index an ETF tracks, e.g. `S&P 500`.
+///
+/// Issuers spell the same index differently, so equality and hashing ignore
+/// case, punctuation and a trailing "Index": `S&P 500 Index` equals
+/// `s&p 500`. The name is kept as reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TrackingIndex(pub String);
+
+impl TrackingIndex {
+    /// The index name as reported.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    fn key(&self) -> String {
+        let key: String = self
+            .0
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match key.strip_suffix("index") {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => key,
+        }
+    }
+}
+
+impl PartialEq for TrackingIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for TrackingIndex {}
+
+impl Hash for TrackingIndex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl fmt::Display for TrackingIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for TrackingIndex {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+fn non_empty<'de, D: Deserializer<'de>, T: From<String>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.filter(|v| !v.trim().is_empty()).map(T::from))
+}
+
+fn domicile<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let domicile: Option<String> = non_empty(deserializer)?;
+    Ok(domicile.map(|d| d.trim().to_ascii_uppercase()))
+}
+
+fn inception_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NaiveDate>, D::Error> {
+    let date = Option::<String>::deserialize(deserializer)?;
+    Ok(date.as_deref().and_then(parse_date))
+}
+
+/// Key figures of several ETFs side by side, for picking between funds.
+///
+/// Rows are sorted by expense ratio, cheapest first; funds without one come
+/// last. `Display` renders a plain-text table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ETFComparison {
+    /// One row per fund.
+    pub rows: Vec<ETFComparisonRow>,
+}
+
+/// One fund in an [`ETFComparison`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ETFComparisonRow {
+    /// ETF symbol.
+    pub symbol: String,
+    /// Name.
+    pub name: Option<String>,
+    /// Expense ratio, in percent.
+    pub expense_ratio: Option<f64>,
+    /// Assets under management, in dollars.
+    pub aum: Option<f64>,
+    /// Tracking index.
+    pub tracking_index: Option<TrackingIndex>,
+    /// Inception date.
+    pub inception_date: Option<NaiveDate>,
+}
+
+impl ETFComparison {
+    /// Build a comparison from profile responses.
+    pub fn from_profiles(profiles: impl IntoIterator<Item = ETFProfile>) -> Self {
+        let mut rows: Vec<ETFComparisonRow> = profiles
+            .into_iter()
+            .map(|ETFProfile { symbol, profile }| ETFComparisonRow {
+                symbol,
+                name: profile.name,
+                expense_ratio: profile.expense_ratio,
+                aum: profile.aum,
+                tracking_index: profile.tracking_index,
+                inception_date: profile.inception_date,
+            })
+            .collect();
+        rows.sort_by(|a, b| match (a.expense_ratio, b.expense_ratio) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        Self { rows }
+    }
+
+    /// The fund with the lowest expense ratio.
+    #[must_use]
+    pub fn cheapest(&self) -> Option<&ETFComparisonRow> {
+        self.rows.first().filter(|row| row.expense_ratio.is_some())
+    }
+
+    /// Funds tracking `index`, cheapest first.
+    pub fn tracking<'a>(
+        &'a self,
+        index: &'a TrackingIndex,
+    ) -> impl Iterator<Item = &'a ETFComparisonRow> + 'a {
+        self.rows
+            .iter()
+            .filter(move |row| row.tracking_index.as_ref() == Some(index))
+    }
+}
+
+impl fmt::Display for ETFComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<8} {:>8} {:>10}  Index", "Symbol", "Expense", "AUM")?;
+        for row in &self.rows {
+            let expense = row
+                .expense_ratio
+                .map_or_else(|| "-".to_string(), |e| format!("{e:.2}%"));
+            let aum = row
+                .aum
+                .map_or_else(|| "-".to_string(), |a| format!("${:.2}B", a / 1e9));
+            let index = row.tracking_index.as_ref().map_or("-", TrackingIndex::name);
+            writeln!(f, "{:<8} {expense:>8} {aum:>10}  {index}", row.symbol)?;
+        }
+        Ok(())
+    }
+}
+
 /// ETF holding data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ETFHolding {
@@ -148,3 +314,58 @@ pub struct ETFSectorExposure {
     #[serde(rename = "sectorExposure")]
     pub sector_exposure: Vec<SectorExposure>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(symbol: &str, json: &serde_json::Value) -> ETFProfile {
+        serde_json::from_value(serde_json::json!({"symbol": symbol, "profile": json})).unwrap()
+    }
+
+    #[test]
+    fn test_typed_profile_fields_and_comparison() {
+        let spy = profile(
+            "SPY",
+            &serde_json::json!({
+                "expenseRatio": 0.0945,
+                "aum": 500_000_000_000.0,
+                "trackingIndex": "S&P 500 Index",
+                "domicile": "us",
+                "inceptionDate": "1993-01-22",
+            }),
+        );
+        assert_eq!(spy.profile.domicile.as_deref(), Some("US"));
+        assert_eq!(
+            spy.profile.inception_date,
+            NaiveDate::from_ymd_opt(1993, 1, 22)
+        );
+
+        let voo = profile(
+            "VOO",
+            &serde_json::json!({
+                "expenseRatio": 0.03,
+                "trackingIndex": "S&P 500",
+                "domicile": "",
+                "inceptionDate": "",
+            }),
+        );
+        assert_eq!(voo.profile.domicile, None);
+        assert_eq!(voo.profile.inception_date, None);
+        let unknown = profile("XYZ", &serde_json::json!({"trackingIndex": ""}));
+        assert_eq!(unknown.profile.tracking_index, None);
+
+        let comparison = ETFComparison::from_profiles([unknown, spy, voo]);
+        let symbols: Vec<_> = comparison.rows.iter().map(|r| r.symbol.as_str()).collect();
+        assert_eq!(symbols, ["VOO", "SPY", "XYZ"]);
+        assert_eq!(comparison.cheapest().unwrap().symbol, "VOO");
+
+        let sp500 = TrackingIndex("s&p 500".to_string());
+        assert_eq!(comparison.tracking(&sp500).count(), 2);
+        let table = comparison.to_string();
+        assert!(
+            table.contains("SPY         0.09%   $500.00B  S&P 500 Index"),
+            "{table}"
+        );
+    }
+}
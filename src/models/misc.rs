@@ -3,17 +3,65 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::models::TimeSeries;
+
+/// Author of an AI chat message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    /// Instructions for the assistant.
+    System,
+    /// A question from the user.
+    User,
+    /// An earlier reply from the assistant.
+    Assistant,
+}
+
 /// AI chat message.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AIChatMessage {
-    /// Role (system/user).
-    pub role: String,
+    /// Who wrote the message.
+    pub role: ChatRole,
     /// Content.
     pub content: String,
 }
 
-/// AI chat request.
-#[derive(Debug, Serialize, Deserialize)]
+impl AIChatMessage {
+    /// A message with the given role.
+    pub fn new(role: ChatRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+        }
+    }
+
+    /// A system message.
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::System, content)
+    }
+
+    /// A user message.
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::User, content)
+    }
+
+    /// An assistant message.
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::Assistant, content)
+    }
+}
+
+/// AI chat request: the conversation so far, oldest message first.
+///
+/// ```rust
+/// use finnhub::models::misc::{AIChatRequest, ChatRole};
+///
+/// let request = AIChatRequest::new()
+///     .system("Answer in one paragraph.")
+///     .user("How did AAPL's margins change last quarter?");
+/// assert_eq!(request.messages[1].role, ChatRole::User);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AIChatRequest {
     /// Messages.
     pub messages: Vec<AIChatMessage>,
@@ -22,6 +70,59 @@ pub struct AIChatRequest {
     pub stream: Option<bool>,
 }
 
+impl AIChatRequest {
+    /// An empty conversation.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a system message.
+    #[must_use]
+    pub fn system(mut self, content: impl Into<String>) -> Self {
+        self.messages.push(AIChatMessage::system(content));
+        self
+    }
+
+    /// Append a user message.
+    #[must_use]
+    pub fn user(mut self, content: impl Into<String>) -> Self {
+        self.messages.push(AIChatMessage::user(content));
+        self
+    }
+
+    /// Append an assistant message.
+    #[must_use]
+    pub fn assistant(mut self, content: impl Into<String>) -> Self {
+        self.messages.push(AIChatMessage::assistant(content));
+        self
+    }
+
+    /// Ask for the reply to be streamed in chunks.
+    #[must_use]
+    pub fn streaming(mut self) -> Self {
+        self.stream = Some(true);
+        self
+    }
+
+    /// Append a message.
+    pub fn push(&mut self, message: AIChatMessage) {
+        self.messages.push(message);
+    }
+
+    /// Append the assistant's reply, so the next user message continues the
+    /// conversation.
+    pub fn push_reply(&mut self, response: &AIChatResponse) {
+        self.push(AIChatMessage::assistant(response.content.clone()));
+    }
+
+    /// Whether the reply should be streamed.
+    #[must_use]
+    pub fn is_streaming(&self) -> bool {
+        self.stream.unwrap_or(false)
+    }
+}
+
 /// AI chat response.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -42,6 +143,76 @@ pub struct AIChatResponse {
     pub widgets: Vec<String>,
 }
 
+/// A piece of a streamed AI chat reply.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AIChatChunk {
+    /// Chat ID, if the chunk carries one.
+    #[serde(default)]
+    pub chat_id: Option<String>,
+    /// Text to append to the reply.
+    #[serde(default)]
+    pub content: String,
+}
+
+/// Splits a streamed AI chat body into [`AIChatChunk`]s.
+///
+/// The body is read as one event per line, either server-sent events
+/// (`data: {...}`) or bare JSON lines; blank lines, comments and the
+/// `[DONE]` marker are skipped. Bytes can be fed in arbitrary pieces, as
+/// they arrive from the network.
+#[derive(Debug, Default)]
+pub struct AIChatStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl AIChatStreamDecoder {
+    /// A decoder with nothing buffered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next bytes of the body and return the chunks completed by
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error for a line that isn't a chunk.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Vec<AIChatChunk>, serde_json::Error> {
+        self.buffer.extend_from_slice(bytes);
+        let mut chunks = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            if let Some(chunk) = decode_line(&line)? {
+                chunks.push(chunk);
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Decode whatever is left once the body has ended.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the last line isn't a chunk.
+    pub fn finish(mut self) -> Result<Option<AIChatChunk>, serde_json::Error> {
+        decode_line(&std::mem::take(&mut self.buffer))
+    }
+}
+
+fn decode_line(line: &[u8]) -> Result<Option<AIChatChunk>, serde_json::Error> {
+    let line = line.trim_ascii();
+    if line.is_empty() || line.starts_with(b":") {
+        return Ok(None);
+    }
+    let data = line.strip_prefix(b"data:").map_or(line, <[u8]>::trim_ascii);
+    if data.is_empty() || data == b"[DONE]" {
+        return Ok(None);
+    }
+    serde_json::from_slice(data).map(Some)
+}
+
 /// Airline price index data point.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -69,6 +240,17 @@ pub struct AirlinePriceIndexData {
     pub to: String,
 }
 
+impl From<AirlinePriceIndexData> for TimeSeries<AirlinePriceIndex> {
+    fn from(index: AirlinePriceIndexData) -> Self {
+        TimeSeries::from_date_strings(
+            index
+                .data
+                .into_iter()
+                .map(|point| (point.date.clone(), point)),
+        )
+    }
+}
+
 /// Country metadata.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -183,6 +365,44 @@ pub struct SymbolLookupInfo {
     pub security_type: String,
 }
 
+impl SymbolLookupInfo {
+    /// Whether the symbol or display symbol equals `ticker`, ignoring case.
+    #[must_use]
+    pub fn is_exact(&self, ticker: &str) -> bool {
+        self.symbol.eq_ignore_ascii_case(ticker) || self.display_symbol.eq_ignore_ascii_case(ticker)
+    }
+
+    /// How well this result matches a search query.
+    #[must_use]
+    pub fn match_quality(&self, query: &str) -> SymbolMatch {
+        let query = query.to_ascii_uppercase();
+        let starts = |s: &str| s.to_ascii_uppercase().starts_with(&query);
+
+        if self.is_exact(&query) {
+            SymbolMatch::Exact
+        } else if starts(&self.symbol) || starts(&self.display_symbol) {
+            SymbolMatch::Prefix
+        } else if self.description.to_ascii_uppercase().contains(&query) {
+            SymbolMatch::Description
+        } else {
+            SymbolMatch::Other
+        }
+    }
+}
+
+/// How a symbol search result matches the query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SymbolMatch {
+    /// Symbol equals the query.
+    Exact,
+    /// Symbol starts with the query.
+    Prefix,
+    /// Description contains the query.
+    Description,
+    /// Anything else the API returned.
+    Other,
+}
+
 /// Symbol lookup response.
 #[derive(Debug, Deserialize)]
 pub struct SymbolLookup {
@@ -192,6 +412,20 @@ pub struct SymbolLookup {
     pub result: Vec<SymbolLookupInfo>,
 }
 
+impl SymbolLookup {
+    /// Sort results by [`SymbolMatch`] against `query`, keeping API order
+    /// within each group.
+    pub fn rank(&mut self, query: &str) {
+        self.result.sort_by_key(|info| info.match_quality(query));
+    }
+
+    /// The first result whose symbol is exactly `ticker`.
+    #[must_use]
+    pub fn into_exact(self, ticker: &str) -> Option<SymbolLookupInfo> {
+        self.result.into_iter().find(|info| info.is_exact(ticker))
+    }
+}
+
 /// Sector metric data.
 #[derive(Debug, Deserialize)]
 pub struct SectorMetricData {
@@ -209,3 +443,76 @@ pub struct SectorMetric {
     /// Metrics for each sector.
     pub data: Vec<SectorMetricData>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(symbol: &str, description: &str) -> SymbolLookupInfo {
+        SymbolLookupInfo {
+            description: description.to_string(),
+            display_symbol: symbol.to_string(),
+            symbol: symbol.to_string(),
+            security_type: "Common Stock".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rank_and_exact() {
+        let mut lookup = SymbolLookup {
+            count: 5,
+            result: vec![
+                info("APC.F", "APPLE INC"),
+                info("APPLU", "APPLE ACQUISITION UNITS"),
+                info("FB", "META PLATFORMS INC"),
+                info("APLE", "APPLE HOSPITALITY REIT INC"),
+                info("APPL", "APPLIED DIGITAL CORP"),
+            ],
+        };
+
+        lookup.rank("appl");
+        let order: Vec<_> = lookup.result.iter().map(|i| i.symbol.as_str()).collect();
+        assert_eq!(order, ["APPL", "APPLU", "APC.F", "APLE", "FB"]);
+
+        assert_eq!(
+            lookup.into_exact("appl").map(|i| i.symbol),
+            Some("APPL".to_string())
+        );
+    }
+
+    #[test]
+    fn test_request_serializes_roles() {
+        let request = AIChatRequest::new()
+            .system("Be brief.")
+            .user("Hi")
+            .streaming();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "messages": [
+                    {"role": "system", "content": "Be brief."},
+                    {"role": "user", "content": "Hi"},
+                ],
+                "stream": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_stream_decoder_handles_split_events() {
+        let mut decoder = AIChatStreamDecoder::new();
+        let first = decoder
+            .push(b": keep-alive\n\ndata: {\"chatId\":\"c1\",\"content\":\"Hel")
+            .unwrap();
+        assert!(first.is_empty());
+
+        let rest = decoder
+            .push(b"lo\"}\n\n{\"content\":\", world\"}\r\ndata: [DONE]\n")
+            .unwrap();
+        let text: Vec<_> = rest.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(text, ["Hello", ", world"]);
+        assert_eq!(rest[0].chat_id.as_deref(), Some("c1"));
+        assert_eq!(decoder.finish().unwrap(), None);
+    }
+}
@@ -1,17 +1,33 @@
 //! Data models for Finnhub API responses.
+//!
+//! Models for an asset class are only compiled when its cargo feature is
+//! enabled. The shared types in [`stock::common`] (such as `CandleResolution`)
+//! are always available since forex and crypto candles use them too.
 
+#[cfg(feature = "bond")]
 pub mod bond;
 pub mod calendar;
 pub mod common;
+pub mod count;
+#[cfg(feature = "crypto")]
 pub mod crypto;
+#[cfg(feature = "economic")]
 pub mod economic;
+pub mod envelope;
+#[cfg(feature = "etf")]
 pub mod etf;
+#[cfg(feature = "forex")]
 pub mod forex;
 pub mod index;
 pub mod misc;
+#[cfg(feature = "mutual-fund")]
 pub mod mutual_fund;
 pub mod news;
 pub mod scanner;
 pub mod stock;
+pub mod time_series;
 
 pub use common::*;
+pub use count::Count;
+pub use envelope::DataEnvelope;
+pub use time_series::TimeSeries;
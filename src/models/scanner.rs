@@ -1,7 +1,8 @@
 //! Scanner/Technical Analysis models.
 
-use serde::Deserialize;
-use std::collections::HashMap;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 
 /// Pattern data point.
 #[derive(Debug, Deserialize)]
@@ -46,6 +47,15 @@ pub struct ScanPattern {
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl ScanPattern {
+    /// Reward per unit of risk to the first profit target; see
+    /// [`toolkit::risk_reward`](crate::toolkit::risk_reward).
+    #[must_use]
+    pub fn risk_reward(&self) -> Option<f64> {
+        crate::toolkit::risk_reward(self.entry, self.stoploss, self.profit1)
+    }
+}
+
 /// Pattern recognition response.
 #[derive(Debug, Deserialize)]
 pub struct PatternRecognition {
@@ -60,6 +70,126 @@ pub struct SupportResistance {
     pub levels: Vec<f64>,
 }
 
+impl SupportResistance {
+    /// Lowest level strictly above `price`: the nearest resistance.
+    #[must_use]
+    pub fn nearest_above(&self, price: f64) -> Option<f64> {
+        self.resistances(price).first().copied()
+    }
+
+    /// Highest level strictly below `price`: the nearest support.
+    #[must_use]
+    pub fn nearest_below(&self, price: f64) -> Option<f64> {
+        self.supports(price).first().copied()
+    }
+
+    /// Levels strictly above `price`, nearest first.
+    #[must_use]
+    pub fn resistances(&self, price: f64) -> Vec<f64> {
+        let mut levels: Vec<f64> = self.levels.iter().copied().filter(|&l| l > price).collect();
+        levels.sort_by(f64::total_cmp);
+        levels
+    }
+
+    /// Levels strictly below `price`, nearest first.
+    #[must_use]
+    pub fn supports(&self, price: f64) -> Vec<f64> {
+        let mut levels: Vec<f64> = self.levels.iter().copied().filter(|&l| l < price).collect();
+        levels.sort_by(|a, b| b.total_cmp(a));
+        levels
+    }
+}
+
+/// Support and resistance levels returned for one date and resolution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSnapshot {
+    /// Date the levels were fetched.
+    pub date: NaiveDate,
+    /// Candle resolution the levels were computed on, e.g. `D`.
+    pub resolution: String,
+    /// The levels.
+    pub levels: Vec<f64>,
+}
+
+/// A price level that recurs across snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfluenceLevel {
+    /// Mean price of the clustered levels.
+    pub price: f64,
+    /// Lowest clustered level.
+    pub low: f64,
+    /// Highest clustered level.
+    pub high: f64,
+    /// Share of snapshots containing the level, from 0 to 1.
+    pub strength: f64,
+    /// Number of snapshots containing the level.
+    pub snapshots: usize,
+    /// Distinct dates the level appeared on.
+    pub dates: usize,
+    /// Resolutions the level appeared on.
+    pub resolutions: Vec<String>,
+    /// Most recent date the level appeared on.
+    pub last_seen: NaiveDate,
+}
+
+/// Cluster the levels of `snapshots` and rank them by how often they recur.
+///
+/// Levels within `tolerance` (a fraction of price, e.g. `0.005` for 0.5%) of
+/// a cluster's lowest level are merged. Clusters are ranked by strength, then
+/// by the number of resolutions they appear on, then by recency.
+#[must_use]
+pub fn confluence(snapshots: &[LevelSnapshot], tolerance: f64) -> Vec<ConfluenceLevel> {
+    let mut points: Vec<(f64, usize)> = snapshots
+        .iter()
+        .enumerate()
+        .flat_map(|(i, snapshot)| snapshot.levels.iter().map(move |&level| (level, i)))
+        .filter(|(level, _)| level.is_finite())
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut clusters: Vec<Vec<(f64, usize)>> = Vec::new();
+    for point in points {
+        match clusters.last_mut() {
+            Some(cluster) if point.0 - cluster[0].0 <= cluster[0].0.abs() * tolerance => {
+                cluster.push(point);
+            }
+            _ => clusters.push(vec![point]),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut ranked: Vec<ConfluenceLevel> = clusters
+        .into_iter()
+        .filter_map(|cluster| {
+            let members: BTreeSet<usize> = cluster.iter().map(|&(_, i)| i).collect();
+            let dates: BTreeSet<NaiveDate> = members.iter().map(|&i| snapshots[i].date).collect();
+            let resolutions: BTreeSet<&str> = members
+                .iter()
+                .map(|&i| snapshots[i].resolution.as_str())
+                .collect();
+            Some(ConfluenceLevel {
+                price: cluster.iter().map(|&(level, _)| level).sum::<f64>() / cluster.len() as f64,
+                low: cluster.first()?.0,
+                high: cluster.last()?.0,
+                strength: members.len() as f64 / snapshots.len() as f64,
+                snapshots: members.len(),
+                dates: dates.len(),
+                resolutions: resolutions.into_iter().map(str::to_string).collect(),
+                last_seen: *dates.last()?,
+            })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.strength
+            .total_cmp(&a.strength)
+            .then(b.resolutions.len().cmp(&a.resolutions.len()))
+            .then(b.last_seen.cmp(&a.last_seen))
+            .then(a.price.total_cmp(&b.price))
+    });
+    ranked
+}
+
 /// Indicator count.
 #[derive(Debug, Deserialize)]
 pub struct IndicatorCount {
@@ -98,3 +228,50 @@ pub struct AggregateIndicators {
     /// Trend information.
     pub trend: Trend,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confluence_ranks_recurring_levels() {
+        let d = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        let snapshot = |day, resolution: &str, levels: &[f64]| LevelSnapshot {
+            date: d(day),
+            resolution: resolution.to_string(),
+            levels: levels.to_vec(),
+        };
+        let snapshots = [
+            snapshot(1, "D", &[100.0, 120.0]),
+            snapshot(2, "D", &[100.3, 130.0]),
+            snapshot(2, "60", &[99.9, 120.2]),
+            snapshot(3, "D", &[100.1]),
+        ];
+
+        let ranked = confluence(&snapshots, 0.005);
+        assert_eq!(ranked.len(), 3);
+
+        let top = &ranked[0];
+        assert!((top.strength - 1.0).abs() < f64::EPSILON);
+        assert!((top.low - 99.9).abs() < f64::EPSILON && (top.high - 100.3).abs() < f64::EPSILON);
+        assert_eq!(top.dates, 3);
+        assert_eq!(top.resolutions, ["60", "D"]);
+        assert_eq!(top.last_seen, d(3));
+
+        assert!((ranked[1].price - 120.1).abs() < 1e-9);
+        assert_eq!(ranked[2].snapshots, 1);
+    }
+
+    #[test]
+    fn test_nearest_levels() {
+        let levels = SupportResistance {
+            levels: vec![110.0, 95.0, 100.0, 120.0, 90.0],
+        };
+        assert_eq!(levels.nearest_above(100.0), Some(110.0));
+        assert_eq!(levels.nearest_below(100.0), Some(95.0));
+        assert_eq!(levels.resistances(100.0), [110.0, 120.0]);
+        assert_eq!(levels.supports(100.0), [95.0, 90.0]);
+        assert_eq!(levels.nearest_above(130.0), None);
+        assert_eq!(levels.nearest_below(80.0), None);
+    }
+}
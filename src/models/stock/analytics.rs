@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Price target data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +26,22 @@ pub struct PriceTarget {
     pub last_updated: String,
 }
 
+impl fmt::Display for PriceTarget {
+    /// `AAPL target 150.00–250.00 (mean 200.00, median 205.00) as of 2024-01-02`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} target {:.2}–{:.2} (mean {:.2}, median {:.2}) as of {}",
+            self.symbol,
+            self.target_low,
+            self.target_high,
+            self.target_mean,
+            self.target_median,
+            self.last_updated
+        )
+    }
+}
+
 /// Recommendation trend data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecommendationTrend {
@@ -46,6 +63,54 @@ pub struct RecommendationTrend {
     pub symbol: String,
 }
 
+impl RecommendationTrend {
+    /// Mean rating on a scale from 1 (strong buy) to 5 (strong sell), or
+    /// `None` if no analysts cover the period.
+    #[must_use]
+    pub fn consensus_score(&self) -> Option<f64> {
+        let weights = [
+            (self.strong_buy, 1),
+            (self.buy, 2),
+            (self.hold, 3),
+            (self.sell, 4),
+            (self.strong_sell, 5),
+        ];
+        let count: i32 = weights.iter().map(|(n, _)| n).sum();
+        let total: i32 = weights.iter().map(|(n, w)| n * w).sum();
+        (count > 0).then(|| f64::from(total) / f64::from(count))
+    }
+
+    /// [`consensus_score`](Self::consensus_score) rounded to the nearest
+    /// rating: "Strong Buy", "Buy", "Hold", "Sell" or "Strong Sell".
+    #[must_use]
+    pub fn consensus(&self) -> Option<&'static str> {
+        let score = self.consensus_score()?;
+        Some(match score {
+            s if s < 1.5 => "Strong Buy",
+            s if s < 2.5 => "Buy",
+            s if s < 3.5 => "Hold",
+            s if s < 4.5 => "Sell",
+            _ => "Strong Sell",
+        })
+    }
+}
+
+impl fmt::Display for RecommendationTrend {
+    /// `AAPL 2024-01-01: Buy (1.93) – 12 strong buy, 24 buy, 7 hold, 1 sell, 0 strong sell`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: ", self.symbol, self.period)?;
+        match (self.consensus(), self.consensus_score()) {
+            (Some(consensus), Some(score)) => write!(f, "{consensus} ({score:.2})")?,
+            _ => write!(f, "no coverage")?,
+        }
+        write!(
+            f,
+            " – {} strong buy, {} buy, {} hold, {} sell, {} strong sell",
+            self.strong_buy, self.buy, self.hold, self.sell, self.strong_sell
+        )
+    }
+}
+
 /// Upgrade/downgrade data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpgradeDowngrade {
@@ -76,3 +141,50 @@ pub struct RevenueBreakdown {
     /// Revenue breakdown data.
     pub data: Vec<HashMap<String, serde_json::Value>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let target = PriceTarget {
+            symbol: "AAPL".to_string(),
+            target_high: 250.0,
+            target_low: 150.0,
+            target_mean: 200.0,
+            target_median: 205.0,
+            last_updated: "2024-01-02".to_string(),
+        };
+        assert_eq!(
+            target.to_string(),
+            "AAPL target 150.00–250.00 (mean 200.00, median 205.00) as of 2024-01-02"
+        );
+
+        let mut trend = RecommendationTrend {
+            buy: 24,
+            hold: 7,
+            period: "2024-01-01".to_string(),
+            sell: 1,
+            strong_buy: 12,
+            strong_sell: 0,
+            symbol: "AAPL".to_string(),
+        };
+        assert_eq!(
+            trend.to_string(),
+            "AAPL 2024-01-01: Buy (1.93) – 12 strong buy, 24 buy, 7 hold, 1 sell, 0 strong sell"
+        );
+
+        trend = RecommendationTrend {
+            buy: 0,
+            hold: 0,
+            sell: 0,
+            strong_buy: 0,
+            ..trend
+        };
+        assert_eq!(trend.consensus(), None);
+        assert!(trend
+            .to_string()
+            .starts_with("AAPL 2024-01-01: no coverage"));
+    }
+}
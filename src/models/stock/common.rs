@@ -1,5 +1,6 @@
 //! Common enums and types used across stock models.
 
+use chrono::{DateTime, Datelike, Months};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -46,3 +47,50 @@ impl fmt::Display for CandleResolution {
         }
     }
 }
+
+impl CandleResolution {
+    /// Start of the UTC bucket containing `timestamp` (UNIX seconds).
+    ///
+    /// Intraday buckets are aligned to multiples of their length since the
+    /// epoch, daily buckets to midnight, weekly buckets to Monday midnight and
+    /// monthly buckets to midnight on the 1st.
+    #[must_use]
+    pub fn bar_start(&self, timestamp: i64) -> i64 {
+        const DAY: i64 = 86_400;
+        let floor = |length: i64| timestamp - timestamp.rem_euclid(length);
+        match self {
+            CandleResolution::OneMinute => floor(60),
+            CandleResolution::FiveMinutes => floor(5 * 60),
+            CandleResolution::FifteenMinutes => floor(15 * 60),
+            CandleResolution::ThirtyMinutes => floor(30 * 60),
+            CandleResolution::SixtyMinutes => floor(60 * 60),
+            CandleResolution::Daily => floor(DAY),
+            // 1970-01-05, four days after the epoch, was a Monday.
+            CandleResolution::Weekly => timestamp - (timestamp - 4 * DAY).rem_euclid(7 * DAY),
+            CandleResolution::Monthly => DateTime::from_timestamp(timestamp, 0)
+                .and_then(|t| t.date_naive().with_day(1))
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map_or(floor(DAY), |t| t.and_utc().timestamp()),
+        }
+    }
+
+    /// UNIX time (seconds) at which a bar starting at `start` closes.
+    ///
+    /// Monthly bars close at the same day and time one calendar month later.
+    #[must_use]
+    pub fn bar_end(&self, start: i64) -> i64 {
+        const DAY: i64 = 86_400;
+        match self {
+            CandleResolution::OneMinute => start + 60,
+            CandleResolution::FiveMinutes => start + 5 * 60,
+            CandleResolution::FifteenMinutes => start + 15 * 60,
+            CandleResolution::ThirtyMinutes => start + 30 * 60,
+            CandleResolution::SixtyMinutes => start + 60 * 60,
+            CandleResolution::Daily => start + DAY,
+            CandleResolution::Weekly => start + 7 * DAY,
+            CandleResolution::Monthly => DateTime::from_timestamp(start, 0)
+                .and_then(|t| t.checked_add_months(Months::new(1)))
+                .map_or(start + 31 * DAY, |t| t.timestamp()),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Company information models.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::models::Count;
 
 /// Company profile data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,9 +23,14 @@ pub struct CompanyProfile {
     /// Market capitalization.
     #[serde(rename = "marketCapitalization")]
     pub market_capitalization: Option<f64>,
-    /// Number of shares outstanding.
-    #[serde(rename = "shareOutstanding")]
-    pub share_outstanding: Option<f64>,
+    /// Number of shares outstanding. The API reports it in millions; see
+    /// [`share_outstanding`](Self::share_outstanding).
+    #[serde(
+        rename = "shareOutstanding",
+        default,
+        with = "crate::models::count::millions"
+    )]
+    pub shares_outstanding: Option<Count>,
     /// Company logo URL.
     pub logo: Option<String>,
     /// Company phone number.
@@ -34,6 +42,14 @@ pub struct CompanyProfile {
     pub finnhub_industry: Option<String>,
 }
 
+impl CompanyProfile {
+    /// Shares outstanding in millions, as the API reports them.
+    #[must_use]
+    pub fn share_outstanding(&self) -> Option<f64> {
+        self.shares_outstanding.map(|count| count.as_f64() / 1e6)
+    }
+}
+
 /// Stock symbol information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Symbol {
@@ -57,3 +73,59 @@ pub struct Symbol {
     /// Currency.
     pub currency: Option<String>,
 }
+
+/// Security type accepted by the `securityType` filter of the symbols endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityType {
+    /// Common stock
+    CommonStock,
+    /// American depositary receipt
+    ADR,
+    /// Exchange-traded product (ETFs and ETNs)
+    ETP,
+    /// Real estate investment trust
+    REIT,
+    /// Preferred shares
+    Preference,
+    /// Closed-end fund
+    ClosedEndFund,
+    /// Open-end fund
+    OpenEndFund,
+    /// Unit
+    Unit,
+    /// Right
+    Right,
+    /// Warrant
+    Warrant,
+    /// Any other type, as spelled in [`Symbol::symbol_type`]
+    Other(String),
+}
+
+impl fmt::Display for SecurityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecurityType::CommonStock => write!(f, "Common Stock"),
+            SecurityType::ADR => write!(f, "ADR"),
+            SecurityType::ETP => write!(f, "ETP"),
+            SecurityType::REIT => write!(f, "REIT"),
+            SecurityType::Preference => write!(f, "Preference"),
+            SecurityType::ClosedEndFund => write!(f, "Closed-End Fund"),
+            SecurityType::OpenEndFund => write!(f, "Open-End Fund"),
+            SecurityType::Unit => write!(f, "Unit"),
+            SecurityType::Right => write!(f, "Right"),
+            SecurityType::Warrant => write!(f, "Warrant"),
+            SecurityType::Other(other) => write!(f, "{other}"),
+        }
+    }
+}
+
+/// Server-side filters for the symbols endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolFilter {
+    /// Market identifier code of the listing venue, e.g. `XNAS`.
+    pub mic: Option<String>,
+    /// Security type.
+    pub security_type: Option<SecurityType>,
+    /// Trading currency, e.g. `USD`.
+    pub currency: Option<String>,
+}
@@ -63,3 +63,126 @@ pub struct DividendV2 {
     /// Dividend amount.
     pub amount: f64,
 }
+
+/// Ticker changes within a date range, e.g. `FB` becoming `META`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolChanges {
+    /// Start of the range.
+    #[serde(rename = "fromDate")]
+    pub from_date: Option<String>,
+    /// End of the range.
+    #[serde(rename = "toDate")]
+    pub to_date: Option<String>,
+    /// Changes, at most 2000 per request.
+    #[serde(default)]
+    pub data: Vec<SymbolChange>,
+}
+
+/// A ticker change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolChange {
+    /// Date the new symbol took effect.
+    #[serde(rename = "atDate")]
+    pub at_date: String,
+    /// Symbol before the change.
+    #[serde(rename = "oldSymbol")]
+    pub old_symbol: String,
+    /// Symbol after the change.
+    #[serde(rename = "newSymbol")]
+    pub new_symbol: String,
+}
+
+impl SymbolChanges {
+    /// The symbol `symbol` trades under after every change in the range,
+    /// following chains of renames in date order. Returns `symbol` if it
+    /// wasn't renamed.
+    #[must_use]
+    pub fn latest_symbol<'a>(&'a self, symbol: &'a str) -> &'a str {
+        follow(
+            symbol,
+            self.data.iter().map(|c| {
+                (
+                    c.at_date.as_str(),
+                    c.old_symbol.as_str(),
+                    c.new_symbol.as_str(),
+                )
+            }),
+        )
+    }
+}
+
+/// ISIN changes within a date range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsinChanges {
+    /// Start of the range.
+    #[serde(rename = "fromDate")]
+    pub from_date: Option<String>,
+    /// End of the range.
+    #[serde(rename = "toDate")]
+    pub to_date: Option<String>,
+    /// Changes, at most 2000 per request.
+    #[serde(default)]
+    pub data: Vec<IsinChange>,
+}
+
+/// An ISIN change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsinChange {
+    /// Date the new ISIN took effect.
+    #[serde(rename = "atDate")]
+    pub at_date: String,
+    /// ISIN before the change.
+    #[serde(rename = "oldIsin")]
+    pub old_isin: String,
+    /// ISIN after the change.
+    #[serde(rename = "newIsin")]
+    pub new_isin: String,
+}
+
+impl IsinChanges {
+    /// The ISIN `isin` is known by after every change in the range,
+    /// following chains of changes in date order.
+    #[must_use]
+    pub fn latest_isin<'a>(&'a self, isin: &'a str) -> &'a str {
+        follow(
+            isin,
+            self.data
+                .iter()
+                .map(|c| (c.at_date.as_str(), c.old_isin.as_str(), c.new_isin.as_str())),
+        )
+    }
+}
+
+/// Apply `(date, old, new)` renames to `id` in date order.
+fn follow<'a>(id: &'a str, changes: impl Iterator<Item = (&'a str, &'a str, &'a str)>) -> &'a str {
+    let mut changes: Vec<_> = changes.collect();
+    // ISO dates sort chronologically as strings.
+    changes.sort_by_key(|&(date, _, _)| date);
+    changes.into_iter().fold(
+        id,
+        |current, (_, old, new)| if old == current { new } else { current },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_symbol_follows_renames_in_date_order() {
+        let changes: SymbolChanges = serde_json::from_value(serde_json::json!({
+            "fromDate": "2021-01-01",
+            "toDate": "2023-12-31",
+            "data": [
+                {"atDate": "2023-03-01", "oldSymbol": "XYZ", "newSymbol": "XYZQ"},
+                {"atDate": "2022-06-09", "oldSymbol": "FB", "newSymbol": "META"},
+                {"atDate": "2021-10-28", "oldSymbol": "ABC", "newSymbol": "XYZ"},
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(changes.latest_symbol("FB"), "META");
+        assert_eq!(changes.latest_symbol("ABC"), "XYZQ");
+        assert_eq!(changes.latest_symbol("AAPL"), "AAPL");
+    }
+}
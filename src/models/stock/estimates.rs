@@ -1,6 +1,9 @@
 //! Earnings and revenue estimates models.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::financials::{FinancialStatements, LineItem};
 
 /// EPS estimate data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -169,3 +172,263 @@ pub struct EarningsQualityScoreData {
     /// Overall score.
     pub score: Option<f64>,
 }
+
+/// Consensus estimate and reported actual for one fiscal period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EstimateComparison {
+    /// Fiscal period, e.g. `2024-06-30` or `2024Q2`.
+    pub period: String,
+    /// Consensus (average) estimate.
+    pub estimate: f64,
+    /// Reported value.
+    pub actual: f64,
+}
+
+impl EstimateComparison {
+    /// Actual minus estimate.
+    #[must_use]
+    pub fn error(&self) -> f64 {
+        self.actual - self.estimate
+    }
+
+    /// Error relative to the absolute estimate, or `None` for a zero estimate.
+    #[must_use]
+    pub fn percent_error(&self) -> Option<f64> {
+        (self.estimate != 0.0).then(|| self.error() / self.estimate.abs())
+    }
+}
+
+/// How well consensus estimates predicted reported results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EstimateAccuracy {
+    /// Periods with both an estimate and an actual, most recent first.
+    pub periods: Vec<EstimateComparison>,
+    /// Periods where the actual exceeded the estimate.
+    pub beats: usize,
+    /// Periods where the actual fell short of the estimate.
+    pub misses: usize,
+    /// Share of periods that beat, or `None` if there are no periods.
+    pub beat_rate: Option<f64>,
+    /// Mean of actual minus estimate.
+    pub mean_error: Option<f64>,
+    /// Mean absolute relative error, ignoring periods with a zero estimate.
+    pub mean_abs_percent_error: Option<f64>,
+}
+
+impl EstimateAccuracy {
+    /// Summarize a list of comparisons, most recent first.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_comparisons(periods: Vec<EstimateComparison>) -> Self {
+        let beats = periods.iter().filter(|p| p.actual > p.estimate).count();
+        let misses = periods.iter().filter(|p| p.actual < p.estimate).count();
+        let mean = |values: &[f64]| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+
+        let errors: Vec<f64> = periods.iter().map(EstimateComparison::error).collect();
+        let percent_errors: Vec<f64> = periods
+            .iter()
+            .filter_map(EstimateComparison::percent_error)
+            .map(f64::abs)
+            .collect();
+
+        Self {
+            beat_rate: (!periods.is_empty()).then(|| beats as f64 / periods.len() as f64),
+            mean_error: mean(&errors),
+            mean_abs_percent_error: mean(&percent_errors),
+            beats,
+            misses,
+            periods,
+        }
+    }
+}
+
+/// EPS and revenue estimate accuracy for a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EstimatesAccuracy {
+    /// Symbol.
+    pub symbol: String,
+    /// EPS estimates against reported EPS.
+    pub eps: EstimateAccuracy,
+    /// Revenue estimates against reported revenue.
+    pub revenue: EstimateAccuracy,
+}
+
+/// Consensus revenue estimate and reported revenue for one fiscal period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevenuePoint {
+    /// Fiscal period. The statement's period end date when revenue has been
+    /// reported, otherwise the estimate's period.
+    pub period: String,
+    /// Fiscal year.
+    pub year: i32,
+    /// Fiscal quarter (`0` for annual figures).
+    pub quarter: i32,
+    /// Average revenue estimate.
+    pub estimate_avg: Option<f64>,
+    /// High revenue estimate.
+    pub estimate_high: Option<f64>,
+    /// Low revenue estimate.
+    pub estimate_low: Option<f64>,
+    /// Reported revenue.
+    pub actual: Option<f64>,
+}
+
+impl RevenuePoint {
+    /// Reported minus average estimate, when both are known.
+    #[must_use]
+    pub fn surprise(&self) -> Option<f64> {
+        Some(self.actual? - self.estimate_avg?)
+    }
+
+    fn empty(period: &str, year: i32, quarter: i32) -> Self {
+        Self {
+            period: period.to_string(),
+            year,
+            quarter,
+            estimate_avg: None,
+            estimate_high: None,
+            estimate_low: None,
+            actual: None,
+        }
+    }
+}
+
+/// Revenue estimates lined up with reported revenue, oldest period first.
+///
+/// Periods are matched by fiscal year and quarter. Upcoming periods only have
+/// an estimate and periods older than the estimate history only have an
+/// actual, so the series can be plotted as two lines sharing one axis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevenueSeries {
+    /// Symbol.
+    pub symbol: String,
+    /// One point per fiscal period.
+    pub points: Vec<RevenuePoint>,
+}
+
+impl RevenueSeries {
+    /// Align revenue estimates with the revenue line of income statements of
+    /// the same frequency.
+    #[must_use]
+    pub fn from_parts(estimates: &RevenueEstimates, statements: &FinancialStatements) -> Self {
+        let mut points: BTreeMap<(i32, i32), RevenuePoint> = BTreeMap::new();
+        for estimate in &estimates.data {
+            let Some(year) = estimate.year else {
+                continue;
+            };
+            let quarter = estimate.quarter.unwrap_or(0);
+            let point = points
+                .entry((year, quarter))
+                .or_insert_with(|| RevenuePoint::empty(&estimate.period, year, quarter));
+            point.estimate_avg = estimate.revenue_avg;
+            point.estimate_high = estimate.revenue_high;
+            point.estimate_low = estimate.revenue_low;
+        }
+
+        for period in &statements.financials {
+            let field = |key: &str| {
+                period
+                    .get(key)
+                    .and_then(serde_json::Value::as_i64)
+                    .and_then(|value| i32::try_from(value).ok())
+            };
+            let (Some(year), Some(actual)) = (field("year"), LineItem::Revenue.value_in(period))
+            else {
+                continue;
+            };
+            let quarter = field("quarter").unwrap_or(0);
+            let end = period.get("period").and_then(serde_json::Value::as_str);
+            let point = points
+                .entry((year, quarter))
+                .or_insert_with(|| RevenuePoint::empty(end.unwrap_or_default(), year, quarter));
+            if let Some(end) = end {
+                point.period = end.to_string();
+            }
+            point.actual = Some(actual);
+        }
+
+        Self {
+            symbol: estimates.symbol.clone(),
+            points: points.into_values().collect(),
+        }
+    }
+
+    /// Points with both an average estimate and a reported value.
+    pub fn reported(&self) -> impl Iterator<Item = &RevenuePoint> {
+        self.points.iter().filter(|p| p.surprise().is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comparison(estimate: f64, actual: f64) -> EstimateComparison {
+        EstimateComparison {
+            period: String::new(),
+            estimate,
+            actual,
+        }
+    }
+
+    #[test]
+    fn test_estimate_accuracy() {
+        let accuracy = EstimateAccuracy::from_comparisons(vec![
+            comparison(1.0, 1.2),
+            comparison(2.0, 1.5),
+            comparison(0.0, 0.1),
+            comparison(1.0, 1.0),
+        ]);
+
+        assert_eq!(accuracy.beats, 2);
+        assert_eq!(accuracy.misses, 1);
+        assert_eq!(accuracy.beat_rate, Some(0.5));
+        assert!((accuracy.mean_error.unwrap() - (-0.05)).abs() < 1e-9);
+        // The zero estimate is skipped: (0.2 + 0.25 + 0.0) / 3.
+        assert!((accuracy.mean_abs_percent_error.unwrap() - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_revenue_series_alignment() {
+        let estimate = |year, quarter, avg| RevenueEstimate {
+            revenue_avg: Some(avg),
+            revenue_high: None,
+            revenue_low: None,
+            number_analysts: Some(10),
+            period: format!("{year}-Q{quarter}"),
+            year: Some(year),
+            quarter: Some(quarter),
+        };
+        let estimates = RevenueEstimates {
+            symbol: "TEST".to_string(),
+            data: vec![estimate(2024, 3, 105.0), estimate(2024, 2, 98.0)],
+            freq: Some("quarterly".to_string()),
+        };
+        let statements: FinancialStatements = serde_json::from_value(serde_json::json!({
+            "symbol": "TEST",
+            "financials": [
+                {"year": 2024, "quarter": 2, "period": "2024-06-30", "revenue": 100.0},
+                {"year": 2024, "quarter": 1, "period": "2024-03-31", "revenue": 95.0},
+            ],
+        }))
+        .unwrap();
+
+        let series = RevenueSeries::from_parts(&estimates, &statements);
+        let periods: Vec<_> = series.points.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(periods, ["2024-03-31", "2024-06-30", "2024-Q3"]);
+        assert_eq!(series.points[0].estimate_avg, None);
+        assert_eq!(series.points[2].actual, None);
+
+        let reported: Vec<_> = series.reported().collect();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].surprise(), Some(2.0));
+    }
+
+    #[test]
+    fn test_empty_accuracy() {
+        let accuracy = EstimateAccuracy::from_comparisons(Vec::new());
+        assert_eq!(accuracy, EstimateAccuracy::default());
+    }
+}
@@ -136,4 +136,207 @@ impl fmt::Display for StatementFrequency {
     }
 }
 
+/// Line item of a standardized financial statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineItem {
+    /// Total revenue (`revenue`, income statement).
+    Revenue,
+    /// Net income (`netIncome`, income statement).
+    NetIncome,
+    /// Free cash flow (`fcf`, cash flow statement).
+    FreeCashFlow,
+    /// Any other field, by its Finnhub key.
+    Other(String),
+}
+
+impl LineItem {
+    /// Keys under which this item may appear in a statement period.
+    fn keys(&self) -> &[&str] {
+        match self {
+            LineItem::Revenue => &["revenue", "totalRevenue"],
+            LineItem::NetIncome => &["netIncome"],
+            LineItem::FreeCashFlow => &["fcf", "freeCashFlow"],
+            LineItem::Other(_) => &[],
+        }
+    }
+
+    pub(crate) fn value_in(&self, period: &HashMap<String, serde_json::Value>) -> Option<f64> {
+        match self {
+            LineItem::Other(key) => period.get(key).and_then(serde_json::Value::as_f64),
+            _ => self
+                .keys()
+                .iter()
+                .find_map(|key| period.get(*key).and_then(serde_json::Value::as_f64)),
+        }
+    }
+}
+
+/// Period pairing used for growth calculations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthBasis {
+    /// Same period one year earlier.
+    YearOverYear,
+    /// Immediately preceding quarter. Only meaningful for quarterly statements.
+    QuarterOverQuarter,
+}
+
+/// Growth of a line item between two aligned periods.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrowthPoint {
+    /// Period end date of the current period.
+    pub period: Option<String>,
+    /// Fiscal year of the current period.
+    pub year: i64,
+    /// Fiscal quarter of the current period (`0` for annual statements).
+    pub quarter: i64,
+    /// Value in the current period.
+    pub value: f64,
+    /// Value in the comparison period.
+    pub previous: f64,
+    /// Relative change, e.g. `0.12` for 12% growth. Measured against the
+    /// absolute previous value so a loss shrinking reads as positive growth.
+    pub growth: f64,
+}
+
+/// Growth of one line item across a statement history, oldest period first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrowthSeries {
+    /// Line item the series was computed for.
+    pub item: LineItem,
+    /// Period pairing used.
+    pub basis: GrowthBasis,
+    /// Growth points for every period whose comparison period is present.
+    pub points: Vec<GrowthPoint>,
+}
+
+impl FinancialStatements {
+    /// Year-over-year growth of `item`.
+    #[must_use]
+    pub fn yoy_growth(&self, item: LineItem) -> GrowthSeries {
+        self.growth(item, GrowthBasis::YearOverYear)
+    }
+
+    /// Quarter-over-quarter growth of `item`.
+    #[must_use]
+    pub fn qoq_growth(&self, item: LineItem) -> GrowthSeries {
+        self.growth(item, GrowthBasis::QuarterOverQuarter)
+    }
+
+    /// Growth of `item` between periods aligned by fiscal year and quarter.
+    ///
+    /// Periods missing the item, lacking a `year`, or whose comparison value is
+    /// zero are skipped.
+    #[must_use]
+    pub fn growth(&self, item: LineItem, basis: GrowthBasis) -> GrowthSeries {
+        let mut periods: HashMap<(i64, i64), (Option<String>, f64)> = HashMap::new();
+        for period in &self.financials {
+            let Some(year) = period.get("year").and_then(serde_json::Value::as_i64) else {
+                continue;
+            };
+            let quarter = period
+                .get("quarter")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0);
+            if let Some(value) = item.value_in(period) {
+                let end = period
+                    .get("period")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string);
+                periods.insert((year, quarter), (end, value));
+            }
+        }
+
+        let mut keys: Vec<_> = periods.keys().copied().collect();
+        keys.sort_unstable();
+
+        let points = keys
+            .into_iter()
+            .filter_map(|(year, quarter)| {
+                let prior = match basis {
+                    GrowthBasis::YearOverYear => (year - 1, quarter),
+                    GrowthBasis::QuarterOverQuarter if quarter == 1 => (year - 1, 4),
+                    GrowthBasis::QuarterOverQuarter if quarter > 1 => (year, quarter - 1),
+                    GrowthBasis::QuarterOverQuarter => return None,
+                };
+                let (_, previous) = *periods.get(&prior)?;
+                if previous == 0.0 {
+                    return None;
+                }
+                let (period, value) = periods[&(year, quarter)].clone();
+                Some(GrowthPoint {
+                    period,
+                    year,
+                    quarter,
+                    value,
+                    previous,
+                    growth: (value - previous) / previous.abs(),
+                })
+            })
+            .collect();
+
+        GrowthSeries {
+            item,
+            basis,
+            points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
+    fn statements(periods: &[(i64, i64, f64)]) -> FinancialStatements {
+        FinancialStatements {
+            symbol: "TEST".to_string(),
+            financials: periods
+                .iter()
+                .map(|&(year, quarter, revenue)| {
+                    serde_json::from_value(json!({
+                        "year": year,
+                        "quarter": quarter,
+                        "period": format!("{year}-Q{quarter}"),
+                        "revenue": revenue,
+                    }))
+                    .unwrap()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_yoy_growth_aligns_quarters() {
+        // Newest first, as returned by the API, with 2023 Q3 missing.
+        let s = statements(&[
+            (2024, 4, 130.0),
+            (2024, 3, 120.0),
+            (2023, 4, 100.0),
+            (2023, 2, 90.0),
+        ]);
+        let series = s.yoy_growth(LineItem::Revenue);
+        assert_eq!(series.points.len(), 1);
+        let point = &series.points[0];
+        assert_eq!((point.year, point.quarter), (2024, 4));
+        assert!((point.growth - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_qoq_growth_wraps_year() {
+        let s = statements(&[(2024, 1, 110.0), (2023, 4, 100.0), (2023, 3, 50.0)]);
+        let series = s.qoq_growth(LineItem::Revenue);
+        let growth: Vec<_> = series.points.iter().map(|p| p.growth).collect();
+        assert_eq!(growth.len(), 2);
+        assert!((growth[0] - 1.0).abs() < 1e-9);
+        assert!((growth[1] - 0.1).abs() < 1e-9);
+        assert!(s.qoq_growth(LineItem::NetIncome).points.is_empty());
+    }
+
+    #[test]
+    fn test_annual_statements() {
+        let s = statements(&[(2024, 0, 80.0), (2023, 0, -100.0)]);
+        let series = s.yoy_growth(LineItem::Revenue);
+        assert!((series.points[0].growth - 1.8).abs() < 1e-9);
+        assert!(s.qoq_growth(LineItem::Revenue).points.is_empty());
+    }
+}
@@ -2,8 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::TimeSeries;
+
 /// Market cap data point.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketCapData {
     /// Date.
     #[serde(rename = "atDate")]
@@ -24,8 +26,19 @@ pub struct HistoricalMarketCapData {
     pub data: Vec<MarketCapData>,
 }
 
+impl From<HistoricalMarketCapData> for TimeSeries<f64> {
+    fn from(history: HistoricalMarketCapData) -> Self {
+        TimeSeries::from_date_strings(
+            history
+                .data
+                .into_iter()
+                .map(|point| (point.at_date, point.market_capitalization)),
+        )
+    }
+}
+
 /// Employee count data point.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EmployeeCountData {
     /// Date.
     #[serde(rename = "atDate")]
@@ -44,8 +57,19 @@ pub struct HistoricalEmployeeCount {
     pub data: Vec<EmployeeCountData>,
 }
 
+impl From<HistoricalEmployeeCount> for TimeSeries<i64> {
+    fn from(history: HistoricalEmployeeCount) -> Self {
+        TimeSeries::from_date_strings(
+            history
+                .data
+                .into_iter()
+                .map(|point| (point.at_date, point.employee_total)),
+        )
+    }
+}
+
 /// ESG score data point.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ESGData {
     /// Date.
     #[serde(rename = "atDate")]
@@ -73,6 +97,17 @@ pub struct HistoricalESG {
     pub data: Vec<ESGData>,
 }
 
+impl From<HistoricalESG> for TimeSeries<ESGData> {
+    fn from(history: HistoricalESG) -> Self {
+        TimeSeries::from_date_strings(
+            history
+                .data
+                .into_iter()
+                .map(|point| (point.at_date.clone(), point)),
+        )
+    }
+}
+
 /// Historical NBBO (National Best Bid and Offer) data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoricalNBBO {
@@ -1,6 +1,7 @@
 //! Insider activity models.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Insider transactions data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,8 +9,16 @@ pub struct InsiderTransaction {
     /// Insider name.
     pub name: String,
     /// Share number.
+    #[serde(
+        default,
+        deserialize_with = "crate::models::count::deserialize_option_i64"
+    )]
     pub share: Option<i64>,
     /// Change.
+    #[serde(
+        default,
+        deserialize_with = "crate::models::count::deserialize_option_i64"
+    )]
     pub change: Option<i64>,
     /// Filing date.
     #[serde(rename = "filingDate")]
@@ -25,6 +34,216 @@ pub struct InsiderTransaction {
     pub transaction_code: String,
 }
 
+impl InsiderTransaction {
+    /// Typed [`transaction_code`](Self::transaction_code).
+    #[must_use]
+    pub fn code(&self) -> TransactionCode {
+        TransactionCode::from_code(&self.transaction_code)
+    }
+
+    /// Whether this is a purchase on the open market or in a private deal
+    /// (code `P`), the clearest signal of insider conviction.
+    #[must_use]
+    pub fn is_open_market_buy(&self) -> bool {
+        self.code() == TransactionCode::Purchase
+    }
+
+    /// Whether this is a sale on the open market or in a private deal (code `S`).
+    #[must_use]
+    pub fn is_open_market_sale(&self) -> bool {
+        self.code() == TransactionCode::Sale
+    }
+
+    /// Whether this is an exercise or conversion of a derivative (codes `M`,
+    /// `X`, `O` and `C`).
+    #[must_use]
+    pub fn is_option_exercise(&self) -> bool {
+        matches!(
+            self.code(),
+            TransactionCode::Exercise
+                | TransactionCode::InTheMoneyExercise
+                | TransactionCode::OutOfTheMoneyExercise
+                | TransactionCode::Conversion
+        )
+    }
+
+    /// Whether the insider's holding grew.
+    #[must_use]
+    pub fn is_acquisition(&self) -> bool {
+        self.change.is_some_and(|change| change > 0)
+    }
+}
+
+/// SEC Form 4 transaction code.
+///
+/// See the SEC's Form 4 instructions, section 8, for the full definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransactionCode {
+    /// `P`: open market or private purchase.
+    Purchase,
+    /// `S`: open market or private sale.
+    Sale,
+    /// `A`: grant or award from the issuer.
+    Grant,
+    /// `D`: disposition to the issuer.
+    DispositionToIssuer,
+    /// `F`: payment of exercise price or tax by delivering securities.
+    TaxWithholding,
+    /// `I`: discretionary transaction.
+    Discretionary,
+    /// `M`: exercise or conversion of an exempt derivative.
+    Exercise,
+    /// `C`: conversion of a derivative.
+    Conversion,
+    /// `E`: expiration of a short derivative position.
+    ShortExpiration,
+    /// `H`: expiration or cancellation of a long derivative position.
+    LongExpiration,
+    /// `O`: exercise of an out-of-the-money derivative.
+    OutOfTheMoneyExercise,
+    /// `X`: exercise of an in-the-money or at-the-money derivative.
+    InTheMoneyExercise,
+    /// `G`: bona fide gift.
+    Gift,
+    /// `L`: small acquisition.
+    SmallAcquisition,
+    /// `W`: acquisition or disposition by will or the laws of descent.
+    Inheritance,
+    /// `Z`: deposit into or withdrawal from a voting trust.
+    VotingTrust,
+    /// `J`: other acquisition or disposition.
+    Other,
+    /// `K`: equity swap or similar instrument.
+    EquitySwap,
+    /// `U`: disposition in a change of control tender.
+    Tender,
+    /// `V`: voluntarily reported transaction.
+    Voluntary,
+    /// Any code not listed above, as reported.
+    Unknown(String),
+}
+
+impl TransactionCode {
+    /// Parse a code as reported, e.g. `P`.
+    #[must_use]
+    pub fn from_code(code: &str) -> Self {
+        match code.trim() {
+            "P" => Self::Purchase,
+            "S" => Self::Sale,
+            "A" => Self::Grant,
+            "D" => Self::DispositionToIssuer,
+            "F" => Self::TaxWithholding,
+            "I" => Self::Discretionary,
+            "M" => Self::Exercise,
+            "C" => Self::Conversion,
+            "E" => Self::ShortExpiration,
+            "H" => Self::LongExpiration,
+            "O" => Self::OutOfTheMoneyExercise,
+            "X" => Self::InTheMoneyExercise,
+            "G" => Self::Gift,
+            "L" => Self::SmallAcquisition,
+            "W" => Self::Inheritance,
+            "Z" => Self::VotingTrust,
+            "J" => Self::Other,
+            "K" => Self::EquitySwap,
+            "U" => Self::Tender,
+            "V" => Self::Voluntary,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+
+    /// The one-letter SEC code.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Purchase => "P",
+            Self::Sale => "S",
+            Self::Grant => "A",
+            Self::DispositionToIssuer => "D",
+            Self::TaxWithholding => "F",
+            Self::Discretionary => "I",
+            Self::Exercise => "M",
+            Self::Conversion => "C",
+            Self::ShortExpiration => "E",
+            Self::LongExpiration => "H",
+            Self::OutOfTheMoneyExercise => "O",
+            Self::InTheMoneyExercise => "X",
+            Self::Gift => "G",
+            Self::SmallAcquisition => "L",
+            Self::Inheritance => "W",
+            Self::VotingTrust => "Z",
+            Self::Other => "J",
+            Self::EquitySwap => "K",
+            Self::Tender => "U",
+            Self::Voluntary => "V",
+            Self::Unknown(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for TransactionCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Filters for insider transactions.
+///
+/// The date range is sent to the API; transaction codes are matched
+/// client-side since the API doesn't filter on them.
+///
+/// ```
+/// use finnhub::models::stock::{InsiderFilter, TransactionCode};
+///
+/// let filter = InsiderFilter::new()
+///     .from("2024-01-01")
+///     .codes([TransactionCode::Purchase, TransactionCode::Sale]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InsiderFilter {
+    /// Earliest filing date (`YYYY-MM-DD`).
+    pub from: Option<String>,
+    /// Latest filing date (`YYYY-MM-DD`).
+    pub to: Option<String>,
+    /// Transaction codes to keep; empty keeps all.
+    pub codes: Vec<TransactionCode>,
+}
+
+impl InsiderFilter {
+    /// A filter that keeps everything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only transactions filed on or after `date` (`YYYY-MM-DD`).
+    #[must_use]
+    pub fn from(mut self, date: impl Into<String>) -> Self {
+        self.from = Some(date.into());
+        self
+    }
+
+    /// Only transactions filed on or before `date` (`YYYY-MM-DD`).
+    #[must_use]
+    pub fn to(mut self, date: impl Into<String>) -> Self {
+        self.to = Some(date.into());
+        self
+    }
+
+    /// Only transactions with one of `codes`.
+    #[must_use]
+    pub fn codes(mut self, codes: impl IntoIterator<Item = TransactionCode>) -> Self {
+        self.codes.extend(codes);
+        self
+    }
+
+    /// Whether `transaction` passes the code filter.
+    #[must_use]
+    pub fn matches(&self, transaction: &InsiderTransaction) -> bool {
+        self.codes.is_empty() || self.codes.contains(&transaction.code())
+    }
+}
+
 /// Insider transactions response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InsiderTransactions {
@@ -44,6 +263,7 @@ pub struct InsiderSentiment {
     /// Month.
     pub month: i32,
     /// Change.
+    #[serde(deserialize_with = "crate::models::count::deserialize_i64")]
     pub change: i64,
     /// MSPR (Monthly Share Purchase Ratio).
     pub mspr: f64,
@@ -57,3 +277,37 @@ pub struct InsiderSentimentData {
     /// Insider sentiment data.
     pub data: Vec<InsiderSentiment>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(code: &str, change: i64) -> InsiderTransaction {
+        InsiderTransaction {
+            name: "Jane Doe".to_string(),
+            share: Some(1_000),
+            change: Some(change),
+            filing_date: "2024-01-03".to_string(),
+            transaction_date: "2024-01-02".to_string(),
+            transaction_price: 10.0,
+            transaction_code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_transaction_codes_and_filter() {
+        let buy = transaction("P", 500);
+        let exercise = transaction("M", 200);
+        let odd = transaction("Q", -1);
+
+        assert!(buy.is_open_market_buy() && buy.is_acquisition());
+        assert!(exercise.is_option_exercise() && !exercise.is_open_market_buy());
+        assert_eq!(odd.code(), TransactionCode::Unknown("Q".to_string()));
+        assert_eq!(TransactionCode::from_code("X").to_string(), "X");
+
+        let filter = InsiderFilter::new().codes([TransactionCode::Purchase]);
+        assert!(filter.matches(&buy));
+        assert!(!filter.matches(&exercise));
+        assert!(InsiderFilter::new().matches(&odd));
+    }
+}
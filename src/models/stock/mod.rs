@@ -1,32 +1,72 @@
 //! Stock market data models organized by category.
+//!
+//! Everything except [`common`] requires the `stock` feature.
 
+#[cfg(feature = "stock")]
 pub mod analytics;
 pub mod common;
+#[cfg(feature = "stock")]
 pub mod company;
+#[cfg(feature = "stock")]
 pub mod compliance;
+#[cfg(feature = "stock")]
 pub mod corporate_actions;
+#[cfg(feature = "stock")]
 pub mod estimates;
+#[cfg(feature = "stock")]
 pub mod filings;
+#[cfg(feature = "stock")]
 pub mod financials;
+#[cfg(feature = "stock")]
 pub mod historical;
+#[cfg(feature = "stock")]
 pub mod insider;
+#[cfg(feature = "stock")]
 pub mod market;
+#[cfg(feature = "stock")]
+pub mod normalization;
+#[cfg(feature = "stock")]
 pub mod ownership;
+#[cfg(feature = "stock")]
 pub mod price;
+#[cfg(feature = "stock")]
 pub mod sentiment;
+#[cfg(feature = "stock")]
+pub mod session;
+#[cfg(feature = "stock")]
+pub mod timeline;
 
 // Re-export all types for backward compatibility
+#[cfg(feature = "stock")]
 pub use analytics::*;
 pub use common::*;
+#[cfg(feature = "stock")]
 pub use company::*;
+#[cfg(feature = "stock")]
 pub use compliance::*;
+#[cfg(feature = "stock")]
 pub use corporate_actions::*;
+#[cfg(feature = "stock")]
 pub use estimates::*;
+#[cfg(feature = "stock")]
 pub use filings::*;
+#[cfg(feature = "stock")]
 pub use financials::*;
+#[cfg(feature = "stock")]
 pub use historical::*;
+#[cfg(feature = "stock")]
 pub use insider::*;
+#[cfg(feature = "stock")]
 pub use market::*;
+#[cfg(feature = "stock")]
+pub use normalization::*;
+#[cfg(feature = "stock")]
 pub use ownership::*;
+#[cfg(feature = "stock")]
 pub use price::*;
+#[cfg(feature = "stock")]
 pub use sentiment::*;
+#[cfg(feature = "stock")]
+pub use session::*;
+#[cfg(feature = "stock")]
+pub use timeline::*;
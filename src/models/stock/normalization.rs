@@ -0,0 +1,314 @@
+//! Unit and currency normalization of standardized financial statements.
+//!
+//! `/stock/financials` reports amounts in millions of the company's reporting
+//! currency, so statements of companies filing in different currencies can't
+//! be compared directly. [`FinancialStatements::normalized`] scales amounts to
+//! absolute units and [`FinancialStatements::normalized_to`] also converts them
+//! into a target currency. Every period lists the [`Conversion`]s applied to
+//! it, so a period left unconverted for lack of a rate is easy to spot.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::financials::FinancialStatements;
+
+/// Unit the amounts of a statement are reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitScale {
+    /// Absolute units.
+    Units,
+    /// Thousands.
+    Thousands,
+    /// Millions, as in standardized statements.
+    Millions,
+    /// Billions.
+    Billions,
+}
+
+impl UnitScale {
+    /// Multiplier to absolute units.
+    #[must_use]
+    pub fn factor(self) -> f64 {
+        match self {
+            UnitScale::Units => 1.0,
+            UnitScale::Thousands => 1e3,
+            UnitScale::Millions => 1e6,
+            UnitScale::Billions => 1e9,
+        }
+    }
+}
+
+/// How normalization treats a statement field, inferred from its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// An amount of money, e.g. `revenue`: scaled and converted.
+    Monetary,
+    /// An amount per share, e.g. `dilutedEPS`: converted but not scaled.
+    PerShare,
+    /// A share count, e.g. `dilutedAverageSharesOutstanding`: scaled but not
+    /// converted.
+    Shares,
+    /// A ratio or rate, e.g. `taxRate`: left as is.
+    Ratio,
+}
+
+impl ValueKind {
+    /// Classify a statement key.
+    #[must_use]
+    pub fn of(key: &str) -> Self {
+        let key = key.to_ascii_lowercase();
+        if key.ends_with("eps") || key.contains("pershare") {
+            ValueKind::PerShare
+        } else if key.contains("shares") {
+            ValueKind::Shares
+        } else if key.contains("ratio") || key.contains("margin") || key.ends_with("rate") {
+            ValueKind::Ratio
+        } else {
+            ValueKind::Monetary
+        }
+    }
+}
+
+/// A conversion applied to a normalized period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Conversion {
+    /// Amounts and share counts multiplied from `from` to absolute units.
+    Scaled {
+        /// Unit the statement was reported in.
+        from: UnitScale,
+    },
+    /// Amounts converted at `rate` units of `to` per unit of `from`, the rate
+    /// quoted for `date`.
+    Currency {
+        /// Reporting currency.
+        from: String,
+        /// Target currency.
+        to: String,
+        /// Exchange rate used.
+        rate: f64,
+        /// Date of the rate, the period end date.
+        date: String,
+    },
+}
+
+/// One statement period in absolute units.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedPeriod {
+    /// Period end date.
+    pub period: Option<String>,
+    /// Fiscal year.
+    pub year: Option<i64>,
+    /// Fiscal quarter, `0` or `None` for annual statements.
+    pub quarter: Option<i64>,
+    /// Currency of the amounts, `None` if the reporting currency is unknown.
+    pub currency: Option<String>,
+    /// Numeric fields by Finnhub key.
+    pub values: BTreeMap<String, f64>,
+    /// Conversions applied, in order.
+    pub conversions: Vec<Conversion>,
+}
+
+impl NormalizedPeriod {
+    /// Value of a field.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<f64> {
+        self.values.get(key).copied()
+    }
+
+    /// The exchange rate applied to the period, if it was converted.
+    #[must_use]
+    pub fn fx_rate(&self) -> Option<f64> {
+        self.conversions.iter().find_map(|c| match c {
+            Conversion::Currency { rate, .. } => Some(*rate),
+            Conversion::Scaled { .. } => None,
+        })
+    }
+}
+
+/// Standardized statements in absolute units, periods in response order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedStatements {
+    /// Symbol.
+    pub symbol: String,
+    /// Normalized periods.
+    pub periods: Vec<NormalizedPeriod>,
+}
+
+impl NormalizedStatements {
+    /// Periods whose amounts are not in `currency`, e.g. because no rate was
+    /// available for their date.
+    pub fn not_in<'a>(&'a self, currency: &'a str) -> impl Iterator<Item = &'a NormalizedPeriod> {
+        self.periods.iter().filter(move |p| {
+            !p.currency
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(currency))
+        })
+    }
+}
+
+impl FinancialStatements {
+    /// Scale amounts reported in `scale` to absolute units, tagging each
+    /// period with the reporting `currency`.
+    #[must_use]
+    pub fn normalized(&self, scale: UnitScale, currency: Option<&str>) -> NormalizedStatements {
+        NormalizedStatements {
+            symbol: self.symbol.clone(),
+            periods: self
+                .financials
+                .iter()
+                .map(|period| normalize_period(period, scale, currency))
+                .collect(),
+        }
+    }
+
+    /// Scale amounts to absolute units and convert them from `currency` to
+    /// `target`.
+    ///
+    /// `rate` is called with each period end date and returns the units of
+    /// `target` per unit of `currency` on that date. Periods without a date
+    /// or a rate keep their reporting currency and carry no
+    /// [`Conversion::Currency`].
+    #[must_use]
+    pub fn normalized_to(
+        &self,
+        scale: UnitScale,
+        currency: &str,
+        target: &str,
+        mut rate: impl FnMut(&str) -> Option<f64>,
+    ) -> NormalizedStatements {
+        let mut normalized = self.normalized(scale, Some(currency));
+        if currency.eq_ignore_ascii_case(target) {
+            return normalized;
+        }
+        for period in &mut normalized.periods {
+            let Some(date) = period.period.clone() else {
+                continue;
+            };
+            let Some(fx) = rate(&date) else {
+                continue;
+            };
+            for (key, value) in &mut period.values {
+                if matches!(
+                    ValueKind::of(key),
+                    ValueKind::Monetary | ValueKind::PerShare
+                ) {
+                    *value *= fx;
+                }
+            }
+            period.currency = Some(target.to_string());
+            period.conversions.push(Conversion::Currency {
+                from: currency.to_string(),
+                to: target.to_string(),
+                rate: fx,
+                date,
+            });
+        }
+        normalized
+    }
+}
+
+fn normalize_period(
+    period: &std::collections::HashMap<String, serde_json::Value>,
+    scale: UnitScale,
+    currency: Option<&str>,
+) -> NormalizedPeriod {
+    let factor = scale.factor();
+    let values = period
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "year" | "quarter"))
+        .filter_map(|(key, value)| {
+            let value = value.as_f64()?;
+            let value = match ValueKind::of(key) {
+                ValueKind::Monetary | ValueKind::Shares => value * factor,
+                ValueKind::PerShare | ValueKind::Ratio => value,
+            };
+            Some((key.clone(), value))
+        })
+        .collect();
+    let conversions = if scale == UnitScale::Units {
+        Vec::new()
+    } else {
+        vec![Conversion::Scaled { from: scale }]
+    };
+
+    NormalizedPeriod {
+        period: period
+            .get("period")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+        year: period.get("year").and_then(serde_json::Value::as_i64),
+        quarter: period.get("quarter").and_then(serde_json::Value::as_i64),
+        currency: currency.map(str::to_string),
+        values,
+        conversions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn statements() -> FinancialStatements {
+        serde_json::from_value(json!({
+            "symbol": "SAP",
+            "financials": [
+                {"period": "2023-12-31", "year": 2023, "revenue": 31207.0,
+                 "dilutedEPS": 5.16, "dilutedAverageSharesOutstanding": 1170.0, "taxRate": 0.27},
+                {"period": "2022-12-31", "year": 2022, "revenue": 30871.0,
+                 "dilutedEPS": 2.0, "dilutedAverageSharesOutstanding": 1176.0, "taxRate": 0.3},
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_value_kinds() {
+        let kinds: Vec<_> = [
+            "revenue",
+            "dilutedEPS",
+            "bookValuePerShare",
+            "dilutedAverageSharesOutstanding",
+            "taxRate",
+            "grossMargin",
+        ]
+        .into_iter()
+        .map(ValueKind::of)
+        .collect();
+        assert_eq!(
+            kinds,
+            [
+                ValueKind::Monetary,
+                ValueKind::PerShare,
+                ValueKind::PerShare,
+                ValueKind::Shares,
+                ValueKind::Ratio,
+                ValueKind::Ratio,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalized_to_scales_and_converts() {
+        let normalized = statements().normalized_to(UnitScale::Millions, "EUR", "USD", |date| {
+            (date == "2023-12-31").then_some(1.1)
+        });
+
+        let latest = &normalized.periods[0];
+        assert_eq!(latest.currency.as_deref(), Some("USD"));
+        assert!((latest.get("revenue").unwrap() - 31_207e6 * 1.1).abs() < 1.0);
+        assert!((latest.get("dilutedEPS").unwrap() - 5.676).abs() < 1e-9);
+        assert!((latest.get("dilutedAverageSharesOutstanding").unwrap() - 1_170e6).abs() < 1.0);
+        assert!((latest.get("taxRate").unwrap() - 0.27).abs() < 1e-12);
+        assert_eq!(latest.get("year"), None);
+        assert_eq!(latest.fx_rate(), Some(1.1));
+        assert_eq!(latest.conversions.len(), 2);
+
+        // No rate for 2022: scaled, but still in euros.
+        let unconverted: Vec<_> = normalized.not_in("USD").collect();
+        assert_eq!(unconverted.len(), 1);
+        assert_eq!(unconverted[0].year, Some(2022));
+        assert_eq!(unconverted[0].currency.as_deref(), Some("EUR"));
+        assert!((unconverted[0].get("revenue").unwrap() - 30_871e6).abs() < 1.0);
+    }
+}
@@ -8,8 +8,13 @@ pub struct Ownership {
     /// Name.
     pub name: String,
     /// Share.
+    #[serde(deserialize_with = "crate::models::count::deserialize_i64")]
     pub share: i64,
     /// Change.
+    #[serde(
+        default,
+        deserialize_with = "crate::models::count::deserialize_option_i64"
+    )]
     pub change: Option<i64>,
     /// Filing date.
     #[serde(rename = "filingDate")]
@@ -40,8 +45,10 @@ pub struct FundOwner {
     /// Name of the fund.
     pub name: String,
     /// Number of shares held.
+    #[serde(deserialize_with = "crate::models::count::deserialize_i64")]
     pub share: i64,
     /// Change in shares from previous period.
+    #[serde(deserialize_with = "crate::models::count::deserialize_i64")]
     pub change: i64,
     /// Filing date.
     #[serde(rename = "filingDate")]
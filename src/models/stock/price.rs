@@ -1,6 +1,11 @@
 //! Price and market data models.
 
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+use super::{CandleResolution, MarketStatus};
+use crate::models::Count;
 
 /// Stock quote data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,11 +13,12 @@ pub struct Quote {
     /// Current price.
     #[serde(rename = "c")]
     pub current_price: f64,
-    /// Change.
-    #[serde(rename = "d")]
+    /// Change. Off-hours the API may send 0 or `null`, read as 0; see
+    /// [`Quote::computed_change`].
+    #[serde(rename = "d", default, deserialize_with = "null_as_zero")]
     pub change: f64,
-    /// Percent change.
-    #[serde(rename = "dp")]
+    /// Percent change. Off-hours the API may send 0 or `null`, read as 0.
+    #[serde(rename = "dp", default, deserialize_with = "null_as_zero")]
     pub percent_change: f64,
     /// High price of the day.
     #[serde(rename = "h")]
@@ -31,6 +37,41 @@ pub struct Quote {
     pub timestamp: i64,
 }
 
+impl Quote {
+    /// Quote time in UTC, or `None` if the timestamp is out of range.
+    #[must_use]
+    pub fn timestamp_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.timestamp, 0)
+    }
+
+    /// Quote time in the exchange's timezone, e.g. `chrono_tz::America::New_York`.
+    #[must_use]
+    pub fn timestamp_local<Tz: TimeZone>(&self, exchange_tz: &Tz) -> Option<DateTime<Tz>> {
+        self.timestamp_utc()
+            .map(|utc| utc.with_timezone(exchange_tz))
+    }
+
+    /// Change and percent change since the previous close.
+    ///
+    /// Uses the API's `d`/`dp` fields, unless they are both zero while the
+    /// price has moved, as happens off-hours; then they are derived from the
+    /// current price and previous close. The percent change is 0 when there
+    /// is no previous close.
+    #[must_use]
+    pub fn computed_change(&self) -> (f64, f64) {
+        let reported = self.change != 0.0 || self.percent_change != 0.0;
+        if reported || self.previous_close == 0.0 {
+            return (self.change, self.percent_change);
+        }
+        let change = self.current_price - self.previous_close;
+        (change, change / self.previous_close * 100.0)
+    }
+}
+
+fn null_as_zero<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Last bid-ask data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BidAsk {
@@ -51,6 +92,40 @@ pub struct BidAsk {
     pub timestamp: Option<i64>,
 }
 
+impl BidAsk {
+    /// Reference time in UTC, if present and in range.
+    #[must_use]
+    pub fn timestamp_utc(&self) -> Option<DateTime<Utc>> {
+        self.timestamp.and_then(DateTime::from_timestamp_millis)
+    }
+
+    /// Reference time in the exchange's timezone.
+    #[must_use]
+    pub fn timestamp_local<Tz: TimeZone>(&self, exchange_tz: &Tz) -> Option<DateTime<Tz>> {
+        self.timestamp_utc()
+            .map(|utc| utc.with_timezone(exchange_tz))
+    }
+}
+
+impl fmt::Display for Quote {
+    /// `182.52 +1.23 (+0.68%) O 181.00 H 183.10 L 180.90 PC 181.29`, with the
+    /// change from [`Quote::computed_change`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (change, percent_change) = self.computed_change();
+        write!(
+            f,
+            "{:.2} {:+.2} ({:+.2}%) O {:.2} H {:.2} L {:.2} PC {:.2}",
+            self.current_price,
+            change,
+            percent_change,
+            self.open,
+            self.high,
+            self.low,
+            self.previous_close
+        )
+    }
+}
+
 /// Stock candles (OHLCV) data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockCandles {
@@ -72,9 +147,48 @@ pub struct StockCandles {
     /// List of timestamps.
     #[serde(rename = "t")]
     pub timestamp: Vec<i64>,
-    /// List of volume data.
+    /// List of volume data, in shares.
     #[serde(rename = "v")]
-    pub volume: Vec<f64>,
+    pub volume: Vec<Count>,
+}
+
+impl StockCandles {
+    /// [`volume`](Self::volume) as `f64`s, for arithmetic with prices.
+    #[must_use]
+    pub fn volume_f64(&self) -> Vec<f64> {
+        self.volume.iter().map(|volume| volume.as_f64()).collect()
+    }
+
+    /// Whether the last bar is still forming.
+    ///
+    /// A bar is incomplete while the market is open and the exchange clock in
+    /// `status` is before the bar's close. Requesting candles up to "now"
+    /// during a session returns such a bar; its close, high, low and volume
+    /// will still change.
+    #[must_use]
+    pub fn last_bar_incomplete(&self, resolution: CandleResolution, status: &MarketStatus) -> bool {
+        self.timestamp
+            .last()
+            .is_some_and(|&start| status.is_open && status.timestamp < resolution.bar_end(start))
+    }
+
+    /// Drop the last bar if it is still forming, so only final bars remain.
+    #[must_use]
+    pub fn completed(mut self, resolution: CandleResolution, status: &MarketStatus) -> Self {
+        if self.last_bar_incomplete(resolution, status) {
+            for series in [
+                &mut self.close,
+                &mut self.high,
+                &mut self.low,
+                &mut self.open,
+            ] {
+                series.pop();
+            }
+            self.volume.pop();
+            self.timestamp.pop();
+        }
+        self
+    }
 }
 
 /// Tick data.
@@ -89,9 +203,9 @@ pub struct TickData {
     pub count: i64,
     /// Total number of ticks for that date.
     pub total: i64,
-    /// List of volume data.
+    /// List of volume data, in shares.
     #[serde(rename = "v")]
-    pub volume: Vec<f64>,
+    pub volume: Vec<Count>,
     /// List of price data.
     #[serde(rename = "p")]
     pub price: Vec<f64>,
@@ -106,6 +220,64 @@ pub struct TickData {
     pub conditions: Option<Vec<Vec<String>>>,
 }
 
+impl TickData {
+    /// [`volume`](Self::volume) as `f64`s, for arithmetic with prices.
+    #[must_use]
+    pub fn volume_f64(&self) -> Vec<f64> {
+        self.volume.iter().map(|volume| volume.as_f64()).collect()
+    }
+
+    /// Iterate over the ticks as rows.
+    pub fn ticks(&self) -> impl Iterator<Item = Tick<'_>> {
+        let len = self
+            .timestamp
+            .len()
+            .min(self.price.len())
+            .min(self.volume.len());
+        (0..len).map(move |i| Tick {
+            price: self.price[i],
+            volume: self.volume[i],
+            timestamp: self.timestamp[i],
+            exchange: self.exchange.get(i).map(String::as_str),
+            conditions: self
+                .conditions
+                .as_ref()
+                .and_then(|c| c.get(i))
+                .map(Vec::as_slice),
+        })
+    }
+}
+
+/// A single trade from [`TickData`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick<'a> {
+    /// Price.
+    pub price: f64,
+    /// Volume, in shares.
+    pub volume: Count,
+    /// UNIX timestamp in ms.
+    pub timestamp: i64,
+    /// Venue.
+    pub exchange: Option<&'a str>,
+    /// Trade conditions.
+    pub conditions: Option<&'a [String]>,
+}
+
+impl Tick<'_> {
+    /// Trade time in UTC, or `None` if the timestamp is out of range.
+    #[must_use]
+    pub fn timestamp_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.timestamp)
+    }
+
+    /// Trade time in the exchange's timezone.
+    #[must_use]
+    pub fn timestamp_local<Tz: TimeZone>(&self, exchange_tz: &Tz) -> Option<DateTime<Tz>> {
+        self.timestamp_utc()
+            .map(|utc| utc.with_timezone(exchange_tz))
+    }
+}
+
 /// Price performance metrics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceMetrics {
@@ -159,3 +331,123 @@ pub struct PricePerformance {
     pub percent: f64,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_timestamp_conversions() {
+        let bid_ask = BidAsk {
+            bid: Some(1.0),
+            ask: Some(1.1),
+            bid_volume: None,
+            ask_volume: None,
+            timestamp: Some(1_700_000_000_123),
+        };
+        let utc = bid_ask.timestamp_utc().unwrap();
+        assert_eq!(utc.to_rfc3339(), "2023-11-14T22:13:20.123+00:00");
+
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let local = bid_ask.timestamp_local(&new_york).unwrap();
+        assert_eq!(local.to_rfc3339(), "2023-11-14T17:13:20.123-05:00");
+        assert_eq!(local, utc);
+    }
+
+    #[test]
+    fn test_tick_rows() {
+        let ticks = TickData {
+            symbol: "AAPL".to_string(),
+            skip: 0,
+            count: 2,
+            total: 2,
+            volume: vec![Count::new(100), Count::new(50)],
+            price: vec![190.0, 190.5],
+            timestamp: vec![1_700_000_000_000, 1_700_000_000_500],
+            exchange: vec!["Q".to_string(), "N".to_string()],
+            conditions: None,
+        };
+        let rows: Vec<_> = ticks.ticks().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].exchange, Some("N"));
+        assert_eq!(
+            rows[1].timestamp_utc().unwrap().timestamp_millis(),
+            1_700_000_000_500
+        );
+    }
+
+    #[test]
+    fn test_quote_computed_change_fallback() {
+        let quote: Quote = serde_json::from_value(serde_json::json!({
+            "c": 110.0, "d": null, "dp": null, "h": 0.0, "l": 0.0, "o": 0.0, "pc": 100.0, "t": 0
+        }))
+        .unwrap();
+        assert!(quote.change.abs() < f64::EPSILON);
+        let (change, percent) = quote.computed_change();
+        assert!((change - 10.0).abs() < 1e-9);
+        assert!((percent - 10.0).abs() < 1e-9);
+        assert!(quote.to_string().starts_with("110.00 +10.00 (+10.00%)"));
+
+        let reported = Quote {
+            change: 1.0,
+            percent_change: 1.0,
+            ..quote.clone()
+        };
+        assert_eq!(reported.computed_change(), (1.0, 1.0));
+        let no_close = Quote {
+            previous_close: 0.0,
+            ..quote
+        };
+        assert_eq!(no_close.computed_change(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_quote_display() {
+        let quote = Quote {
+            current_price: 182.52,
+            change: 1.23,
+            percent_change: 0.678,
+            high: 183.1,
+            low: 180.9,
+            open: 181.0,
+            previous_close: 181.29,
+            timestamp: 1_700_000_000,
+        };
+        assert_eq!(
+            quote.to_string(),
+            "182.52 +1.23 (+0.68%) O 181.00 H 183.10 L 180.90 PC 181.29"
+        );
+    }
+
+    #[test]
+    fn test_incomplete_last_bar() {
+        let day = 86_400;
+        let candles = StockCandles {
+            close: vec![1.0, 2.0],
+            high: vec![1.0, 2.0],
+            low: vec![1.0, 2.0],
+            open: vec![1.0, 2.0],
+            status: "ok".to_string(),
+            timestamp: vec![0, day],
+            volume: vec![Count::new(10), Count::new(20)],
+        };
+        let status = |is_open, timestamp| MarketStatus {
+            exchange: "US".to_string(),
+            holiday: None,
+            is_open,
+            session: None,
+            state: None,
+            timezone: "America/New_York".to_string(),
+            timestamp,
+        };
+
+        let open = status(true, day + 3600);
+        assert!(candles.last_bar_incomplete(CandleResolution::Daily, &open));
+        assert!(!candles.last_bar_incomplete(CandleResolution::Daily, &status(false, day + 3600)));
+        assert!(!candles.last_bar_incomplete(CandleResolution::OneMinute, &open));
+
+        let completed = candles.completed(CandleResolution::Daily, &open);
+        assert_eq!(completed.timestamp, [0]);
+        assert_eq!(completed.volume, [Count::new(10)]);
+    }
+}
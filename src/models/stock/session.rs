@@ -0,0 +1,371 @@
+//! Trading sessions for intraday data.
+//!
+//! Intraday candles and ticks include pre-market and after-hours trading,
+//! whose thin volume and wide spreads skew indicators computed over the
+//! regular session. A [`TradingCalendar`] classifies timestamps by session
+//! using the exchange's hours and holiday calendar, and
+//! [`StockCandles::split_sessions`] and [`TickData::split_sessions`] separate
+//! the data accordingly.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{MarketHoliday, StockCandles, TickData};
+
+/// The part of the trading day a timestamp falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Session {
+    /// Extended trading before the open.
+    PreMarket,
+    /// The regular session, from the open to the close.
+    Regular,
+    /// Extended trading after the close.
+    AfterHours,
+    /// Overnight, weekends and holidays.
+    Closed,
+}
+
+/// Session boundaries in exchange-local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionHours {
+    /// Start of pre-market trading.
+    pub pre_market_open: NaiveTime,
+    /// Start of the regular session.
+    pub regular_open: NaiveTime,
+    /// End of the regular session.
+    pub regular_close: NaiveTime,
+    /// End of after-hours trading.
+    pub after_hours_close: NaiveTime,
+}
+
+impl SessionHours {
+    /// US equities: pre-market from 04:00, regular 09:30–16:00 and
+    /// after-hours until 20:00, New York time.
+    #[must_use]
+    pub fn us_equities() -> Self {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap_or(NaiveTime::MIN);
+        Self {
+            pre_market_open: time(4, 0),
+            regular_open: time(9, 30),
+            regular_close: time(16, 0),
+            after_hours_close: time(20, 0),
+        }
+    }
+}
+
+impl Default for SessionHours {
+    fn default() -> Self {
+        Self::us_equities()
+    }
+}
+
+/// Classifies timestamps by trading session for one exchange.
+///
+/// `timezone` is the exchange's, e.g. `chrono_tz::America::New_York` for US
+/// equities. A [`FixedOffset`](chrono::FixedOffset) works too, but ignores
+/// daylight saving time, so a year of data shifts by an hour for part of it.
+///
+/// ```rust
+/// use chrono::FixedOffset;
+/// use finnhub::models::stock::{Session, SessionHours, TradingCalendar};
+///
+/// let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+/// let calendar = TradingCalendar::new(new_york, SessionHours::us_equities());
+/// // 2023-11-14 08:00 in New York.
+/// assert_eq!(calendar.session_at(1_699_966_800), Session::PreMarket);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TradingCalendar<Tz: TimeZone> {
+    timezone: Tz,
+    hours: SessionHours,
+    /// Regular session on holidays: `None` when closed all day, otherwise
+    /// the shortened open and close.
+    holidays: HashMap<NaiveDate, Option<(NaiveTime, NaiveTime)>>,
+}
+
+impl<Tz: TimeZone> TradingCalendar<Tz> {
+    /// A calendar open every weekday with `hours`, in `timezone`.
+    pub fn new(timezone: Tz, hours: SessionHours) -> Self {
+        Self {
+            timezone,
+            hours,
+            holidays: HashMap::new(),
+        }
+    }
+
+    /// Add the exchange's holidays, as returned by
+    /// `client.stock().market_holiday(exchange)`.
+    ///
+    /// Days without trading hours are closed. Half days close the regular
+    /// session early, e.g. at 13:00 for `09:30-13:00`; after-hours trading
+    /// then runs from the early close to the usual end. Entries whose date or
+    /// hours don't parse are skipped.
+    #[must_use]
+    pub fn with_holidays(mut self, holidays: &MarketHoliday) -> Self {
+        for holiday in &holidays.data {
+            let Ok(date) = NaiveDate::parse_from_str(&holiday.at_date, "%Y-%m-%d") else {
+                continue;
+            };
+            let hours = holiday.trading_hour.trim();
+            if hours.is_empty() {
+                self.holidays.insert(date, None);
+            } else if let Some(window) = parse_window(hours) {
+                self.holidays.insert(date, Some(window));
+            }
+        }
+        self
+    }
+
+    /// The session at `timestamp` (UNIX seconds).
+    #[must_use]
+    pub fn session_at(&self, timestamp: i64) -> Session {
+        let Some(utc) = DateTime::from_timestamp(timestamp, 0) else {
+            return Session::Closed;
+        };
+        let local = utc.with_timezone(&self.timezone).naive_local();
+        let (date, time) = (local.date(), local.time());
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return Session::Closed;
+        }
+
+        let (open, close) = match self.holidays.get(&date) {
+            Some(None) => return Session::Closed,
+            Some(Some(window)) => *window,
+            None => (self.hours.regular_open, self.hours.regular_close),
+        };
+        if time < self.hours.pre_market_open || time >= self.hours.after_hours_close {
+            Session::Closed
+        } else if time < open {
+            Session::PreMarket
+        } else if time < close {
+            Session::Regular
+        } else {
+            Session::AfterHours
+        }
+    }
+}
+
+/// Parse trading hours such as `09:30-13:00`.
+fn parse_window(hours: &str) -> Option<(NaiveTime, NaiveTime)> {
+    let (open, close) = hours.split_once('-')?;
+    let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+    Some((parse(open)?, parse(close)?))
+}
+
+/// Data separated by trading session. Data from outside all three sessions
+/// is dropped.
+#[derive(Debug, Clone)]
+pub struct SessionSplit<T> {
+    /// Pre-market data.
+    pub pre_market: T,
+    /// Regular session data.
+    pub regular: T,
+    /// After-hours data.
+    pub after_hours: T,
+}
+
+impl StockCandles {
+    /// The bars that start in `session`.
+    ///
+    /// Bars are classified by their start, so a bar straddling a session
+    /// boundary goes entirely to the session it starts in. Use a resolution
+    /// that divides the session boundaries, 30 minutes or finer for US
+    /// equities, to keep sessions apart. Daily and longer bars have no
+    /// sessions.
+    #[must_use]
+    pub fn in_session<Tz: TimeZone>(
+        &self,
+        calendar: &TradingCalendar<Tz>,
+        session: Session,
+    ) -> Self {
+        let len = [
+            self.close.len(),
+            self.high.len(),
+            self.low.len(),
+            self.open.len(),
+            self.volume.len(),
+        ]
+        .into_iter()
+        .fold(self.timestamp.len(), usize::min);
+        let keep: Vec<usize> = (0..len)
+            .filter(|&i| calendar.session_at(self.timestamp[i]) == session)
+            .collect();
+        let pick = |series: &[f64]| keep.iter().map(|&i| series[i]).collect();
+        Self {
+            close: pick(&self.close),
+            high: pick(&self.high),
+            low: pick(&self.low),
+            open: pick(&self.open),
+            status: self.status.clone(),
+            timestamp: keep.iter().map(|&i| self.timestamp[i]).collect(),
+            volume: keep.iter().map(|&i| self.volume[i]).collect(),
+        }
+    }
+
+    /// Split the bars into pre-market, regular and after-hours.
+    #[must_use]
+    pub fn split_sessions<Tz: TimeZone>(
+        &self,
+        calendar: &TradingCalendar<Tz>,
+    ) -> SessionSplit<Self> {
+        SessionSplit {
+            pre_market: self.in_session(calendar, Session::PreMarket),
+            regular: self.in_session(calendar, Session::Regular),
+            after_hours: self.in_session(calendar, Session::AfterHours),
+        }
+    }
+}
+
+impl TickData {
+    /// The trades made in `session`. `count` becomes the number of trades
+    /// kept; `skip` and `total` still describe the original request.
+    #[must_use]
+    pub fn in_session<Tz: TimeZone>(
+        &self,
+        calendar: &TradingCalendar<Tz>,
+        session: Session,
+    ) -> Self {
+        let len = self
+            .timestamp
+            .len()
+            .min(self.price.len())
+            .min(self.volume.len());
+        let keep: Vec<usize> = (0..len)
+            .filter(|&i| calendar.session_at(self.timestamp[i].div_euclid(1000)) == session)
+            .collect();
+        Self {
+            symbol: self.symbol.clone(),
+            skip: self.skip,
+            count: i64::try_from(keep.len()).unwrap_or(i64::MAX),
+            total: self.total,
+            volume: keep.iter().map(|&i| self.volume[i]).collect(),
+            price: keep.iter().map(|&i| self.price[i]).collect(),
+            timestamp: keep.iter().map(|&i| self.timestamp[i]).collect(),
+            exchange: keep
+                .iter()
+                .filter_map(|&i| self.exchange.get(i).cloned())
+                .collect(),
+            conditions: self.conditions.as_ref().map(|conditions| {
+                keep.iter()
+                    .filter_map(|&i| conditions.get(i).cloned())
+                    .collect()
+            }),
+        }
+    }
+
+    /// Split the trades into pre-market, regular and after-hours.
+    #[must_use]
+    pub fn split_sessions<Tz: TimeZone>(
+        &self,
+        calendar: &TradingCalendar<Tz>,
+    ) -> SessionSplit<Self> {
+        SessionSplit {
+            pre_market: self.in_session(calendar, Session::PreMarket),
+            regular: self.in_session(calendar, Session::Regular),
+            after_hours: self.in_session(calendar, Session::AfterHours),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::stock::Holiday;
+    use crate::models::Count;
+    use chrono::FixedOffset;
+
+    /// UNIX seconds for `date` at `hh:mm` New York standard time.
+    fn new_york(date: &str, time: &str) -> i64 {
+        let local = format!("{date} {time} -0500");
+        DateTime::parse_from_str(&local, "%Y-%m-%d %H:%M %z")
+            .unwrap()
+            .timestamp()
+    }
+
+    fn calendar() -> TradingCalendar<FixedOffset> {
+        let holidays = MarketHoliday {
+            exchange: "US".to_string(),
+            data: vec![
+                Holiday {
+                    event_name: "Thanksgiving Day".to_string(),
+                    at_date: "2023-11-23".to_string(),
+                    trading_hour: String::new(),
+                },
+                Holiday {
+                    event_name: "Thanksgiving Day".to_string(),
+                    at_date: "2023-11-24".to_string(),
+                    trading_hour: "09:30-13:00".to_string(),
+                },
+            ],
+            timezone: "America/New_York".to_string(),
+        };
+        TradingCalendar::new(
+            FixedOffset::west_opt(5 * 3600).unwrap(),
+            SessionHours::us_equities(),
+        )
+        .with_holidays(&holidays)
+    }
+
+    #[test]
+    fn test_session_at() {
+        let calendar = calendar();
+        let at = |date, time| calendar.session_at(new_york(date, time));
+
+        assert_eq!(at("2023-11-14", "03:59"), Session::Closed);
+        assert_eq!(at("2023-11-14", "04:00"), Session::PreMarket);
+        assert_eq!(at("2023-11-14", "09:30"), Session::Regular);
+        assert_eq!(at("2023-11-14", "15:59"), Session::Regular);
+        assert_eq!(at("2023-11-14", "16:00"), Session::AfterHours);
+        assert_eq!(at("2023-11-14", "20:00"), Session::Closed);
+        // Saturday, a full holiday and a half day.
+        assert_eq!(at("2023-11-18", "10:00"), Session::Closed);
+        assert_eq!(at("2023-11-23", "10:00"), Session::Closed);
+        assert_eq!(at("2023-11-24", "12:59"), Session::Regular);
+        assert_eq!(at("2023-11-24", "13:00"), Session::AfterHours);
+    }
+
+    #[test]
+    fn test_split_candles_and_ticks() {
+        let times = [
+            new_york("2023-11-14", "08:00"),
+            new_york("2023-11-14", "09:30"),
+            new_york("2023-11-14", "15:30"),
+            new_york("2023-11-14", "16:30"),
+            new_york("2023-11-14", "21:00"),
+        ];
+        let candles = StockCandles {
+            close: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            high: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            low: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            open: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            status: "ok".to_string(),
+            timestamp: times.to_vec(),
+            volume: [10, 20, 30, 40, 50].map(Count::new).to_vec(),
+        };
+        let split = candles.split_sessions(&calendar());
+        assert_eq!(split.pre_market.close, [1.0]);
+        assert_eq!(split.regular.timestamp, [times[1], times[2]]);
+        assert_eq!(split.regular.volume, [Count::new(20), Count::new(30)]);
+        assert_eq!(split.after_hours.close, [4.0]);
+
+        let ticks = TickData {
+            symbol: "AAPL".to_string(),
+            skip: 0,
+            count: 3,
+            total: 3,
+            volume: [100, 50, 25].map(Count::new).to_vec(),
+            price: vec![190.0, 190.5, 191.0],
+            timestamp: times[..3].iter().map(|t| t * 1000).collect(),
+            exchange: vec!["Q".to_string(), "N".to_string(), "P".to_string()],
+            conditions: Some(vec![vec!["12".to_string()], vec![], vec![]]),
+        };
+        let regular = ticks.in_session(&calendar(), Session::Regular);
+        assert_eq!(regular.count, 2);
+        assert_eq!(regular.total, 3);
+        assert_eq!(regular.price, [190.5, 191.0]);
+        assert_eq!(regular.exchange, ["N", "P"]);
+        assert_eq!(regular.conditions.unwrap().len(), 2);
+    }
+}
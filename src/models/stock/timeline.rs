@@ -0,0 +1,180 @@
+//! Company event timeline models.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+use super::{Dividend, Filing, StockSplit, UpgradeDowngrade};
+use crate::models::{calendar::EarningsRelease, news::CompanyNews, time_series::parse_date};
+
+/// Which sources a timeline includes. All are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TimelineSources {
+    /// Earnings releases from the earnings calendar.
+    pub earnings: bool,
+    /// Dividends, dated by ex-dividend date.
+    pub dividends: bool,
+    /// Stock splits.
+    pub splits: bool,
+    /// SEC filings.
+    pub filings: bool,
+    /// Analyst upgrades and downgrades.
+    pub rating_changes: bool,
+    /// Company news.
+    pub news: bool,
+}
+
+impl Default for TimelineSources {
+    fn default() -> Self {
+        Self {
+            earnings: true,
+            dividends: true,
+            splits: true,
+            filings: true,
+            rating_changes: true,
+            news: true,
+        }
+    }
+}
+
+impl TimelineSources {
+    /// No sources; enable the ones you need.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            earnings: false,
+            dividends: false,
+            splits: false,
+            filings: false,
+            rating_changes: false,
+            news: false,
+        }
+    }
+}
+
+/// The record behind a [`CompanyEvent`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum CompanyEventKind {
+    /// Earnings release.
+    Earnings(EarningsRelease),
+    /// Dividend.
+    Dividend(Dividend),
+    /// Stock split.
+    Split(StockSplit),
+    /// SEC filing.
+    Filing(Filing),
+    /// Analyst upgrade or downgrade.
+    RatingChange(UpgradeDowngrade),
+    /// News article.
+    News(CompanyNews),
+}
+
+/// A dated event in a company's history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanyEvent {
+    /// When the event happened. Sources that only report a date are placed
+    /// at midnight UTC.
+    pub time: DateTime<Utc>,
+    /// The event itself.
+    pub kind: CompanyEventKind,
+}
+
+impl CompanyEvent {
+    /// Date the event is placed on.
+    #[must_use]
+    pub fn date(&self) -> NaiveDate {
+        self.time.date_naive()
+    }
+
+    /// Place a source record on the timeline, or `None` if it carries no
+    /// usable date.
+    #[must_use]
+    pub fn from_kind(kind: CompanyEventKind) -> Option<Self> {
+        let midnight = |date: &str| parse_date(date).map(|d| d.and_time(NaiveTime::MIN).and_utc());
+        let time = match &kind {
+            CompanyEventKind::Earnings(e) => midnight(e.date.as_deref()?),
+            CompanyEventKind::Dividend(d) => {
+                midnight(d.ex_dividend_date.as_deref().unwrap_or(&d.pay_date))
+            }
+            CompanyEventKind::Split(s) => midnight(&s.date),
+            CompanyEventKind::Filing(f) => midnight(f.filed_date.as_deref()?),
+            CompanyEventKind::RatingChange(u) => DateTime::from_timestamp(u.grade_time, 0),
+            CompanyEventKind::News(n) => DateTime::from_timestamp(n.datetime, 0),
+        }?;
+        Some(Self { time, kind })
+    }
+}
+
+/// Events for one company, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompanyTimeline {
+    /// Events in chronological order.
+    pub events: Vec<CompanyEvent>,
+}
+
+impl CompanyTimeline {
+    /// Build a timeline from source records, dropping records without a date
+    /// and events outside `range`.
+    pub fn new(
+        records: impl IntoIterator<Item = CompanyEventKind>,
+        range: &RangeInclusive<NaiveDate>,
+    ) -> Self {
+        let mut events: Vec<_> = records
+            .into_iter()
+            .filter_map(CompanyEvent::from_kind)
+            .filter(|event| range.contains(&event.date()))
+            .collect();
+        events.sort_by_key(|event| event.time);
+        Self { events }
+    }
+
+    /// Events on `date`.
+    pub fn on(&self, date: NaiveDate) -> impl Iterator<Item = &CompanyEvent> {
+        self.events.iter().filter(move |event| event.date() == date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_orders_and_filters() {
+        let split: StockSplit = serde_json::from_value(serde_json::json!({
+            "symbol": "AAPL", "date": "2020-08-31", "fromFactor": 1.0, "toFactor": 4.0
+        }))
+        .unwrap();
+        let filing: Filing = serde_json::from_value(serde_json::json!({
+            "symbol": "AAPL", "form": "8-K", "filedDate": "2020-07-30 00:00:00"
+        }))
+        .unwrap();
+        let rating: UpgradeDowngrade = serde_json::from_value(serde_json::json!({
+            "symbol": "AAPL", "gradeTime": 1_596_153_600, "fromGrade": "Hold",
+            "toGrade": "Buy", "company": "Example", "action": "up"
+        }))
+        .unwrap();
+        let undated: Filing = serde_json::from_value(serde_json::json!({"form": "4"})).unwrap();
+        let early: StockSplit = serde_json::from_value(serde_json::json!({
+            "symbol": "AAPL", "date": "2014-06-09", "fromFactor": 1.0, "toFactor": 7.0
+        }))
+        .unwrap();
+
+        let d = |m, day| NaiveDate::from_ymd_opt(2020, m, day).unwrap();
+        let timeline = CompanyTimeline::new(
+            [
+                CompanyEventKind::Split(split),
+                CompanyEventKind::RatingChange(rating),
+                CompanyEventKind::Filing(filing),
+                CompanyEventKind::Filing(undated),
+                CompanyEventKind::Split(early),
+            ],
+            &(d(1, 1)..=d(12, 31)),
+        );
+
+        let dates: Vec<_> = timeline.events.iter().map(CompanyEvent::date).collect();
+        assert_eq!(dates, [d(7, 30), d(7, 31), d(8, 31)]);
+        assert_eq!(timeline.on(d(8, 31)).count(), 1);
+    }
+}
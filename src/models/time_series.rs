@@ -0,0 +1,256 @@
+//! Date-indexed series shared by the historical endpoints.
+//!
+//! Historical market cap, employee count, ESG, economic data and airline price
+//! index responses all convert into [`TimeSeries`], so they can be lined up
+//! against each other with one API:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use finnhub::models::TimeSeries;
+//!
+//! let d = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+//! let market_cap = TimeSeries::new([(d(1), 100.0), (d(2), 110.0), (d(3), 120.0)]);
+//! let employees = TimeSeries::new([(d(1), 1000)]);
+//!
+//! // Employee counts are sparse, so carry the last known value forward.
+//! let per_employee = market_cap
+//!     .align(&employees)
+//!     .map(|(cap, staff)| cap / f64::from(*staff));
+//! assert_eq!(per_employee.len(), 3);
+//! ```
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Values keyed by date, sorted ascending with at most one value per date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSeries<T> {
+    points: Vec<(NaiveDate, T)>,
+}
+
+impl<T> Default for TimeSeries<T> {
+    fn default() -> Self {
+        Self { points: Vec::new() }
+    }
+}
+
+impl<T> TimeSeries<T> {
+    /// Build a series from `(date, value)` pairs in any order. If a date
+    /// appears more than once the last value wins.
+    pub fn new(points: impl IntoIterator<Item = (NaiveDate, T)>) -> Self {
+        let mut points: Vec<_> = points.into_iter().collect();
+        points.sort_by_key(|(date, _)| *date);
+        // Keep the last of each run of equal dates; the sort is stable.
+        points.reverse();
+        points.dedup_by_key(|(date, _)| *date);
+        points.reverse();
+        Self { points }
+    }
+
+    /// Build a series from API date strings (`YYYY-MM-DD`, optionally followed
+    /// by a time). Entries whose date can't be parsed are skipped.
+    pub fn from_date_strings<S: AsRef<str>>(points: impl IntoIterator<Item = (S, T)>) -> Self {
+        Self::new(
+            points
+                .into_iter()
+                .filter_map(|(date, value)| Some((parse_date(date.as_ref())?, value))),
+        )
+    }
+
+    /// Number of points.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the series has no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Iterate over `(date, value)` pairs in date order.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, &T)> {
+        self.points.iter().map(|(date, value)| (*date, value))
+    }
+
+    /// Dates in order.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.points.iter().map(|(date, _)| *date)
+    }
+
+    /// Values in date order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.points.iter().map(|(_, value)| value)
+    }
+
+    /// Earliest point.
+    #[must_use]
+    pub fn first(&self) -> Option<(NaiveDate, &T)> {
+        self.points.first().map(|(date, value)| (*date, value))
+    }
+
+    /// Latest point.
+    #[must_use]
+    pub fn last(&self) -> Option<(NaiveDate, &T)> {
+        self.points.last().map(|(date, value)| (*date, value))
+    }
+
+    /// Value on exactly `date`.
+    #[must_use]
+    pub fn get(&self, date: NaiveDate) -> Option<&T> {
+        self.points
+            .binary_search_by_key(&date, |(d, _)| *d)
+            .ok()
+            .map(|i| &self.points[i].1)
+    }
+
+    /// Latest value on or before `date`.
+    #[must_use]
+    pub fn as_of(&self, date: NaiveDate) -> Option<&T> {
+        let end = self.points.partition_point(|(d, _)| *d <= date);
+        end.checked_sub(1).map(|i| &self.points[i].1)
+    }
+
+    /// Transform every value, keeping the dates.
+    #[must_use]
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> TimeSeries<U> {
+        TimeSeries {
+            points: self
+                .points
+                .iter()
+                .map(|(date, value)| (*date, f(value)))
+                .collect(),
+        }
+    }
+
+    /// Sliding windows of `size` consecutive points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = &[(NaiveDate, T)]> {
+        self.points.windows(size)
+    }
+}
+
+impl<T: Clone> TimeSeries<T> {
+    /// Points with `from <= date <= to`.
+    #[must_use]
+    pub fn window(&self, from: NaiveDate, to: NaiveDate) -> Self {
+        let start = self.points.partition_point(|(d, _)| *d < from);
+        let end = self.points.partition_point(|(d, _)| *d <= to);
+        Self {
+            points: self.points[start..end.max(start)].to_vec(),
+        }
+    }
+
+    /// Pair values on dates present in both series.
+    #[must_use]
+    pub fn join<U: Clone>(&self, other: &TimeSeries<U>) -> TimeSeries<(T, U)> {
+        TimeSeries {
+            points: self
+                .points
+                .iter()
+                .filter_map(|(date, value)| {
+                    other
+                        .get(*date)
+                        .map(|other| (*date, (value.clone(), other.clone())))
+                })
+                .collect(),
+        }
+    }
+
+    /// Pair every value with the latest value of `other` on or before the same
+    /// date. Points before `other` starts are dropped.
+    ///
+    /// Use this to line up datasets of different frequency, e.g. daily market
+    /// cap against quarterly employee counts.
+    #[must_use]
+    pub fn align<U: Clone>(&self, other: &TimeSeries<U>) -> TimeSeries<(T, U)> {
+        TimeSeries {
+            points: self
+                .points
+                .iter()
+                .filter_map(|(date, value)| {
+                    other
+                        .as_of(*date)
+                        .map(|other| (*date, (value.clone(), other.clone())))
+                })
+                .collect(),
+        }
+    }
+
+    /// Union of both series. Where both have a value for a date, `other` wins.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::new(self.points.iter().chain(&other.points).cloned())
+    }
+}
+
+impl<T> IntoIterator for TimeSeries<T> {
+    type Item = (NaiveDate, T);
+    type IntoIter = std::vec::IntoIter<(NaiveDate, T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.into_iter()
+    }
+}
+
+impl<T> FromIterator<(NaiveDate, T)> for TimeSeries<T> {
+    fn from_iter<I: IntoIterator<Item = (NaiveDate, T)>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Parse the date part of an API date or datetime string.
+pub(crate) fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    #[test]
+    fn test_new_sorts_and_dedups() {
+        let series = TimeSeries::new([(d(3), 'c'), (d(1), 'a'), (d(3), 'z')]);
+        assert_eq!(series.dates().collect::<Vec<_>>(), vec![d(1), d(3)]);
+        assert_eq!(series.get(d(3)), Some(&'z'));
+    }
+
+    #[test]
+    fn test_from_date_strings() {
+        let series = TimeSeries::from_date_strings([
+            ("2024-01-02 00:00:00", 2),
+            ("2024-01-01", 1),
+            ("n/a", 0),
+        ]);
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.first(), Some((d(1), &1)));
+    }
+
+    #[test]
+    fn test_window_join_align_merge() {
+        let a = TimeSeries::new([(d(1), 1), (d(2), 2), (d(4), 4)]);
+        let b = TimeSeries::new([(d(2), 20), (d(3), 30)]);
+
+        assert_eq!(a.window(d(2), d(3)).len(), 1);
+        assert!(a.window(d(5), d(1)).is_empty());
+
+        assert_eq!(a.join(&b), TimeSeries::new([(d(2), (2, 20))]));
+        assert_eq!(
+            a.align(&b),
+            TimeSeries::new([(d(2), (2, 20)), (d(4), (4, 30))])
+        );
+        assert_eq!(
+            a.merge(&b),
+            TimeSeries::new([(d(1), 1), (d(2), 20), (d(3), 30), (d(4), 4)])
+        );
+        assert_eq!(a.windows(2).count(), 2);
+    }
+}
@@ -0,0 +1,207 @@
+//! Bulk requests that keep going when some items fail.
+//!
+//! Fetching a quote for each of 500 symbols shouldn't fail as a whole because
+//! one symbol was delisted. Bulk helpers such as
+//! [`StockEndpoints::quotes`](crate::endpoints::StockEndpoints::quotes) return
+//! a [`PartialResult`] with every success and the error of each item that
+//! failed, which can then be retried on its own:
+//!
+//! ```rust,no_run
+//! use finnhub::FinnhubClient;
+//!
+//! # async fn run() {
+//! let client = FinnhubClient::new("your-api-key");
+//! let quotes = client.stock().quotes(&["AAPL", "MSFT", "NVDA"]).await;
+//! let quotes = quotes
+//!     .retry_failed(4, |symbol| {
+//!         let client = client.clone();
+//!         async move { client.stock().quote(&symbol).await }
+//!     })
+//!     .await;
+//! println!("{} quotes, {} failed", quotes.success_count(), quotes.error_count());
+//! # }
+//! ```
+//!
+//! [`fetch_all`] runs any per-item request this way.
+
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+
+use crate::error::{Error, Result};
+
+/// Requests in flight for bulk helpers that take no concurrency option.
+/// Requests still go through the rate limiter.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Successes and per-item errors of a bulk request.
+#[derive(Debug)]
+pub struct PartialResult<T, K = String> {
+    /// Values of the items that succeeded.
+    pub values: HashMap<K, T>,
+    /// Items that failed, with their errors.
+    pub errors: Vec<(K, Error)>,
+}
+
+impl<T, K> Default for PartialResult<T, K> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<T, K: Hash + Eq> PartialResult<T, K> {
+    /// Record the outcome of one item.
+    pub fn push(&mut self, key: K, result: Result<T>) {
+        match result {
+            Ok(value) => {
+                self.values.insert(key, value);
+            }
+            Err(err) => self.errors.push((key, err)),
+        }
+    }
+
+    /// Value of an item that succeeded.
+    pub fn get(&self, key: &K) -> Option<&T> {
+        self.values.get(key)
+    }
+
+    /// Values of the items that succeeded, in no particular order.
+    pub fn ok_values(&self) -> impl Iterator<Item = &T> {
+        self.values.values()
+    }
+
+    /// Items that failed.
+    pub fn failed_keys(&self) -> impl Iterator<Item = &K> {
+        self.errors.iter().map(|(key, _)| key)
+    }
+
+    /// Number of items that succeeded.
+    #[must_use]
+    pub fn success_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Number of items that failed.
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Whether every item succeeded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// All values, or the first error if any item failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failed item.
+    pub fn into_result(self) -> Result<HashMap<K, T>> {
+        match self.errors.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(self.values),
+        }
+    }
+
+    /// Fetch the failed items again, up to `concurrency` at a time, and merge
+    /// the outcome. Items that fail again keep their new error.
+    pub async fn retry_failed<F, Fut>(mut self, concurrency: usize, fetch: F) -> Self
+    where
+        K: Clone,
+        F: FnMut(K) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let keys = std::mem::take(&mut self.errors)
+            .into_iter()
+            .map(|(key, _)| key);
+        let retried = fetch_all(keys, concurrency, fetch).await;
+        self.values.extend(retried.values);
+        self.errors = retried.errors;
+        self
+    }
+}
+
+impl<T, K: Hash + Eq> Extend<(K, Result<T>)> for PartialResult<T, K> {
+    fn extend<I: IntoIterator<Item = (K, Result<T>)>>(&mut self, iter: I) {
+        for (key, result) in iter {
+            self.push(key, result);
+        }
+    }
+}
+
+impl<T, K: Hash + Eq> FromIterator<(K, Result<T>)> for PartialResult<T, K> {
+    fn from_iter<I: IntoIterator<Item = (K, Result<T>)>>(iter: I) -> Self {
+        let mut partial = Self::default();
+        partial.extend(iter);
+        partial
+    }
+}
+
+/// Run `fetch` for every key, up to `concurrency` at a time, and collect the
+/// outcomes without stopping at failures.
+///
+/// Duplicate keys are fetched once.
+pub async fn fetch_all<K, T, F, Fut>(
+    keys: impl IntoIterator<Item = K>,
+    concurrency: usize,
+    mut fetch: F,
+) -> PartialResult<T, K>
+where
+    K: Hash + Eq + Clone,
+    F: FnMut(K) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut seen = HashSet::new();
+    let unique: Vec<K> = keys
+        .into_iter()
+        .filter(|key| seen.insert(key.clone()))
+        .collect();
+    stream::iter(unique)
+        .map(|key| {
+            let request = fetch(key.clone());
+            async move { (key, request.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(key: &str) -> Result<usize> {
+        if key.starts_with('!') {
+            Err(Error::InvalidParameter(key.to_string()))
+        } else {
+            Ok(key.len())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_keeps_successes() {
+        let keys = ["a", "bb", "!c", "bb", "!dd"].map(String::from);
+        let partial = fetch_all(keys, 2, |key| std::future::ready(lookup(&key))).await;
+
+        assert_eq!(partial.success_count(), 2);
+        assert_eq!(partial.error_count(), 2);
+        assert_eq!(partial.get(&"bb".to_string()), Some(&2));
+        assert_eq!(partial.ok_values().sum::<usize>(), 3);
+        assert!(!partial.is_complete());
+
+        let retried = partial
+            .retry_failed(2, |key| {
+                std::future::ready(lookup(key.trim_start_matches('!')))
+            })
+            .await;
+        assert!(retried.is_complete());
+        assert_eq!(retried.get(&"!dd".to_string()), Some(&2));
+        assert_eq!(retried.into_result().unwrap().len(), 4);
+    }
+}
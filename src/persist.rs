@@ -0,0 +1,172 @@
+//! Pluggable storage for state that should survive restarts.
+//!
+//! Watchers that compare current data against what they saw before keep that
+//! baseline in a [`StateStore`]. [`MemoryStore`] is enough for a single
+//! process; [`FileStore`] writes one JSON file per key. Implement the trait to
+//! keep state in a database or object store instead.
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::Error;
+use crate::error::Result;
+
+/// Key-value storage of JSON documents.
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Load the value stored under `key`, if any.
+    async fn load(&self, key: &str) -> Result<Option<serde_json::Value>>;
+
+    /// Store `value` under `key`, replacing any previous value.
+    async fn save(&self, key: &str, value: serde_json::Value) -> Result<()>;
+}
+
+/// Load and deserialize the value stored under `key`.
+///
+/// # Errors
+///
+/// Returns the store's error, or [`Error::Deserialization`] if the stored
+/// value doesn't match `T`.
+pub async fn load<T: DeserializeOwned>(store: &dyn StateStore, key: &str) -> Result<Option<T>> {
+    match store.load(key).await? {
+        Some(value) => Ok(Some(serde_json::from_value(value)?)),
+        None => Ok(None),
+    }
+}
+
+/// Serialize `value` and store it under `key`.
+///
+/// # Errors
+///
+/// Returns the store's error, or [`Error::Deserialization`] if `value` can't be
+/// serialized.
+pub async fn save<T: Serialize + Sync>(store: &dyn StateStore, key: &str, value: &T) -> Result<()> {
+    store.save(key, serde_json::to_value(value)?).await
+}
+
+/// In-process store; state is lost when the process exits.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    values: Mutex<HashMap<String, serde_json::Value>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStore {
+    async fn load(&self, key: &str) -> Result<Option<serde_json::Value>> {
+        Ok(self.values.lock().await.get(key).cloned())
+    }
+
+    async fn save(&self, key: &str, value: serde_json::Value) -> Result<()> {
+        self.values.lock().await.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+/// Store that keeps each key in a JSON file under a directory.
+///
+/// Characters other than ASCII letters, digits, `-`, `_` and `.` in keys are
+/// replaced with `_` to form the file name. Files are read and written with
+/// `tokio::fs`, which needs a Tokio runtime; not available on wasm32.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStore {
+    /// Store files under `dir`, which is created on first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", file_name(key)))
+    }
+}
+
+/// Replace characters that aren't safe in a file name with `_`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn file_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl StateStore for FileStore {
+    async fn load(&self, key: &str) -> Result<Option<serde_json::Value>> {
+        match tokio::fs::read(self.path(key)).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Error::Storage(err.to_string())),
+        }
+    }
+
+    async fn save(&self, key: &str, value: serde_json::Value) -> Result<()> {
+        let path = self.path(key);
+        let tmp = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec_pretty(&value)?;
+
+        // Write then rename so a crash never leaves a truncated file behind.
+        async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&tmp, bytes).await?;
+            tokio::fs::rename(&tmp, &path).await
+        }
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_round_trip() {
+        let store = MemoryStore::new();
+        assert_eq!(load::<Vec<u32>>(&store, "k").await.unwrap(), None);
+        save(&store, "k", &vec![1, 2]).await.unwrap();
+        assert_eq!(
+            load::<Vec<u32>>(&store, "k").await.unwrap(),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("finnhub-store-{}", std::process::id()));
+        let store = FileStore::new(&dir);
+
+        assert_eq!(load::<String>(&store, "etf/SPY").await.unwrap(), None);
+        save(&store, "etf/SPY", &"hello").await.unwrap();
+        assert_eq!(
+            load::<String>(&store, "etf/SPY").await.unwrap().as_deref(),
+            Some("hello")
+        );
+        assert!(dir.join("etf_SPY.json").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
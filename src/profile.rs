@@ -0,0 +1,372 @@
+//! Named client configurations in a TOML file.
+//!
+//! CLIs and services of one deployment can share a single file, each picking
+//! its profile by name:
+//!
+//! ```toml
+//! # Settings every profile starts from.
+//! [default]
+//! timeout_secs = 30
+//!
+//! [prod]
+//! api_key_env = "FINNHUB_PROD_KEY"
+//! rate_limit_strategy = "fifteen_second_window"
+//!
+//! [staging]
+//! base_url = "https://finnhub-proxy.staging.internal/api/v1"
+//! rate_limit_strategy = { custom = { capacity = 10, refill_rate = 10 } }
+//! ```
+//!
+//! ```rust,no_run
+//! use finnhub::{ClientConfig, FinnhubClient};
+//!
+//! # fn run() -> finnhub::Result<()> {
+//! // The settings alone...
+//! let config = ClientConfig::from_path("finnhub.toml", "prod")?;
+//! // ...or a client with the key from the profile's environment variable.
+//! let client = FinnhubClient::from_profile("finnhub.toml", "prod")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Keys are never read from the file itself, so it can be committed; a
+//! profile names the environment variable holding its key instead. Settings
+//! a profile leaves out keep their [`ClientConfig::default`] values.
+
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::auth::{ApiKey, AuthMethod};
+use crate::client::{ClientConfig, ConnectionConfig, RateLimitStrategy};
+use crate::error::{Error, Result};
+
+/// Table whose settings every profile starts from.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable holding the API key of a profile without
+/// `api_key_env`.
+pub const DEFAULT_API_KEY_ENV: &str = "FINNHUB_API_KEY";
+
+/// Settings of one profile. Unset settings are left as they are.
+///
+/// Unknown keys are rejected, so a typo doesn't silently fall back to a
+/// default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Environment variable holding the API key; [`DEFAULT_API_KEY_ENV`] if
+    /// unset.
+    pub api_key_env: Option<String>,
+    /// See [`ClientConfig::base_url`].
+    pub base_url: Option<String>,
+    /// See [`ClientConfig::fallback_base_urls`].
+    pub fallback_base_urls: Option<Vec<String>>,
+    /// See [`ClientConfig::failover_after_secs`].
+    pub failover_after_secs: Option<u64>,
+    /// See [`ClientConfig::timeout_secs`].
+    pub timeout_secs: Option<u64>,
+    /// See [`ClientConfig::connect_timeout_secs`].
+    pub connect_timeout_secs: Option<u64>,
+    /// See [`ClientConfig::compression`].
+    pub compression: Option<bool>,
+    /// A `[<profile>.connection]` table; see [`ClientConfig::connection`].
+    pub connection: Option<ConnectionConfig>,
+    /// See [`ClientConfig::max_response_bytes`].
+    pub max_response_bytes: Option<u64>,
+    /// `"header"` or `"url_parameter"`; see [`ClientConfig::auth_method`].
+    pub auth_method: Option<AuthMethod>,
+    /// See [`ClientConfig::tag`].
+    pub tag: Option<String>,
+    /// See [`ClientConfig::rate_limit_strategy`].
+    pub rate_limit_strategy: Option<RateLimitStrategy>,
+    /// See [`ClientConfig::retry_budget`].
+    pub retry_budget: Option<u32>,
+    /// See [`ClientConfig::weighted_rate_limit`].
+    pub weighted_rate_limit: Option<bool>,
+    /// See [`ClientConfig::pause_on_rate_limit`].
+    pub pause_on_rate_limit: Option<bool>,
+    /// See [`ClientConfig::auto_retry_rate_limit`].
+    pub auto_retry_rate_limit: Option<bool>,
+    /// See [`ClientConfig::adaptive_rate_limit`].
+    pub adaptive_rate_limit: Option<bool>,
+    /// See [`ClientConfig::rate_limit_jitter_ms`].
+    pub rate_limit_jitter_ms: Option<u64>,
+    /// See [`ClientConfig::max_concurrent_requests`].
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl Profile {
+    /// Read profile `name` from the TOML file at `path`, on top of the
+    /// [`DEFAULT_PROFILE`] table if the file has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Storage`] if the file can't be read, and
+    /// [`Error::InvalidConfig`] if it isn't valid TOML, has no table `name`
+    /// or holds an unknown or mistyped setting.
+    pub fn load(path: impl AsRef<Path>, name: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| Error::Storage(format!("{}: {err}", path.display())))?;
+        Self::parse(&text, name)
+    }
+
+    /// Read profile `name` from TOML text; see [`load`](Self::load).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] as [`load`](Self::load) does.
+    pub fn parse(text: &str, name: &str) -> Result<Self> {
+        let mut tables: toml::Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| Error::invalid_config("profile", err.to_string()))?;
+        let profile = table(tables.remove(name), name)?
+            .ok_or_else(|| Error::invalid_config("profile", format!("no profile {name:?}")))?;
+
+        let mut settings = if name == DEFAULT_PROFILE {
+            toml::Table::new()
+        } else {
+            table(tables.remove(DEFAULT_PROFILE), DEFAULT_PROFILE)?.unwrap_or_default()
+        };
+        merge(&mut settings, profile);
+        toml::Value::Table(settings)
+            .try_into()
+            .map_err(|err: toml::de::Error| {
+                Error::invalid_config("profile", format!("[{name}]: {err}"))
+            })
+    }
+
+    /// `config` with the settings of this profile applied.
+    #[must_use]
+    pub fn apply(self, mut config: ClientConfig) -> ClientConfig {
+        if let Some(base_url) = self.base_url {
+            config.base_url = base_url;
+        }
+        if let Some(fallback_base_urls) = self.fallback_base_urls {
+            config.fallback_base_urls = fallback_base_urls;
+        }
+        if let Some(failover_after_secs) = self.failover_after_secs {
+            config.failover_after_secs = failover_after_secs;
+        }
+        if let Some(timeout_secs) = self.timeout_secs {
+            config.timeout_secs = timeout_secs;
+        }
+        if let Some(connect_timeout_secs) = self.connect_timeout_secs {
+            config.connect_timeout_secs = connect_timeout_secs;
+        }
+        if let Some(compression) = self.compression {
+            config.compression = compression;
+        }
+        if let Some(connection) = self.connection {
+            config.connection = connection;
+        }
+        if self.max_response_bytes.is_some() {
+            config.max_response_bytes = self.max_response_bytes;
+        }
+        if let Some(auth_method) = self.auth_method {
+            config.auth_method = auth_method;
+        }
+        if self.tag.is_some() {
+            config.tag = self.tag;
+        }
+        if let Some(strategy) = self.rate_limit_strategy {
+            config.rate_limit_strategy = strategy;
+        }
+        if self.retry_budget.is_some() {
+            config.retry_budget = self.retry_budget;
+        }
+        if let Some(weighted) = self.weighted_rate_limit {
+            config.weighted_rate_limit = weighted;
+        }
+        if let Some(pause) = self.pause_on_rate_limit {
+            config.pause_on_rate_limit = pause;
+        }
+        if let Some(retry) = self.auto_retry_rate_limit {
+            config.auto_retry_rate_limit = retry;
+        }
+        if let Some(adaptive) = self.adaptive_rate_limit {
+            config.adaptive_rate_limit = adaptive;
+        }
+        if let Some(jitter) = self.rate_limit_jitter_ms {
+            config.rate_limit_jitter_ms = jitter;
+        }
+        if self.max_concurrent_requests.is_some() {
+            config.max_concurrent_requests = self.max_concurrent_requests;
+        }
+        config
+    }
+
+    /// The API key, from the environment variable named by
+    /// [`api_key_env`](Self::api_key_env).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the variable is unset or empty.
+    pub fn api_key(&self) -> Result<ApiKey> {
+        let var = self.api_key_env.as_deref().unwrap_or(DEFAULT_API_KEY_ENV);
+        std::env::var(var)
+            .ok()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .map(ApiKey::from)
+            .ok_or_else(|| Error::invalid_config("api_key_env", format!("{var} is not set")))
+    }
+}
+
+/// The profile table `name`, if the file has one.
+fn table(value: Option<toml::Value>, name: &str) -> Result<Option<toml::Table>> {
+    match value {
+        Some(toml::Value::Table(table)) => Ok(Some(table)),
+        Some(_) => Err(Error::invalid_config(
+            "profile",
+            format!("[{name}] is not a table"),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Lay `overlay` over `base`. Tables present in both, such as
+/// `[<profile>.connection]`, are merged key by key; other values replace.
+fn merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(overlay)) => merge(inner, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Load profile `profile` of the TOML file at `path`; see
+    /// [`crate::profile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Profile::load`], and [`Error::InvalidConfig`]
+    /// if the resulting configuration fails [`validate`](Self::validate).
+    pub fn from_path(path: impl AsRef<Path>, profile: &str) -> Result<Self> {
+        let config = Profile::load(path, profile)?.apply(Self::default());
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl crate::FinnhubClient {
+    /// Build a client from profile `profile` of the TOML file at `path`, with
+    /// the API key from the profile's environment variable; see
+    /// [`crate::profile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`ClientConfig::from_path`], and
+    /// [`Error::InvalidConfig`] if the key's environment variable is unset.
+    pub fn from_profile(path: impl AsRef<Path>, profile: &str) -> Result<Self> {
+        let profile = Profile::load(path, profile)?;
+        let api_key = profile.api_key()?;
+        Self::try_with_config(api_key, profile.apply(ClientConfig::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"
+        [default]
+        timeout_secs = 5
+        tag = "shared"
+
+        [default.connection]
+        pool_idle_timeout_secs = 30
+
+        [prod]
+        api_key_env = "FINNHUB_PROFILE_TEST_KEY"
+        rate_limit_strategy = "fifteen_second_window"
+        auth_method = "url_parameter"
+
+        [staging]
+        base_url = "http://localhost:8080/api/v1"
+        timeout_secs = 60
+        rate_limit_strategy = { custom = { capacity = 10, refill_rate = 2 } }
+
+        [staging.connection]
+        http_version = "http1"
+        pool_max_idle_per_host = 16
+
+        [typo]
+        timeout_sec = 5
+    "#;
+
+    #[test]
+    fn test_profiles_inherit_default_table() {
+        let prod = Profile::parse(FILE, "prod")
+            .unwrap()
+            .apply(ClientConfig::default());
+        assert_eq!(prod.timeout_secs, 5);
+        assert_eq!(prod.tag.as_deref(), Some("shared"));
+        assert_eq!(
+            prod.rate_limit_strategy,
+            RateLimitStrategy::FifteenSecondWindow
+        );
+        assert!(matches!(prod.auth_method, AuthMethod::UrlParameter));
+        assert_eq!(prod.base_url, ClientConfig::default().base_url);
+        assert_eq!(prod.connection.pool_idle_timeout_secs, Some(30));
+
+        let staging = Profile::parse(FILE, "staging").unwrap();
+        assert_eq!(staging.api_key_env, None);
+        let staging = staging.apply(ClientConfig::default());
+        assert_eq!(staging.timeout_secs, 60);
+        assert_eq!(staging.base_url, "http://localhost:8080/api/v1");
+        assert_eq!(
+            staging.connection.http_version,
+            crate::client::HttpVersion::Http1
+        );
+        assert_eq!(staging.connection.pool_max_idle_per_host, Some(16));
+        assert_eq!(staging.connection.pool_idle_timeout_secs, Some(30));
+        assert_eq!(
+            staging.rate_limit_strategy,
+            RateLimitStrategy::Custom {
+                capacity: 10,
+                refill_rate: 2
+            }
+        );
+
+        for (name, message) in [("typo", "timeout_sec"), ("dev", "no profile")] {
+            let err = Profile::parse(FILE, name).unwrap_err();
+            assert!(err.to_string().contains(message), "{err}");
+        }
+        assert!(Profile::parse("[prod", "prod").is_err());
+    }
+
+    #[test]
+    fn test_from_path_and_profile_key() {
+        let path =
+            std::env::temp_dir().join(format!("finnhub-profile-{}.toml", std::process::id()));
+        std::fs::write(&path, FILE).unwrap();
+
+        let config = ClientConfig::from_path(&path, "staging").unwrap();
+        assert_eq!(config.timeout_secs, 60);
+        assert!(matches!(
+            ClientConfig::from_path(path.with_extension("missing"), "staging"),
+            Err(Error::Storage(_))
+        ));
+
+        let profile = Profile::load(&path, "prod").unwrap();
+        assert!(matches!(
+            profile.api_key(),
+            Err(Error::InvalidConfig {
+                field: "api_key_env",
+                ..
+            })
+        ));
+        std::env::set_var("FINNHUB_PROFILE_TEST_KEY", "prod-key");
+        assert_eq!(profile.api_key().unwrap().expose(), "prod-key");
+        let client = crate::FinnhubClient::from_profile(&path, "prod").unwrap();
+        assert_eq!(client.config().timeout_secs, 5);
+        std::env::remove_var("FINNHUB_PROFILE_TEST_KEY");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,14 +1,224 @@
 //! Rate limiting implementation for the Finnhub API.
+//!
+//! The client waits on a [`RateLimit`] before each request. [`RateLimiter`],
+//! a token bucket, is the one it builds by default; pass another
+//! implementation, such as a leaky bucket, a `governor` limiter or
+//! [`Unlimited`], in `ClientConfig::rate_limiter`.
 
-use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
-use tokio::time::sleep;
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError};
+use std::time::Duration;
+use tokio::sync::{Mutex, MutexGuard, Notify};
+
+use crate::coverage::{self, Category};
+use crate::limits::RATE_LIMIT_PER_SECOND;
+use crate::retry::random_fraction;
+use crate::rt::{sleep, Instant};
+
+/// A limit on the rate of requests, waited on before each one.
+///
+/// Only [`acquire_weighted`](Self::acquire_weighted),
+/// [`try_acquire`](Self::try_acquire) and [`available`](Self::available) are
+/// required. The other methods let the client feed back what it learns from
+/// the server, for `ClientConfig::pause_on_rate_limit`,
+/// `ClientConfig::adaptive_rate_limit` and reloaded limits; by default they
+/// do nothing, and low-priority requests wait like any other.
+///
+/// ```
+/// use finnhub::rate_limiter::{RateLimit, RateLimiter};
+/// use finnhub::Result;
+/// use std::time::Instant;
+///
+/// /// Logs how long requests wait for the Finnhub limits.
+/// #[derive(Debug)]
+/// struct Logged(RateLimiter);
+///
+/// #[async_trait::async_trait]
+/// impl RateLimit for Logged {
+///     async fn acquire_weighted(&self, cost: u32) -> Result<()> {
+///         let start = Instant::now();
+///         self.0.acquire_weighted(cost).await?;
+///         println!("waited {:?}", start.elapsed());
+///         Ok(())
+///     }
+///     async fn try_acquire(&self) -> Result<()> {
+///         self.0.try_acquire().await
+///     }
+///     async fn available(&self) -> u32 {
+///         self.0.available_tokens().await
+///     }
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait RateLimit: Send + Sync + fmt::Debug {
+    /// Wait for one token.
+    ///
+    /// # Errors
+    ///
+    /// Returns the limiter's error when the request may not be sent at all.
+    async fn acquire(&self) -> Result<(), crate::Error> {
+        self.acquire_weighted(1).await
+    }
+
+    /// Wait for `cost` tokens, for endpoints weighted by
+    /// `ClientConfig::weighted_rate_limit` or `ClientConfig::endpoint_weights`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the limiter's error when the request may not be sent at all.
+    async fn acquire_weighted(&self, cost: u32) -> Result<(), crate::Error>;
+
+    /// Wait for `cost` tokens for a client tagged
+    /// [`ClientConfig::tag`](crate::ClientConfig::tag), so waits can be
+    /// attributed to it. Defaults to
+    /// [`acquire_weighted`](Self::acquire_weighted).
+    ///
+    /// # Errors
+    ///
+    /// Returns the limiter's error when the request may not be sent at all.
+    async fn acquire_tagged(&self, cost: u32, tag: Option<&str>) -> Result<(), crate::Error> {
+        let _ = tag;
+        self.acquire_weighted(cost).await
+    }
+
+    /// Take a token without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RateLimitExceeded`](crate::Error::RateLimitExceeded)
+    /// when none is available.
+    async fn try_acquire(&self) -> Result<(), crate::Error>;
+
+    /// Tokens available now.
+    async fn available(&self) -> u32;
+
+    /// Wait for a token for background work such as cache prefetching.
+    ///
+    /// # Errors
+    ///
+    /// Returns the limiter's error when the request may not be sent at all.
+    async fn acquire_low_priority(&self) -> Result<(), crate::Error> {
+        self.acquire_low_priority_weighted(1).await
+    }
+
+    /// Wait for `cost` tokens for background work; see
+    /// [`acquire_low_priority`](Self::acquire_low_priority).
+    ///
+    /// # Errors
+    ///
+    /// Returns the limiter's error when the request may not be sent at all.
+    async fn acquire_low_priority_weighted(&self, cost: u32) -> Result<(), crate::Error> {
+        self.acquire_weighted(cost).await
+    }
+
+    /// Hold every request for `duration`, after a 429 response.
+    async fn pause_for(&self, _duration: Duration) {}
+
+    /// Apply the budget the server reported; see
+    /// [`RateLimiter::sync_with_server`].
+    async fn sync_with_server(&self, _remaining: u32, _reset_in: Option<Duration>) {}
+
+    /// Change the limit to `capacity` tokens refilled at `refill_rate` per
+    /// second. Returns whether anything changed.
+    async fn set_rate(&self, _capacity: u32, _refill_rate: u32) -> bool {
+        false
+    }
+}
 
 /// Rate limiter using token bucket algorithm.
+///
+/// Waiting callers are served in the order they called, so under contention
+/// every caller waits about as long as the tokens requested before it take
+/// to refill, and none is starved by callers that happen to wake up first.
+///
+/// Clones share the bucket, its [`stats`](Self::stats) and its
+/// [`on_throttle`](Self::on_throttle) hooks.
 #[derive(Clone, Debug)]
 pub struct RateLimiter {
     inner: Arc<Mutex<RateLimiterInner>>,
+    counters: Arc<Counters>,
+    /// Wakes callers held by [`pause`](Self::pause) on [`resume`](Self::resume).
+    resumed: Arc<Notify>,
+    /// Callers of [`acquire_weighted`](Self::acquire_weighted) line up here,
+    /// in call order since tokio's mutex is fair; only the one holding it
+    /// waits on the bucket.
+    queue: Arc<Mutex<()>>,
+    /// Longest random delay added to each wait; see [`with_jitter`](Self::with_jitter).
+    jitter: Duration,
+}
+
+/// A snapshot of a [`RateLimiter`]'s activity; see [`RateLimiter::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimiterStats {
+    /// Tokens in the bucket now.
+    pub available: u32,
+    /// Acquisitions granted so far, each counted once whatever its cost.
+    pub acquired: u64,
+    /// Acquisitions that had to wait for tokens or for a pause to end.
+    pub throttled: u64,
+    /// Time spent waiting by all callers together.
+    pub total_wait: Duration,
+    /// Callers waiting right now.
+    pub waiting: usize,
+}
+
+/// A caller that has to wait for the bucket, as passed to
+/// [`RateLimiter::on_throttle`] hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleEvent {
+    /// Tokens the caller asked for.
+    pub cost: u32,
+    /// Expected wait before the caller's next try; [`Duration::MAX`] while
+    /// the limiter is [paused](RateLimiter::pause) until resumed.
+    pub wait: Duration,
+    /// Callers waiting, this one included.
+    pub waiting: usize,
+    /// [`ClientConfig::tag`](crate::ClientConfig::tag) of the client the
+    /// request is for; `None` for untagged clients and direct calls.
+    pub tag: Option<String>,
+}
+
+type ThrottleHook = Arc<dyn Fn(&ThrottleEvent) + Send + Sync>;
+
+/// Counters behind [`RateLimiterStats`], kept outside the bucket's lock so
+/// waiting callers can update them.
+#[derive(Default)]
+struct Counters {
+    acquired: AtomicU64,
+    throttled: AtomicU64,
+    wait_nanos: AtomicU64,
+    waiting: AtomicUsize,
+    hooks: std::sync::Mutex<Vec<ThrottleHook>>,
+}
+
+impl fmt::Debug for Counters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Counters")
+            .field("acquired", &self.acquired.load(Ordering::Relaxed))
+            .field("throttled", &self.throttled.load(Ordering::Relaxed))
+            .field("waiting", &self.waiting.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A caller waiting for the bucket; adds its wait to the totals when dropped,
+/// whether it got its tokens or was cancelled.
+struct Waiter<'a> {
+    counters: &'a Counters,
+    since: Instant,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        let waited = u64::try_from(self.since.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.counters
+            .wait_nanos
+            .fetch_add(waited, Ordering::Relaxed);
+        self.counters.waiting.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug)]
@@ -21,6 +231,10 @@ struct RateLimiterInner {
     refill_rate: u32,
     /// Last time tokens were refilled.
     last_refill: Instant,
+    /// No tokens are handed out before this time; see `RateLimiter::pause_for`.
+    paused_until: Option<Instant>,
+    /// No tokens are handed out until `RateLimiter::resume`.
+    held: bool,
 }
 
 impl RateLimiter {
@@ -32,13 +246,40 @@ impl RateLimiter {
                 tokens: capacity,
                 refill_rate,
                 last_refill: Instant::now(),
+                paused_until: None,
+                held: false,
             })),
+            counters: Arc::default(),
+            resumed: Arc::default(),
+            queue: Arc::default(),
+            jitter: Duration::ZERO,
         }
     }
 
+    /// Add a random delay of up to `max` to every wait for tokens or for a
+    /// pause to end.
+    ///
+    /// Callers that start waiting together otherwise all wake when the same
+    /// token is due and retry in lockstep, and a burst of waiters released
+    /// at the end of a pause reaches the server as one burst. Jitter spreads
+    /// the wakeups out. A deadline given to
+    /// [`acquire_until`](Self::acquire_until) is still kept.
+    ///
+    /// ```
+    /// use finnhub::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let limiter = RateLimiter::finnhub_default().with_jitter(Duration::from_millis(20));
+    /// ```
+    #[must_use]
+    pub fn with_jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
     /// Create a rate limiter for Finnhub's default limits (30 requests/second).
     pub fn finnhub_default() -> Self {
-        Self::new(30, 30)
+        Self::new(RATE_LIMIT_PER_SECOND, RATE_LIMIT_PER_SECOND)
     }
 
     /// Create a rate limiter for Finnhub with 15-second averaging window.
@@ -46,57 +287,189 @@ impl RateLimiter {
     pub fn finnhub_15s_window() -> Self {
         // 450 tokens capacity, refill at 30 tokens/second
         // To maintain 30 req/s average, we need to consider the measurement period
-        Self::new(450, 30)
+        Self::new(RATE_LIMIT_PER_SECOND * 15, RATE_LIMIT_PER_SECOND)
     }
 
     /// Acquire a token, waiting if necessary.
     pub async fn acquire(&self) -> Result<(), crate::Error> {
-        loop {
-            let mut limiter = self.inner.lock().await;
+        self.acquire_weighted(1).await
+    }
 
-            // Refill tokens based on elapsed time
-            let now = Instant::now();
-            let elapsed = now.duration_since(limiter.last_refill);
-            let tokens_to_add = (elapsed.as_secs_f64() * f64::from(limiter.refill_rate)) as u32;
+    /// Acquire `cost` tokens at once, waiting until that many are available.
+    ///
+    /// A cost above the bucket's capacity is capped at the capacity, so heavy
+    /// requests wait for a full bucket rather than forever. Callers get their
+    /// tokens in the order they called, a heavy request included: later
+    /// callers queue behind it rather than take the tokens it is waiting for.
+    ///
+    /// # Errors
+    ///
+    /// Never fails; the `Result` matches [`acquire`](Self::acquire).
+    pub async fn acquire_weighted(&self, cost: u32) -> Result<(), crate::Error> {
+        self.acquire_before(cost, None, None).await
+    }
+
+    /// Acquire a token, waiting at most `timeout`; see
+    /// [`acquire_until`](Self::acquire_until).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RateLimitExceeded`](crate::Error::RateLimitExceeded)
+    /// when no token is available in time.
+    pub async fn acquire_timeout(&self, timeout: Duration) -> Result<(), crate::Error> {
+        // A timeout too long to add to the clock is no timeout at all.
+        self.acquire_before(1, Instant::now().checked_add(timeout), None)
+            .await
+    }
 
-            if tokens_to_add > 0 {
-                limiter.tokens = (limiter.tokens + tokens_to_add).min(limiter.capacity);
-                limiter.last_refill = now;
+    /// Acquire a token, waiting no later than `deadline`, for callers that
+    /// would rather fail than queue.
+    ///
+    /// Fails as soon as the wait is known to run past the deadline, without
+    /// waiting for it, and straight away while the limiter is
+    /// [paused](Self::pause) until resumed. `deadline` is a
+    /// `web_time::Instant` on wasm32.
+    ///
+    /// ```
+    /// # async fn run() {
+    /// use finnhub::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let limiter = RateLimiter::new(1, 1);
+    /// limiter.acquire().await.unwrap();
+    /// // The next token is a second away.
+    /// let err = limiter.acquire_timeout(Duration::from_millis(100)).await.unwrap_err();
+    /// assert_eq!(err.retry_after(), Some(1));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RateLimitExceeded`](crate::Error::RateLimitExceeded)
+    /// with the expected wait, rounded up to whole seconds, when no token is
+    /// available in time.
+    pub async fn acquire_until(&self, deadline: Instant) -> Result<(), crate::Error> {
+        self.acquire_before(1, Some(deadline), None).await
+    }
+
+    /// Acquire `cost` tokens for a client tagged `tag`, failing when they
+    /// can't be had by `deadline`.
+    async fn acquire_before(
+        &self,
+        cost: u32,
+        deadline: Option<Instant>,
+        tag: Option<&str>,
+    ) -> Result<(), crate::Error> {
+        let mut waiter = None;
+        let _turn = if let Ok(turn) = self.queue.try_lock() {
+            turn
+        } else {
+            let wait = self.queue_wait(cost).await;
+            waiter.get_or_insert_with(|| self.throttled(cost, wait, tag));
+            let turn = self.queue.lock();
+            match deadline {
+                None => turn.await,
+                Some(deadline) => tokio::select! {
+                    turn = turn => turn,
+                    () = sleep(deadline.saturating_duration_since(Instant::now())) => {
+                        return Err(exceeded(wait));
+                    }
+                },
             }
+        };
+        loop {
+            let limiter = self.inner.lock().await;
+            let Some(mut limiter) = self
+                .wait_out_pause(limiter, cost, deadline, tag, &mut waiter)
+                .await?
+            else {
+                continue;
+            };
+            limiter.refill();
 
-            // Try to acquire a token
-            if limiter.tokens > 0 {
-                limiter.tokens -= 1;
+            let cost = cost.clamp(1, limiter.capacity.max(1));
+            if limiter.tokens >= cost {
+                limiter.tokens -= cost;
+                self.counters.acquired.fetch_add(1, Ordering::Relaxed);
                 return Ok(());
             }
 
             // Calculate wait time
-            let tokens_needed = 1;
-            let wait_time =
-                Duration::from_secs_f64(f64::from(tokens_needed) / f64::from(limiter.refill_rate));
+            let tokens_needed = cost - limiter.tokens;
+            let wait_time = Duration::from_secs_f64(
+                f64::from(tokens_needed) / f64::from(limiter.refill_rate.max(1)),
+            );
 
             drop(limiter); // Release lock while waiting
-            sleep(wait_time).await;
+            check_deadline(deadline, wait_time)?;
+            waiter.get_or_insert_with(|| self.throttled(cost, wait_time, tag));
+            sleep(self.jittered(wait_time, deadline)).await;
         }
     }
 
-    /// Try to acquire a token without waiting.
-    pub async fn try_acquire(&self) -> Result<(), crate::Error> {
-        let mut limiter = self.inner.lock().await;
+    /// Acquire a token for background work, waiting while fewer than half of
+    /// the bucket's tokens are available.
+    ///
+    /// Low-priority callers never drain the bucket below half, and don't
+    /// take tokens while callers of [`acquire`](Self::acquire) are queued, so
+    /// those keep headroom while background work such as cache prefetching
+    /// runs.
+    pub async fn acquire_low_priority(&self) {
+        self.acquire_low_priority_weighted(1).await;
+    }
 
-        // Refill tokens based on elapsed time
-        let now = Instant::now();
-        let elapsed = now.duration_since(limiter.last_refill);
-        let tokens_to_add = (elapsed.as_secs_f64() * f64::from(limiter.refill_rate)) as u32;
+    /// Acquire `cost` tokens for background work, leaving half of the bucket
+    /// like [`acquire_low_priority`](Self::acquire_low_priority). A cost above
+    /// the other half is capped at it.
+    pub async fn acquire_low_priority_weighted(&self, cost: u32) {
+        let mut waiter = None;
+        loop {
+            let limiter = self.inner.lock().await;
+            let Ok(Some(mut limiter)) = self
+                .wait_out_pause(limiter, cost, None, None, &mut waiter)
+                .await
+            else {
+                continue;
+            };
+            limiter.refill();
 
-        if tokens_to_add > 0 {
-            limiter.tokens = (limiter.tokens + tokens_to_add).min(limiter.capacity);
-            limiter.last_refill = now;
+            let reserve = limiter.capacity / 2;
+            let cost = cost.clamp(1, (limiter.capacity - reserve).max(1));
+            if limiter.tokens >= reserve + cost && self.queue.try_lock().is_ok() {
+                limiter.tokens -= cost;
+                self.counters.acquired.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+
+            let wait_time = Duration::from_secs_f64(
+                f64::from((reserve + cost).saturating_sub(limiter.tokens).max(1))
+                    / f64::from(limiter.refill_rate.max(1)),
+            );
+            drop(limiter);
+            waiter.get_or_insert_with(|| self.throttled(cost, wait_time, None));
+            sleep(self.jittered(wait_time, None)).await;
+        }
+    }
+
+    /// Try to acquire a token without waiting. Fails while other callers are
+    /// waiting for tokens, rather than jump the queue.
+    pub async fn try_acquire(&self) -> Result<(), crate::Error> {
+        let mut limiter = self.inner.lock().await;
+        if limiter.held {
+            // Resuming is up to the application; check again in a second.
+            return Err(crate::Error::RateLimitExceeded { retry_after: 1 });
+        }
+        if let Some(pause) = limiter.pause_remaining() {
+            let retry_after = pause.as_secs() + u64::from(pause.subsec_nanos() > 0);
+            return Err(crate::Error::RateLimitExceeded { retry_after });
         }
 
+        limiter.refill();
+
         // Try to acquire a token
-        if limiter.tokens > 0 {
+        if limiter.tokens > 0 && self.queue.try_lock().is_ok() {
             limiter.tokens -= 1;
+            self.counters.acquired.fetch_add(1, Ordering::Relaxed);
             Ok(())
         } else {
             let retry_after = (1.0 / f64::from(limiter.refill_rate)).ceil() as u64;
@@ -107,18 +480,421 @@ impl RateLimiter {
     /// Get the current number of available tokens.
     pub async fn available_tokens(&self) -> u32 {
         let mut limiter = self.inner.lock().await;
+        limiter.refill();
+        limiter.tokens
+    }
+
+    /// Change the bucket's capacity and refill rate in place, e.g. after a
+    /// plan upgrade. Tokens already in the bucket are kept, up to the new
+    /// capacity, and callers waiting for tokens pick up the new rate on their
+    /// next check. Returns whether anything changed.
+    pub async fn set_rate(&self, capacity: u32, refill_rate: u32) -> bool {
+        let mut limiter = self.inner.lock().await;
+        if limiter.capacity == capacity && limiter.refill_rate == refill_rate {
+            return false;
+        }
+        limiter.refill();
+        limiter.capacity = capacity;
+        limiter.refill_rate = refill_rate;
+        limiter.tokens = limiter.tokens.min(capacity);
+        true
+    }
+
+    /// Hand out no tokens for `duration`, e.g. after the server answered
+    /// 429 Too Many Requests. Every caller waits, and [`try_acquire`]
+    /// fails, until the pause is over.
+    ///
+    /// The bucket is emptied and starts refilling when the pause ends, so
+    /// requests resume at the refill rate instead of in one burst. A pause
+    /// ending before one already in effect changes nothing.
+    ///
+    /// [`try_acquire`]: Self::try_acquire
+    pub async fn pause_for(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut limiter = self.inner.lock().await;
+        if limiter.paused_until.is_some_and(|paused| paused >= until) {
+            return;
+        }
+        limiter.paused_until = Some(until);
+        limiter.tokens = 0;
+        limiter.last_refill = until;
+    }
+
+    /// Bring the bucket in line with the budget the server reports, e.g. in
+    /// `X-Ratelimit-Remaining`: `remaining` calls left in its window, which
+    /// resets `reset_in` from now.
+    ///
+    /// The bucket never holds more tokens than `remaining`; it isn't raised
+    /// to match, since the server's window may be longer than the bucket's.
+    /// With no calls left, every caller is [paused](Self::pause_for) until
+    /// the reset, or the bucket is emptied when the reset is unknown.
+    pub async fn sync_with_server(&self, remaining: u32, reset_in: Option<Duration>) {
+        match reset_in {
+            Some(reset_in) if remaining == 0 && !reset_in.is_zero() => {
+                self.pause_for(reset_in).await;
+            }
+            _ => {
+                let mut limiter = self.inner.lock().await;
+                limiter.refill();
+                limiter.tokens = limiter.tokens.min(remaining);
+            }
+        }
+    }
+
+    /// Time left until a [`pause_for`](Self::pause_for) pause ends, if one is
+    /// in effect.
+    pub async fn paused_for(&self) -> Option<Duration> {
+        self.inner.lock().await.pause_remaining()
+    }
+
+    /// Hand out no tokens until [`resume`](Self::resume), e.g. while the
+    /// market is closed. Callers wait and [`try_acquire`](Self::try_acquire)
+    /// fails in the meantime; the bucket keeps its tokens and goes on
+    /// refilling.
+    pub async fn pause(&self) {
+        self.inner.lock().await.held = true;
+    }
+
+    /// End a [`pause`](Self::pause) or [`pause_for`](Self::pause_for) pause
+    /// and wake the callers waiting on it.
+    pub async fn resume(&self) {
+        let mut limiter = self.inner.lock().await;
+        limiter.held = false;
+        limiter.paused_until = None;
+        // `pause_for` starts the refill at the end of the pause.
+        limiter.last_refill = limiter.last_refill.min(Instant::now());
+        // Waiters register before releasing the lock, so none misses this.
+        self.resumed.notify_waiters();
+    }
+
+    /// Whether the limiter is paused by [`pause`](Self::pause) or
+    /// [`pause_for`](Self::pause_for).
+    pub async fn is_paused(&self) -> bool {
+        let mut limiter = self.inner.lock().await;
+        limiter.held || limiter.pause_remaining().is_some()
+    }
+
+    /// Tokens available now and totals since the limiter was created.
+    ///
+    /// A `waiting` count that stays above zero, or `total_wait` growing about
+    /// as fast as the clock, means requests are persistently rate-bound.
+    pub async fn stats(&self) -> RateLimiterStats {
+        let available = self.available_tokens().await;
+        let counters = &self.counters;
+        RateLimiterStats {
+            available,
+            acquired: counters.acquired.load(Ordering::Relaxed),
+            throttled: counters.throttled.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(counters.wait_nanos.load(Ordering::Relaxed)),
+            waiting: counters.waiting.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Call `hook` whenever a caller has to wait for tokens or for a pause
+    /// to end, once per acquisition.
+    ///
+    /// The hook runs on the waiting task before it sleeps, so it should
+    /// return quickly.
+    ///
+    /// ```
+    /// use finnhub::RateLimiter;
+    ///
+    /// let limiter = RateLimiter::finnhub_default();
+    /// limiter.on_throttle(|event| {
+    ///     if event.waiting > 10 {
+    ///         eprintln!("{} requests waiting for the rate limit", event.waiting);
+    ///     }
+    /// });
+    /// ```
+    pub fn on_throttle(&self, hook: impl Fn(&ThrottleEvent) + Send + Sync + 'static) {
+        self.counters
+            .hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Arc::new(hook));
+    }
+
+    /// Hand `limiter` back when the limiter isn't paused. Otherwise release
+    /// it and wait until the pause ends or [`resume`](Self::resume) is
+    /// called, counting the caller as throttled, or fail if that is past
+    /// `deadline`.
+    async fn wait_out_pause<'a, 'g>(
+        &'a self,
+        mut limiter: MutexGuard<'g, RateLimiterInner>,
+        cost: u32,
+        deadline: Option<Instant>,
+        tag: Option<&str>,
+        waiter: &mut Option<Waiter<'a>>,
+    ) -> Result<Option<MutexGuard<'g, RateLimiterInner>>, crate::Error> {
+        let pause = match (limiter.held, limiter.pause_remaining()) {
+            (false, None) => return Ok(Some(limiter)),
+            (true, _) => None,
+            (false, pause) => pause,
+        };
+        let resumed = self.resumed.notified();
+        drop(limiter);
+        check_deadline(deadline, pause.unwrap_or(Duration::MAX))?;
+        waiter.get_or_insert_with(|| self.throttled(cost, pause.unwrap_or(Duration::MAX), tag));
+        match pause {
+            Some(pause) => tokio::select! {
+                () = sleep(self.jittered(pause, deadline)) => {}
+                () = resumed => {}
+            },
+            None => resumed.await,
+        }
+        Ok(None)
+    }
+
+    /// Rough wait for a caller queued behind others: the time to refill its
+    /// own tokens and one for each caller waiting already, beyond those in
+    /// the bucket.
+    async fn queue_wait(&self, cost: u32) -> Duration {
+        let mut limiter = self.inner.lock().await;
+        limiter.refill();
+        let ahead =
+            u32::try_from(self.counters.waiting.load(Ordering::Relaxed)).unwrap_or(u32::MAX);
+        let needed = ahead.saturating_add(cost).saturating_sub(limiter.tokens);
+        Duration::from_secs_f64(f64::from(needed) / f64::from(limiter.refill_rate.max(1)))
+    }
+
+    /// `wait` plus a random part of the jitter, ending no later than
+    /// `deadline`.
+    fn jittered(&self, wait: Duration, deadline: Option<Instant>) -> Duration {
+        let wait = wait.saturating_add(self.jitter.mul_f64(random_fraction()));
+        deadline.map_or(wait, |deadline| {
+            wait.min(deadline.saturating_duration_since(Instant::now()))
+        })
+    }
+
+    /// Count a caller that has to wait `wait` for `cost` tokens, and call the
+    /// throttle hooks.
+    fn throttled(&self, cost: u32, wait: Duration, tag: Option<&str>) -> Waiter<'_> {
+        let counters = &self.counters;
+        counters.throttled.fetch_add(1, Ordering::Relaxed);
+        let waiting = counters.waiting.fetch_add(1, Ordering::Relaxed) + 1;
+        let waiter = Waiter {
+            counters,
+            since: Instant::now(),
+        };
+        let hooks = counters
+            .hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let event = ThrottleEvent {
+            cost,
+            wait,
+            waiting,
+            tag: tag.map(str::to_owned),
+        };
+        for hook in hooks {
+            hook(&event);
+        }
+        waiter
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl RateLimit for RateLimiter {
+    async fn acquire(&self) -> Result<(), crate::Error> {
+        RateLimiter::acquire(self).await
+    }
+
+    async fn acquire_weighted(&self, cost: u32) -> Result<(), crate::Error> {
+        RateLimiter::acquire_weighted(self, cost).await
+    }
+
+    async fn acquire_tagged(&self, cost: u32, tag: Option<&str>) -> Result<(), crate::Error> {
+        self.acquire_before(cost, None, tag).await
+    }
+
+    async fn try_acquire(&self) -> Result<(), crate::Error> {
+        RateLimiter::try_acquire(self).await
+    }
+
+    async fn available(&self) -> u32 {
+        self.available_tokens().await
+    }
+
+    async fn acquire_low_priority_weighted(&self, cost: u32) -> Result<(), crate::Error> {
+        RateLimiter::acquire_low_priority_weighted(self, cost).await;
+        Ok(())
+    }
+
+    async fn pause_for(&self, duration: Duration) {
+        RateLimiter::pause_for(self, duration).await;
+    }
 
-        // Refill tokens based on elapsed time
+    async fn sync_with_server(&self, remaining: u32, reset_in: Option<Duration>) {
+        RateLimiter::sync_with_server(self, remaining, reset_in).await;
+    }
+
+    async fn set_rate(&self, capacity: u32, refill_rate: u32) -> bool {
+        RateLimiter::set_rate(self, capacity, refill_rate).await
+    }
+}
+
+/// A [`RateLimit`] that never waits, for requests limited elsewhere, e.g. by
+/// a proxy, or tests against a mock server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unlimited;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl RateLimit for Unlimited {
+    async fn acquire_weighted(&self, _cost: u32) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    async fn try_acquire(&self) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    async fn available(&self) -> u32 {
+        u32::MAX
+    }
+}
+
+/// Fail when a wait of `wait` would end after `deadline`.
+fn check_deadline(deadline: Option<Instant>, wait: Duration) -> Result<(), crate::Error> {
+    match deadline {
+        Some(deadline) if deadline.saturating_duration_since(Instant::now()) < wait => {
+            Err(exceeded(wait))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The error for a caller that gave up on a wait of `wait`, with the wait
+/// rounded up to whole seconds, and at least one.
+fn exceeded(wait: Duration) -> crate::Error {
+    // A pause until `resume` has no known end; check again in a second.
+    let retry_after = if wait == Duration::MAX {
+        1
+    } else {
+        (wait.as_secs() + u64::from(wait.subsec_nanos() > 0)).max(1)
+    };
+    crate::Error::RateLimitExceeded { retry_after }
+}
+
+impl RateLimiterInner {
+    /// Time left in the current pause, clearing it once it's over.
+    fn pause_remaining(&mut self) -> Option<Duration> {
+        let until = self.paused_until?;
+        let remaining = until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            self.paused_until = None;
+            return None;
+        }
+        Some(remaining)
+    }
+
+    /// Add the tokens earned since the last refill.
+    fn refill(&mut self) {
         let now = Instant::now();
-        let elapsed = now.duration_since(limiter.last_refill);
-        let tokens_to_add = (elapsed.as_secs_f64() * f64::from(limiter.refill_rate)) as u32;
+        let elapsed = now.duration_since(self.last_refill);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let tokens_to_add = (elapsed.as_secs_f64() * f64::from(self.refill_rate)) as u32;
 
         if tokens_to_add > 0 {
-            limiter.tokens = (limiter.tokens + tokens_to_add).min(limiter.capacity);
-            limiter.last_refill = now;
+            self.tokens = (self.tokens + tokens_to_add).min(self.capacity);
+            self.last_refill = now;
         }
+    }
+}
 
-        limiter.tokens
+/// A token bucket of its own for some endpoints, on top of the client's rate
+/// limit; see `ClientConfig::endpoint_buckets`.
+///
+/// Requests to a matching endpoint wait for this bucket before the client's,
+/// so a heavy backfill is held to the bucket's rate and leaves the rest of
+/// the client's budget to other calls:
+///
+/// ```
+/// use finnhub::coverage::Category;
+/// use finnhub::rate_limiter::EndpointBucket;
+///
+/// // At most 2 tick pages and 5 ETF calls per second.
+/// let ticks = EndpointBucket::new(2, 2).with_path("/stock/tick");
+/// let etfs = EndpointBucket::new(5, 5).with_category(Category::Etf);
+/// assert!(ticks.matches("/stock/tick?symbol=AAPL&date=2024-03-01"));
+/// assert!(!etfs.matches("/quote?symbol=AAPL"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointBucket {
+    /// Endpoint paths, e.g. `/stock/tick`, whose requests use the bucket.
+    pub paths: Vec<String>,
+    /// API groups whose requests use the bucket.
+    pub categories: Vec<Category>,
+    /// Maximum tokens in the bucket.
+    pub capacity: u32,
+    /// Tokens refilled per second.
+    pub refill_rate: u32,
+}
+
+impl EndpointBucket {
+    /// A bucket of `capacity` tokens refilled at `refill_rate` per second,
+    /// matching no endpoint yet.
+    #[must_use]
+    pub fn new(capacity: u32, refill_rate: u32) -> Self {
+        Self {
+            paths: Vec::new(),
+            categories: Vec::new(),
+            capacity,
+            refill_rate,
+        }
+    }
+
+    /// Also use the bucket for requests to `path`.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Also use the bucket for requests to the endpoints of `category`.
+    #[must_use]
+    pub fn with_category(mut self, category: Category) -> Self {
+        self.categories.push(category);
+        self
+    }
+
+    /// Whether requests to `endpoint`, a path with or without its query,
+    /// use the bucket.
+    #[must_use]
+    pub fn matches(&self, endpoint: &str) -> bool {
+        let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+        self.paths.iter().any(|p| p == path)
+            || coverage::endpoint_info(path)
+                .is_some_and(|info| self.categories.contains(&info.category))
+    }
+}
+
+/// The rate limiters of a client's [`EndpointBucket`]s.
+#[derive(Debug, Default)]
+pub(crate) struct EndpointLimiters(Vec<(EndpointBucket, RateLimiter)>);
+
+impl EndpointLimiters {
+    pub(crate) fn new(buckets: &[EndpointBucket], jitter: Duration) -> Self {
+        Self(
+            buckets
+                .iter()
+                .map(|bucket| {
+                    let limiter =
+                        RateLimiter::new(bucket.capacity, bucket.refill_rate).with_jitter(jitter);
+                    (bucket.clone(), limiter)
+                })
+                .collect(),
+        )
+    }
+
+    /// Limiter of the first bucket matching `endpoint`, if any.
+    pub(crate) fn for_endpoint(&self, endpoint: &str) -> Option<&RateLimiter> {
+        self.0
+            .iter()
+            .find(|(bucket, _)| bucket.matches(endpoint))
+            .map(|(_, limiter)| limiter)
     }
 }
 
@@ -143,4 +919,293 @@ mod tests {
         // Should be able to acquire again
         assert!(limiter.try_acquire().await.is_ok());
     }
+
+    #[test]
+    #[cfg(feature = "runtime-agnostic")]
+    fn test_acquire_without_tokio_runtime() {
+        let limiter = RateLimiter::new(1, 20);
+        let start = Instant::now();
+        futures::executor::block_on(async {
+            limiter.acquire().await.unwrap();
+            // Waits for a refill on futures-timer; Tokio's timer would panic
+            // outside a runtime.
+            limiter.acquire().await.unwrap();
+        });
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_weighted_acquire_spends_cost() {
+        let limiter = RateLimiter::new(6, 1);
+
+        limiter.acquire_weighted(5).await.unwrap();
+        assert_eq!(limiter.available_tokens().await, 1);
+
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(100), limiter.acquire_weighted(2)).await;
+        assert!(blocked.is_err());
+        assert!(limiter.try_acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_timeout_fails_fast() {
+        let limiter = RateLimiter::new(1, 10);
+        limiter.acquire_timeout(Duration::ZERO).await.unwrap();
+
+        // The next token is 100ms away: too late for 20ms, in time for 500ms.
+        let start = Instant::now();
+        let err = limiter
+            .acquire_timeout(Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::RateLimitExceeded { retry_after: 1 }
+        ));
+        assert!(start.elapsed() < Duration::from_millis(20));
+        limiter
+            .acquire_until(Instant::now() + Duration::from_millis(500))
+            .await
+            .unwrap();
+
+        limiter.pause().await;
+        assert!(limiter
+            .acquire_timeout(Duration::from_secs(5))
+            .await
+            .is_err());
+        limiter.resume().await;
+        limiter.pause_for(Duration::from_secs(3)).await;
+        let err = limiter
+            .acquire_timeout(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.retry_after(), Some(3));
+    }
+
+    #[test]
+    fn test_jitter_spreads_wakeups() {
+        let wait = Duration::from_millis(50);
+        let limiter = RateLimiter::new(1, 20);
+        assert_eq!(limiter.jittered(wait, None), wait);
+
+        let limiter = limiter.with_jitter(Duration::from_millis(200));
+        let waits: Vec<Duration> = (0..20).map(|_| limiter.jittered(wait, None)).collect();
+        assert!(waits
+            .iter()
+            .all(|&w| w >= wait && w <= Duration::from_millis(250)));
+        assert!(waits.iter().any(|&w| w != waits[0]));
+
+        // Jitter never pushes a wait past the deadline.
+        let deadline = Instant::now() + Duration::from_millis(60);
+        assert!(limiter.jittered(wait, Some(deadline)) <= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_waiters_are_served_in_order() {
+        let limiter = RateLimiter::new(5, 50);
+        limiter.acquire_weighted(5).await.unwrap();
+
+        // A heavy request first, then light ones arriving while it waits:
+        // none of them may take the tokens it is waiting for.
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for i in 0..10 {
+            let (limiter, order) = (limiter.clone(), Arc::clone(&order));
+            tasks.push(tokio::spawn(async move {
+                let start = Instant::now();
+                limiter
+                    .acquire_weighted(if i == 0 { 5 } else { 1 })
+                    .await
+                    .unwrap();
+                order.lock().unwrap().push(i);
+                start.elapsed()
+            }));
+            sleep(Duration::from_millis(2)).await;
+        }
+        let mut waits = Vec::new();
+        for task in tasks {
+            waits.push(task.await.unwrap());
+        }
+
+        assert_eq!(*order.lock().unwrap(), (0..10).collect::<Vec<_>>());
+        // 14 tokens at 50 per second: nobody waits much beyond 280ms.
+        assert!(waits.iter().all(|&wait| wait < Duration::from_millis(450)));
+        let stats = limiter.stats().await;
+        assert_eq!((stats.throttled, stats.waiting), (10, 0));
+    }
+
+    #[tokio::test]
+    async fn test_queued_caller_keeps_its_deadline() {
+        let limiter = RateLimiter::new(1, 1);
+        limiter.acquire().await.unwrap();
+        let head = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        sleep(Duration::from_millis(20)).await;
+
+        // Queued behind a caller waiting for the next token, then out of time.
+        let start = Instant::now();
+        assert!(limiter
+            .acquire_timeout(Duration::from_millis(100))
+            .await
+            .is_err());
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(limiter.try_acquire().await.is_err());
+        head.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_low_priority_keeps_half_the_bucket() {
+        let limiter = RateLimiter::new(4, 1);
+
+        limiter.acquire_low_priority().await;
+        limiter.acquire_low_priority().await;
+        assert_eq!(limiter.available_tokens().await, 2);
+
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(100), limiter.acquire_low_priority()).await;
+        assert!(blocked.is_err());
+        assert!(limiter.try_acquire().await.is_ok());
+
+        // A weighted call needs its whole cost above the reserve.
+        let limiter = RateLimiter::new(10, 1);
+        limiter.acquire_low_priority_weighted(3).await;
+        assert_eq!(limiter.available_tokens().await, 7);
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(100),
+            limiter.acquire_low_priority_weighted(3),
+        )
+        .await;
+        assert!(blocked.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_rate_keeps_tokens_up_to_capacity() {
+        let limiter = RateLimiter::new(10, 1);
+        limiter.acquire_weighted(2).await.unwrap();
+
+        assert!(limiter.set_rate(5, 100).await);
+        assert_eq!(limiter.available_tokens().await, 5);
+        assert!(!limiter.set_rate(5, 100).await);
+
+        // The new refill rate applies straight away.
+        limiter.acquire_weighted(5).await.unwrap();
+        let start = Instant::now();
+        limiter.acquire().await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_stats_and_throttle_hooks() {
+        let limiter = RateLimiter::new(1, 20);
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let events_for_tag = Arc::clone(&events);
+        limiter.on_throttle(move |event| seen.lock().unwrap().push(event.clone()));
+
+        limiter.acquire().await.unwrap();
+        limiter.acquire().await.unwrap();
+        // A cancelled wait is no longer counted as waiting.
+        let cancelled =
+            tokio::time::timeout(Duration::from_millis(10), limiter.acquire_weighted(1)).await;
+        assert!(cancelled.is_err());
+
+        let stats = limiter.stats().await;
+        assert_eq!((stats.acquired, stats.throttled, stats.waiting), (2, 2, 0));
+        assert!(stats.total_wait >= Duration::from_millis(50));
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].cost, events[0].waiting), (1, 1));
+        assert!(events[0].wait <= Duration::from_millis(50));
+        assert_eq!(events[0].tag, None);
+        drop(events);
+
+        limiter.acquire_tagged(1, Some("research")).await.unwrap();
+        let events = events_for_tag.lock().unwrap();
+        assert_eq!(events.last().unwrap().tag.as_deref(), Some("research"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_trait_objects() {
+        let limiters: [Arc<dyn RateLimit>; 2] =
+            [Arc::new(RateLimiter::new(2, 1)), Arc::new(Unlimited)];
+        for limiter in &limiters {
+            limiter.acquire().await.unwrap();
+            limiter.acquire_weighted(1).await.unwrap();
+        }
+        assert!(limiters[0].try_acquire().await.is_err());
+        assert_eq!(limiters[0].available().await, 0);
+        assert!(limiters[0].set_rate(4, 1).await);
+
+        assert!(limiters[1].try_acquire().await.is_ok());
+        assert!(!limiters[1].set_rate(4, 1).await);
+    }
+
+    #[tokio::test]
+    async fn test_pause_until_resumed() {
+        let limiter = RateLimiter::new(10, 10);
+        limiter.pause().await;
+        assert!(limiter.is_paused().await);
+        assert!(limiter.try_acquire().await.is_err());
+
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+        limiter.resume().await;
+        let acquired = tokio::time::timeout(Duration::from_millis(100), waiting).await;
+        assert!(acquired.unwrap().unwrap().is_ok());
+        assert!(!limiter.is_paused().await);
+
+        // Resuming also ends a timed pause early.
+        limiter.pause_for(Duration::from_secs(60)).await;
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        sleep(Duration::from_millis(20)).await;
+        limiter.resume().await;
+        let acquired = tokio::time::timeout(Duration::from_millis(300), waiting).await;
+        assert!(acquired.unwrap().unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_server_caps_tokens() {
+        let limiter = RateLimiter::new(10, 1);
+        limiter.sync_with_server(3, None).await;
+        assert_eq!(limiter.available_tokens().await, 3);
+        // A larger server budget doesn't add tokens.
+        limiter.sync_with_server(50, None).await;
+        assert_eq!(limiter.available_tokens().await, 3);
+
+        limiter.sync_with_server(0, None).await;
+        assert_eq!(limiter.available_tokens().await, 0);
+        assert_eq!(limiter.paused_for().await, None);
+
+        limiter
+            .sync_with_server(0, Some(Duration::from_secs(5)))
+            .await;
+        assert!(limiter.paused_for().await > Some(Duration::from_secs(4)));
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_every_caller() {
+        let limiter = RateLimiter::new(10, 10);
+        limiter.pause_for(Duration::from_millis(300)).await;
+        // A shorter pause doesn't cut the current one short.
+        limiter.pause_for(Duration::from_millis(10)).await;
+
+        assert!(limiter.paused_for().await.is_some());
+        assert!(limiter.try_acquire().await.is_err());
+
+        let start = Instant::now();
+        limiter.acquire().await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(limiter.paused_for().await, None);
+        // The bucket refills from the end of the pause.
+        assert!(limiter.available_tokens().await < 10);
+    }
 }
@@ -0,0 +1,515 @@
+//! Configuration reload for long-running services.
+//!
+//! A service ingesting data around the clock shouldn't restart to rotate its
+//! API key, move to a higher plan's rate limit or retune cache TTLs. A
+//! [`ConfigWatcher`] re-reads those settings from a [`ConfigSource`] on an
+//! interval, and whenever a trigger such as `SIGHUP` fires, and applies them
+//! to the live client with [`FinnhubClient::apply_reload`]:
+//!
+//! ```rust,no_run
+//! use finnhub::reload::{ConfigWatcher, FileSource};
+//! use finnhub::FinnhubClient;
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//!
+//! # async fn run() -> finnhub::Result<()> {
+//! let client = FinnhubClient::new("your-api-key");
+//! let reloads = ConfigWatcher::new(client.clone(), FileSource::new("/etc/finnhub.json"))
+//!     .with_interval(Duration::from_secs(30))
+//!     .reload_on_sighup()?
+//!     .into_stream();
+//! futures::pin_mut!(reloads);
+//! while let Some(reload) = reloads.next().await {
+//!     match reload {
+//!         Ok(changed) => println!("reloaded {changed:?}"),
+//!         Err(e) => eprintln!("keeping the current settings: {e}"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests already in flight finish with the settings they started with.
+//! A source that fails to load or holds invalid settings leaves the client
+//! unchanged.
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::Deserialize;
+#[cfg(feature = "cache")]
+use serde::Deserializer;
+#[cfg(feature = "cache")]
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth::REDACTED;
+use crate::client::{FinnhubClient, RateLimitStrategy};
+use crate::error::{Error, Result};
+use crate::rt::Interval;
+
+/// Settings that can change while the client runs. `None` leaves a setting
+/// as it is.
+///
+/// Deserializes from JSON such as:
+///
+/// ```json
+/// {
+///   "api_key": "new-key",
+///   "rate_limit_strategy": "fifteen_second_window",
+///   "cache_ttls": { "/quote": 5, "/stock/metric": 21600 }
+/// }
+/// ```
+///
+/// with cache TTLs in seconds.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ReloadableConfig {
+    /// API key to send from now on.
+    pub api_key: Option<String>,
+    /// New capacity and refill rate for the shared rate limiter.
+    pub rate_limit_strategy: Option<RateLimitStrategy>,
+    /// Complete table of cache TTLs by endpoint path, replacing the current
+    /// one; see [`CachePolicy`](crate::cache::CachePolicy).
+    #[cfg(feature = "cache")]
+    #[serde(deserialize_with = "deserialize_ttls")]
+    pub cache_ttls: Option<HashMap<String, Duration>>,
+}
+
+impl fmt::Debug for ReloadableConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ReloadableConfig");
+        debug
+            .field("api_key", &self.api_key.as_ref().map(|_| REDACTED))
+            .field("rate_limit_strategy", &self.rate_limit_strategy);
+        #[cfg(feature = "cache")]
+        debug.field("cache_ttls", &self.cache_ttls);
+        debug.finish()
+    }
+}
+
+#[cfg(feature = "cache")]
+fn deserialize_ttls<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<HashMap<String, Duration>>, D::Error> {
+    let secs = Option::<HashMap<String, u64>>::deserialize(deserializer)?;
+    Ok(secs.map(|secs| {
+        secs.into_iter()
+            .map(|(path, secs)| (path, Duration::from_secs(secs)))
+            .collect()
+    }))
+}
+
+/// Where reloaded settings come from.
+///
+/// Implement it to read settings from a secrets manager or config service.
+#[async_trait]
+pub trait ConfigSource: Send + Sync {
+    /// Read the current settings.
+    async fn load(&self) -> Result<ReloadableConfig>;
+}
+
+/// Settings from environment variables, read on every reload:
+///
+/// | Variable | Value |
+/// |----------|-------|
+/// | `FINNHUB_API_KEY` | API key |
+/// | `FINNHUB_RATE_LIMIT` | `per_second`, `fifteen_second_window` or `<capacity>/<refill_rate>` |
+/// | `FINNHUB_CACHE_TTLS` | `<path>=<seconds>` pairs separated by commas, e.g. `/quote=5,/stock/metric=21600` |
+///
+/// Unset or empty variables leave their setting as it is. The `FINNHUB_`
+/// prefix can be changed with [`with_prefix`](Self::with_prefix).
+#[derive(Debug, Clone)]
+pub struct EnvSource {
+    prefix: String,
+}
+
+impl Default for EnvSource {
+    fn default() -> Self {
+        Self {
+            prefix: "FINNHUB_".to_string(),
+        }
+    }
+}
+
+impl EnvSource {
+    /// Read the `FINNHUB_` variables.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read variables starting with `prefix` instead, e.g. `INGEST_FINNHUB_`.
+    #[must_use]
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(format!("{}{name}", self.prefix))
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+    }
+}
+
+#[async_trait]
+impl ConfigSource for EnvSource {
+    async fn load(&self) -> Result<ReloadableConfig> {
+        Ok(ReloadableConfig {
+            api_key: self.var("API_KEY").map(|key| key.trim().to_string()),
+            rate_limit_strategy: self
+                .var("RATE_LIMIT")
+                .map(|value| parse_strategy(&value))
+                .transpose()?,
+            #[cfg(feature = "cache")]
+            cache_ttls: self
+                .var("CACHE_TTLS")
+                .map(|value| parse_ttls(&value))
+                .transpose()?,
+        })
+    }
+}
+
+/// Parse `per_second`, `fifteen_second_window` or `<capacity>/<refill_rate>`.
+fn parse_strategy(value: &str) -> Result<RateLimitStrategy> {
+    let invalid = || {
+        Error::invalid_config(
+            "rate_limit_strategy",
+            format!(
+                "expected per_second, fifteen_second_window or <capacity>/<refill_rate>, got {value:?}"
+            ),
+        )
+    };
+    match value.trim() {
+        "per_second" => Ok(RateLimitStrategy::PerSecond),
+        "fifteen_second_window" => Ok(RateLimitStrategy::FifteenSecondWindow),
+        custom => {
+            let (capacity, refill_rate) = custom.split_once('/').ok_or_else(invalid)?;
+            Ok(RateLimitStrategy::Custom {
+                capacity: capacity.trim().parse().map_err(|_| invalid())?,
+                refill_rate: refill_rate.trim().parse().map_err(|_| invalid())?,
+            })
+        }
+    }
+}
+
+/// Parse `<path>=<seconds>` pairs separated by commas.
+#[cfg(feature = "cache")]
+fn parse_ttls(value: &str) -> Result<HashMap<String, Duration>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let parsed = pair
+                .split_once('=')
+                .and_then(|(path, secs)| Some((path.trim(), secs.trim().parse().ok()?)));
+            match parsed {
+                Some((path, secs)) if path.starts_with('/') => {
+                    Ok((path.to_string(), Duration::from_secs(secs)))
+                }
+                _ => Err(Error::invalid_config(
+                    "cache",
+                    format!("expected <path>=<seconds>, got {pair:?}"),
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Settings from a JSON file in the format of [`ReloadableConfig`].
+///
+/// The file is read with `tokio::fs`, which needs a Tokio runtime; not
+/// available on wasm32. Write it by renaming a temporary file into place so
+/// a reload never sees it half-written.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileSource {
+    /// Read settings from `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl ConfigSource for FileSource {
+    async fn load(&self) -> Result<ReloadableConfig> {
+        let bytes = tokio::fs::read(&self.path)
+            .await
+            .map_err(|err| Error::Storage(format!("{}: {err}", self.path.display())))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+/// Re-reads a [`ConfigSource`] and applies changed settings to a client.
+pub struct ConfigWatcher {
+    client: FinnhubClient,
+    source: Arc<dyn ConfigSource>,
+    interval: Duration,
+    triggers: Vec<BoxStream<'static, ()>>,
+}
+
+impl fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("client", &self.client)
+            .field("interval", &self.interval)
+            .field("triggers", &self.triggers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigWatcher {
+    /// Watch `source`, reloading every minute.
+    pub fn new(client: FinnhubClient, source: impl ConfigSource + 'static) -> Self {
+        Self {
+            client,
+            source: Arc::new(source),
+            interval: Duration::from_secs(60),
+            triggers: Vec::new(),
+        }
+    }
+
+    /// Time between reloads.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Also reload whenever `trigger` yields, e.g. on an admin endpoint.
+    #[must_use]
+    pub fn reload_on(mut self, trigger: impl Stream<Item = ()> + Send + 'static) -> Self {
+        self.triggers.push(trigger.boxed());
+        self
+    }
+
+    /// Also reload when the process receives `SIGHUP`. Needs a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Internal`] if the signal handler can't be installed.
+    #[cfg(all(unix, not(target_arch = "wasm32")))]
+    pub fn reload_on_sighup(self) -> Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let hangups = signal(SignalKind::hangup())
+            .map_err(|err| Error::Internal(format!("can't listen for SIGHUP: {err}")))?;
+        Ok(
+            self.reload_on(stream::unfold(hangups, |mut hangups| async move {
+                hangups.recv().await.map(|()| ((), hangups))
+            })),
+        )
+    }
+
+    /// Read the source once and apply it, returning the names of the
+    /// settings that changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the source's error, or [`Error::InvalidConfig`] if the
+    /// settings are invalid. The client is unchanged in either case.
+    pub async fn reload(&self) -> Result<Vec<&'static str>> {
+        reload(&self.client, self.source.as_ref()).await
+    }
+
+    /// Reload now, then on every interval and trigger.
+    ///
+    /// Yields the names of the settings that changed, skipping reloads that
+    /// changed nothing, and yields failed reloads as `Err` items without
+    /// stopping.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Vec<&'static str>>> {
+        let ticks = stream::unfold(Interval::new(self.interval), |mut ticker| async move {
+            ticker.tick().await;
+            Some(((), ticker))
+        });
+        let triggers = stream::select_all(self.triggers);
+        let client = self.client;
+        let source = self.source;
+
+        stream::select(ticks, triggers)
+            .then(move |()| {
+                let client = client.clone();
+                let source = Arc::clone(&source);
+                async move { reload(&client, source.as_ref()).await }
+            })
+            .filter(|result| {
+                std::future::ready(result.as_ref().map_or(true, |changed| !changed.is_empty()))
+            })
+    }
+}
+
+async fn reload(client: &FinnhubClient, source: &dyn ConfigSource) -> Result<Vec<&'static str>> {
+    let update = source.load().await.inspect_err(|err| {
+        tracing::warn!(error = %err, "failed to load reloaded configuration");
+    })?;
+    client.apply_reload(&update).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    struct Fixed(ReloadableConfig);
+
+    #[async_trait]
+    impl ConfigSource for Fixed {
+        async fn load(&self) -> Result<ReloadableConfig> {
+            Ok(self.0.clone())
+        }
+    }
+
+    /// An update to `new-key` and `strategy`.
+    #[cfg_attr(not(feature = "cache"), allow(clippy::needless_update))]
+    fn update(strategy: Option<RateLimitStrategy>) -> ReloadableConfig {
+        ReloadableConfig {
+            api_key: Some("new-key".to_string()),
+            rate_limit_strategy: strategy,
+            ..ReloadableConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_env_values() {
+        assert_eq!(
+            parse_strategy("fifteen_second_window").unwrap(),
+            RateLimitStrategy::FifteenSecondWindow
+        );
+        assert_eq!(
+            parse_strategy(" 60 / 20 ").unwrap(),
+            RateLimitStrategy::Custom {
+                capacity: 60,
+                refill_rate: 20
+            }
+        );
+        assert!(parse_strategy("fast").is_err());
+
+        #[cfg(feature = "cache")]
+        {
+            let ttls = parse_ttls("/quote=5, /stock/metric=21600,").unwrap();
+            assert_eq!(ttls["/stock/metric"], Duration::from_secs(6 * 60 * 60));
+            assert!(parse_ttls("quote=5").is_err());
+            assert!(parse_ttls("/quote=soon").is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_and_redact() {
+        let config: ReloadableConfig = serde_json::from_str(
+            r#"{"api_key": "secret-key", "rate_limit_strategy": {"custom": {"capacity": 60, "refill_rate": 60}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.rate_limit_strategy,
+            Some(RateLimitStrategy::from_legacy(60))
+        );
+        assert!(!format!("{config:?}").contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_file_source() {
+        let path = std::env::temp_dir().join(format!("finnhub-reload-{}.json", std::process::id()));
+        let source = FileSource::new(&path);
+        assert!(matches!(source.load().await, Err(Error::Storage(_))));
+
+        std::fs::write(
+            &path,
+            r#"{"rate_limit_strategy": "per_second", "cache_ttls": {"/quote": 5}}"#,
+        )
+        .unwrap();
+        let config = source.load().await.unwrap();
+        assert_eq!(config.api_key, None);
+        assert_eq!(
+            config.rate_limit_strategy,
+            Some(RateLimitStrategy::PerSecond)
+        );
+        #[cfg(feature = "cache")]
+        assert_eq!(config.cache_ttls.unwrap()["/quote"], Duration::from_secs(5));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reload_rotates_key_for_derived_clients() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/symbol"))
+            .and(header("X-Finnhub-Token", "new-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: format!("{}/api/v1", server.uri()),
+            ..ClientConfig::default()
+        };
+        let client = FinnhubClient::with_config("old-key", config);
+        let derived = client.reconfigure(|cfg| cfg.timeout_secs = 120);
+        let watcher = ConfigWatcher::new(
+            client.clone(),
+            Fixed(update(Some(RateLimitStrategy::FifteenSecondWindow))),
+        );
+
+        assert_eq!(
+            watcher.reload().await.unwrap(),
+            ["api_key", "rate_limit_strategy"]
+        );
+        assert!(watcher.reload().await.unwrap().is_empty());
+
+        let _: serde_json::Value = client.get("/stock/symbol").await.unwrap();
+        let _: serde_json::Value = derived.get("/stock/symbol").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_update_changes_nothing() {
+        let client = FinnhubClient::new("old-key");
+        let update = update(Some(RateLimitStrategy::Custom {
+            capacity: 10,
+            refill_rate: 0,
+        }));
+
+        let err = client.apply_reload(&update).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidConfig {
+                field: "rate_limit_strategy",
+                ..
+            }
+        ));
+        assert_eq!(client.api_key().expose(), "old-key");
+    }
+
+    #[tokio::test]
+    async fn test_stream_reloads_on_trigger() {
+        let client = FinnhubClient::new("old-key");
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let reloads = ConfigWatcher::new(client.clone(), Fixed(update(None)))
+            .with_interval(Duration::from_secs(60 * 60))
+            .reload_on(rx)
+            .into_stream();
+        futures::pin_mut!(reloads);
+
+        // The first tick reloads straight away; later unchanged reloads
+        // are skipped.
+        assert_eq!(reloads.next().await.unwrap().unwrap(), ["api_key"]);
+        tx.unbounded_send(()).unwrap();
+        drop(tx);
+        let next = tokio::time::timeout(Duration::from_millis(200), reloads.next()).await;
+        assert!(next.is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Responses together with their HTTP metadata.
+//!
+//! [`FinnhubClient::get_enveloped`] returns a [`ResponseEnvelope`]: the
+//! deserialized body along with the status, the headers, the rate limit state
+//! the server reported, the server's clock and how long the request took.
+//! Data pipelines can log or export these to watch their quota and latency:
+//!
+//! ```rust,no_run
+//! use finnhub::{models::stock::Quote, FinnhubClient};
+//!
+//! # async fn run(client: FinnhubClient) -> finnhub::Result<()> {
+//! let quote = client
+//!     .get_enveloped::<Quote>("/quote", &[("symbol", "AAPL")])
+//!     .await?;
+//! println!(
+//!     "{} in {:?}, {:?} calls left",
+//!     quote.body.current_price, quote.latency, quote.rate_limit.remaining
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`FinnhubClient::get_enveloped`]: crate::FinnhubClient::get_enveloped
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, DATE};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// The rate limit headers Finnhub sends with each response.
+///
+/// Fields are `None` when the header is missing or can't be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    /// `X-Ratelimit-Limit`: calls allowed per window.
+    pub limit: Option<u32>,
+    /// `X-Ratelimit-Remaining`: calls left in the current window.
+    pub remaining: Option<u32>,
+    /// `X-Ratelimit-Reset`: when the window resets.
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimitHeaders {
+    /// Read the `X-Ratelimit-*` headers of a response.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        fn parse<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+        Self {
+            limit: parse(headers, "x-ratelimit-limit"),
+            remaining: parse(headers, "x-ratelimit-remaining"),
+            reset: parse(headers, "x-ratelimit-reset")
+                .and_then(|secs: i64| DateTime::from_timestamp(secs, 0)),
+        }
+    }
+
+    /// Time from `now` until [`reset`](Self::reset); zero if it has passed.
+    ///
+    /// Pass the response's [server time](ResponseEnvelope::server_time), when
+    /// it has one, so clock skew doesn't shift the result.
+    #[must_use]
+    pub fn reset_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let reset = self.reset?;
+        Some((reset - now).to_std().unwrap_or_default())
+    }
+
+    /// Whether the response carried any of the headers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A deserialized response body and the HTTP metadata it came with.
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+    /// The deserialized body.
+    pub body: T,
+    /// HTTP status; always a success, since error statuses become errors.
+    pub status: StatusCode,
+    /// All response headers.
+    pub headers: HeaderMap,
+    /// The `X-Ratelimit-*` headers.
+    pub rate_limit: RateLimitHeaders,
+    /// The server's clock when it sent the response, from the `Date` header.
+    pub server_time: Option<DateTime<Utc>>,
+    /// Time from sending the request, after the rate limiter, to reading the
+    /// whole body.
+    pub latency: Duration,
+}
+
+impl<T> ResponseEnvelope<T> {
+    pub(crate) fn new(body: T, status: StatusCode, headers: HeaderMap, latency: Duration) -> Self {
+        Self {
+            body,
+            status,
+            rate_limit: RateLimitHeaders::from_headers(&headers),
+            server_time: server_time(&headers),
+            headers,
+            latency,
+        }
+    }
+
+    /// The body, dropping the metadata.
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// Transform the body, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ResponseEnvelope<U> {
+        ResponseEnvelope {
+            body: f(self.body),
+            status: self.status,
+            headers: self.headers,
+            rate_limit: self.rate_limit,
+            server_time: self.server_time,
+            latency: self.latency,
+        }
+    }
+}
+
+/// The time in a response's `Date` header.
+pub(crate) fn server_time(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let date = headers.get(DATE)?.to_str().ok()?;
+    DateTime::parse_from_rfc2822(date)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Ratelimit-Limit", "30".parse().unwrap());
+        headers.insert("X-Ratelimit-Remaining", " 29".parse().unwrap());
+        headers.insert("X-Ratelimit-Reset", "1700000000".parse().unwrap());
+        headers.insert(DATE, "Tue, 14 Nov 2023 22:13:20 GMT".parse().unwrap());
+
+        let envelope = ResponseEnvelope::new(1, StatusCode::OK, headers, Duration::from_millis(5))
+            .map(|n| n + 1);
+        assert_eq!(envelope.body, 2);
+        assert_eq!(envelope.rate_limit.limit, Some(30));
+        assert_eq!(envelope.rate_limit.remaining, Some(29));
+        assert_eq!(
+            envelope.rate_limit.reset,
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert_eq!(envelope.server_time, envelope.rate_limit.reset);
+        let reset_in = envelope.rate_limit.reset_in(envelope.server_time.unwrap());
+        assert_eq!(reset_in, Some(Duration::ZERO));
+        let earlier = DateTime::from_timestamp(1_699_999_990, 0).unwrap();
+        assert_eq!(
+            envelope.rate_limit.reset_in(earlier),
+            Some(Duration::from_secs(10))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Ratelimit-Remaining", "lots".parse().unwrap());
+        let rate_limit = RateLimitHeaders::from_headers(&headers);
+        assert!(rate_limit.is_empty());
+    }
+}
@@ -0,0 +1,338 @@
+//! Automatic retries and the retry budget shared by every request of a client.
+//!
+//! The client doesn't retry by default. Setting
+//! [`ClientConfig::retry_policy`](crate::ClientConfig::retry_policy) makes
+//! every GET request retry retryable errors with exponential backoff and
+//! jitter; see [`RetryPolicy`].
+//!
+//! Retrying each failed request a few times is harmless while failures are
+//! rare, but during an outage every request fails and per-request retries
+//! multiply traffic against a service that is already struggling. A
+//! [`RetryBudget`] caps the retries spent per minute across the whole client;
+//! once it is used up, failures are returned to the caller straight away.
+//!
+//! ```rust,no_run
+//! use finnhub::{ClientConfig, FinnhubClient};
+//!
+//! # async fn run() -> finnhub::Result<()> {
+//! let config = ClientConfig {
+//!     retry_budget: Some(20),
+//!     ..ClientConfig::default()
+//! };
+//! let client = FinnhubClient::with_config("your-api-key", config);
+//! if let Some(budget) = client.retry_budget() {
+//!     budget.on_exhausted(|error| eprintln!("retry budget exhausted, giving up: {error}"));
+//! }
+//!
+//! let quote = client.retry(3, || async { client.stock().quote("AAPL").await }).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::rt::Instant;
+
+/// Length of the window the budget is counted over.
+const WINDOW: Duration = Duration::from_secs(60);
+
+type ExhaustedHook = Arc<dyn Fn(&Error) + Send + Sync>;
+
+/// Retries allowed per minute, shared by clones of the handle.
+#[derive(Clone)]
+pub struct RetryBudget {
+    per_minute: u32,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    spent: VecDeque<Instant>,
+    denied: u64,
+    hooks: Vec<ExhaustedHook>,
+}
+
+impl fmt::Debug for RetryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryBudget")
+            .field("per_minute", &self.per_minute)
+            .field("remaining", &self.remaining())
+            .field("denied", &self.denied())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryBudget {
+    /// Allow `per_minute` retries in any sliding one-minute window.
+    #[must_use]
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            state: Arc::default(),
+        }
+    }
+
+    /// Retries allowed per minute.
+    #[must_use]
+    pub fn per_minute(&self) -> u32 {
+        self.per_minute
+    }
+
+    /// Spend one retry if any are left in the current window.
+    ///
+    /// Returns `false`, and counts a denial, when the budget is used up.
+    #[must_use]
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Retries left in the current window.
+    #[must_use]
+    pub fn remaining(&self) -> u32 {
+        let mut state = self.lock();
+        state.expire(Instant::now());
+        let spent = u32::try_from(state.spent.len()).unwrap_or(u32::MAX);
+        self.per_minute.saturating_sub(spent)
+    }
+
+    /// Retries refused so far because the budget was used up.
+    #[must_use]
+    pub fn denied(&self) -> u64 {
+        self.lock().denied
+    }
+
+    /// Call `hook` whenever [`FinnhubClient::retry`](crate::FinnhubClient::retry)
+    /// gives up on a retryable error because the budget is used up.
+    ///
+    /// The hook receives the error returned to the caller. It runs on the
+    /// task that made the request, so it should return quickly.
+    pub fn on_exhausted(&self, hook: impl Fn(&Error) + Send + Sync + 'static) {
+        self.lock().hooks.push(Arc::new(hook));
+    }
+
+    /// Report that a retry of `error` was refused.
+    pub(crate) fn notify_exhausted(&self, error: &Error) {
+        tracing::warn!(
+            per_minute = self.per_minute,
+            %error,
+            "retry budget exhausted, not retrying"
+        );
+        // Call the hooks without holding the lock so they can inspect the budget.
+        let hooks = self.lock().hooks.clone();
+        for hook in hooks {
+            hook(error);
+        }
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        let mut state = self.lock();
+        state.expire(now);
+        if state.spent.len() < self.per_minute as usize {
+            state.spent.push_back(now);
+            true
+        } else {
+            state.denied += 1;
+            false
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl State {
+    fn expire(&mut self, now: Instant) {
+        while self
+            .spent
+            .front()
+            .is_some_and(|&spent| now.duration_since(spent) >= WINDOW)
+        {
+            self.spent.pop_front();
+        }
+    }
+}
+
+/// Automatic retries for GET requests, off unless set in
+/// [`ClientConfig::retry_policy`](crate::ClientConfig::retry_policy).
+///
+/// The delay before retry `n` is `initial_backoff * 2^(n-1)`, capped at
+/// `max_backoff`, shortened by a random fraction of up to `jitter` so clients
+/// that failed together don't retry together, and raised to the error's
+/// [`retry_after`](Error::retry_after) when `respect_retry_after` is set.
+///
+/// ```rust,no_run
+/// use finnhub::retry::RetryPolicy;
+/// use finnhub::{ClientConfig, FinnhubClient};
+/// use std::time::Duration;
+///
+/// let config = ClientConfig {
+///     retry_policy: Some(RetryPolicy {
+///         max_attempts: 5,
+///         initial_backoff: Duration::from_secs(1),
+///         ..RetryPolicy::default()
+///     }),
+///     retry_budget: Some(60),
+///     ..ClientConfig::default()
+/// };
+/// let client = FinnhubClient::with_config("your-api-key", config);
+/// ```
+///
+/// Retries spend from the client's [`RetryBudget`], if one is configured.
+/// POST requests such as AI chat are never retried automatically.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per request, the first one included; `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Longest delay between two attempts, before `Retry-After` is applied.
+    pub max_backoff: Duration,
+    /// Fraction of each delay, from 0.0 to 1.0, that is randomly taken off.
+    pub jitter: f64,
+    /// Wait at least as long as the error's
+    /// [`retry_after`](Error::retry_after), e.g. a 429's `Retry-After`.
+    pub respect_retry_after: bool,
+    /// Which errors are retried; [`Error::is_retryable`] by default.
+    pub retry_if: fn(&Error) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.2,
+            respect_retry_after: true,
+            retry_if: Error::is_retryable,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether to retry `error` after `attempt` attempts.
+    #[must_use]
+    pub fn should_retry(&self, attempt: u32, error: &Error) -> bool {
+        attempt < self.max_attempts && (self.retry_if)(error)
+    }
+
+    /// Delay before retry number `retry`, counting from 1, of a request that
+    /// failed with `error`.
+    #[must_use]
+    pub fn backoff(&self, retry: u32, error: &Error) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        let backoff = backoff.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * random_fraction());
+        match error.retry_after() {
+            Some(secs) if self.respect_retry_after => backoff.max(Duration::from_secs(secs)),
+            _ => backoff,
+        }
+    }
+
+    /// One retry of a 429 after its `Retry-After` delay, for
+    /// [`ClientConfig::auto_retry_rate_limit`](crate::ClientConfig::auto_retry_rate_limit).
+    pub(crate) fn rate_limit_once() -> Self {
+        Self {
+            max_attempts: 2,
+            initial_backoff: Duration::ZERO,
+            jitter: 0.0,
+            respect_retry_after: true,
+            retry_if: |error| matches!(error.root(), Error::RateLimitExceeded { .. }),
+            ..Self::default()
+        }
+    }
+
+    /// Reject settings that can't work.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.max_attempts == 0 {
+            return Err(Error::invalid_config(
+                "retry_policy",
+                "max_attempts must be at least 1",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.jitter) {
+            return Err(Error::invalid_config(
+                "retry_policy",
+                format!("jitter must be between 0.0 and 1.0, got {}", self.jitter),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A number in `[0, 1)` that differs between calls. Jitter only needs to
+/// spread retries out, not to be unpredictable.
+pub(crate) fn random_fraction() -> f64 {
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+    let bits = RandomState::new().hash_one(CALLS.fetch_add(1, Ordering::Relaxed)) >> 11;
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = bits as f64 / (1_u64 << 53) as f64;
+    fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_refills_after_window() {
+        let budget = RetryBudget::new(2);
+        let start = Instant::now();
+
+        assert!(budget.try_acquire_at(start));
+        assert!(budget.try_acquire_at(start + Duration::from_secs(10)));
+        assert!(!budget.try_acquire_at(start + Duration::from_secs(20)));
+        assert_eq!(budget.denied(), 1);
+
+        // The first retry leaves the window, the second is still in it.
+        assert!(budget.try_acquire_at(start + WINDOW));
+        assert!(!budget.try_acquire_at(start + WINDOW + Duration::from_secs(5)));
+        assert_eq!(budget.denied(), 2);
+    }
+
+    #[test]
+    fn test_policy_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let error = Error::Timeout;
+        let no_hint = Error::UnexpectedContentType {
+            status: 503,
+            content_type: Some("text/html".to_string()),
+            snippet: String::new(),
+        };
+        assert!(policy.should_retry(2, &error));
+        assert!(!policy.should_retry(3, &error));
+        assert!(!policy.should_retry(1, &Error::Unauthorized));
+
+        assert_eq!(policy.backoff(1, &no_hint), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2, &no_hint), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3, &no_hint), Duration::from_millis(350));
+        // A timeout suggests waiting 5 seconds.
+        assert_eq!(policy.backoff(1, &error), Duration::from_secs(5));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            respect_retry_after: false,
+            ..policy
+        };
+        for _ in 0..20 {
+            let delay = jittered.backoff(1, &error);
+            assert!(delay > Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+}
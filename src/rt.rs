@@ -0,0 +1,108 @@
+//! Clock and timers for the platforms and runtimes the client runs on.
+//!
+//! By default these are std's and tokio's, so the rate limiter, retries and
+//! watchers need a Tokio runtime. With the `runtime-agnostic` feature timers
+//! come from `futures-timer` instead, which works under async-std, smol or
+//! any other executor. On `wasm32` tokio has no time driver and
+//! `std::time::Instant::now` panics, so the browser's clock and `setTimeout`
+//! are used.
+
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+#[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+pub(crate) use tokio::task::{spawn, JoinHandle};
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "runtime-agnostic")))]
+pub(crate) use tokio::time::sleep;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+/// Wait for `duration` on `futures-timer`'s own timer thread.
+#[cfg(all(not(target_arch = "wasm32"), feature = "runtime-agnostic"))]
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Wait for `duration` on the JavaScript event loop.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        // `globalThis` is a window in pages and a worker scope in workers;
+        // both have `setTimeout`.
+        let global = js_sys::global();
+        let set_timeout = js_sys::Reflect::get(&global, &"setTimeout".into())
+            .map(js_sys::Function::from)
+            .expect("setTimeout is not available");
+        set_timeout
+            .call2(&global, &resolve, &millis.into())
+            .expect("setTimeout failed");
+    });
+    // The promise only ever resolves.
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Ticks every `period`, starting immediately. A tick that is late is not
+/// made up for; the next one is scheduled a full period after it.
+#[derive(Debug)]
+pub(crate) struct Interval {
+    #[cfg(all(not(target_arch = "wasm32"), not(feature = "runtime-agnostic")))]
+    inner: tokio::time::Interval,
+    #[cfg(any(target_arch = "wasm32", feature = "runtime-agnostic"))]
+    period: Duration,
+    #[cfg(any(target_arch = "wasm32", feature = "runtime-agnostic"))]
+    next: Instant,
+}
+
+impl Interval {
+    pub(crate) fn new(period: Duration) -> Self {
+        #[cfg(all(not(target_arch = "wasm32"), not(feature = "runtime-agnostic")))]
+        {
+            let mut inner = tokio::time::interval(period);
+            inner.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            Self { inner }
+        }
+        #[cfg(any(target_arch = "wasm32", feature = "runtime-agnostic"))]
+        {
+            Self {
+                period,
+                next: Instant::now(),
+            }
+        }
+    }
+
+    /// Wait for the next tick.
+    pub(crate) async fn tick(&mut self) {
+        #[cfg(all(not(target_arch = "wasm32"), not(feature = "runtime-agnostic")))]
+        {
+            self.inner.tick().await;
+        }
+        #[cfg(any(target_arch = "wasm32", feature = "runtime-agnostic"))]
+        {
+            let now = Instant::now();
+            if self.next > now {
+                sleep(self.next - now).await;
+            }
+            self.next = Instant::now().max(self.next) + self.period;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "runtime-agnostic"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_without_tokio_runtime() {
+        let mut interval = Interval::new(Duration::from_millis(20));
+        let start = Instant::now();
+        futures::executor::block_on(async {
+            for _ in 0..3 {
+                interval.tick().await;
+            }
+        });
+        // The first tick is immediate.
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,374 @@
+//! The request pipeline as a [`tower::Service`].
+//!
+//! [`FinnhubClient`] implements `Service<FinnhubRequest>`, so existing tower
+//! middleware (timeouts, concurrency limits, load shedding, retries) can be
+//! layered around it instead of being reimplemented in the application:
+//!
+//! ```rust,no_run
+//! use finnhub::models::stock::Quote;
+//! use finnhub::service::FinnhubRequest;
+//! use finnhub::FinnhubClient;
+//! use std::time::Duration;
+//! use tower::{Service, ServiceBuilder};
+//!
+//! # async fn run() -> Result<(), tower::BoxError> {
+//! let client = FinnhubClient::new("your-api-key");
+//! let mut service = ServiceBuilder::new()
+//!     .concurrency_limit(4)
+//!     .timeout(Duration::from_secs(5))
+//!     .service(client);
+//!
+//! futures::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+//! let response = service.call(FinnhubRequest::get("/quote?symbol=AAPL")).await?;
+//! let quote: Quote = response.json()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `stock` feature, `QuoteService` adapts a client into a
+//! `Service<QuoteRequest>` answering with a typed, validated
+//! [`Quote`](crate::models::stock::Quote), for stacks that only serve quotes.
+//!
+//! Each call goes through the client's own pipeline: response cache, rate
+//! limiter, authentication and error mapping. The service is always ready;
+//! requests wait for rate limit tokens inside the call. To let a tower rate
+//! limit govern instead, build the client with a generous
+//! [`RateLimitStrategy::Custom`](crate::RateLimitStrategy::Custom).
+
+use futures::future::FutureExt;
+use serde::de::DeserializeOwned;
+use std::task::{Context, Poll};
+
+use crate::client::FinnhubClient;
+use crate::error::{Error, Result};
+use crate::lenient;
+
+#[cfg(not(target_arch = "wasm32"))]
+type ResponseFuture = futures::future::BoxFuture<'static, Result<FinnhubResponse>>;
+// Futures of the browser's fetch API aren't `Send`.
+#[cfg(target_arch = "wasm32")]
+type ResponseFuture = futures::future::LocalBoxFuture<'static, Result<FinnhubResponse>>;
+
+/// A request to the Finnhub API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinnhubRequest {
+    endpoint: String,
+    body: Option<serde_json::Value>,
+}
+
+impl FinnhubRequest {
+    /// GET `endpoint`, a path and query relative to the base URL such as
+    /// `/quote?symbol=AAPL`.
+    pub fn get(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            body: None,
+        }
+    }
+
+    /// POST `body` as JSON to `endpoint`. POST responses are never cached.
+    pub fn post(endpoint: impl Into<String>, body: serde_json::Value) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            body: Some(body),
+        }
+    }
+
+    /// Path and query of the request.
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// JSON body of a POST request.
+    #[must_use]
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        self.body.as_ref()
+    }
+}
+
+/// The JSON body of a successful response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinnhubResponse {
+    endpoint: String,
+    body: Vec<u8>,
+}
+
+impl FinnhubResponse {
+    /// Path and query of the request this answers.
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The raw JSON body.
+    #[must_use]
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Decode the body the way endpoint methods do.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Endpoint`] wrapping the decoding error if the body
+    /// doesn't match `T`.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        lenient::from_slice(&self.body).map_err(|err| Error::from(err).at_endpoint(&self.endpoint))
+    }
+
+    /// Take the raw JSON body.
+    #[must_use]
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// A request for the quote of `symbol`, served by a [`QuoteService`].
+#[cfg(feature = "stock")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuoteRequest {
+    symbol: String,
+}
+
+#[cfg(feature = "stock")]
+impl QuoteRequest {
+    /// Request the quote of `symbol`.
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+        }
+    }
+
+    /// Symbol of the request.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+}
+
+#[cfg(feature = "stock")]
+impl From<&str> for QuoteRequest {
+    fn from(symbol: &str) -> Self {
+        Self::new(symbol)
+    }
+}
+
+#[cfg(feature = "stock")]
+impl From<String> for QuoteRequest {
+    fn from(symbol: String) -> Self {
+        Self::new(symbol)
+    }
+}
+
+/// A client as a `tower::Service` answering [`QuoteRequest`]s with quotes
+/// from [`StockEndpoints::quote`](crate::endpoints::StockEndpoints::quote).
+///
+/// Clones share the client, so the service can be cloned into each task
+/// like the client itself.
+///
+/// ```rust,no_run
+/// use finnhub::service::{QuoteRequest, QuoteService};
+/// use finnhub::FinnhubClient;
+/// use std::time::Duration;
+/// use tower::{Service, ServiceBuilder};
+///
+/// # async fn run() -> Result<(), tower::BoxError> {
+/// let client = FinnhubClient::new("your-api-key");
+/// let mut quotes = ServiceBuilder::new()
+///     .timeout(Duration::from_secs(2))
+///     .service(QuoteService::new(client));
+///
+/// futures::future::poll_fn(|cx| quotes.poll_ready(cx)).await?;
+/// let quote = quotes.call(QuoteRequest::new("AAPL")).await?;
+/// println!("AAPL: {}", quote.current_price);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stock")]
+#[derive(Debug, Clone)]
+pub struct QuoteService {
+    client: FinnhubClient,
+}
+
+#[cfg(feature = "stock")]
+impl QuoteService {
+    /// Serve quotes with `client`.
+    #[must_use]
+    pub fn new(client: FinnhubClient) -> Self {
+        Self { client }
+    }
+
+    /// The client serving the quotes.
+    #[must_use]
+    pub fn client(&self) -> &FinnhubClient {
+        &self.client
+    }
+}
+
+#[cfg(feature = "stock")]
+impl tower::Service<QuoteRequest> for QuoteService {
+    type Response = crate::models::stock::Quote;
+    type Error = Error;
+    #[cfg(not(target_arch = "wasm32"))]
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response>>;
+    #[cfg(target_arch = "wasm32")]
+    type Future = futures::future::LocalBoxFuture<'static, Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: QuoteRequest) -> Self::Future {
+        let client = self.client.clone();
+        let future = async move { client.stock().quote(&request.symbol).await };
+        #[cfg(not(target_arch = "wasm32"))]
+        return future.boxed();
+        #[cfg(target_arch = "wasm32")]
+        return future.boxed_local();
+    }
+}
+
+impl tower::Service<FinnhubRequest> for FinnhubClient {
+    type Response = FinnhubResponse;
+    type Error = Error;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: FinnhubRequest) -> Self::Future {
+        let client = self.clone();
+        let future = async move {
+            let FinnhubRequest { endpoint, body } = request;
+            let body = match &body {
+                Some(body) => client.post_json_body(&endpoint, body).await?,
+                None => client.get_json_body(&endpoint).await?,
+            };
+            Ok(FinnhubResponse { endpoint, body })
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        return future.boxed();
+        #[cfg(target_arch = "wasm32")]
+        return future.boxed_local();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock_client;
+    use std::time::Duration;
+    use tower::{Service, ServiceBuilder};
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn call<S, R>(service: &mut S, request: R) -> std::result::Result<S::Response, S::Error>
+    where
+        S: Service<R>,
+    {
+        futures::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+        service.call(request).await
+    }
+
+    #[tokio::test]
+    async fn test_get_through_middleware() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/peers"))
+            .and(query_param("symbol", "AAPL"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(["AAPL", "MSFT"]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "c": 1.0 }))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let mut service = ServiceBuilder::new()
+            .timeout(Duration::from_millis(500))
+            .service(mock_client(&server));
+
+        let response = call(
+            &mut service,
+            FinnhubRequest::get("/stock/peers?symbol=AAPL"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.endpoint(), "/stock/peers?symbol=AAPL");
+        assert_eq!(response.json::<Vec<String>>().unwrap(), ["AAPL", "MSFT"]);
+
+        let err = call(&mut service, FinnhubRequest::get("/quote?symbol=AAPL"))
+            .await
+            .unwrap_err();
+        assert!(err.is::<tower::timeout::error::Elapsed>());
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_post() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/stock/profile2"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/ai-chat"))
+            .and(body_json(serde_json::json!({ "q": 1 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server);
+        let err = call(&mut client, FinnhubRequest::get("/stock/profile2?symbol=X"))
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+        assert!(err.to_string().contains("/stock/profile2"));
+
+        let request = FinnhubRequest::post("/ai-chat", serde_json::json!({ "q": 1 }));
+        assert!(call(&mut client, request).await.is_ok());
+    }
+
+    #[cfg(feature = "stock")]
+    #[tokio::test]
+    async fn test_quote_service() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(query_param("symbol", "AAPL"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "c": 190.5, "d": 1.5, "dp": 0.79, "h": 191.0, "l": 188.0,
+                "o": 189.0, "pc": 189.0, "t": 1_717_200_000,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/quote"))
+            .and(query_param("symbol", "MSFT"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let mut service = ServiceBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .service(QuoteService::new(mock_client(&server)));
+
+        let quote = call(&mut service, QuoteRequest::from("AAPL"))
+            .await
+            .unwrap();
+        assert!((quote.current_price - 190.5).abs() < f64::EPSILON);
+
+        let err = call(&mut service, QuoteRequest::new("MSFT"))
+            .await
+            .unwrap_err();
+        let err = err.downcast::<Error>().unwrap();
+        assert!(matches!(err.root(), Error::ApiError { status: 403, .. }));
+    }
+}
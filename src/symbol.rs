@@ -0,0 +1,422 @@
+//! Symbol normalization between common vendor formats.
+//!
+//! Finnhub, Yahoo Finance and Bloomberg-style feeds spell the same instrument
+//! differently:
+//!
+//! | Instrument     | Finnhub           | Yahoo      | Bloomberg-ish   |
+//! |----------------|-------------------|------------|-----------------|
+//! | Share class    | `BRK.B`           | `BRK-B`    | `BRK/B`         |
+//! | Forex pair     | `OANDA:EUR_USD`   | `EURUSD=X` | `EURUSD Curncy` |
+//! | Crypto pair    | `BINANCE:BTCUSDT` | `BTC-USDT` | not supported   |
+//!
+//! Exchange suffixes such as `.L` or `.TO` are shared by Finnhub and Yahoo and
+//! are passed through unchanged.
+//!
+//! ```
+//! use finnhub::symbol::{normalize, Vendor};
+//!
+//! assert_eq!(normalize("BRK-B", Vendor::Yahoo, Vendor::Finnhub).unwrap(), "BRK.B");
+//! assert_eq!(normalize("OANDA:EUR_USD", Vendor::Finnhub, Vendor::Yahoo).unwrap(), "EURUSD=X");
+//! ```
+
+use std::borrow::Cow;
+
+use crate::error::{Error, Result};
+
+/// Default Finnhub venue for forex pairs without an explicit exchange prefix.
+pub const DEFAULT_FOREX_VENUE: &str = "OANDA";
+/// Default Finnhub venue for crypto pairs without an explicit exchange prefix.
+pub const DEFAULT_CRYPTO_VENUE: &str = "BINANCE";
+
+/// Venues prepended to forex and crypto symbols passed without one.
+///
+/// Set on `ClientConfig::venues`. With `forex` set to `OANDA`, forex
+/// endpoints turn `EUR_USD` into `OANDA:EUR_USD`; symbols that already carry
+/// a `VENUE:` prefix are sent unchanged. Only the prefix is added, so the pair
+/// must already be spelled the way the venue expects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VenueDefaults {
+    /// Venue for forex pairs, e.g. [`DEFAULT_FOREX_VENUE`].
+    pub forex: Option<String>,
+    /// Venue for crypto pairs, e.g. [`DEFAULT_CRYPTO_VENUE`].
+    pub crypto: Option<String>,
+}
+
+impl VenueDefaults {
+    /// Prefix `symbol` with the default forex venue if it has none.
+    #[must_use]
+    pub fn forex_symbol<'a>(&self, symbol: &'a str) -> Cow<'a, str> {
+        qualify(self.forex.as_deref(), symbol)
+    }
+
+    /// Prefix `symbol` with the default crypto venue if it has none.
+    #[must_use]
+    pub fn crypto_symbol<'a>(&self, symbol: &'a str) -> Cow<'a, str> {
+        qualify(self.crypto.as_deref(), symbol)
+    }
+}
+
+fn qualify<'a>(venue: Option<&str>, symbol: &'a str) -> Cow<'a, str> {
+    match venue {
+        Some(venue) if !symbol.contains(':') => Cow::Owned(format!("{venue}:{symbol}")),
+        _ => Cow::Borrowed(symbol),
+    }
+}
+
+/// Exchange suffixes used by both Finnhub and Yahoo for non-US listings.
+const EXCHANGE_SUFFIXES: &[&str] = &[
+    "AS", "AX", "BA", "BE", "BK", "BO", "BR", "CO", "DE", "F", "HE", "HK", "IR", "IS", "JK", "JO",
+    "KL", "KQ", "KS", "L", "LS", "MC", "ME", "MI", "MX", "NE", "NS", "NZ", "OL", "PA", "SA", "SG",
+    "SI", "SN", "SR", "SS", "ST", "SW", "SZ", "T", "TA", "TO", "TW", "TWO", "V", "VI", "WA",
+];
+
+/// Finnhub forex venues (the part before `:`).
+const FOREX_VENUES: &[&str] = &[
+    "OANDA",
+    "FXCM",
+    "FOREX.COM",
+    "IC MARKETS",
+    "FXPRO",
+    "OCTAFX",
+    "PEPPERSTONE",
+];
+
+/// Quote assets recognised when splitting concatenated crypto pairs like `BTCUSDT`.
+/// Longer codes come first so `USDT` wins over `USD`.
+const CRYPTO_QUOTES: &[&str] = &[
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "DAI", "USD", "EUR", "GBP", "JPY", "TRY", "BRL",
+    "BTC", "ETH", "BNB",
+];
+
+/// Symbol format of a data vendor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vendor {
+    /// Finnhub (`BRK.B`, `OANDA:EUR_USD`, `BINANCE:BTCUSDT`).
+    Finnhub,
+    /// Yahoo Finance (`BRK-B`, `EURUSD=X`, `BTC-USD`).
+    Yahoo,
+    /// Bloomberg-style tickers (`BRK/B`, `EURUSD Curncy`).
+    Bloomberg,
+}
+
+/// Asset class of a parsed symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instrument {
+    /// Listed equity, optionally with a share class and exchange suffix.
+    Equity {
+        /// Root ticker (`BRK`).
+        ticker: String,
+        /// Share class (`B` in `BRK.B`).
+        class: Option<String>,
+        /// Exchange suffix (`L` in `VOD.L`).
+        exchange: Option<String>,
+    },
+    /// Currency pair.
+    Forex {
+        /// Base currency.
+        base: String,
+        /// Quote currency.
+        quote: String,
+    },
+    /// Crypto pair.
+    Crypto {
+        /// Base asset.
+        base: String,
+        /// Quote asset.
+        quote: String,
+    },
+}
+
+/// A symbol parsed into vendor-neutral parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSymbol {
+    /// The instrument.
+    pub instrument: Instrument,
+    /// Finnhub venue (`OANDA`, `BINANCE`) for forex and crypto pairs, if known.
+    pub venue: Option<String>,
+}
+
+/// Convert `symbol` from one vendor format to another.
+///
+/// Forex and crypto pairs without a venue are given [`DEFAULT_FOREX_VENUE`] or
+/// [`DEFAULT_CRYPTO_VENUE`] when converted to Finnhub. Use [`parse`] and
+/// [`ParsedSymbol::format`] to pick a different venue.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidParameter`] if the symbol can't be parsed in the
+/// `from` format or has no representation in the `to` format.
+pub fn normalize(symbol: &str, from: Vendor, to: Vendor) -> Result<String> {
+    parse(symbol, from)?.format(to)
+}
+
+/// Parse a symbol written in `vendor`'s format.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidParameter`] if the symbol is empty or malformed.
+pub fn parse(symbol: &str, vendor: Vendor) -> Result<ParsedSymbol> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        return Err(Error::invalid_parameter("symbol must not be empty"));
+    }
+
+    match vendor {
+        Vendor::Finnhub => parse_finnhub(symbol),
+        Vendor::Yahoo => parse_yahoo(symbol),
+        Vendor::Bloomberg => parse_bloomberg(symbol),
+    }
+}
+
+impl ParsedSymbol {
+    /// Render the symbol in `vendor`'s format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParameter`] if the instrument has no
+    /// representation in that format.
+    pub fn format(&self, vendor: Vendor) -> Result<String> {
+        match (&self.instrument, vendor) {
+            (
+                Instrument::Equity {
+                    ticker,
+                    class,
+                    exchange,
+                },
+                _,
+            ) => {
+                let separator = match vendor {
+                    Vendor::Finnhub => '.',
+                    Vendor::Yahoo => '-',
+                    Vendor::Bloomberg => '/',
+                };
+                let mut out = ticker.clone();
+                if let Some(class) = class {
+                    out.push(separator);
+                    out.push_str(class);
+                }
+                if let Some(exchange) = exchange {
+                    out.push('.');
+                    out.push_str(exchange);
+                }
+                Ok(out)
+            }
+            (Instrument::Forex { base, quote }, Vendor::Finnhub) => {
+                let venue = self.venue.as_deref().unwrap_or(DEFAULT_FOREX_VENUE);
+                let pair = match venue {
+                    "OANDA" => format!("{base}_{quote}"),
+                    "FXCM" => format!("{base}/{quote}"),
+                    _ => format!("{base}{quote}"),
+                };
+                Ok(format!("{venue}:{pair}"))
+            }
+            (Instrument::Forex { base, quote }, Vendor::Yahoo) => Ok(format!("{base}{quote}=X")),
+            (Instrument::Forex { base, quote }, Vendor::Bloomberg) => {
+                Ok(format!("{base}{quote} Curncy"))
+            }
+            (Instrument::Crypto { base, quote }, Vendor::Finnhub) => {
+                let venue = self.venue.as_deref().unwrap_or(DEFAULT_CRYPTO_VENUE);
+                let pair = match venue {
+                    "COINBASE" => format!("{base}-{quote}"),
+                    _ => format!("{base}{quote}"),
+                };
+                Ok(format!("{venue}:{pair}"))
+            }
+            (Instrument::Crypto { base, quote }, Vendor::Yahoo) => Ok(format!("{base}-{quote}")),
+            (Instrument::Crypto { .. }, Vendor::Bloomberg) => Err(Error::invalid_parameter(
+                "crypto pairs have no Bloomberg-style representation",
+            )),
+        }
+    }
+}
+
+fn parse_finnhub(symbol: &str) -> Result<ParsedSymbol> {
+    if let Some((venue, pair)) = symbol.split_once(':') {
+        let venue = venue.to_ascii_uppercase();
+        let (base, quote) = split_pair(pair)
+            .ok_or_else(|| Error::invalid_parameter(format!("unrecognised pair: {symbol}")))?;
+        let instrument = if FOREX_VENUES.contains(&venue.as_str()) {
+            Instrument::Forex { base, quote }
+        } else {
+            Instrument::Crypto { base, quote }
+        };
+        return Ok(ParsedSymbol {
+            instrument,
+            venue: Some(venue),
+        });
+    }
+
+    Ok(equity(symbol, '.'))
+}
+
+fn parse_yahoo(symbol: &str) -> Result<ParsedSymbol> {
+    if let Some(pair) = symbol.strip_suffix("=X") {
+        return forex_from_concatenated(pair, symbol);
+    }
+
+    if let Some((base, quote)) = symbol.split_once('-') {
+        if CRYPTO_QUOTES.contains(&quote) && !quote.contains('.') {
+            return Ok(ParsedSymbol {
+                instrument: Instrument::Crypto {
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                },
+                venue: None,
+            });
+        }
+    }
+
+    Ok(equity(symbol, '-'))
+}
+
+fn parse_bloomberg(symbol: &str) -> Result<ParsedSymbol> {
+    if let Some(pair) = symbol.strip_suffix(" Curncy") {
+        return forex_from_concatenated(pair.trim(), symbol);
+    }
+
+    Ok(equity(symbol, '/'))
+}
+
+fn forex_from_concatenated(pair: &str, symbol: &str) -> Result<ParsedSymbol> {
+    if pair.len() != 6 || !pair.is_ascii() {
+        return Err(Error::invalid_parameter(format!(
+            "unrecognised currency pair: {symbol}"
+        )));
+    }
+    Ok(ParsedSymbol {
+        instrument: Instrument::Forex {
+            base: pair[..3].to_string(),
+            quote: pair[3..].to_string(),
+        },
+        venue: None,
+    })
+}
+
+/// Split an equity symbol into ticker, share class and exchange suffix.
+fn equity(symbol: &str, class_separator: char) -> ParsedSymbol {
+    let (rest, exchange) = match symbol.rsplit_once('.') {
+        Some((rest, suffix)) if EXCHANGE_SUFFIXES.contains(&suffix) => {
+            (rest, Some(suffix.to_string()))
+        }
+        _ => (symbol, None),
+    };
+
+    let (ticker, class) = match rest.split_once(class_separator) {
+        Some((ticker, class)) if !ticker.is_empty() && !class.is_empty() => {
+            (ticker.to_string(), Some(class.to_string()))
+        }
+        _ => (rest.to_string(), None),
+    };
+
+    ParsedSymbol {
+        instrument: Instrument::Equity {
+            ticker,
+            class,
+            exchange,
+        },
+        venue: None,
+    }
+}
+
+/// Split the pair part of an exchange-prefixed symbol (`EUR_USD`, `BTC-USD`, `BTCUSDT`).
+fn split_pair(pair: &str) -> Option<(String, String)> {
+    if let Some((base, quote)) = pair.split_once(['_', '/', '-']) {
+        return Some((base.to_string(), quote.to_string()));
+    }
+
+    let upper = pair.to_ascii_uppercase();
+    CRYPTO_QUOTES.iter().find_map(|quote| {
+        upper
+            .strip_suffix(quote)
+            .filter(|base| !base.is_empty())
+            .map(|base| (base.to_string(), (*quote).to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_venue_defaults() {
+        let venues = VenueDefaults {
+            forex: Some("OANDA".to_string()),
+            crypto: None,
+        };
+        assert_eq!(venues.forex_symbol("EUR_USD"), "OANDA:EUR_USD");
+        assert_eq!(venues.forex_symbol("FXCM:EUR/USD"), "FXCM:EUR/USD");
+        assert_eq!(venues.crypto_symbol("BTCUSDT"), "BTCUSDT");
+    }
+
+    #[test]
+    fn test_share_class_round_trip() {
+        assert_eq!(
+            normalize("BRK.B", Vendor::Finnhub, Vendor::Yahoo).unwrap(),
+            "BRK-B"
+        );
+        assert_eq!(
+            normalize("BRK-B", Vendor::Yahoo, Vendor::Bloomberg).unwrap(),
+            "BRK/B"
+        );
+        assert_eq!(
+            normalize("BRK/B", Vendor::Bloomberg, Vendor::Finnhub).unwrap(),
+            "BRK.B"
+        );
+        assert_eq!(
+            normalize("AAPL", Vendor::Finnhub, Vendor::Yahoo).unwrap(),
+            "AAPL"
+        );
+    }
+
+    #[test]
+    fn test_exchange_suffix_is_preserved() {
+        assert_eq!(
+            normalize("VOD.L", Vendor::Finnhub, Vendor::Yahoo).unwrap(),
+            "VOD.L"
+        );
+        assert_eq!(
+            normalize("RCI-B.TO", Vendor::Yahoo, Vendor::Finnhub).unwrap(),
+            "RCI.B.TO"
+        );
+    }
+
+    #[test]
+    fn test_forex() {
+        assert_eq!(
+            normalize("OANDA:EUR_USD", Vendor::Finnhub, Vendor::Yahoo).unwrap(),
+            "EURUSD=X"
+        );
+        assert_eq!(
+            normalize("EURUSD=X", Vendor::Yahoo, Vendor::Finnhub).unwrap(),
+            "OANDA:EUR_USD"
+        );
+        assert_eq!(
+            normalize("GBPJPY Curncy", Vendor::Bloomberg, Vendor::Yahoo).unwrap(),
+            "GBPJPY=X"
+        );
+    }
+
+    #[test]
+    fn test_crypto() {
+        assert_eq!(
+            normalize("BINANCE:BTCUSDT", Vendor::Finnhub, Vendor::Yahoo).unwrap(),
+            "BTC-USDT"
+        );
+        assert_eq!(
+            normalize("ETH-USD", Vendor::Yahoo, Vendor::Finnhub).unwrap(),
+            "BINANCE:ETHUSD"
+        );
+
+        let mut parsed = parse("BTC-USD", Vendor::Yahoo).unwrap();
+        parsed.venue = Some("COINBASE".to_string());
+        assert_eq!(parsed.format(Vendor::Finnhub).unwrap(), "COINBASE:BTC-USD");
+
+        assert!(normalize("BINANCE:BTCUSDT", Vendor::Finnhub, Vendor::Bloomberg).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(normalize("", Vendor::Finnhub, Vendor::Yahoo).is_err());
+        assert!(normalize("EURO=X", Vendor::Yahoo, Vendor::Finnhub).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+//! Indicator and forex math used by the examples, as plain functions.
+//!
+//! Everything here works on slices and maps already fetched from the API, so
+//! it needs no client and is easy to test:
+//!
+//! ```
+//! use finnhub::toolkit;
+//!
+//! let closes = [10.0, 11.0, 12.0, 13.0, 14.0];
+//! assert_eq!(toolkit::sma(&closes, 3), [11.0, 12.0, 13.0]);
+//! assert_eq!(toolkit::trailing_mean(&closes, 2), Some(13.5));
+//!
+//! // A long entry at 100 with a stop at 95 and a target at 110.
+//! assert_eq!(toolkit::risk_reward(100.0, 95.0, 110.0), Some(2.0));
+//! ```
+//!
+//! Candle responses keep each series in its own `Vec`, so pass
+//! `&candles.high, &candles.low, &candles.close` to [`atr`] and
+//! [`true_ranges`]. Forex helpers take the `quote` map of
+//! `forex().rates("USD")`, i.e. units of each currency per US dollar.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// Simple moving average: the mean of each window of `period` values.
+///
+/// The result has one value per full window, `values.len() - period + 1` in
+/// all, aligned with the last value of its window. Empty if `period` is zero
+/// or longer than `values`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn sma(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 {
+        return Vec::new();
+    }
+    values
+        .windows(period)
+        .map(|window| window.iter().sum::<f64>() / period as f64)
+        .collect()
+}
+
+/// Exponential moving average with smoothing `2 / (period + 1)`, seeded with
+/// the simple average of the first `period` values.
+///
+/// Aligned like [`sma`]: the first value covers `values[..period]`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn ema(values: &[f64], period: usize) -> Vec<f64> {
+    let Some(&seed) = sma(values.get(..period).unwrap_or_default(), period).first() else {
+        return Vec::new();
+    };
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut out = Vec::with_capacity(values.len() - period + 1);
+    out.push(seed);
+    for &value in &values[period..] {
+        let last = out[out.len() - 1];
+        out.push(last + alpha * (value - last));
+    }
+    out
+}
+
+/// Mean of the last `period` values, or of all of them when there are
+/// fewer; `None` if `values` is empty or `period` is zero.
+///
+/// The "20-day average" of the latest bar when the history may be short.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn trailing_mean(values: &[f64], period: usize) -> Option<f64> {
+    let tail = &values[values.len().saturating_sub(period)..];
+    (period > 0 && !tail.is_empty()).then(|| tail.iter().sum::<f64>() / tail.len() as f64)
+}
+
+/// True range of each bar after the first: the largest of its high-low
+/// range and its gaps from the previous close.
+///
+/// Bars are read up to the shortest of the three series.
+#[must_use]
+pub fn true_ranges(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    let len = high.len().min(low.len()).min(close.len());
+    (1..len)
+        .map(|i| {
+            let previous = close[i - 1];
+            (high[i] - low[i])
+                .max((high[i] - previous).abs())
+                .max((low[i] - previous).abs())
+        })
+        .collect()
+}
+
+/// Average true range: the simple mean of the last `period` [true
+/// ranges](true_ranges), in price units.
+///
+/// `None` if there are fewer than `period` true ranges, i.e. fewer than
+/// `period + 1` bars, or `period` is zero. Divide by the last close for a
+/// volatility percentage.
+#[must_use]
+pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Option<f64> {
+    let ranges = true_ranges(high, low, close);
+    if ranges.len() < period {
+        return None;
+    }
+    trailing_mean(&ranges, period)
+}
+
+/// Where `value` sits between `low` and `high`, from 0 at the low to 1 at
+/// the high; outside that when `value` is outside the range. `None` if the
+/// range is empty.
+#[must_use]
+pub fn range_position(value: f64, low: f64, high: f64) -> Option<f64> {
+    (high > low).then(|| (value - low) / (high - low))
+}
+
+/// Reward per unit of risk of a trade entered at `entry`, with a stop loss at
+/// `stop` and a profit target at `target`; works for long and short trades.
+///
+/// `None` if the stop is at the entry. A result of 2.0 is usually written
+/// "1:2".
+#[must_use]
+pub fn risk_reward(entry: f64, stop: f64, target: f64) -> Option<f64> {
+    let risk = (entry - stop).abs();
+    (risk > 0.0).then(|| (target - entry).abs() / risk)
+}
+
+/// Currencies and exponents of the US Dollar Index (DXY).
+pub const DOLLAR_INDEX_WEIGHTS: [(&str, f64); 6] = [
+    ("EUR", 0.576),
+    ("JPY", 0.136),
+    ("GBP", 0.119),
+    ("CAD", 0.091),
+    ("SEK", 0.042),
+    ("CHF", 0.036),
+];
+
+/// The US Dollar Index from `rates` in units per US dollar, using ICE's
+/// formula: `50.14348112 × Π rate^weight` over [`DOLLAR_INDEX_WEIGHTS`].
+///
+/// `None` if any of the six currencies is missing or not positive. The
+/// result tracks the published index as closely as the rates track the
+/// spot market.
+#[must_use]
+pub fn dollar_index<S: BuildHasher>(rates: &HashMap<String, f64, S>) -> Option<f64> {
+    DOLLAR_INDEX_WEIGHTS
+        .iter()
+        .try_fold(50.143_481_12, |index, &(currency, weight)| {
+            let rate = *rates.get(currency)?;
+            (rate > 0.0).then(|| index * rate.powf(weight))
+        })
+}
+
+/// The `base`/`quote` cross rate, in units of `quote` per `base`, from
+/// `rates` in units per US dollar. `USD` may be either side.
+///
+/// For example `cross_rate(&rates, "EUR", "GBP")` is the EUR/GBP rate.
+/// `None` if a currency is missing or its rate isn't positive.
+#[must_use]
+pub fn cross_rate<S: BuildHasher>(
+    rates: &HashMap<String, f64, S>,
+    base: &str,
+    quote: &str,
+) -> Option<f64> {
+    let per_usd = |currency: &str| match currency {
+        "USD" => Some(1.0),
+        _ => rates.get(currency).copied().filter(|&rate| rate > 0.0),
+    };
+    Some(per_usd(quote)? / per_usd(base)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_moving_averages() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(sma(&values, 2), [1.5, 2.5, 3.5, 4.5, 5.5]);
+        assert!(sma(&values, 0).is_empty() && sma(&values, 7).is_empty());
+
+        // Seeded at 2.0, then alpha = 0.5.
+        assert_eq!(ema(&values, 3), [2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(ema(&values, 6), [3.5]);
+        assert!(ema(&values, 0).is_empty() && ema(&values, 7).is_empty());
+
+        assert_eq!(trailing_mean(&values, 2), Some(5.5));
+        assert_eq!(trailing_mean(&values, 20), Some(3.5));
+        assert_eq!(trailing_mean(&values, 0), None);
+        assert_eq!(trailing_mean(&[], 5), None);
+    }
+
+    #[test]
+    fn test_true_range_and_atr() {
+        let high = [10.0, 11.0, 15.0, 12.0];
+        let low = [9.0, 10.5, 12.0, 8.0];
+        let close = [9.5, 11.0, 14.0, 9.0];
+        // Gaps from the previous close: up from 9.5 and 11.0, down from 14.0.
+        assert_eq!(true_ranges(&high, &low, &close), [1.5, 4.0, 6.0]);
+        assert_eq!(atr(&high, &low, &close, 2), Some(5.0));
+        assert_eq!(atr(&high, &low, &close, 4), None);
+        assert!(true_ranges(&high, &low, &close[..1]).is_empty());
+
+        assert_eq!(range_position(12.0, 10.0, 20.0), Some(0.2));
+        assert_eq!(range_position(12.0, 10.0, 10.0), None);
+    }
+
+    #[test]
+    fn test_risk_reward() {
+        assert_eq!(risk_reward(100.0, 95.0, 110.0), Some(2.0));
+        // Short: stop above, target below.
+        assert_eq!(risk_reward(50.0, 52.0, 47.0), Some(1.5));
+        assert_eq!(risk_reward(50.0, 50.0, 60.0), None);
+    }
+
+    #[test]
+    fn test_forex_helpers() {
+        let mut rates: HashMap<String, f64> = [
+            ("EUR", 0.92),
+            ("JPY", 150.0),
+            ("GBP", 0.79),
+            ("CAD", 1.36),
+            ("SEK", 10.5),
+            ("CHF", 0.88),
+        ]
+        .into_iter()
+        .map(|(currency, rate)| (currency.to_string(), rate))
+        .collect();
+
+        let index = dollar_index(&rates).unwrap();
+        assert!((100.0..110.0).contains(&index), "{index}");
+        // A stronger dollar buys more of every currency.
+        let stronger: HashMap<String, f64> =
+            rates.iter().map(|(c, r)| (c.clone(), r * 1.01)).collect();
+        assert!(close(dollar_index(&stronger).unwrap(), index * 1.01));
+
+        assert!(close(
+            cross_rate(&rates, "EUR", "GBP").unwrap(),
+            0.79 / 0.92
+        ));
+        assert!(close(cross_rate(&rates, "EUR", "USD").unwrap(), 1.0 / 0.92));
+        assert_eq!(cross_rate(&rates, "USD", "JPY"), Some(150.0));
+        assert_eq!(cross_rate(&rates, "EUR", "AUD"), None);
+
+        rates.remove("SEK");
+        assert_eq!(dollar_index(&rates), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! Sanity checks for price data.
+//!
+//! Upstream feeds occasionally return glitched bars: a high below the low,
+//! negative volume, or timestamps out of order. These checks catch such data
+//! before it reaches downstream models.
+//!
+//! The client applies them to quotes and candles according to
+//! `ClientConfig::validation`; they can also be run by hand via [`Validate`].
+//!
+//! ```
+//! # #[cfg(feature = "stock")]
+//! # {
+//! use finnhub::models::{stock::StockCandles, Count};
+//! use finnhub::validation::Validate;
+//!
+//! let candles = StockCandles {
+//!     close: vec![10.0],
+//!     high: vec![9.0],
+//!     low: vec![11.0],
+//!     open: vec![10.0],
+//!     status: "ok".to_string(),
+//!     timestamp: vec![1_700_000_000],
+//!     volume: vec![Count::new(100)],
+//! };
+//! assert!(candles.validate().is_err());
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+
+/// What to do when a response violates a data invariant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Skip validation entirely.
+    #[default]
+    Off,
+    /// Log a warning and return the data unchanged.
+    Warn,
+    /// Fail the request with [`Error::DataInvariantViolation`].
+    Error,
+}
+
+impl ValidationMode {
+    /// Validate `value` according to this mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DataInvariantViolation`] in [`ValidationMode::Error`]
+    /// mode if `value` fails validation.
+    pub fn apply<T: Validate>(self, value: T) -> Result<T> {
+        if self == Self::Off {
+            return Ok(value);
+        }
+
+        match value.validate() {
+            Err(err) if self == Self::Warn => {
+                tracing::warn!("{err}");
+                Ok(value)
+            }
+            Err(err) => Err(err),
+            Ok(()) => Ok(value),
+        }
+    }
+}
+
+/// Price data whose invariants can be checked.
+pub trait Validate {
+    /// Check the invariants of this value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DataInvariantViolation`] describing the first
+    /// violation found.
+    fn validate(&self) -> Result<()>;
+}
+
+fn violation(message: impl Into<String>) -> Error {
+    Error::DataInvariantViolation(message.into())
+}
+
+/// Check a single OHLCV bar.
+fn check_bar(what: &str, high: f64, low: f64, volume: Option<f64>) -> Result<()> {
+    if high < low {
+        return Err(violation(format!("{what}: high {high} is below low {low}")));
+    }
+    if let Some(volume) = volume {
+        if volume < 0.0 {
+            return Err(violation(format!("{what}: negative volume {volume}")));
+        }
+    }
+    Ok(())
+}
+
+/// Check column-oriented candle arrays as returned by the candle endpoints.
+#[cfg_attr(
+    not(any(feature = "stock", feature = "forex", feature = "crypto")),
+    allow(dead_code)
+)]
+fn check_candles(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    timestamp: &[i64],
+) -> Result<()> {
+    let len = timestamp.len();
+    for (name, column) in [
+        ("open", open.len()),
+        ("high", high.len()),
+        ("low", low.len()),
+        ("close", close.len()),
+        ("volume", volume.len()),
+    ] {
+        if column != len {
+            return Err(violation(format!(
+                "candles: {name} has {column} entries but timestamp has {len}"
+            )));
+        }
+    }
+
+    for i in 0..len {
+        check_bar(&format!("candle {i}"), high[i], low[i], Some(volume[i]))?;
+        if i > 0 && timestamp[i] <= timestamp[i - 1] {
+            return Err(violation(format!(
+                "candle {i}: timestamp {} does not follow {}",
+                timestamp[i],
+                timestamp[i - 1]
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "stock")]
+impl Validate for crate::models::stock::Quote {
+    fn validate(&self) -> Result<()> {
+        check_bar("quote", self.high, self.low, None)
+    }
+}
+
+#[cfg(feature = "stock")]
+impl Validate for crate::models::stock::StockCandles {
+    fn validate(&self) -> Result<()> {
+        check_candles(
+            &self.open,
+            &self.high,
+            &self.low,
+            &self.close,
+            &self.volume_f64(),
+            &self.timestamp,
+        )
+    }
+}
+
+#[cfg(feature = "forex")]
+impl Validate for crate::models::forex::ForexCandles {
+    fn validate(&self) -> Result<()> {
+        check_candles(
+            &self.open,
+            &self.high,
+            &self.low,
+            &self.close,
+            &self.volume,
+            &self.timestamp,
+        )
+    }
+}
+
+#[cfg(feature = "crypto")]
+impl Validate for crate::models::crypto::CryptoCandles {
+    fn validate(&self) -> Result<()> {
+        check_candles(
+            &self.open,
+            &self.high,
+            &self.low,
+            &self.close,
+            &self.volume,
+            &self.timestamp,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "stock"))]
+mod tests {
+    use super::*;
+    use crate::models::stock::{Quote, StockCandles};
+    use crate::models::Count;
+
+    fn candles() -> StockCandles {
+        StockCandles {
+            close: vec![10.0, 11.0],
+            high: vec![10.5, 11.5],
+            low: vec![9.5, 10.5],
+            open: vec![10.0, 10.8],
+            status: "ok".to_string(),
+            timestamp: vec![1_700_000_000, 1_700_086_400],
+            volume: vec![Count::new(1000), Count::new(1200)],
+        }
+    }
+
+    #[test]
+    fn test_valid_candles() {
+        assert!(candles().validate().is_ok());
+    }
+
+    #[test]
+    fn test_candle_violations() {
+        let mut bad = candles();
+        bad.low[1] = 12.0;
+        assert!(matches!(
+            bad.validate(),
+            Err(Error::DataInvariantViolation(_))
+        ));
+
+        let mut bad = candles();
+        bad.volume[0] = Count::new(-1);
+        assert!(bad.validate().is_err());
+
+        let mut bad = candles();
+        bad.timestamp[1] = bad.timestamp[0];
+        assert!(bad.validate().is_err());
+
+        let mut bad = candles();
+        bad.close.pop();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_modes() {
+        let quote = Quote {
+            current_price: 10.0,
+            change: 0.0,
+            percent_change: 0.0,
+            high: 9.0,
+            low: 11.0,
+            open: 10.0,
+            previous_close: 10.0,
+            timestamp: 0,
+        };
+
+        assert!(ValidationMode::Off.apply(quote.clone()).is_ok());
+        assert!(ValidationMode::Warn.apply(quote.clone()).is_ok());
+        assert!(matches!(
+            ValidationMode::Error.apply(quote),
+            Err(Error::DataInvariantViolation(_))
+        ));
+    }
+}
@@ -0,0 +1,278 @@
+//! Early warning for plan and schema changes.
+
+use futures::stream::{self, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+use super::poll;
+use crate::{client::FinnhubClient, error::Error, lenient};
+
+/// Outcome of a canary's latest check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CanaryStatus {
+    /// The endpoint answered and the response decoded.
+    Ok,
+    /// The API refused access: 403 for an endpoint outside the plan, or 401
+    /// when the key itself stopped working.
+    PremiumLost,
+    /// The endpoint answered, but the response no longer decodes into its
+    /// model.
+    SchemaDrift,
+}
+
+/// An endpoint to check and the model its response must decode into.
+#[derive(Debug, Clone)]
+pub struct Canary {
+    endpoint: String,
+    decode: fn(&[u8]) -> serde_json::Result<()>,
+}
+
+impl Canary {
+    /// Check `endpoint`, a path and query such as `/quote?symbol=AAPL`,
+    /// against the model `T`.
+    ///
+    /// ```rust
+    /// use finnhub::models::stock::StockCandles;
+    /// use finnhub::watch::Canary;
+    ///
+    /// let candles = Canary::new::<StockCandles>(
+    ///     "/stock/candle?symbol=AAPL&resolution=D&from=1700000000&to=1700600000",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn new<T: DeserializeOwned>(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            decode: |body| lenient::from_slice::<T>(body).map(drop),
+        }
+    }
+
+    /// Path and query of the checked endpoint.
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The canary's status, or `None` if the check failed for an unrelated
+    /// reason such as a timeout or a server error.
+    async fn check(&self, client: &FinnhubClient) -> Option<(CanaryStatus, Option<String>)> {
+        match client.get_fresh_body(&self.endpoint).await {
+            Ok(body) => match (self.decode)(&body) {
+                Ok(()) => Some((CanaryStatus::Ok, None)),
+                Err(err) => Some((CanaryStatus::SchemaDrift, Some(err.to_string()))),
+            },
+            Err(err) => match err.root() {
+                Error::Unauthorized | Error::ApiError { status: 403, .. } => {
+                    Some((CanaryStatus::PremiumLost, Some(err.to_string())))
+                }
+                _ => {
+                    tracing::debug!(endpoint = %self.endpoint, error = %err, "canary check failed");
+                    None
+                }
+            },
+        }
+    }
+}
+
+/// A canary whose status changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanaryEvent {
+    /// Path and query of the canary's endpoint.
+    pub endpoint: String,
+    /// Status before this check.
+    pub previous: CanaryStatus,
+    /// Status now.
+    pub current: CanaryStatus,
+    /// Why the check failed; `None` when the canary recovered.
+    pub detail: Option<String>,
+}
+
+/// Periodically calls a few representative endpoints and reports when one
+/// loses entitlement or its response stops decoding, before production
+/// ingestion starts failing.
+///
+/// Checks bypass the response cache but go through the rate limiter. Every
+/// canary starts out [`CanaryStatus::Ok`], and only changes are reported.
+/// Failures that say nothing about plan or schema, such as timeouts and
+/// server errors, leave the status as it was.
+///
+/// The default canaries are free endpoints (market news and, with the
+/// `stock` feature, a quote and a company profile), so they only catch
+/// schema drift and revoked keys. Add the premium endpoints your
+/// application relies on with [`with_canaries`](Self::with_canaries).
+///
+/// ```no_run
+/// use finnhub::models::stock::BasicFinancials;
+/// use finnhub::watch::{Canary, CanaryWatcher};
+/// use finnhub::FinnhubClient;
+///
+/// # async fn run() {
+/// let client = FinnhubClient::new("your-api-key");
+/// let watchdog = CanaryWatcher::new(client)
+///     .with_canaries([Canary::new::<BasicFinancials>(
+///         "/stock/metric?symbol=AAPL&metric=all",
+///     )])
+///     .spawn(|event| {
+///         eprintln!("{}: {:?} -> {:?}", event.endpoint, event.previous, event.current);
+///     });
+/// // Stop checking.
+/// watchdog.abort();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CanaryWatcher {
+    client: FinnhubClient,
+    canaries: Vec<Canary>,
+    interval: Duration,
+}
+
+impl CanaryWatcher {
+    /// Check the default canaries every 15 minutes.
+    #[must_use]
+    pub fn new(client: FinnhubClient) -> Self {
+        #[allow(unused_mut)]
+        let mut canaries = vec![Canary::new::<Vec<crate::models::news::MarketNews>>(
+            "/news?category=general",
+        )];
+        #[cfg(feature = "stock")]
+        canaries.extend([
+            Canary::new::<crate::models::stock::Quote>("/quote?symbol=AAPL"),
+            Canary::new::<crate::models::stock::CompanyProfile>("/stock/profile2?symbol=AAPL"),
+        ]);
+        Self {
+            client,
+            canaries,
+            interval: Duration::from_secs(15 * 60),
+        }
+    }
+
+    /// Check `canaries` instead of the defaults.
+    #[must_use]
+    pub fn with_canaries(mut self, canaries: impl IntoIterator<Item = Canary>) -> Self {
+        self.canaries = canaries.into_iter().collect();
+        self
+    }
+
+    /// Time between checks.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Start checking and stream status changes.
+    pub fn into_stream(self) -> impl Stream<Item = CanaryEvent> {
+        let Self {
+            client,
+            canaries,
+            interval,
+        } = self;
+        let mut statuses = vec![CanaryStatus::Ok; canaries.len()];
+        let endpoints: Vec<String> = canaries.iter().map(|c| c.endpoint.clone()).collect();
+
+        poll(interval, move || {
+            let client = client.clone();
+            let canaries = canaries.clone();
+            async move {
+                let checks = canaries.iter().map(|canary| canary.check(&client));
+                futures::future::join_all(checks).await
+            }
+        })
+        .flat_map(move |results| {
+            let mut events = Vec::new();
+            for (index, result) in results.into_iter().enumerate() {
+                let Some((current, detail)) = result else {
+                    continue;
+                };
+                let previous = std::mem::replace(&mut statuses[index], current);
+                if previous != current {
+                    events.push(CanaryEvent {
+                        endpoint: endpoints[index].clone(),
+                        previous,
+                        current,
+                        detail,
+                    });
+                }
+            }
+            stream::iter(events)
+        })
+    }
+
+    /// Check in the background and call `on_change` for each status change.
+    ///
+    /// The task is spawned on the current Tokio runtime and runs until the
+    /// returned handle is aborted, so this is not available on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn<F>(self, mut on_change: F) -> tokio::task::JoinHandle<()>
+    where
+        F: FnMut(CanaryEvent) + Send + 'static,
+    {
+        tokio::spawn(async move {
+            let events = self.into_stream();
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                on_change(event);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock_client;
+    use crate::models::news::MarketNews;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_reports_status_transitions() {
+        let server = MockServer::start().await;
+        for response in [
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([])),
+            ResponseTemplate::new(403).set_body_string("You don't have access to this resource."),
+            ResponseTemplate::new(500),
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "error": "moved" })),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/news"))
+                .respond_with(response)
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/api/v1/news"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let watchdog = CanaryWatcher::new(client)
+            .with_canaries([Canary::new::<Vec<MarketNews>>("/news?category=general")])
+            .with_interval(Duration::from_millis(10))
+            .spawn(move |event| {
+                let _ = sender.send(event);
+            });
+
+        let mut transitions = Vec::new();
+        for _ in 0..3 {
+            let event = receiver.recv().await.unwrap();
+            assert_eq!(event.endpoint, "/news?category=general");
+            assert_eq!(event.detail.is_some(), event.current != CanaryStatus::Ok);
+            transitions.push((event.previous, event.current));
+        }
+        watchdog.abort();
+
+        // The 500 in between changes nothing.
+        assert_eq!(
+            transitions,
+            [
+                (CanaryStatus::Ok, CanaryStatus::PremiumLost),
+                (CanaryStatus::PremiumLost, CanaryStatus::SchemaDrift),
+                (CanaryStatus::SchemaDrift, CanaryStatus::Ok),
+            ]
+        );
+    }
+}
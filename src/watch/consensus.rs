@@ -0,0 +1,252 @@
+//! Analyst consensus change alerts.
+
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::poll;
+use crate::{client::FinnhubClient, error::Result, models::stock::RecommendationTrend};
+
+/// Thresholds and interval for [`ConsensusWatcher`].
+#[derive(Debug, Clone)]
+pub struct ConsensusWatchConfig {
+    /// Time between polls of each symbol.
+    pub interval: Duration,
+    /// Minimum relative change of the mean price target, e.g. `0.05` for 5%.
+    pub target_change: f64,
+    /// Minimum change of the consensus score (1 = strong buy, 5 = strong sell).
+    pub rating_change: f64,
+}
+
+impl Default for ConsensusWatchConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(15 * 60),
+            target_change: 0.05,
+            rating_change: 0.25,
+        }
+    }
+}
+
+/// A change in analyst consensus that crossed a threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsensusEvent {
+    /// The mean price target moved.
+    TargetChanged {
+        /// Symbol.
+        symbol: String,
+        /// Mean target when last reported.
+        previous: f64,
+        /// Current mean target.
+        current: f64,
+    },
+    /// The consensus score of the latest recommendation period moved.
+    ///
+    /// See [`RecommendationTrend::consensus_score`].
+    RatingChanged {
+        /// Symbol.
+        symbol: String,
+        /// Score when last reported.
+        previous: f64,
+        /// Current score.
+        current: f64,
+    },
+}
+
+/// Watches `price_target()` and `recommendations()` for a set of symbols.
+///
+/// Changes are measured against the value at the last emitted event (or the
+/// first poll), so slow drift is reported once it adds up to the threshold.
+#[derive(Debug, Clone)]
+pub struct ConsensusWatcher {
+    client: FinnhubClient,
+    symbols: Vec<String>,
+    config: ConsensusWatchConfig,
+}
+
+impl ConsensusWatcher {
+    /// Create a watcher for `symbols`.
+    pub fn new<I, S>(client: FinnhubClient, symbols: I, config: ConsensusWatchConfig) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            client,
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            config,
+        }
+    }
+
+    /// Start polling and stream consensus events.
+    ///
+    /// Request failures are yielded as `Err` items; polling continues.
+    pub fn into_stream(self) -> impl Stream<Item = Result<ConsensusEvent>> {
+        let Self {
+            client,
+            symbols,
+            config,
+        } = self;
+        let mut baselines: HashMap<String, Snapshot> = HashMap::new();
+
+        poll(config.interval, move || {
+            let client = client.clone();
+            let symbols = symbols.clone();
+            async move {
+                let fetches = symbols.into_iter().map(|symbol| {
+                    let client = client.clone();
+                    async move {
+                        let snapshot = Snapshot::fetch(&client, &symbol).await;
+                        (symbol, snapshot)
+                    }
+                });
+                futures::future::join_all(fetches).await
+            }
+        })
+        .flat_map(move |results| {
+            let mut events = Vec::new();
+            for (symbol, result) in results {
+                match result {
+                    Ok(current) => {
+                        let baseline = baselines.entry(symbol.clone()).or_default();
+                        events.extend(
+                            baseline
+                                .update(&symbol, current, &config)
+                                .into_iter()
+                                .map(Ok),
+                        );
+                    }
+                    Err(err) => events.push(Err(err)),
+                }
+            }
+            stream::iter(events)
+        })
+    }
+}
+
+/// Last reported consensus for a symbol.
+#[derive(Debug, Clone, Copy, Default)]
+struct Snapshot {
+    target: Option<f64>,
+    rating: Option<f64>,
+}
+
+impl Snapshot {
+    async fn fetch(client: &FinnhubClient, symbol: &str) -> Result<Self> {
+        let stock = client.stock();
+        let (target, trends) =
+            futures::try_join!(stock.price_target(symbol), stock.recommendations(symbol))?;
+
+        Ok(Self {
+            target: (target.target_mean > 0.0).then_some(target.target_mean),
+            rating: trends
+                .iter()
+                .max_by(|a, b| a.period.cmp(&b.period))
+                .and_then(RecommendationTrend::consensus_score),
+        })
+    }
+
+    /// Move the baseline to `current` where it crossed a threshold, returning
+    /// the resulting events.
+    fn update(
+        &mut self,
+        symbol: &str,
+        current: Self,
+        config: &ConsensusWatchConfig,
+    ) -> Vec<ConsensusEvent> {
+        let mut events = Vec::new();
+
+        match (self.target, current.target) {
+            (Some(previous), Some(target))
+                if ((target - previous) / previous).abs() >= config.target_change =>
+            {
+                events.push(ConsensusEvent::TargetChanged {
+                    symbol: symbol.to_string(),
+                    previous,
+                    current: target,
+                });
+                self.target = Some(target);
+            }
+            (None, Some(target)) => self.target = Some(target),
+            _ => {}
+        }
+
+        match (self.rating, current.rating) {
+            (Some(previous), Some(rating)) if (rating - previous).abs() >= config.rating_change => {
+                events.push(ConsensusEvent::RatingChanged {
+                    symbol: symbol.to_string(),
+                    previous,
+                    current: rating,
+                });
+                self.rating = Some(rating);
+            }
+            (None, Some(rating)) => self.rating = Some(rating),
+            _ => {}
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(target: f64, rating: f64) -> Snapshot {
+        Snapshot {
+            target: Some(target),
+            rating: Some(rating),
+        }
+    }
+
+    #[test]
+    fn test_first_poll_sets_baseline() {
+        let mut baseline = Snapshot::default();
+        let config = ConsensusWatchConfig::default();
+        assert!(baseline
+            .update("AAPL", snapshot(200.0, 2.0), &config)
+            .is_empty());
+        assert_eq!(baseline.target, Some(200.0));
+    }
+
+    #[test]
+    fn test_drift_accumulates_until_threshold() {
+        let mut baseline = snapshot(200.0, 2.0);
+        let config = ConsensusWatchConfig::default();
+
+        assert!(baseline
+            .update("AAPL", snapshot(206.0, 2.1), &config)
+            .is_empty());
+        let events = baseline.update("AAPL", snapshot(212.0, 2.3), &config);
+        assert_eq!(
+            events,
+            vec![
+                ConsensusEvent::TargetChanged {
+                    symbol: "AAPL".to_string(),
+                    previous: 200.0,
+                    current: 212.0,
+                },
+                ConsensusEvent::RatingChanged {
+                    symbol: "AAPL".to_string(),
+                    previous: 2.0,
+                    current: 2.3,
+                },
+            ]
+        );
+        assert_eq!(baseline.target, Some(212.0));
+    }
+
+    #[test]
+    fn test_consensus_score() {
+        let trend = RecommendationTrend {
+            buy: 1,
+            hold: 1,
+            period: "2024-01-01".to_string(),
+            sell: 0,
+            strong_buy: 2,
+            strong_sell: 0,
+            symbol: "AAPL".to_string(),
+        };
+        assert_eq!(trend.consensus_score(), Some(1.75));
+    }
+}
@@ -0,0 +1,262 @@
+//! ETF and index constituent weight drift monitoring.
+
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::poll;
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    persist::{self, StateStore},
+};
+
+/// Where constituent weights come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstituentSource {
+    /// Holdings of an ETF (`percent` of each holding).
+    #[cfg(feature = "etf")]
+    Etf(String),
+    /// Constituents of an index (`weight` of each constituent).
+    Index(String),
+}
+
+impl fmt::Display for ConstituentSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "etf")]
+            ConstituentSource::Etf(symbol) => write!(f, "etf:{symbol}"),
+            ConstituentSource::Index(symbol) => write!(f, "index:{symbol}"),
+        }
+    }
+}
+
+impl ConstituentSource {
+    /// Current weights in percent, keyed by constituent symbol.
+    async fn fetch(&self, client: &FinnhubClient) -> Result<HashMap<String, f64>> {
+        match self {
+            #[cfg(feature = "etf")]
+            ConstituentSource::Etf(symbol) => {
+                let holdings = client
+                    .etf()
+                    .holdings(Some(symbol), None, None, None)
+                    .await?;
+                Ok(holdings
+                    .holdings
+                    .into_iter()
+                    .filter_map(|h| Some((h.symbol?, h.percent?)))
+                    .collect())
+            }
+            ConstituentSource::Index(symbol) => {
+                let constituents = client.index().constituents(symbol).await?;
+                Ok(constituents
+                    .constituents_breakdown
+                    .into_iter()
+                    .filter_map(|c| Some((c.symbol, c.weight?)))
+                    .collect())
+            }
+        }
+    }
+}
+
+/// Interval and threshold for [`WeightDriftWatcher`].
+#[derive(Debug, Clone)]
+pub struct WeightDriftConfig {
+    /// Time between polls.
+    pub interval: Duration,
+    /// Minimum absolute weight change, in percentage points.
+    pub threshold: f64,
+}
+
+impl Default for WeightDriftConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(24 * 60 * 60),
+            threshold: 0.5,
+        }
+    }
+}
+
+/// A constituent whose weight moved past the threshold since it was last
+/// recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalanceEvent {
+    /// ETF or index.
+    pub source: ConstituentSource,
+    /// Constituent symbol.
+    pub symbol: String,
+    /// Stored weight in percent, `None` if the constituent was added.
+    pub previous: Option<f64>,
+    /// Current weight in percent, `None` if the constituent was removed.
+    pub current: Option<f64>,
+}
+
+/// Compares current constituent weights against weights kept in a
+/// [`StateStore`] and emits a [`RebalanceEvent`] for every constituent that
+/// drifted past the threshold, was added, or was removed.
+///
+/// The first poll for a source only records its weights. Afterwards each
+/// constituent's stored weight is updated when an event is emitted for it, so
+/// slow drift is reported once it adds up to the threshold.
+pub struct WeightDriftWatcher {
+    client: FinnhubClient,
+    sources: Vec<ConstituentSource>,
+    store: Arc<dyn StateStore>,
+    config: WeightDriftConfig,
+}
+
+impl fmt::Debug for WeightDriftWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightDriftWatcher")
+            .field("sources", &self.sources)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WeightDriftWatcher {
+    /// Create a watcher for `sources`, keeping baselines in `store`.
+    pub fn new(
+        client: FinnhubClient,
+        sources: impl IntoIterator<Item = ConstituentSource>,
+        store: Arc<dyn StateStore>,
+        config: WeightDriftConfig,
+    ) -> Self {
+        Self {
+            client,
+            sources: sources.into_iter().collect(),
+            store,
+            config,
+        }
+    }
+
+    /// Start polling and stream rebalance events.
+    ///
+    /// Request and storage failures are yielded as `Err` items; polling
+    /// continues.
+    pub fn into_stream(self) -> impl Stream<Item = Result<RebalanceEvent>> {
+        let Self {
+            client,
+            sources,
+            store,
+            config,
+        } = self;
+
+        poll(config.interval, move || {
+            let client = client.clone();
+            let sources = sources.clone();
+            let store = Arc::clone(&store);
+            let threshold = config.threshold;
+            async move {
+                let mut events = Vec::new();
+                for source in sources {
+                    match check(&client, store.as_ref(), &source, threshold).await {
+                        Ok(found) => events.extend(found.into_iter().map(Ok)),
+                        Err(err) => events.push(Err(err)),
+                    }
+                }
+                events
+            }
+        })
+        .flat_map(stream::iter)
+    }
+}
+
+async fn check(
+    client: &FinnhubClient,
+    store: &dyn StateStore,
+    source: &ConstituentSource,
+    threshold: f64,
+) -> Result<Vec<RebalanceEvent>> {
+    let current = source.fetch(client).await?;
+    let key = format!("weights/{source}");
+
+    let Some(mut stored) = persist::load::<BTreeMap<String, f64>>(store, &key).await? else {
+        let baseline: BTreeMap<_, _> = current.into_iter().collect();
+        persist::save(store, &key, &baseline).await?;
+        return Ok(Vec::new());
+    };
+
+    let events = drift(source, &mut stored, &current, threshold);
+    if !events.is_empty() {
+        persist::save(store, &key, &stored).await?;
+    }
+    Ok(events)
+}
+
+/// Compare `current` against `stored`, updating `stored` for every constituent
+/// that produced an event.
+fn drift(
+    source: &ConstituentSource,
+    stored: &mut BTreeMap<String, f64>,
+    current: &HashMap<String, f64>,
+    threshold: f64,
+) -> Vec<RebalanceEvent> {
+    let mut events = Vec::new();
+    let event = |symbol: &str, previous, current| RebalanceEvent {
+        source: source.clone(),
+        symbol: symbol.to_string(),
+        previous,
+        current,
+    };
+
+    for (symbol, &weight) in current {
+        match stored.get(symbol) {
+            Some(&previous) if (weight - previous).abs() < threshold => {}
+            previous => {
+                events.push(event(symbol, previous.copied(), Some(weight)));
+                stored.insert(symbol.clone(), weight);
+            }
+        }
+    }
+
+    stored.retain(|symbol, &mut previous| {
+        let kept = current.contains_key(symbol);
+        if !kept {
+            events.push(event(symbol, Some(previous), None));
+        }
+        kept
+    });
+
+    events.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drift() {
+        let source = ConstituentSource::Index("^GSPC".to_string());
+        let mut stored: BTreeMap<String, f64> = [("AAPL", 7.0), ("MSFT", 6.5), ("XOM", 1.0)]
+            .into_iter()
+            .map(|(s, w)| (s.to_string(), w))
+            .collect();
+        let current: HashMap<String, f64> = [("AAPL", 7.6), ("MSFT", 6.7), ("NVDA", 5.0)]
+            .into_iter()
+            .map(|(s, w)| (s.to_string(), w))
+            .collect();
+
+        let events = drift(&source, &mut stored, &current, 0.5);
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.symbol.as_str(), e.previous, e.current))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("AAPL", Some(7.0), Some(7.6)),
+                ("NVDA", None, Some(5.0)),
+                ("XOM", Some(1.0), None),
+            ]
+        );
+
+        // MSFT keeps its old baseline so further drift accumulates.
+        assert_eq!(stored.get("MSFT"), Some(&6.5));
+        assert_eq!(stored.get("AAPL"), Some(&7.6));
+        assert!(!stored.contains_key("XOM"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Earnings date tracking with a stability-based confidence.
+//!
+//! The earnings calendar doesn't say whether a date was confirmed by the
+//! company or projected by Finnhub, and projected dates move as the release
+//! approaches. [`EarningsDates`] remembers each upcoming release across polls
+//! and rates its date by how long it has held.
+
+use chrono::{Duration as Days, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::poll;
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    models::calendar::{EarningsFilter, EarningsRelease, ReportingTime},
+};
+
+/// How far an earnings date can be trusted.
+///
+/// Variants are ordered from least to most certain, so
+/// `confidence >= DateConfidence::Stable` selects dates worth scheduling
+/// around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DateConfidence {
+    /// First seen or moved recently; may still be a projection.
+    Unconfirmed,
+    /// Unchanged across the configured number of polls.
+    Stable,
+    /// Actual results are in, so the date is final.
+    Reported,
+}
+
+/// What is known about one release's date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedDate {
+    /// Latest reported date, `YYYY-MM-DD`.
+    pub date: String,
+    /// Latest reported session.
+    pub hour: Option<ReportingTime>,
+    /// Consecutive polls that reported the current date and session.
+    pub polls: u32,
+    /// Times the release moved since it was first seen.
+    pub moves: u32,
+    /// Whether actual EPS or revenue has been reported.
+    pub reported: bool,
+}
+
+impl TrackedDate {
+    /// Confidence in the date, treating it as stable once it held for
+    /// `stable_polls` polls.
+    #[must_use]
+    pub fn confidence(&self, stable_polls: u32) -> DateConfidence {
+        if self.reported {
+            DateConfidence::Reported
+        } else if self.polls >= stable_polls {
+            DateConfidence::Stable
+        } else {
+            DateConfidence::Unconfirmed
+        }
+    }
+}
+
+/// A change in the earnings calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EarningsEvent {
+    /// A release appeared in the calendar.
+    Scheduled(EarningsRelease),
+    /// A release moved to another date or session.
+    Rescheduled {
+        /// Date before the move.
+        previous_date: String,
+        /// Session before the move.
+        previous_hour: Option<ReportingTime>,
+        /// The release as now reported.
+        release: EarningsRelease,
+    },
+    /// A release's date held long enough to be
+    /// [`Stable`](DateConfidence::Stable).
+    Stable(EarningsRelease),
+}
+
+impl EarningsEvent {
+    /// The release the event is about.
+    #[must_use]
+    pub fn release(&self) -> &EarningsRelease {
+        match self {
+            EarningsEvent::Scheduled(release)
+            | EarningsEvent::Rescheduled { release, .. }
+            | EarningsEvent::Stable(release) => release,
+        }
+    }
+}
+
+/// Release dates remembered across polls, keyed by symbol and fiscal
+/// quarter.
+///
+/// Feed each poll of the earnings calendar to [`observe`](Self::observe); a
+/// date that stays put for `stable_polls` polls is rated
+/// [`Stable`](DateConfidence::Stable), and a move starts the count over.
+#[derive(Debug, Clone)]
+pub struct EarningsDates {
+    stable_polls: u32,
+    releases: HashMap<(String, i64, i64), TrackedDate>,
+}
+
+impl EarningsDates {
+    /// Track dates, treating them as stable after `stable_polls` polls.
+    #[must_use]
+    pub fn new(stable_polls: u32) -> Self {
+        Self {
+            stable_polls,
+            releases: HashMap::new(),
+        }
+    }
+
+    /// Record a poll of the calendar and return the resulting events.
+    ///
+    /// Releases without a symbol, date, year or quarter are ignored. A
+    /// session appearing where there was none is not a move.
+    pub fn observe(&mut self, releases: &[EarningsRelease]) -> Vec<EarningsEvent> {
+        let mut events = Vec::new();
+        for release in releases {
+            let (Some(symbol), Some(date), Some(year), Some(quarter)) = (
+                &release.symbol,
+                &release.date,
+                release.year,
+                release.quarter,
+            ) else {
+                continue;
+            };
+            let reported = release.eps_actual.is_some() || release.revenue_actual.is_some();
+
+            let Some(tracked) = self.releases.get_mut(&(symbol.clone(), year, quarter)) else {
+                self.releases.insert(
+                    (symbol.clone(), year, quarter),
+                    TrackedDate {
+                        date: date.clone(),
+                        hour: release.hour.clone(),
+                        polls: 1,
+                        moves: 0,
+                        reported,
+                    },
+                );
+                events.push(EarningsEvent::Scheduled(release.clone()));
+                if self.stable_polls <= 1 {
+                    events.push(EarningsEvent::Stable(release.clone()));
+                }
+                continue;
+            };
+
+            tracked.reported |= reported;
+            let hour_moved = matches!(
+                (&tracked.hour, &release.hour),
+                (Some(previous), Some(current)) if previous != current
+            );
+            if tracked.date != *date || hour_moved {
+                events.push(EarningsEvent::Rescheduled {
+                    previous_date: std::mem::replace(&mut tracked.date, date.clone()),
+                    previous_hour: std::mem::replace(&mut tracked.hour, release.hour.clone()),
+                    release: release.clone(),
+                });
+                tracked.polls = 1;
+                tracked.moves += 1;
+                continue;
+            }
+
+            if release.hour.is_some() {
+                tracked.hour.clone_from(&release.hour);
+            }
+            tracked.polls += 1;
+            if tracked.polls == self.stable_polls {
+                events.push(EarningsEvent::Stable(release.clone()));
+            }
+        }
+        events
+    }
+
+    /// What is known about a release's date.
+    #[must_use]
+    pub fn get(&self, symbol: &str, year: i64, quarter: i64) -> Option<&TrackedDate> {
+        self.releases.get(&(symbol.to_string(), year, quarter))
+    }
+
+    /// Confidence in a release's date, `None` if it was never seen.
+    #[must_use]
+    pub fn confidence(&self, symbol: &str, year: i64, quarter: i64) -> Option<DateConfidence> {
+        self.get(symbol, year, quarter)
+            .map(|tracked| tracked.confidence(self.stable_polls))
+    }
+
+    /// Forget releases dated before `date` (`YYYY-MM-DD`).
+    pub fn forget_before(&mut self, date: &str) {
+        self.releases
+            .retain(|_, tracked| tracked.date.as_str() >= date);
+    }
+}
+
+/// Window, interval and stability threshold for [`EarningsWatcher`].
+#[derive(Debug, Clone)]
+pub struct EarningsWatchConfig {
+    /// Time between polls.
+    pub interval: Duration,
+    /// Number of days ahead of today to watch.
+    pub days_ahead: u32,
+    /// Consecutive polls a date must hold to be
+    /// [`Stable`](DateConfidence::Stable).
+    pub stable_polls: u32,
+    /// Include international (non-US) companies.
+    pub international: bool,
+}
+
+impl Default for EarningsWatchConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(6 * 60 * 60),
+            days_ahead: 30,
+            stable_polls: 4,
+            international: false,
+        }
+    }
+}
+
+/// Watches the earnings calendar and reports new, moved and settled release
+/// dates.
+///
+/// Watch a set of symbols, or the whole calendar by passing none. Dates are
+/// tracked with [`EarningsDates`], in memory, so confidence starts over when
+/// the watcher is restarted.
+#[derive(Debug, Clone)]
+pub struct EarningsWatcher {
+    client: FinnhubClient,
+    symbols: Vec<String>,
+    config: EarningsWatchConfig,
+}
+
+impl EarningsWatcher {
+    /// Create a watcher for `symbols`, or for every company if empty.
+    pub fn new<I, S>(client: FinnhubClient, symbols: I, config: EarningsWatchConfig) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            client,
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            config,
+        }
+    }
+
+    /// Start polling and stream earnings events.
+    ///
+    /// Request failures are yielded as `Err` items; polling continues.
+    pub fn into_stream(self) -> impl Stream<Item = Result<EarningsEvent>> {
+        let Self {
+            client,
+            symbols,
+            config,
+        } = self;
+        let mut dates = EarningsDates::new(config.stable_polls);
+        let symbols: Vec<Option<String>> = if symbols.is_empty() {
+            vec![None]
+        } else {
+            symbols.into_iter().map(Some).collect()
+        };
+
+        poll(config.interval, move || {
+            let client = client.clone();
+            let symbols = symbols.clone();
+            let today = Utc::now().date_naive();
+            let from = today.format("%Y-%m-%d").to_string();
+            let to = (today + Days::days(i64::from(config.days_ahead)))
+                .format("%Y-%m-%d")
+                .to_string();
+            let international = config.international;
+            async move {
+                let fetches = symbols.into_iter().map(|symbol| {
+                    let client = client.clone();
+                    let filter = EarningsFilter {
+                        from: Some(from.clone()),
+                        to: Some(to.clone()),
+                        symbol,
+                        international,
+                    };
+                    async move { client.calendar().earnings_filtered(&filter).await }
+                });
+                let results = futures::future::join_all(fetches).await;
+                (from, results)
+            }
+        })
+        .flat_map(move |(today, results)| {
+            dates.forget_before(&today);
+            let mut events = Vec::new();
+            for result in results {
+                match result {
+                    Ok(calendar) => events.extend(
+                        dates
+                            .observe(&calendar.earnings_calendar)
+                            .into_iter()
+                            .map(Ok),
+                    ),
+                    Err(err) => events.push(Err(err)),
+                }
+            }
+            stream::iter(events)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(date: &str, hour: Option<&str>) -> EarningsRelease {
+        EarningsRelease {
+            symbol: Some("AAPL".to_string()),
+            date: Some(date.to_string()),
+            hour: hour.map(|h| ReportingTime::from(h.to_string())),
+            year: Some(2024),
+            quarter: Some(3),
+            eps_estimate: Some(1.35),
+            eps_actual: None,
+            revenue_estimate: None,
+            revenue_actual: None,
+        }
+    }
+
+    fn kinds(events: &[EarningsEvent]) -> Vec<&'static str> {
+        events
+            .iter()
+            .map(|e| match e {
+                EarningsEvent::Scheduled(_) => "scheduled",
+                EarningsEvent::Rescheduled { .. } => "rescheduled",
+                EarningsEvent::Stable(_) => "stable",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_date_settles_after_stable_polls() {
+        let mut dates = EarningsDates::new(3);
+        let poll = [release("2024-08-01", None)];
+
+        assert_eq!(kinds(&dates.observe(&poll)), ["scheduled"]);
+        assert_eq!(
+            dates.confidence("AAPL", 2024, 3),
+            Some(DateConfidence::Unconfirmed)
+        );
+        // A session filled in later doesn't count as a move.
+        assert!(dates
+            .observe(&[release("2024-08-01", Some("amc"))])
+            .is_empty());
+        assert_eq!(kinds(&dates.observe(&poll)), ["stable"]);
+        assert!(dates.observe(&poll).is_empty());
+        assert_eq!(
+            dates.confidence("AAPL", 2024, 3),
+            Some(DateConfidence::Stable)
+        );
+        assert_eq!(dates.get("AAPL", 2024, 3).unwrap().polls, 4);
+    }
+
+    #[test]
+    fn test_move_resets_confidence() {
+        let mut dates = EarningsDates::new(2);
+        dates.observe(&[release("2024-08-01", Some("amc"))]);
+        dates.observe(&[release("2024-08-01", Some("amc"))]);
+
+        let events = dates.observe(&[release("2024-08-01", Some("bmo"))]);
+        assert_eq!(
+            events,
+            [EarningsEvent::Rescheduled {
+                previous_date: "2024-08-01".to_string(),
+                previous_hour: Some(ReportingTime::AfterClose),
+                release: release("2024-08-01", Some("bmo")),
+            }]
+        );
+        let events = dates.observe(&[release("2024-08-02", Some("bmo"))]);
+        assert_eq!(kinds(&events), ["rescheduled"]);
+
+        let tracked = dates.get("AAPL", 2024, 3).unwrap();
+        assert_eq!((tracked.polls, tracked.moves), (1, 2));
+        assert_eq!(tracked.confidence(2), DateConfidence::Unconfirmed);
+
+        let mut reported = release("2024-08-02", Some("bmo"));
+        reported.eps_actual = Some(1.40);
+        dates.observe(&[reported]);
+        assert_eq!(
+            dates.confidence("AAPL", 2024, 3),
+            Some(DateConfidence::Reported)
+        );
+
+        dates.forget_before("2024-08-03");
+        assert_eq!(dates.confidence("AAPL", 2024, 3), None);
+    }
+}
@@ -0,0 +1,31 @@
+//! Polling-based watchers that turn periodic REST calls into event streams.
+//!
+//! [`poll`] is the building block: it runs a fetch on a fixed interval and
+//! yields each result. Watchers such as `ConsensusWatcher` and
+//! [`WeightDriftWatcher`] layer change detection on top and emit only the
+//! events that cross a threshold; [`EarningsWatcher`] rates how settled each
+//! upcoming earnings date is. Watchers that must remember state across
+//! restarts keep it in a [`StateStore`](crate::persist::StateStore).
+//! `TickerTape` merges polled quotes and WebSocket trades into one
+//! throttled stream. [`CanaryWatcher`] checks a few endpoints for lost
+//! entitlements and schema drift.
+
+mod canary;
+#[cfg(feature = "stock")]
+mod consensus;
+mod drift;
+mod earnings;
+mod poller;
+#[cfg(all(feature = "stock", not(target_arch = "wasm32")))]
+mod tape;
+
+pub use canary::{Canary, CanaryEvent, CanaryStatus, CanaryWatcher};
+#[cfg(feature = "stock")]
+pub use consensus::{ConsensusEvent, ConsensusWatchConfig, ConsensusWatcher};
+pub use drift::{ConstituentSource, RebalanceEvent, WeightDriftConfig, WeightDriftWatcher};
+pub use earnings::{
+    DateConfidence, EarningsDates, EarningsEvent, EarningsWatchConfig, EarningsWatcher, TrackedDate,
+};
+pub use poller::poll;
+#[cfg(all(feature = "stock", not(target_arch = "wasm32")))]
+pub use tape::{TapeUpdate, TickerTape};
@@ -0,0 +1,64 @@
+//! Generic interval poller.
+
+use futures::stream::{self, Stream};
+use std::future::Future;
+use std::time::Duration;
+
+use crate::rt::Interval;
+
+/// Call `fetch` every `interval` and yield each result.
+///
+/// The first call happens immediately. If a call takes longer than the
+/// interval, the next one starts right after it instead of bursting to catch
+/// up. The stream never ends; drop it to stop polling.
+///
+/// ```no_run
+/// use finnhub::{watch::poll, FinnhubClient};
+/// use futures::StreamExt;
+/// use std::time::Duration;
+///
+/// # async fn run() {
+/// let client = FinnhubClient::new("your-api-key");
+/// let quotes = poll(Duration::from_secs(60), || {
+///     let client = client.clone();
+///     async move { client.stock().quote("AAPL").await }
+/// });
+/// futures::pin_mut!(quotes);
+/// while let Some(quote) = quotes.next().await {
+///     println!("{:?}", quote.map(|q| q.current_price));
+/// }
+/// # }
+/// ```
+pub fn poll<T, F, Fut>(interval: Duration, fetch: F) -> impl Stream<Item = T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let ticker = Interval::new(interval);
+    stream::unfold((ticker, fetch), |(mut ticker, mut fetch)| async move {
+        ticker.tick().await;
+        let value = fetch().await;
+        Some((value, (ticker, fetch)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_poll_yields_each_fetch() {
+        let mut calls = 0;
+        let values: Vec<i32> = poll(Duration::from_millis(5), || {
+            calls += 1;
+            let n = calls;
+            async move { n }
+        })
+        .take(3)
+        .collect()
+        .await;
+
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+}
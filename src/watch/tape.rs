@@ -0,0 +1,414 @@
+//! Multiplexed quote and trade stream for ticker displays.
+
+use futures::stream::{self, Stream};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
+
+use crate::{client::FinnhubClient, error::Result, models::stock::Quote};
+
+#[cfg(feature = "websocket")]
+use crate::websocket::{TradeData, WebSocketClient, WebSocketMessage, WebSocketStream};
+
+/// Time to wait before reconnecting after the WebSocket failed.
+#[cfg(feature = "websocket")]
+const WEBSOCKET_RETRY: Duration = Duration::from_secs(60);
+
+/// One update on a [`TickerTape`].
+///
+/// Non-exhaustive because the `Trade` variant requires the `websocket`
+/// feature.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum TapeUpdate {
+    /// A REST quote, from polling or the snapshot taken on (re)connecting.
+    Quote(Quote),
+    /// A trade from the WebSocket feed.
+    #[cfg(feature = "websocket")]
+    Trade(TradeData),
+}
+
+impl TapeUpdate {
+    /// The latest price.
+    #[must_use]
+    pub fn price(&self) -> f64 {
+        match self {
+            TapeUpdate::Quote(quote) => quote.current_price,
+            #[cfg(feature = "websocket")]
+            TapeUpdate::Trade(trade) => trade.price,
+        }
+    }
+}
+
+/// Merged price updates for a set of symbols, the building block of
+/// terminal-style ticker displays.
+///
+/// With the `websocket` feature, the tape takes a quote snapshot of every
+/// symbol and then streams trades from the WebSocket feed. If the WebSocket
+/// can't connect or drops, it polls quotes instead and retries the WebSocket
+/// every minute. Without the feature it always polls.
+///
+/// Each symbol emits at most one update per throttle window (one second by
+/// default). Updates arriving faster replace each other, and the latest one
+/// is emitted when the window ends, so the display never lags behind.
+/// Polled quotes that haven't changed are skipped.
+///
+/// The tape runs on Tokio's timers, even with the `runtime-agnostic` feature.
+///
+/// ```no_run
+/// use finnhub::{watch::TapeUpdate, FinnhubClient};
+/// use futures::StreamExt;
+///
+/// # async fn run() {
+/// let client = FinnhubClient::new("your-api-key");
+/// let tape = client.ticker_tape(["AAPL", "MSFT", "NVDA"]).into_stream();
+/// futures::pin_mut!(tape);
+/// while let Some(update) = tape.next().await {
+///     match update {
+///         Ok((symbol, update)) => println!("{symbol} {:.2}", update.price()),
+///         Err(err) => eprintln!("tape error: {err}"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TickerTape {
+    client: FinnhubClient,
+    symbols: Vec<String>,
+    poll_interval: Duration,
+    throttle: Duration,
+    #[cfg(feature = "websocket")]
+    websocket: bool,
+}
+
+impl TickerTape {
+    /// Create a tape for `symbols`, polling every 15 seconds when polling and
+    /// throttling each symbol to one update per second.
+    pub fn new<I, S>(client: FinnhubClient, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            client,
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            poll_interval: Duration::from_secs(15),
+            throttle: Duration::from_secs(1),
+            #[cfg(feature = "websocket")]
+            websocket: true,
+        }
+    }
+
+    /// Time between quote polls when the WebSocket isn't in use.
+    #[must_use]
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Minimum time between two updates of the same symbol.
+    #[must_use]
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Poll quotes even though the WebSocket is available, e.g. for symbols
+    /// the feed doesn't cover.
+    #[cfg(feature = "websocket")]
+    #[must_use]
+    pub fn polling_only(mut self) -> Self {
+        self.websocket = false;
+        self
+    }
+
+    /// Start the tape.
+    ///
+    /// Quote request failures are yielded as `Err` items and the tape keeps
+    /// running. WebSocket failures are logged and trigger the polling
+    /// fallback. The stream never ends; drop it to stop.
+    pub fn into_stream(self) -> impl Stream<Item = Result<(String, TapeUpdate)>> {
+        stream::unfold(Tape::new(self), |mut tape| async move {
+            let item = tape.next().await;
+            Some((item, tape))
+        })
+    }
+}
+
+/// Running state behind [`TickerTape::into_stream`].
+struct Tape {
+    config: TickerTape,
+    throttle: Throttle,
+    ready: VecDeque<Result<(String, TapeUpdate)>>,
+    ticker: Interval,
+    /// Timestamp and price of the last quote offered per symbol.
+    last_quotes: HashMap<String, (i64, f64)>,
+    #[cfg(feature = "websocket")]
+    ws: Option<WebSocketStream>,
+    #[cfg(feature = "websocket")]
+    ws_retry_at: Instant,
+}
+
+impl Tape {
+    fn new(config: TickerTape) -> Self {
+        let mut ticker = time::interval(config.poll_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            throttle: Throttle::new(config.throttle),
+            config,
+            ready: VecDeque::new(),
+            ticker,
+            last_quotes: HashMap::new(),
+            #[cfg(feature = "websocket")]
+            ws: None,
+            #[cfg(feature = "websocket")]
+            ws_retry_at: Instant::now(),
+        }
+    }
+
+    async fn next(&mut self) -> Result<(String, TapeUpdate)> {
+        loop {
+            self.ready
+                .extend(self.throttle.due(Instant::now()).into_iter().map(Ok));
+            if let Some(item) = self.ready.pop_front() {
+                return item;
+            }
+            let deadline = self.throttle.next_due();
+
+            #[cfg(feature = "websocket")]
+            {
+                if self.config.websocket && self.ws.is_none() && Instant::now() >= self.ws_retry_at
+                {
+                    self.connect().await;
+                    continue;
+                }
+                if let Some(ws) = self.ws.as_mut() {
+                    tokio::select! {
+                        message = ws.next() => self.handle_message(message),
+                        () = sleep_until(deadline) => {}
+                    }
+                    continue;
+                }
+            }
+
+            tokio::select! {
+                _ = self.ticker.tick() => self.poll_quotes().await,
+                () = sleep_until(deadline) => {}
+            }
+        }
+    }
+
+    /// Fetch every symbol's quote and offer the changed ones.
+    async fn poll_quotes(&mut self) {
+        let fetches = self.config.symbols.iter().map(|symbol| {
+            let client = &self.config.client;
+            async move { (symbol.clone(), client.stock().quote(symbol).await) }
+        });
+        let results = futures::future::join_all(fetches).await;
+        let now = Instant::now();
+        for (symbol, result) in results {
+            match result {
+                Ok(quote) => {
+                    let key = (quote.timestamp, quote.current_price);
+                    if self.last_quotes.insert(symbol.clone(), key) == Some(key) {
+                        continue;
+                    }
+                    self.offer(symbol, TapeUpdate::Quote(quote), now);
+                }
+                Err(err) => self.ready.push_back(Err(err)),
+            }
+        }
+    }
+
+    fn offer(&mut self, symbol: String, update: TapeUpdate, now: Instant) {
+        if let Some(item) = self.throttle.offer(symbol, update, now) {
+            self.ready.push_back(Ok(item));
+        }
+    }
+
+    /// Connect and subscribe, then take a quote snapshot. On failure, fall
+    /// back to polling until the retry time.
+    #[cfg(feature = "websocket")]
+    async fn connect(&mut self) {
+        let ws = WebSocketClient::new(self.config.client.api_key());
+        let result = async {
+            let mut stream = ws.connect().await?;
+            for symbol in &self.config.symbols {
+                stream.subscribe(symbol).await?;
+            }
+            Ok::<_, crate::Error>(stream)
+        }
+        .await;
+
+        match result {
+            Ok(stream) => {
+                self.ws = Some(stream);
+                self.poll_quotes().await;
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "ticker tape WebSocket unavailable, polling quotes");
+                self.ws_retry_at = Instant::now() + WEBSOCKET_RETRY;
+            }
+        }
+    }
+
+    #[cfg(feature = "websocket")]
+    fn handle_message(&mut self, message: Result<Option<WebSocketMessage>>) {
+        match message {
+            Ok(Some(WebSocketMessage::Trade { data })) => {
+                let now = Instant::now();
+                for trade in data {
+                    self.offer(trade.symbol.clone(), TapeUpdate::Trade(trade), now);
+                }
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                tracing::warn!("ticker tape WebSocket closed, reconnecting");
+                self.ws = None;
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "ticker tape WebSocket failed, polling quotes");
+                self.ws = None;
+                self.ws_retry_at = Instant::now() + WEBSOCKET_RETRY;
+            }
+        }
+    }
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Per-symbol rate limit that keeps the latest suppressed update.
+#[derive(Debug)]
+struct Throttle {
+    interval: Duration,
+    last_emitted: HashMap<String, Instant>,
+    pending: HashMap<String, TapeUpdate>,
+}
+
+impl Throttle {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_emitted: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Return the update if `symbol` may emit now, otherwise hold it until
+    /// its window ends, replacing any update already held.
+    fn offer(
+        &mut self,
+        symbol: String,
+        update: TapeUpdate,
+        now: Instant,
+    ) -> Option<(String, TapeUpdate)> {
+        let open = self
+            .last_emitted
+            .get(&symbol)
+            .is_none_or(|&last| now >= last + self.interval);
+        if open && !self.pending.contains_key(&symbol) {
+            self.last_emitted.insert(symbol.clone(), now);
+            Some((symbol, update))
+        } else {
+            self.pending.insert(symbol, update);
+            None
+        }
+    }
+
+    /// Held updates whose window has ended.
+    fn due(&mut self, now: Instant) -> Vec<(String, TapeUpdate)> {
+        let due: Vec<String> = self
+            .pending
+            .keys()
+            .filter(|symbol| {
+                self.last_emitted
+                    .get(*symbol)
+                    .is_none_or(|&last| now >= last + self.interval)
+            })
+            .cloned()
+            .collect();
+        due.into_iter()
+            .filter_map(|symbol| {
+                let update = self.pending.remove(&symbol)?;
+                self.last_emitted.insert(symbol.clone(), now);
+                Some((symbol, update))
+            })
+            .collect()
+    }
+
+    /// When the next held update becomes due.
+    fn next_due(&self) -> Option<Instant> {
+        self.pending
+            .keys()
+            .filter_map(|symbol| self.last_emitted.get(symbol))
+            .map(|&last| last + self.interval)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(price: f64) -> TapeUpdate {
+        TapeUpdate::Quote(Quote {
+            current_price: price,
+            change: 0.0,
+            percent_change: 0.0,
+            high: price,
+            low: price,
+            open: price,
+            previous_close: price,
+            timestamp: 0,
+        })
+    }
+
+    #[test]
+    fn test_throttle_emits_latest_after_window() {
+        let mut throttle = Throttle::new(Duration::from_secs(1));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let price = |item: Option<(String, TapeUpdate)>| item.map(|(_, u)| u.price());
+
+        assert_eq!(
+            price(throttle.offer("AAPL".into(), quote(1.0), at(0))),
+            Some(1.0)
+        );
+        assert_eq!(
+            price(throttle.offer("MSFT".into(), quote(5.0), at(10))),
+            Some(5.0)
+        );
+        assert_eq!(
+            price(throttle.offer("AAPL".into(), quote(2.0), at(200))),
+            None
+        );
+        assert_eq!(
+            price(throttle.offer("AAPL".into(), quote(3.0), at(400))),
+            None
+        );
+        assert_eq!(throttle.next_due(), Some(at(1000)));
+        assert!(throttle.due(at(999)).is_empty());
+
+        let due = throttle.due(at(1000));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "AAPL");
+        assert!((due[0].1.price() - 3.0).abs() < f64::EPSILON);
+        assert_eq!(throttle.next_due(), None);
+
+        // The flushed update starts a new window.
+        assert_eq!(
+            price(throttle.offer("AAPL".into(), quote(4.0), at(1500))),
+            None
+        );
+        assert_eq!(
+            price(throttle.offer("AAPL".into(), quote(4.5), at(2500))),
+            None
+        );
+        assert_eq!(throttle.due(at(2500)).len(), 1);
+    }
+}
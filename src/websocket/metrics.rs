@@ -0,0 +1,258 @@
+//! Feed health metrics for WebSocket streams.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::{TradeData, WebSocketMessage};
+
+/// Seconds of history used for message rates.
+const RATE_WINDOW_SECS: usize = 10;
+
+/// Weight of the newest sample in the smoothed lag.
+const LAG_SMOOTHING: f64 = 0.1;
+
+/// Shared handle to a stream's metrics.
+///
+/// Cloning is cheap, so a monitoring task can keep a handle and take
+/// [`snapshot`](Self::snapshot)s while another task drives the stream.
+#[derive(Debug, Clone)]
+pub struct WebSocketMetrics {
+    state: Arc<Mutex<State>>,
+}
+
+/// Point-in-time copy of a stream's metrics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Time since the stream connected.
+    pub uptime: Duration,
+    /// Messages received, of any type.
+    pub messages: u64,
+    /// Messages received per second over the last ten seconds.
+    pub messages_per_sec: f64,
+    /// Metrics for each symbol that has received trades.
+    pub symbols: HashMap<String, SymbolMetrics>,
+}
+
+/// Trade metrics for one symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMetrics {
+    /// Trades received.
+    pub trades: u64,
+    /// Trades received per second over the last ten seconds.
+    pub trades_per_sec: f64,
+    /// Lag of the latest trade: receive time minus exchange timestamp, in
+    /// milliseconds. Negative values mean the local clock is behind.
+    pub last_lag_ms: i64,
+    /// Exponentially smoothed lag in milliseconds, weighted towards recent
+    /// trades.
+    pub mean_lag_ms: f64,
+    /// Largest lag seen, in milliseconds.
+    pub max_lag_ms: i64,
+    /// Time since the latest trade was received.
+    pub since_last_trade: Duration,
+}
+
+#[derive(Debug)]
+struct State {
+    connected: Instant,
+    messages: u64,
+    rate: RateWindow,
+    symbols: HashMap<String, SymbolState>,
+}
+
+#[derive(Debug)]
+struct SymbolState {
+    trades: u64,
+    rate: RateWindow,
+    last_lag_ms: i64,
+    mean_lag_ms: f64,
+    max_lag_ms: i64,
+    last_received: Instant,
+}
+
+/// Event counts in one-second buckets over the last [`RATE_WINDOW_SECS`].
+#[derive(Debug, Default)]
+struct RateWindow {
+    buckets: [u64; RATE_WINDOW_SECS],
+    second: u64,
+}
+
+impl RateWindow {
+    fn record(&mut self, second: u64, count: u64) {
+        self.advance(second);
+        self.buckets[bucket(second)] += count;
+    }
+
+    /// Clear buckets that fell out of the window by `second`.
+    fn advance(&mut self, second: u64) {
+        if second <= self.second {
+            return;
+        }
+        let stale = usize::try_from(second - self.second).unwrap_or(RATE_WINDOW_SECS);
+        for offset in 1..=stale.min(RATE_WINDOW_SECS) {
+            self.buckets[bucket(self.second + offset as u64)] = 0;
+        }
+        self.second = second;
+    }
+
+    /// Mean rate over the window, or over the time since `second == 0` if
+    /// that's shorter.
+    #[allow(clippy::cast_precision_loss)]
+    fn per_sec(&mut self, second: u64) -> f64 {
+        self.advance(second);
+        let span = (second + 1).min(RATE_WINDOW_SECS as u64);
+        self.buckets.iter().sum::<u64>() as f64 / span as f64
+    }
+}
+
+fn bucket(second: u64) -> usize {
+    usize::try_from(second % RATE_WINDOW_SECS as u64).unwrap_or_default()
+}
+
+impl WebSocketMetrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                connected: Instant::now(),
+                messages: 0,
+                rate: RateWindow::default(),
+                symbols: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Count a received message, sampling trade lag against the wall clock.
+    pub(crate) fn record(&self, message: &WebSocketMessage) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        self.record_at(message, Instant::now(), now_ms);
+    }
+
+    fn record_at(&self, message: &WebSocketMessage, now: Instant, now_ms: i64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let second = state.second(now);
+        state.messages += 1;
+        state.rate.record(second, 1);
+
+        let WebSocketMessage::Trade { data } = message else {
+            return;
+        };
+        for TradeData {
+            symbol, timestamp, ..
+        } in data
+        {
+            let lag = now_ms - timestamp;
+            let entry = state
+                .symbols
+                .entry(symbol.clone())
+                .or_insert_with(|| SymbolState {
+                    trades: 0,
+                    rate: RateWindow::default(),
+                    last_lag_ms: lag,
+                    #[allow(clippy::cast_precision_loss)]
+                    mean_lag_ms: lag as f64,
+                    max_lag_ms: lag,
+                    last_received: now,
+                });
+            entry.trades += 1;
+            entry.rate.record(second, 1);
+            entry.last_lag_ms = lag;
+            #[allow(clippy::cast_precision_loss)]
+            let sample = lag as f64;
+            entry.mean_lag_ms += LAG_SMOOTHING * (sample - entry.mean_lag_ms);
+            entry.max_lag_ms = entry.max_lag_ms.max(lag);
+            entry.last_received = now;
+        }
+    }
+
+    /// Copy the current metrics.
+    #[must_use]
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    fn snapshot_at(&self, now: Instant) -> MetricsSnapshot {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let second = state.second(now);
+        let messages_per_sec = state.rate.per_sec(second);
+        let symbols = state
+            .symbols
+            .iter_mut()
+            .map(|(symbol, s)| {
+                let metrics = SymbolMetrics {
+                    trades: s.trades,
+                    trades_per_sec: s.rate.per_sec(second),
+                    last_lag_ms: s.last_lag_ms,
+                    mean_lag_ms: s.mean_lag_ms,
+                    max_lag_ms: s.max_lag_ms,
+                    since_last_trade: now.saturating_duration_since(s.last_received),
+                };
+                (symbol.clone(), metrics)
+            })
+            .collect();
+
+        MetricsSnapshot {
+            uptime: now.saturating_duration_since(state.connected),
+            messages: state.messages,
+            messages_per_sec,
+            symbols,
+        }
+    }
+}
+
+impl State {
+    /// Whole seconds since connecting.
+    fn second(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.connected).as_secs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trades(symbols: &[(&str, i64)]) -> WebSocketMessage {
+        WebSocketMessage::Trade {
+            data: symbols
+                .iter()
+                .map(|&(symbol, timestamp)| TradeData {
+                    symbol: symbol.to_string(),
+                    price: 1.0,
+                    timestamp,
+                    volume: 1.0,
+                    conditions: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_counts_rates_and_lag() {
+        let metrics = WebSocketMetrics::new();
+        let start = metrics.state.lock().unwrap().connected;
+        let at = |secs| start + Duration::from_secs(secs);
+
+        metrics.record_at(&trades(&[("AAPL", 1_000), ("MSFT", 1_000)]), at(0), 1_050);
+        metrics.record_at(&WebSocketMessage::Ping, at(1), 2_000);
+        metrics.record_at(&trades(&[("AAPL", 2_000)]), at(1), 2_250);
+
+        let snapshot = metrics.snapshot_at(at(1));
+        assert_eq!(snapshot.messages, 3);
+        assert!((snapshot.messages_per_sec - 1.5).abs() < f64::EPSILON);
+
+        let aapl = &snapshot.symbols["AAPL"];
+        assert_eq!(aapl.trades, 2);
+        assert_eq!(aapl.last_lag_ms, 250);
+        assert_eq!(aapl.max_lag_ms, 250);
+        assert!((aapl.mean_lag_ms - 70.0).abs() < 1e-9);
+        assert_eq!(
+            snapshot.symbols["MSFT"].since_last_trade,
+            Duration::from_secs(1)
+        );
+
+        // Old activity ages out of the rate window.
+        let later = metrics.snapshot_at(at(30));
+        assert!(later.messages_per_sec.abs() < f64::EPSILON);
+        assert_eq!(later.symbols["AAPL"].trades, 2);
+    }
+}
@@ -1,5 +1,9 @@
 //! WebSocket support for real-time data streaming.
 
+mod metrics;
+mod news;
 mod stream;
 
-pub use stream::{WebSocketClient, WebSocketMessage};
+pub use metrics::{MetricsSnapshot, SymbolMetrics, WebSocketMetrics};
+pub use news::{NewsResumeState, NewsStream};
+pub use stream::{TradeData, WebSocketClient, WebSocketMessage, WebSocketStream};
@@ -0,0 +1,263 @@
+//! Resumable WebSocket news stream.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{WebSocketClient, WebSocketMessage, WebSocketStream};
+use crate::{
+    client::FinnhubClient,
+    error::Result,
+    models::news::CompanyNews,
+    persist::{self, StateStore},
+};
+
+/// The newest article a [`NewsStream`] delivered, kept so that a restarted or
+/// reconnected stream knows where to resume.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewsResumeState {
+    /// Publish time (UNIX seconds) of the newest delivered article.
+    pub last_datetime: i64,
+    /// IDs of delivered articles published at `last_datetime`.
+    pub last_ids: Vec<i64>,
+}
+
+impl NewsResumeState {
+    /// Whether `article` comes after everything already delivered.
+    #[must_use]
+    pub fn is_new(&self, article: &CompanyNews) -> bool {
+        article.datetime > self.last_datetime
+            || (article.datetime == self.last_datetime && !self.last_ids.contains(&article.id))
+    }
+
+    /// Record `article` as delivered.
+    pub fn advance(&mut self, article: &CompanyNews) {
+        if article.datetime > self.last_datetime {
+            self.last_datetime = article.datetime;
+            self.last_ids.clear();
+        }
+        if article.datetime == self.last_datetime && !self.last_ids.contains(&article.id) {
+            self.last_ids.push(article.id);
+        }
+    }
+
+    /// Articles from `fetched` that haven't been delivered, oldest first and
+    /// without duplicates.
+    #[must_use]
+    pub fn missed(&self, fetched: impl IntoIterator<Item = CompanyNews>) -> Vec<CompanyNews> {
+        let mut missed: Vec<_> = fetched
+            .into_iter()
+            .filter(|article| self.is_new(article))
+            .collect();
+        missed.sort_by_key(|article| (article.datetime, article.id));
+        missed.dedup_by_key(|article| article.id);
+        missed
+    }
+}
+
+/// Company news from the WebSocket feed that survives disconnects.
+///
+/// The newest delivered article is saved in a [`StateStore`]. Whenever the
+/// stream (re)connects, including after a process restart, it asks the REST
+/// company news endpoint for each symbol's articles since then and delivers
+/// the missed ones, oldest first, before resuming the live feed. Articles
+/// already delivered are never repeated.
+///
+/// ```no_run
+/// use finnhub::{persist::FileStore, websocket::{NewsStream, WebSocketClient}, FinnhubClient};
+/// use std::sync::Arc;
+///
+/// # async fn run() -> finnhub::Result<()> {
+/// let client = FinnhubClient::new("your-api-key");
+/// let ws = WebSocketClient::new("your-api-key");
+/// let store = Arc::new(FileStore::new("state"));
+/// let mut news = NewsStream::new(ws, client, ["AAPL", "MSFT"], store);
+/// loop {
+///     match news.next().await {
+///         Ok(article) => println!("{}: {}", article.related, article.headline),
+///         // The next call reconnects and fills the gap.
+///         Err(err) => eprintln!("news stream error: {err}"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct NewsStream {
+    ws: WebSocketClient,
+    client: FinnhubClient,
+    symbols: Vec<String>,
+    store: Arc<dyn StateStore>,
+    key: String,
+    state: Option<NewsResumeState>,
+    stream: Option<WebSocketStream>,
+    pending: VecDeque<CompanyNews>,
+}
+
+impl fmt::Debug for NewsStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NewsStream")
+            .field("symbols", &self.symbols)
+            .field("key", &self.key)
+            .field("state", &self.state)
+            .field("connected", &self.stream.is_some())
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl NewsStream {
+    /// Create a news stream for `symbols`, keeping resume state in `store`
+    /// under the key `ws-news`. Nothing is fetched until the first
+    /// [`next`](Self::next).
+    pub fn new(
+        ws: WebSocketClient,
+        client: FinnhubClient,
+        symbols: impl IntoIterator<Item = impl Into<String>>,
+        store: Arc<dyn StateStore>,
+    ) -> Self {
+        Self {
+            ws,
+            client,
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            store,
+            key: "ws-news".to_string(),
+            state: None,
+            stream: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Store resume state under `key`, for running several streams against
+    /// one store.
+    #[must_use]
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Receive the next article.
+    ///
+    /// Connects, or reconnects after the connection closed, subscribing to
+    /// every symbol and filling the gap from the REST API first.
+    ///
+    /// # Errors
+    ///
+    /// Returns connection, request and storage errors. After an error the
+    /// connection is dropped, so the next call reconnects and fills the gap.
+    pub async fn next(&mut self) -> Result<CompanyNews> {
+        let result = self.next_article().await;
+        if result.is_err() {
+            self.stream = None;
+        }
+        result
+    }
+
+    async fn next_article(&mut self) -> Result<CompanyNews> {
+        loop {
+            if let Some(article) = self.pending.pop_front() {
+                let state = self.state.get_or_insert_with(NewsResumeState::default);
+                if !state.is_new(&article) {
+                    continue;
+                }
+                state.advance(&article);
+                persist::save(self.store.as_ref(), &self.key, &*state).await?;
+                return Ok(article);
+            }
+
+            let Some(stream) = self.stream.as_mut() else {
+                self.connect().await?;
+                continue;
+            };
+            match stream.next().await? {
+                Some(WebSocketMessage::News { data }) => {
+                    let mut data = data;
+                    data.sort_by_key(|article| (article.datetime, article.id));
+                    self.pending.extend(data);
+                }
+                Some(_) => {}
+                None => {
+                    tracing::warn!("news WebSocket closed, reconnecting");
+                    self.stream = None;
+                }
+            }
+        }
+    }
+
+    /// Open a connection, subscribe, and queue articles missed since the
+    /// last delivered one.
+    async fn connect(&mut self) -> Result<()> {
+        if self.state.is_none() {
+            self.state = persist::load(self.store.as_ref(), &self.key).await?;
+        }
+
+        let mut stream = self.ws.connect().await?;
+        for symbol in &self.symbols {
+            stream.subscribe_news(symbol).await?;
+        }
+
+        if let Some(state) = &self.state {
+            let from = DateTime::from_timestamp(state.last_datetime, 0)
+                .unwrap_or_default()
+                .date_naive()
+                .to_string();
+            let to = Utc::now().date_naive().to_string();
+            let news = self.client.news();
+
+            let mut fetched = Vec::new();
+            for symbol in &self.symbols {
+                fetched.extend(news.company_news(symbol, &from, &to).await?);
+            }
+            let missed = state.missed(fetched);
+            if !missed.is_empty() {
+                tracing::info!(count = missed.len(), "filling news gap after reconnect");
+            }
+            self.pending.extend(missed);
+        }
+
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(id: i64, datetime: i64) -> CompanyNews {
+        CompanyNews {
+            category: "company".to_string(),
+            datetime,
+            headline: format!("headline {id}"),
+            id,
+            image: String::new(),
+            related: "AAPL".to_string(),
+            source: "test".to_string(),
+            summary: String::new(),
+            url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_missed_articles_resume_after_last_delivered() {
+        let mut state = NewsResumeState::default();
+        state.advance(&article(1, 100));
+        state.advance(&article(2, 200));
+        state.advance(&article(3, 200));
+        assert_eq!(state.last_ids, [2, 3]);
+
+        let missed = state.missed([
+            article(5, 300),
+            article(1, 100),
+            article(3, 200),
+            article(4, 200),
+            article(5, 300),
+        ]);
+        let ids: Vec<_> = missed.iter().map(|a| a.id).collect();
+        assert_eq!(ids, [4, 5]);
+
+        state.advance(&missed[1]);
+        assert_eq!(state.last_datetime, 300);
+        assert_eq!(state.last_ids, [5]);
+    }
+}
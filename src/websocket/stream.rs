@@ -2,10 +2,14 @@
 
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
 
+use super::WebSocketMetrics;
+use crate::auth::ApiKey;
 use crate::error::Result;
+use crate::models::news::CompanyNews;
 
 const WEBSOCKET_URL: &str = "wss://ws.finnhub.io";
 
@@ -18,6 +22,12 @@ pub enum WebSocketMessage {
         /// Trade data.
         data: Vec<TradeData>,
     },
+    /// News for symbols subscribed with
+    /// [`subscribe_news`](WebSocketStream::subscribe_news).
+    News {
+        /// Articles.
+        data: Vec<CompanyNews>,
+    },
     /// Ping message.
     Ping,
     /// Error message.
@@ -47,6 +57,19 @@ pub struct TradeData {
     pub conditions: Option<Vec<String>>,
 }
 
+impl TradeData {
+    /// Whether the trade carries condition code `code`.
+    #[must_use]
+    pub fn has_condition(&self, code: &str) -> bool {
+        self.conditions
+            .as_ref()
+            .is_some_and(|conditions| conditions.iter().any(|c| c == code))
+    }
+}
+
+/// Predicate deciding which trades a stream delivers.
+type TradeFilter = Arc<dyn Fn(&TradeData) -> bool + Send + Sync>;
+
 /// WebSocket subscription request.
 #[derive(Debug, Serialize)]
 struct SubscribeRequest {
@@ -57,24 +80,57 @@ struct SubscribeRequest {
 
 /// WebSocket client for real-time data.
 pub struct WebSocketClient {
-    api_key: String,
+    api_key: ApiKey,
+    trade_filter: Option<TradeFilter>,
 }
 
 impl WebSocketClient {
     /// Create a new WebSocket client.
-    pub fn new(api_key: impl Into<String>) -> Self {
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
         Self {
             api_key: api_key.into(),
+            trade_filter: None,
         }
     }
 
+    /// Only deliver trades for which `filter` returns `true`.
+    ///
+    /// Applies to every stream this client connects. Rejected trades are
+    /// removed from their message before it is returned, and a trade message
+    /// left empty is skipped entirely, so callers never see it. Stream
+    /// [`metrics`](WebSocketStream::metrics) still count every trade received.
+    ///
+    /// ```no_run
+    /// use finnhub::websocket::WebSocketClient;
+    ///
+    /// // Drop odd lots and trades flagged with condition code "12".
+    /// let client = WebSocketClient::new("your-api-key")
+    ///     .with_trade_filter(|trade| trade.volume >= 100.0 && !trade.has_condition("12"));
+    /// ```
+    #[must_use]
+    pub fn with_trade_filter(
+        mut self,
+        filter: impl Fn(&TradeData) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.trade_filter = Some(Arc::new(filter));
+        self
+    }
+
     /// Connect to the WebSocket API.
     pub async fn connect(&self) -> Result<WebSocketStream> {
-        let url = Url::parse(&format!("{}?token={}", WEBSOCKET_URL, self.api_key))?;
+        let url = Url::parse(&format!(
+            "{}?token={}",
+            WEBSOCKET_URL,
+            self.api_key.expose()
+        ))?;
 
         let (ws_stream, _) = connect_async(url.as_str()).await?;
 
-        Ok(WebSocketStream { inner: ws_stream })
+        Ok(WebSocketStream {
+            inner: ws_stream,
+            metrics: WebSocketMetrics::new(),
+            trade_filter: self.trade_filter.clone(),
+        })
     }
 }
 
@@ -83,6 +139,8 @@ pub struct WebSocketStream {
     inner: tokio_tungstenite::WebSocketStream<
         tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
     >,
+    metrics: WebSocketMetrics,
+    trade_filter: Option<TradeFilter>,
 }
 
 impl WebSocketStream {
@@ -99,6 +157,19 @@ impl WebSocketStream {
         Ok(())
     }
 
+    /// Subscribe to news for a symbol.
+    pub async fn subscribe_news(&mut self, symbol: &str) -> Result<()> {
+        let request = SubscribeRequest {
+            request_type: "subscribe-news".to_string(),
+            symbol: symbol.to_string(),
+        };
+
+        let message = Message::Text(serde_json::to_string(&request)?);
+        self.inner.send(message).await?;
+
+        Ok(())
+    }
+
     /// Unsubscribe from a symbol.
     pub async fn unsubscribe(&mut self, symbol: &str) -> Result<()> {
         let request = SubscribeRequest {
@@ -112,17 +183,85 @@ impl WebSocketStream {
         Ok(())
     }
 
+    /// Message counts, rates and trade lag for this stream.
+    ///
+    /// The returned handle stays live, so it can be moved to a monitoring
+    /// task that periodically takes snapshots.
+    pub fn metrics(&self) -> WebSocketMetrics {
+        self.metrics.clone()
+    }
+
     /// Receive the next message from the stream.
+    ///
+    /// Trade messages whose trades were all rejected by the client's
+    /// [trade filter](WebSocketClient::with_trade_filter) are skipped.
     pub async fn next(&mut self) -> Result<Option<WebSocketMessage>> {
-        match self.inner.next().await {
-            Some(Ok(Message::Text(text))) => {
-                let message: WebSocketMessage = serde_json::from_str(&text)?;
-                Ok(Some(message))
+        loop {
+            match self.inner.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    let message: WebSocketMessage = serde_json::from_str(&text)?;
+                    self.metrics.record(&message);
+                    if let Some(message) = filter_trades(self.trade_filter.as_deref(), message) {
+                        return Ok(Some(message));
+                    }
+                }
+                Some(Ok(Message::Close(_))) => return Ok(None),
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(None),
+                _ => return Ok(None), // Ignore other message types
             }
-            Some(Ok(Message::Close(_))) => Ok(None),
-            Some(Err(e)) => Err(e.into()),
-            None => Ok(None),
-            _ => Ok(None), // Ignore other message types
         }
     }
 }
+
+/// Apply `filter` to a trade message, returning `None` if no trades remain.
+fn filter_trades(
+    filter: Option<&(dyn Fn(&TradeData) -> bool + Send + Sync)>,
+    message: WebSocketMessage,
+) -> Option<WebSocketMessage> {
+    match (filter, message) {
+        (Some(filter), WebSocketMessage::Trade { mut data }) => {
+            data.retain(|trade| filter(trade));
+            (!data.is_empty()).then_some(WebSocketMessage::Trade { data })
+        }
+        (_, message) => Some(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(volume: f64, conditions: &[&str]) -> TradeData {
+        TradeData {
+            symbol: "AAPL".to_string(),
+            price: 190.0,
+            timestamp: 0,
+            volume,
+            conditions: Some(conditions.iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    #[test]
+    fn test_filter_trades() {
+        let filter = |trade: &TradeData| trade.volume >= 100.0 && !trade.has_condition("12");
+        let message = WebSocketMessage::Trade {
+            data: vec![
+                trade(50.0, &[]),
+                trade(200.0, &["1"]),
+                trade(300.0, &["12"]),
+            ],
+        };
+        let Some(WebSocketMessage::Trade { data }) = filter_trades(Some(&filter), message) else {
+            panic!("expected trades");
+        };
+        assert_eq!(data.len(), 1);
+        assert!((data[0].volume - 200.0).abs() < f64::EPSILON);
+
+        let odd_lots = WebSocketMessage::Trade {
+            data: vec![trade(10.0, &[])],
+        };
+        assert!(filter_trades(Some(&filter), odd_lots).is_none());
+        assert!(filter_trades(Some(&filter), WebSocketMessage::Ping).is_some());
+    }
+}
@@ -0,0 +1,51 @@
+//! Crypto candle normalization against recorded venue responses.
+//!
+//! Each fixture in `tests/fixtures` is a `/crypto/candle` response from a
+//! venue with one of the quirks handled by `CandleQuirks`.
+
+use finnhub::models::crypto::{CandleQuirks, CryptoCandles};
+use finnhub::models::stock::CandleResolution;
+
+const BITFINEX: &str = include_str!("fixtures/crypto_candles_bitfinex.json");
+const COINBASE: &str = include_str!("fixtures/crypto_candles_coinbase.json");
+
+#[test]
+fn bitfinex_millisecond_timestamps() {
+    // One-minute bars from 2024-01-01T00:00:00Z, stamped in milliseconds.
+    let candles: CryptoCandles = serde_json::from_str(BITFINEX).unwrap();
+    let quirks = CandleQuirks::for_symbol("BITFINEX:tBTCUSD");
+    assert!(quirks.millisecond_timestamps);
+
+    let normalized = candles.normalized(CandleResolution::OneMinute, quirks);
+    assert_eq!(
+        normalized.timestamp,
+        [
+            1_704_067_200,
+            1_704_067_260,
+            1_704_067_320,
+            1_704_067_380,
+            1_704_067_440
+        ]
+    );
+    assert_eq!(normalized.close[4], 42_330.0);
+}
+
+#[test]
+fn coinbase_newest_first_with_revision() {
+    // Five-minute bars, newest first; the 00:15 bar appears twice, the first
+    // copy being the later revision.
+    let candles: CryptoCandles = serde_json::from_str(COINBASE).unwrap();
+    let quirks = CandleQuirks::for_symbol("COINBASE:BTC-USD");
+    assert!(quirks.newest_first);
+
+    let normalized = candles.normalized(CandleResolution::FiveMinutes, quirks);
+    assert_eq!(
+        normalized.timestamp,
+        [1_704_067_200, 1_704_067_500, 1_704_067_800, 1_704_068_100]
+    );
+    assert_eq!(
+        normalized.close,
+        [42_283.58, 42_325.8, 42_340.17, 42_371.32]
+    );
+    assert_eq!(normalized.volume[3], 48.11);
+}
@@ -0,0 +1,269 @@
+//! Property tests for model deserialization against API drift.
+//!
+//! Recorded responses in `tests/fixtures` are mutated the way a changing API
+//! tends to break them: fields dropped or nulled, numbers sent as strings,
+//! integers sent as floats and floats truncated to integers. Lenient decoding
+//! must keep every intact element, and strict decoding must either succeed
+//! or name the mutated field in its error.
+
+use finnhub::lenient::{self, Lenient};
+use finnhub::models::news::MarketNews;
+use finnhub::models::stock::{CompanyProfile, Filing, Quote, StockCandles, Symbol};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+const QUOTE: &str = include_str!("fixtures/quote.json");
+const PROFILE: &str = include_str!("fixtures/profile2.json");
+const CANDLES: &str = include_str!("fixtures/candles.json");
+const SYMBOLS: &str = include_str!("fixtures/symbols.json");
+const FILINGS: &str = include_str!("fixtures/filings.json");
+const NEWS: &str = include_str!("fixtures/news.json");
+
+#[derive(Debug, Clone, Copy)]
+enum Mutation {
+    /// Remove the field or array element.
+    Drop,
+    /// Replace the value with `null`.
+    Null,
+    /// Replace the value with a string of its JSON, e.g. `"189.84"`.
+    Stringify,
+    /// Send an integer as a float, e.g. `1714766400.0`.
+    IntToFloat,
+    /// Truncate a float to an integer.
+    Truncate,
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        Just(Mutation::Drop),
+        Just(Mutation::Null),
+        Just(Mutation::Stringify),
+        Just(Mutation::IntToFloat),
+        Just(Mutation::Truncate),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// Paths of every value below `value`, parents before their children.
+fn paths(value: &Value) -> Vec<Vec<Step>> {
+    fn walk(value: &Value, prefix: &mut Vec<Step>, out: &mut Vec<Vec<Step>>) {
+        let children: Vec<(Step, &Value)> = match value {
+            Value::Object(map) => map.iter().map(|(k, v)| (Step::Key(k.clone()), v)).collect(),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (Step::Index(i), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (step, child) in children {
+            prefix.push(step);
+            out.push(prefix.clone());
+            walk(child, prefix, out);
+            prefix.pop();
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(value, &mut Vec::new(), &mut out);
+    out
+}
+
+/// A path as the decoders print it, e.g. `c[3]` or `[1].displaySymbol`.
+fn display(path: &[Step]) -> String {
+    let mut out = String::new();
+    for step in path {
+        match step {
+            Step::Key(key) if out.is_empty() => out.push_str(key),
+            Step::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            Step::Index(index) => out.push_str(&format!("[{index}]")),
+        }
+    }
+    out
+}
+
+fn child<'a>(value: &'a mut Value, step: &Step) -> &'a mut Value {
+    match step {
+        Step::Key(key) => &mut value[key.as_str()],
+        Step::Index(index) => &mut value[*index],
+    }
+}
+
+/// Apply `mutation` at `path`; returns whether the document changed.
+fn mutate(doc: &mut Value, path: &[Step], mutation: Mutation) -> bool {
+    let (last, parent_path) = path.split_last().expect("paths are below the root");
+    let parent = parent_path
+        .iter()
+        .fold(doc, |value, step| child(value, step));
+    if let Mutation::Drop = mutation {
+        return match (parent, last) {
+            (Value::Object(map), Step::Key(key)) => map.remove(key).is_some(),
+            (Value::Array(items), Step::Index(index)) => {
+                items.remove(*index);
+                true
+            }
+            _ => unreachable!("path steps match the document"),
+        };
+    }
+
+    let target = child(parent, last);
+    let replacement = match mutation {
+        Mutation::Null => Value::Null,
+        Mutation::Stringify => Value::String(target.to_string()),
+        Mutation::IntToFloat => match target.as_i64() {
+            #[allow(clippy::cast_precision_loss)]
+            Some(int) if target.is_i64() || target.is_u64() => Value::from(int as f64),
+            _ => return false,
+        },
+        Mutation::Truncate => match target.as_f64() {
+            #[allow(clippy::cast_possible_truncation)]
+            Some(float) if target.is_f64() => Value::from(float.trunc() as i64),
+            _ => return false,
+        },
+        Mutation::Drop => unreachable!(),
+    };
+    *target = replacement;
+    true
+}
+
+/// Strict decoding of the mutated fixture either succeeds or fails with an
+/// error naming the mutated field.
+fn check_strict<T: DeserializeOwned>(
+    fixture: &str,
+    site: &Index,
+    mutation: Mutation,
+) -> Result<(), TestCaseError> {
+    let mut doc: Value = serde_json::from_str(fixture).unwrap();
+    let paths = paths(&doc);
+    let path: &[Step] = site.get::<Vec<Step>>(&paths);
+    if !mutate(&mut doc, path, mutation) {
+        return Ok(());
+    }
+
+    let bytes = serde_json::to_vec(&doc).unwrap();
+    let Err(err) = lenient::from_slice::<T>(&bytes) else {
+        return Ok(());
+    };
+    let message = err.to_string();
+    prop_assert!(err.is_data(), "not a data error: {}", message);
+
+    let (last, parent) = path.split_last().unwrap();
+    let expected = match (mutation, last) {
+        (Mutation::Drop, Step::Key(key)) if parent.is_empty() => format!("missing field `{key}`"),
+        (Mutation::Drop, Step::Key(key)) => format!("{}: missing field `{key}`", display(parent)),
+        _ => format!("{}: ", display(path)),
+    };
+    prop_assert!(
+        message.starts_with(&expected),
+        "expected {:?} to start with {:?}",
+        message,
+        expected
+    );
+    Ok(())
+}
+
+/// Lenient decoding of a mutated array keeps every element that still
+/// decodes strictly and warns, with the strict error less its position,
+/// about the rest.
+fn check_lenient<T: DeserializeOwned>(
+    fixture: &str,
+    mutations: &[(Index, Mutation)],
+) -> Result<(), TestCaseError> {
+    let mut doc: Value = serde_json::from_str(fixture).unwrap();
+    for (site, mutation) in mutations {
+        let paths = paths(&doc);
+        if paths.is_empty() {
+            break;
+        }
+        mutate(&mut doc, site.get::<Vec<Step>>(&paths), *mutation);
+    }
+
+    let bytes = serde_json::to_vec(&doc).unwrap();
+    let decoded: Lenient<T> = serde_json::from_slice(&bytes).unwrap();
+    let elements = doc.as_array().unwrap();
+    prop_assert_eq!(decoded.items.len() + decoded.warnings.len(), elements.len());
+
+    let mut warnings = decoded.warnings.iter().peekable();
+    for (index, element) in elements.iter().enumerate() {
+        let strict = lenient::from_slice::<T>(&serde_json::to_vec(element).unwrap());
+        match (strict, warnings.next_if(|w| w.index == index)) {
+            (Ok(_), None) => {}
+            (Err(err), Some(warning)) => {
+                prop_assert!(err.to_string().starts_with(&warning.message));
+            }
+            (strict, warning) => {
+                return Err(TestCaseError::fail(format!(
+                    "element {index}: strict {:?}, warning {warning:?}",
+                    strict.map(|_| ())
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn mutations() -> impl Strategy<Value = Vec<(Index, Mutation)>> {
+    prop::collection::vec((any::<Index>(), mutation()), 1..5)
+}
+
+proptest! {
+    #[test]
+    fn strict_quote(site in any::<Index>(), mutation in mutation()) {
+        check_strict::<Quote>(QUOTE, &site, mutation)?;
+    }
+
+    #[test]
+    fn strict_profile(site in any::<Index>(), mutation in mutation()) {
+        check_strict::<CompanyProfile>(PROFILE, &site, mutation)?;
+    }
+
+    #[test]
+    fn strict_candles(site in any::<Index>(), mutation in mutation()) {
+        check_strict::<StockCandles>(CANDLES, &site, mutation)?;
+    }
+
+    #[test]
+    fn strict_symbols(site in any::<Index>(), mutation in mutation()) {
+        check_strict::<Vec<Symbol>>(SYMBOLS, &site, mutation)?;
+    }
+
+    #[test]
+    fn strict_news(site in any::<Index>(), mutation in mutation()) {
+        check_strict::<Vec<MarketNews>>(NEWS, &site, mutation)?;
+    }
+
+    #[test]
+    fn lenient_symbols(mutations in mutations()) {
+        check_lenient::<Symbol>(SYMBOLS, &mutations)?;
+    }
+
+    #[test]
+    fn lenient_filings(mutations in mutations()) {
+        check_lenient::<Filing>(FILINGS, &mutations)?;
+    }
+
+    #[test]
+    fn lenient_news(mutations in mutations()) {
+        check_lenient::<MarketNews>(NEWS, &mutations)?;
+    }
+}
+
+#[test]
+fn fixtures_decode_unmutated() {
+    lenient::from_slice::<Quote>(QUOTE.as_bytes()).unwrap();
+    lenient::from_slice::<CompanyProfile>(PROFILE.as_bytes()).unwrap();
+    lenient::from_slice::<StockCandles>(CANDLES.as_bytes()).unwrap();
+    lenient::from_slice::<Vec<Symbol>>(SYMBOLS.as_bytes()).unwrap();
+    lenient::from_slice::<Vec<Filing>>(FILINGS.as_bytes()).unwrap();
+    lenient::from_slice::<Vec<MarketNews>>(NEWS.as_bytes()).unwrap();
+}
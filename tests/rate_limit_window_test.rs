@@ -94,7 +94,10 @@ async fn test_client_with_strategies() {
     // Example 4: Legacy rate_limit field (for backwards compatibility)
     println!("\nExample 4: Legacy rate_limit field");
     let mut config = ClientConfig::default();
-    config.rate_limit = Some(60); // 60 req/s
+    #[allow(deprecated)]
+    {
+        config.rate_limit = Some(60); // 60 req/s
+    }
     let _client4 = FinnhubClient::with_config("test-key", config);
     println!("  Created client with 60 req/s limit (legacy)");
 }
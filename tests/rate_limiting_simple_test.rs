@@ -1,4 +1,4 @@
-use finnhub::{ClientConfig, FinnhubClient};
+use finnhub::{ClientConfig, FinnhubClient, RateLimitStrategy};
 use std::time::{Duration, Instant};
 
 /// Test to understand rate limiting behavior by timing actual requests
@@ -50,7 +50,7 @@ async fn test_rate_limiting_with_timing() {
 
     println!("\nTest 2: High rate limit client (1000 req/s)");
     let mut config = ClientConfig::default();
-    config.rate_limit = Some(1000);
+    config.rate_limit_strategy = RateLimitStrategy::from_legacy(1000);
     let client = FinnhubClient::with_config(&api_key, config);
 
     println!("  Making 100 requests with high limit...");
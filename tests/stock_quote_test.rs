@@ -64,7 +64,7 @@ async fn test_stock_quote_success() {
             println!("  High: ${:.2}", quote.high);
             println!("  Low: ${:.2}", quote.low);
         }
-        Err(finnhub::Error::ApiError { status: 403, .. }) => {
+        Err(e) if matches!(e.root(), finnhub::Error::ApiError { status: 403, .. }) => {
             println!(
                 "⚠️  API key has limited access (403 Forbidden) - this is expected for free tier"
             );
@@ -106,7 +106,7 @@ async fn test_stock_quote_multiple_symbols() {
                 );
                 println!("✅ {} price: ${:.2}", symbol, quote.current_price);
             }
-            Err(finnhub::Error::ApiError { status: 403, .. }) => {
+            Err(e) if matches!(e.root(), finnhub::Error::ApiError { status: 403, .. }) => {
                 println!(
                     "⚠️  {} quote requires premium access (403 Forbidden)",
                     symbol